@@ -1,6 +1,8 @@
-use crate::app::config::get_config_path;
-use crate::app::states::{ResourceUsageState, SessionBrowserState, TrafficLogsState};
-use crate::app::types::{CollectionStatus, Proxy, ProxyConfig, TabIndex};
+use crate::app::config::{get_config_path, resolve_config_oid};
+use crate::app::proxy_form::{ManagedItem, ProxyForm};
+use crate::app::states::{EventsState, ProxyManagementState, ResourceUsageState, SessionBrowserState, TrafficLogsState};
+use crate::app::types::{CollectionStatus, EventRecord, GroupConfig, Proxy, ProxyConfig, ResourceData, TabIndex};
+use std::collections::BTreeMap;
 
 /// 앱 상태
 pub struct App {
@@ -8,10 +10,15 @@ pub struct App {
     pub current_tab: TabIndex,
     pub should_quit: bool,
     pub proxies: Vec<Proxy>,
+    pub groups: BTreeMap<String, GroupConfig>, // proxies.json의 그룹 공통 설정
+    pub proxy_management: ProxyManagementState,
     pub resource_usage: ResourceUsageState,
     pub session_browser: SessionBrowserState,
     pub traffic_logs: TrafficLogsState,
+    pub events: EventsState,
     pub is_collecting: bool, // 수집 중 플래그
+    pub host_key_prompt: Option<crate::ssh::HostKeyPrompt>, // 확인 대기 중인 SSH 호스트 키
+    pub host_key_error: Option<String>, // known_hosts 저장 실패 메시지
 }
 
 impl App {
@@ -21,10 +28,15 @@ impl App {
             current_tab: TabIndex::ProxyManagement,
             should_quit: false,
             proxies: Vec::new(),
+            groups: BTreeMap::new(),
+            proxy_management: ProxyManagementState::new(),
             resource_usage: ResourceUsageState::new(),
             session_browser: SessionBrowserState::new(),
             traffic_logs: TrafficLogsState::new(),
+            events: EventsState::new(),
             is_collecting: false,
+            host_key_prompt: None,
+            host_key_error: None,
         }
     }
 
@@ -32,22 +44,206 @@ impl App {
         let config_path = get_config_path("proxies.json");
         let content = std::fs::read_to_string(&config_path)
             .map_err(|e| anyhow::anyhow!("설정 파일을 찾을 수 없습니다: {} (에러: {})", config_path.display(), e))?;
-        let config: ProxyConfig = serde_json::from_str(&content)?;
-        self.proxies = config.proxies;
+        let mut config: serde_json::Value = serde_json::from_str(&content)?;
+        // resource_config.json의 SNMP 설정도 볼트를 참조할 수 있음 (읽을 수 없으면 수집할 때 오류 표시)
+        let mut resource_config = std::fs::read_to_string(get_config_path("resource_config.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or(serde_json::Value::Null);
+        // vault: 참조가 있으면 마스터 암호로 볼트를 열어 둠 (실제 값은 접속/수집할 때 가져옴)
+        crate::vault::unlock_if_referenced(&mut [&mut config, &mut resource_config], || {
+            let passphrase = crate::vault::read_passphrase("볼트 마스터 암호: ")?;
+            crate::vault::Vault::open(&crate::vault::vault_path(&config_path), &passphrase)
+        });
+        let config: ProxyConfig = serde_json::from_value(config)?;
+        for proxy in &config.proxies {
+            proxy.validate_maintenance()?;
+            proxy.validate_exec_mode()?;
+        }
+        self.groups = config.groups.clone();
+        self.proxies = config.into_proxies();
         // 그룹 목록 업데이트
-        self.resource_usage.update_groups(&self.proxies);
-        self.session_browser.update_groups(&self.proxies);
+        self.refresh_proxy_lists();
         Ok(())
     }
 
     pub fn on_tick(&mut self) {
         // 주기적 작업이 필요하면 여기에 추가
         // 스피너 애니메이션과 자동 수집은 백그라운드 태스크에서 처리됨
+
+        // 오래 쓰지 않은 SSH 세션 정리
+        crate::ssh::close_idle_sessions();
+
+        // 처음 보는 SSH 호스트 키가 있으면 확인 창 표시
+        if self.host_key_prompt.is_none() {
+            self.host_key_prompt = crate::ssh::take_host_key_prompt();
+        }
+    }
+
+    /// 확인 창의 호스트 키를 known_hosts에 추가합니다. (실패하면 창을 유지하고 오류 표시)
+    pub fn accept_host_key(&mut self) {
+        let Some(prompt) = &self.host_key_prompt else {
+            return;
+        };
+        match crate::ssh::accept_host_key(prompt) {
+            Ok(()) => {
+                self.host_key_prompt = None;
+                self.host_key_error = None;
+            }
+            Err(e) => self.host_key_error = Some(format!("{:#}", e)),
+        }
+    }
+
+    /// 확인 창의 호스트 키를 거부합니다.
+    pub fn reject_host_key(&mut self) {
+        if let Some(prompt) = self.host_key_prompt.take() {
+            crate::ssh::reject_host_key(&prompt);
+        }
+        self.host_key_error = None;
+    }
+
+    /// 프록시 관리 목록 (그룹 헤더와 프록시)
+    pub fn managed_items(&self) -> Vec<ManagedItem> {
+        crate::app::proxy_form::managed_items(&self.proxies, &self.groups)
+    }
+
+    fn selected_managed_item(&self) -> Option<ManagedItem> {
+        let index = self.proxy_management.table_state.selected()?;
+        self.managed_items().into_iter().nth(index)
+    }
+
+    /// 선택한 행의 그룹 (프록시 행이면 그 프록시의 그룹)
+    fn selected_managed_group(&self) -> Option<String> {
+        match self.selected_managed_item()? {
+            ManagedItem::Group(name) => Some(name),
+            ManagedItem::Proxy(id) => self.proxies.iter().find(|p| p.id == id).map(|p| p.group.clone()),
+        }
+    }
+
+    /// A: 프록시 추가 창 (선택한 그룹으로)
+    pub fn add_proxy(&mut self) {
+        let group = self.selected_managed_group();
+        self.proxy_management.form = Some(ProxyForm::new_proxy(&self.proxies, group.as_deref()));
+    }
+
+    /// G: 그룹 추가 창
+    pub fn add_group(&mut self) {
+        self.proxy_management.form = Some(ProxyForm::new_group());
+    }
+
+    /// E/Enter: 선택한 프록시나 그룹 편집 창
+    pub fn edit_selected(&mut self) {
+        self.proxy_management.form = match self.selected_managed_item() {
+            Some(ManagedItem::Proxy(id)) => self.proxies.iter().find(|p| p.id == id).map(ProxyForm::edit_proxy),
+            Some(ManagedItem::Group(name)) => {
+                Some(ProxyForm::edit_group(&name, &self.groups.get(&name).cloned().unwrap_or_default()))
+            }
+            None => None,
+        };
+    }
+
+    /// C: 선택한 프록시를 복제하는 추가 창
+    pub fn clone_selected(&mut self) {
+        if let Some(ManagedItem::Proxy(id)) = self.selected_managed_item() {
+            let proxy = self.proxies.iter().find(|p| p.id == id);
+            self.proxy_management.form = proxy.map(|p| ProxyForm::clone_proxy(p, &self.proxies));
+        }
+    }
+
+    /// D/Delete: 선택한 항목 삭제 확인
+    pub fn request_delete_selected(&mut self) {
+        self.proxy_management.confirm_delete = self.selected_managed_item();
+    }
+
+    /// 삭제 확인 창에서 y
+    pub fn confirm_delete(&mut self) {
+        let Some(item) = self.proxy_management.confirm_delete.take() else {
+            return;
+        };
+        let mut proxies = self.proxies.clone();
+        let mut groups = self.groups.clone();
+        let result = crate::app::proxy_form::delete(&item, &mut proxies, &mut groups)
+            .and_then(|_| self.commit_proxy_config(proxies, groups));
+        if let Err(e) = result {
+            self.proxy_management.last_error = Some(format!("{:#}", e));
+        }
+    }
+
+    pub fn cancel_delete(&mut self) {
+        self.proxy_management.confirm_delete = None;
+    }
+
+    /// 입력 창 저장 (검사에 실패하면 창에 오류를 표시하고 유지)
+    pub fn submit_proxy_form(&mut self) {
+        let Some(form) = &self.proxy_management.form else {
+            return;
+        };
+        let mut proxies = self.proxies.clone();
+        let mut groups = self.groups.clone();
+        let result = form.submit(&proxies, &groups).and_then(|mut result| {
+            crate::app::proxy_form::store_secrets(&mut result)?;
+            crate::app::proxy_form::apply(result, &mut proxies, &mut groups);
+            self.commit_proxy_config(proxies, groups)
+        });
+        match result {
+            Ok(()) => self.proxy_management.form = None,
+            Err(e) => {
+                if let Some(form) = &mut self.proxy_management.form {
+                    form.error = Some(format!("{:#}", e));
+                }
+            }
+        }
+    }
+
+    pub fn cancel_proxy_form(&mut self) {
+        self.proxy_management.form = None;
+    }
+
+    /// 바뀐 프록시/그룹 설정을 proxies.json에 저장(원본은 .bak으로 백업)하고 화면에 바로 반영합니다.
+    fn commit_proxy_config(&mut self, proxies: Vec<Proxy>, groups: BTreeMap<String, GroupConfig>) -> anyhow::Result<()> {
+        let config = ProxyConfig { proxies, groups };
+        let config_path = get_config_path("proxies.json");
+        let backup_path = crate::app::config::replace_with_backup(&config_path, &serde_json::to_string_pretty(&config)?)?;
+        self.groups = config.groups.clone();
+        self.proxies = config.into_proxies();
+        self.proxy_management.last_error = None;
+        self.proxy_management.last_saved =
+            Some(format!("저장했습니다: {} (백업: {})", config_path.display(), backup_path.display()));
+        self.refresh_proxy_lists();
+        Ok(())
+    }
+
+    /// 프록시 목록이 바뀐 뒤 각 탭의 그룹 목록과 선택 상태를 맞춥니다.
+    fn refresh_proxy_lists(&mut self) {
+        self.resource_usage.update_groups(&self.proxies);
+        self.session_browser.update_groups(&self.proxies);
+
+        // 삭제된 프록시의 수집 결과 정리
+        let ids: std::collections::HashSet<u32> = self.proxies.iter().map(|p| p.id).collect();
+        self.resource_usage.data.retain(|d| ids.contains(&d.proxy_id));
+        if self.resource_usage.table_state.selected().is_some_and(|i| i >= self.resource_usage.data.len()) {
+            self.resource_usage.table_state.select(None);
+        }
+
+        // 트래픽 로그 탭의 선택 프록시가 없어졌으면 선택 해제
+        let selected = self.traffic_logs.selected_proxy.and_then(|id| self.proxies.iter().position(|p| p.id as usize == id));
+        if selected.is_none() {
+            self.traffic_logs.selected_proxy = None;
+        }
+        self.traffic_logs.proxy_list_index = selected.unwrap_or(0).min(self.proxies.len().saturating_sub(1));
+
+        let count = self.managed_items().len();
+        if self.proxy_management.table_state.selected().is_some_and(|i| i >= count) {
+            self.proxy_management.table_state.select(count.checked_sub(1));
+        }
     }
 
     pub fn on_up(&mut self) {
         match self.current_tab {
-            TabIndex::ProxyManagement => {}
+            TabIndex::ProxyManagement => {
+                let count = self.managed_items().len();
+                self.proxy_management.previous(count);
+            }
             TabIndex::ResourceUsage => {
                 // 컨트롤 선택 기능 제거, 항상 테이블 모드
                 self.resource_usage.selected_control = None;
@@ -95,12 +291,16 @@ impl App {
                     self.traffic_logs.previous(current_page_items);
                 }
             }
+            TabIndex::Events => self.events.previous(),
         }
     }
 
     pub fn on_down(&mut self) {
         match self.current_tab {
-            TabIndex::ProxyManagement => {}
+            TabIndex::ProxyManagement => {
+                let count = self.managed_items().len();
+                self.proxy_management.next(count);
+            }
             TabIndex::ResourceUsage => {
                 // 컨트롤 선택 기능 제거, 항상 테이블 모드
                 self.resource_usage.selected_control = None;
@@ -148,6 +348,7 @@ impl App {
                     self.traffic_logs.next(current_page_items);
                 }
             }
+            TabIndex::Events => self.events.next(),
         }
     }
 
@@ -171,6 +372,9 @@ impl App {
                     self.traffic_logs.previous_view_mode();
                 }
             }
+            TabIndex::Events => {
+                // 이벤트 탭에서는 동작 없음
+            }
         }
     }
 
@@ -194,6 +398,9 @@ impl App {
                     self.traffic_logs.next_view_mode();
                 }
             }
+            TabIndex::Events => {
+                // 이벤트 탭에서는 동작 없음
+            }
         }
     }
 
@@ -220,6 +427,7 @@ impl App {
             '2' => self.current_tab = TabIndex::ResourceUsage,
             '3' => self.current_tab = TabIndex::SessionBrowser,
             '4' => self.current_tab = TabIndex::TrafficLogs,
+            '5' => self.current_tab = TabIndex::Events,
             // +/- 키는 crossterm.rs에서 직접 처리
             _ => {}
         }
@@ -241,6 +449,45 @@ impl App {
         }
     }
 
+    /// 수신한 트랩을 보낸 주소로 프록시와 매칭해 이벤트 목록에 추가
+    /// (`resolved_hosts`: IP가 아닌 프록시 host의 DNS 조회 결과)
+    pub fn push_trap_event(
+        &mut self,
+        event: &crate::snmp::TrapEvent,
+        resolved_hosts: &std::collections::HashMap<String, Vec<std::net::IpAddr>>,
+    ) {
+        let origin = event.origin();
+        let proxy = self.proxies.iter().find(|p| {
+            p.host.parse::<std::net::IpAddr>().map(|ip| ip == origin).unwrap_or(false)
+                || resolved_hosts.get(&p.host).map(|ips| ips.contains(&origin)).unwrap_or(false)
+        });
+        self.events.push(EventRecord {
+            received_at: chrono::Local::now(),
+            source: origin.to_string(),
+            proxy_id: proxy.map(|p| p.id),
+            proxy_name: proxy.map(|p| p.alias.clone().unwrap_or_else(|| p.host.clone())),
+            group: proxy.map(|p| p.group.clone()),
+            severity: event.severity(),
+            version: event.version.to_string(),
+            kind: event.kind.label().to_string(),
+            name: event.name(),
+            trap_oid: event.trap_oid_string(),
+            details: event.summary(),
+        });
+    }
+
+    /// 현재 필터가 적용된 이벤트를 CSV로 저장
+    pub fn export_events(&mut self) {
+        let events: Vec<EventRecord> = self.events.filtered_events().into_iter().cloned().collect();
+        match crate::csv_writer::CsvWriter::save_events(&events) {
+            Ok(path) => {
+                self.events.last_export = Some(path.display().to_string());
+                self.events.last_error = None;
+            }
+            Err(e) => self.events.last_error = Some(format!("CSV 저장 실패: {}", e)),
+        }
+    }
+
     /// 검색 필터가 적용된 로그 수 반환
     pub fn get_filtered_log_count(&self) -> usize {
         if self.traffic_logs.search_query.is_empty() {
@@ -261,10 +508,24 @@ impl App {
         }
     }
 
-    /// 자원 사용률 수집 시작 (비동기)
-    pub async fn start_collection(&mut self) -> anyhow::Result<()> {
+    /// 자원 사용률 수집 (비동기)
+    ///
+    /// SNMP/SSH 수집 중에는 앱 잠금을 풀어 두어 수집이 오래 걸려도 키 입력과 화면 갱신이 멈추지 않습니다.
+    pub async fn run_collection(app: std::sync::Arc<tokio::sync::Mutex<App>>) -> anyhow::Result<()> {
+        let Some((collector, proxies_to_collect)) = app.lock().await.begin_collection()? else {
+            return Ok(());
+        };
+        let result = collector.collect_multiple(&proxies_to_collect).await;
+        app.lock().await.finish_collection(result);
+        Ok(())
+    }
+
+    /// 수집 준비 - 설정을 읽어 수집기와 수집할 프록시를 반환 (이미 수집 중이거나 프록시가 없으면 None)
+    fn begin_collection(
+        &mut self,
+    ) -> anyhow::Result<Option<(crate::collector::ResourceCollector, Vec<Proxy>)>> {
         if self.is_collecting {
-            return Ok(()); // 이미 수집 중이면 무시
+            return Ok(None); // 이미 수집 중이면 무시
         }
 
         // 설정 파일 읽기
@@ -277,13 +538,57 @@ impl App {
             .as_str()
             .unwrap_or("public")
             .to_string();
+
+        // 전역 v3 사용자 (snmp_version이 3이면 필수, 2c여도 프록시별로 v3를 쓰면 사용)
+        let v3 = match config.get("v3") {
+            Some(v3) => {
+                let v3: crate::snmp::SnmpV3Config = serde_json::from_value(v3.clone())
+                    .map_err(|e| anyhow::anyhow!("v3 설정 형식 오류: {}", e))?;
+                v3.validate()?;
+                Some(v3)
+            }
+            None => None,
+        };
+        let version = match config["snmp_version"].as_str().unwrap_or("2c") {
+            "3" | "v3" => {
+                if v3.is_none() {
+                    anyhow::bail!("snmp_version이 3이지만 v3 설정이 없습니다");
+                }
+                crate::snmp::SnmpVersion::V3
+            }
+            _ => crate::snmp::SnmpVersion::V2c,
+        };
+        let snmp = crate::collector::SnmpSettings {
+            version,
+            community,
+            v3,
+            port: config["snmp_port"].as_u64().and_then(|p| u16::try_from(p).ok()).unwrap_or(161),
+        };
         
+        // SNMP 요청 타임아웃/재시도 설정 (없으면 기본값)
+        let mut snmp_options = crate::snmp::SnmpOptions::default();
+        if let Some(timeout_ms) = config["snmp_timeout_ms"].as_u64() {
+            snmp_options.timeout = std::time::Duration::from_millis(timeout_ms.max(100));
+        }
+        if let Some(retries) = config["snmp_retries"].as_u64() {
+            snmp_options.retries = retries.min(10) as u32;
+        }
+        if let Some(backoff) = config["snmp_backoff"].as_f64() {
+            snmp_options.backoff = backoff.clamp(1.0, 10.0);
+        }
+
         let oids_json = config.get("oids").and_then(|v| v.as_object());
         let mut oids = std::collections::HashMap::new();
         if let Some(oids_obj) = oids_json {
             for (key, value) in oids_obj {
                 if let Some(oid_str) = value.as_str() {
-                    oids.insert(key.clone(), oid_str.to_string());
+                    // mem은 "ssh"이면 SSH로 수집
+                    let oid = if key == "mem" && oid_str.trim().eq_ignore_ascii_case("ssh") {
+                        "ssh".to_string()
+                    } else {
+                        resolve_config_oid(&format!("oids.{}", key), oid_str)?
+                    };
+                    oids.insert(key.clone(), oid);
                 }
             }
         }
@@ -294,21 +599,58 @@ impl App {
         if let Some(if_oids_obj) = interface_oids_json {
             for (if_name, if_config) in if_oids_obj {
                 if let Some(if_config_obj) = if_config.as_object() {
-                    let in_oid = if_config_obj.get("in_oid")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    let out_oid = if_config_obj.get("out_oid")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    if !in_oid.is_empty() || !out_oid.is_empty() {
-                        interface_oids.insert(if_name.clone(), (in_oid, out_oid));
-                    }
+                    let in_oid = resolve_config_oid(
+                        &format!("interface_oids.{}.in_oid", if_name),
+                        if_config_obj.get("in_oid").and_then(|v| v.as_str()).unwrap_or(""),
+                    )?;
+                    let out_oid = resolve_config_oid(
+                        &format!("interface_oids.{}.out_oid", if_name),
+                        if_config_obj.get("out_oid").and_then(|v| v.as_str()).unwrap_or(""),
+                    )?;
+                    // 회선 속도 (없으면 수집기가 ifHighSpeed로 조회)
+                    let speed_mbps = match if_config_obj.get("speed_mbps") {
+                        None | Some(serde_json::Value::Null) => None,
+                        Some(v) => match v.as_f64() {
+                            Some(speed) if speed > 0.0 => Some(speed),
+                            _ => anyhow::bail!("interface_oids.{}.speed_mbps는 0보다 큰 숫자여야 합니다", if_name),
+                        },
+                    };
+                    // OID가 모두 비어 있으면 수집기가 ifName/ifDescr로 ifIndex를 자동 탐색
+                    interface_oids.insert(
+                        if_name.clone(),
+                        crate::collector::InterfaceConfig { in_oid, out_oid, speed_mbps },
+                    );
                 }
             }
         }
 
+        // SSH 호스트 지표 설정 (enabled가 true일 때만 수집)
+        let host_metrics = match config.get("host_metrics") {
+            Some(section) if section["enabled"].as_bool().unwrap_or(false) => {
+                let string_list = |key: &str| -> anyhow::Result<Option<Vec<String>>> {
+                    match section.get(key) {
+                        None | Some(serde_json::Value::Null) => Ok(None),
+                        Some(serde_json::Value::Array(items)) => items
+                            .iter()
+                            .map(|item| item.as_str().filter(|s| !s.trim().is_empty()).map(|s| s.trim().to_string()))
+                            .collect::<Option<Vec<_>>>()
+                            .map(Some)
+                            .ok_or_else(|| anyhow::anyhow!("host_metrics.{}는 비어 있지 않은 문자열 배열이어야 합니다", key)),
+                        Some(_) => anyhow::bail!("host_metrics.{}는 문자열 배열이어야 합니다", key),
+                    }
+                };
+                let mut host_metrics = crate::host_metrics::HostMetricsConfig::default();
+                if let Some(mounts) = string_list("mounts")? {
+                    host_metrics.mounts = mounts;
+                }
+                if let Some(processes) = string_list("processes")? {
+                    host_metrics.processes = processes;
+                }
+                Some(host_metrics)
+            }
+            _ => None,
+        };
+
         // 필터링된 프록시 목록 가져오기
         let proxies_to_collect: Vec<Proxy> = match &self.resource_usage.selected_group {
             None => self.proxies.clone(), // 전체
@@ -321,13 +663,15 @@ impl App {
         };
 
         if proxies_to_collect.is_empty() {
-            return Ok(()); // 수집할 프록시가 없음
+            return Ok(None); // 수집할 프록시가 없음
         }
 
         self.is_collecting = true;
         self.resource_usage.last_error = None;
         self.resource_usage.collection_status = CollectionStatus::Collecting;
-        self.resource_usage.collection_progress = Some((0, proxies_to_collect.len()));
+        // 비활성이거나 점검 중인 프록시는 수집기가 건너뜀
+        let active_count = proxies_to_collect.iter().filter(|p| p.state().is_active()).count();
+        self.resource_usage.collection_progress = Some((0, active_count));
         self.resource_usage.collection_start_time = Some(chrono::Local::now());
         
         // 자동 수집이 활성화되어 있으면 다음 수집 시간 업데이트
@@ -335,22 +679,31 @@ impl App {
             self.resource_usage.update_next_auto_collection_time();
         }
 
-        // 수집 실행
-        let collector = crate::collector::ResourceCollector::new(oids, community, interface_oids);
-        
-        match collector.collect_multiple(&proxies_to_collect).await {
-            Ok(results) => {
+        let mut collector = crate::collector::ResourceCollector::new(oids, snmp, snmp_options, interface_oids);
+        if let Some(host_metrics) = host_metrics {
+            collector = collector.with_host_metrics(host_metrics);
+        }
+        Ok(Some((collector, proxies_to_collect)))
+    }
+
+    /// 수집 결과 반영
+    fn finish_collection(&mut self, result: anyhow::Result<Vec<ResourceData>>) {
+        match result {
+            Ok(mut results) => {
+                // DOWN 회선이나 멈춘 프로세스가 있는 프록시를 목록 위로 (나머지 순서는 유지)
+                results.sort_by_key(|r| !(r.has_down_interface() || r.has_missing_process()));
+
                 // 결과 저장
-                let success_count = results.iter().filter(|r| !r.collection_failed).count();
+                let success_count = results.iter().filter(|r| !r.collection_failed && r.skipped.is_none()).count();
                 let failed_count = results.iter().filter(|r| r.collection_failed).count();
-                let total_count = proxies_to_collect.len();
+                let total_count = success_count + failed_count;
                 
                 self.resource_usage.data = results;
                 let now = chrono::Local::now();
                 self.resource_usage.last_collection_time = Some(now);
                 
-                // 부분 성공도 성공으로 처리
-                if success_count > 0 {
+                // 부분 성공도 성공으로 처리 (모두 비활성/점검 중이면 실패가 아님)
+                if success_count > 0 || total_count == 0 {
                     self.resource_usage.collection_status = CollectionStatus::Success;
                     self.resource_usage.collection_progress = Some((success_count, total_count));
                     
@@ -378,9 +731,9 @@ impl App {
                     if let Err(e) = crate::csv_writer::CsvWriter::save_resource_usage(&self.resource_usage.data) {
                         let existing_error = self.resource_usage.last_error.clone();
                         self.resource_usage.last_error = Some(format!(
-                            "{}{}",
+                            "{}CSV 저장 실패: {}",
                             existing_error.map(|e| format!("{} / ", e)).unwrap_or_default(),
-                            format!("CSV 저장 실패: {}", e)
+                            e
                         ));
                     }
                 }
@@ -397,14 +750,27 @@ impl App {
         }
 
         self.is_collecting = false;
-        Ok(())
     }
 
-    /// 세션 조회 시작 (비동기)
-    pub async fn start_session_query(&mut self) -> anyhow::Result<()> {
+    /// 세션 조회 (비동기)
+    ///
+    /// SSH 조회 중에는 앱 잠금을 풀어 두어 화면이 받은 세션 수를 계속 갱신할 수 있습니다.
+    pub async fn run_session_query(app: std::sync::Arc<tokio::sync::Mutex<App>>, cancel: tokio_util::sync::CancellationToken) {
+        let Some((collector, proxies_to_query)) = app.lock().await.begin_session_query(cancel) else {
+            return;
+        };
+        let result = collector.query_multiple(&proxies_to_query).await;
+        app.lock().await.finish_session_query(proxies_to_query.len(), result);
+    }
+
+    /// 세션 조회 준비 - 조회할 프록시와 수집기를 반환 (이미 조회 중이거나 프록시가 없으면 None)
+    fn begin_session_query(
+        &mut self,
+        cancel: tokio_util::sync::CancellationToken,
+    ) -> Option<(crate::session_collector::SessionCollector, Vec<Proxy>)> {
         // 이미 조회 중이면 무시
         if self.session_browser.query_status == CollectionStatus::Collecting {
-            return Ok(());
+            return None;
         }
 
         // 필터링된 프록시 목록 가져오기
@@ -417,28 +783,60 @@ impl App {
                 .cloned()
                 .collect(),
         };
+        // 비활성이거나 점검 중인 프록시는 조회하지 않음
+        let (proxies_to_query, skipped): (Vec<Proxy>, Vec<Proxy>) =
+            proxies_to_query.into_iter().partition(|p| p.state().is_active());
+        self.session_browser.skipped_proxies = skipped.len();
 
         if proxies_to_query.is_empty() {
-            return Ok(()); // 조회할 프록시가 없음
+            // 조회할 프록시가 없음
+            self.session_browser.query_status = CollectionStatus::Idle;
+            self.session_browser.query_start_time = None;
+            return None;
         }
 
         self.session_browser.last_error = None;
         self.session_browser.query_status = CollectionStatus::Collecting;
         self.session_browser.query_progress = Some((0, proxies_to_query.len()));
         self.session_browser.query_start_time = Some(chrono::Local::now());
+        self.session_browser.rows_received = Default::default();
 
         // 세션 브라우저 설정 로드 (기본값 사용)
         let config = crate::session_collector::SessionBrowserConfig::default();
-        let collector = crate::session_collector::SessionCollector::new(config);
+        let collector = crate::session_collector::SessionCollector::new(config)
+            .with_cancel_token(cancel)
+            .with_row_counter(self.session_browser.rows_received.clone());
+        Some((collector, proxies_to_query))
+    }
 
-        // 세션 조회 실행
-        match collector.query_multiple(&proxies_to_query).await {
-            Ok(sessions) => {
-                let success_count = proxies_to_query.len();
-                let total_count = proxies_to_query.len();
+    /// 세션 조회 결과 반영
+    fn finish_session_query(
+        &mut self,
+        total_count: usize,
+        result: anyhow::Result<crate::session_collector::SessionQueryResult>,
+    ) {
+        match result {
+            Ok(result) if !result.failures.is_empty() && result.failures.len() == total_count => {
+                // 하나도 성공하지 못함 (권한 상승 실패 등 프록시별 오류 표시)
+                self.session_browser.last_error = Some(format!("세션 조회 실패: {}", result.failure_summary()));
+                self.session_browser.query_status = CollectionStatus::Failed;
+                self.session_browser.sessions = Vec::new();
+                self.session_browser.query_progress = Some((0, total_count));
+            }
+            Ok(result) => {
+                let success_count = total_count - result.failures.len();
+                // 부분 성공도 성공으로 처리하고 실패한 프록시는 경고로 표시
+                if !result.failures.is_empty() {
+                    self.session_browser.last_error = Some(format!(
+                        "일부 프록시 조회 실패 ({}개 성공, {}개 실패): {}",
+                        success_count,
+                        result.failures.len(),
+                        result.failure_summary()
+                    ));
+                }
                 
                 // 정렬 적용
-                let mut sorted_sessions = sessions;
+                let mut sorted_sessions = result.sessions;
                 Self::sort_sessions(&mut sorted_sessions, 
                     self.session_browser.sort_column, 
                     self.session_browser.sort_ascending);
@@ -449,7 +847,6 @@ impl App {
                 
                 self.session_browser.query_status = CollectionStatus::Success;
                 self.session_browser.query_progress = Some((success_count, total_count));
-                
                 // 페이지네이션 업데이트
                 self.session_browser.update_total_pages(self.session_browser.sessions.len());
                 
@@ -458,9 +855,9 @@ impl App {
                     if let Err(e) = crate::csv_writer::CsvWriter::save_sessions(&self.session_browser.sessions) {
                         let existing_error = self.session_browser.last_error.clone();
                         self.session_browser.last_error = Some(format!(
-                            "{}{}",
+                            "{}CSV 저장 실패: {}",
                             existing_error.map(|e| format!("{} / ", e)).unwrap_or_default(),
-                            format!("CSV 저장 실패: {}", e)
+                            e
                         ));
                     }
                 }
@@ -475,7 +872,6 @@ impl App {
         }
 
         self.session_browser.query_start_time = None;
-        Ok(())
     }
 
     /// 트래픽 로그 분석 시작 (비동기)
@@ -490,6 +886,8 @@ impl App {
             .find(|p| p.id == proxy_id)
             .ok_or_else(|| anyhow::anyhow!("프록시를 찾을 수 없습니다: {}", proxy_id))?;
 
+        proxy.ensure_active()?;
+
         // traffic_log_path 확인
         let log_path = proxy.traffic_log_path.as_ref()
             .ok_or_else(|| anyhow::anyhow!("프록시에 traffic_log_path가 설정되지 않았습니다"))?;
@@ -539,26 +937,58 @@ impl App {
         Ok(())
     }
 
-    /// 트래픽 로그 조회 시작 (비동기)
-    pub async fn start_traffic_log_query(&mut self, proxy_id: u32) -> anyhow::Result<()> {
+    /// 트래픽 로그 조회 (비동기)
+    ///
+    /// SSH 조회 중에는 앱 잠금을 풀어 두어 화면이 받은 라인 수를 계속 갱신할 수 있습니다.
+    pub async fn run_traffic_log_query(
+        app: std::sync::Arc<tokio::sync::Mutex<App>>,
+        proxy_id: u32,
+        cancel: tokio_util::sync::CancellationToken,
+    ) {
+        let prepared = app.lock().await.begin_traffic_log_query(proxy_id, cancel);
+        let (collector, proxy, log_path) = match prepared {
+            Ok(Some(prepared)) => prepared,
+            Ok(None) => return,
+            Err(e) => {
+                let mut app = app.lock().await;
+                app.traffic_logs.query_status = CollectionStatus::Failed;
+                app.traffic_logs.last_error = Some(format!("{}", e));
+                app.traffic_logs.query_start_time = None;
+                return;
+            }
+        };
+        let result = collector.fetch_logs(&proxy, &log_path).await;
+        app.lock().await.finish_traffic_log_query(result);
+    }
+
+    /// 트래픽 로그 조회 준비 - 수집기와 대상 프록시, 로그 경로를 반환 (이미 조회 중이면 None)
+    fn begin_traffic_log_query(
+        &mut self,
+        proxy_id: u32,
+        cancel: tokio_util::sync::CancellationToken,
+    ) -> anyhow::Result<Option<(crate::traffic_log_collector::TrafficLogCollector, Proxy, String)>> {
         // 이미 조회 중이면 무시
         if self.traffic_logs.query_status == CollectionStatus::Collecting {
-            return Ok(());
+            return Ok(None);
         }
 
         // 프록시 찾기
         let proxy = self.proxies.iter()
             .find(|p| p.id == proxy_id)
-            .ok_or_else(|| anyhow::anyhow!("프록시를 찾을 수 없습니다: {}", proxy_id))?;
+            .ok_or_else(|| anyhow::anyhow!("프록시를 찾을 수 없습니다: {}", proxy_id))?
+            .clone();
+
+        proxy.ensure_active()?;
 
         // traffic_log_path 확인
-        let log_path = proxy.traffic_log_path.as_ref()
+        let log_path = proxy.traffic_log_path.clone()
             .ok_or_else(|| anyhow::anyhow!("프록시에 traffic_log_path가 설정되지 않았습니다"))?;
 
         self.traffic_logs.last_error = None;
         self.traffic_logs.query_status = CollectionStatus::Collecting;
         self.traffic_logs.query_progress = Some((0, 1));
         self.traffic_logs.query_start_time = Some(chrono::Local::now());
+        self.traffic_logs.lines_received = Default::default();
 
         // 트래픽 로그 수집기 설정
         let config = crate::traffic_log_collector::TrafficLogCollectorConfig {
@@ -567,10 +997,15 @@ impl App {
             limit: self.traffic_logs.log_limit,
             direction: crate::traffic_log_collector::LogDirection::Tail,
         };
-        let collector = crate::traffic_log_collector::TrafficLogCollector::new(config);
+        let collector = crate::traffic_log_collector::TrafficLogCollector::new(config)
+            .with_cancel_token(cancel)
+            .with_line_counter(self.traffic_logs.lines_received.clone());
+        Ok(Some((collector, proxy, log_path)))
+    }
 
-        // 로그 조회
-        match collector.fetch_logs(proxy, log_path).await {
+    /// 트래픽 로그 조회 결과 반영
+    fn finish_traffic_log_query(&mut self, result: anyhow::Result<Vec<String>>) {
+        match result {
             Ok(lines) => {
                 // 로그 파싱
                 let mut records = Vec::new();
@@ -612,7 +1047,111 @@ impl App {
         }
 
         self.traffic_logs.query_start_time = None;
-        Ok(())
+    }
+
+    /// 트래픽 로그 파일 다운로드 후 분석 (비동기)
+    ///
+    /// 현재 로그와 최근 하루 동안의 회전 로그를 SFTP로 캐시에 받고(끊긴 파일은 이어받기),
+    /// 받은 파일 전체를 분석합니다. 다운로드 중에는 앱 잠금을 풀어 두어 진행률을 표시합니다.
+    pub async fn run_log_download(
+        app: std::sync::Arc<tokio::sync::Mutex<App>>,
+        proxy_id: u32,
+        cancel: tokio_util::sync::CancellationToken,
+    ) {
+        let prepared = app.lock().await.begin_log_download(proxy_id, cancel);
+        let (transfer, cache, log_path, progress, top_n) = match prepared {
+            Ok(Some(prepared)) => prepared,
+            Ok(None) => return,
+            Err(e) => {
+                let mut app = app.lock().await;
+                app.traffic_logs.download_status = CollectionStatus::Failed;
+                app.traffic_logs.last_error = Some(format!("{}", e));
+                app.traffic_logs.download_start_time = None;
+                return;
+            }
+        };
+        let since = (chrono::Utc::now().timestamp() - 24 * 60 * 60).max(0) as u64;
+        let result = async {
+            let cached = cache.sync(transfer.as_ref(), &log_path, Some(since), &progress).await?;
+            let paths: Vec<std::path::PathBuf> = cached.iter().map(|log| log.path.clone()).collect();
+            let analysis = tokio::task::spawn_blocking(move || {
+                crate::traffic_log_parser::TrafficLogAnalyzer::new(top_n).analyze_files(&paths)
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!("분석 태스크 실패: {}", e))??;
+            Ok((cached, analysis))
+        }
+        .await;
+        app.lock().await.finish_log_download(result);
+    }
+
+    /// 로그 다운로드 준비 - 전송기, 캐시, 로그 경로, 진행 상황, TOP N을 반환 (이미 받는 중이면 None)
+    #[allow(clippy::type_complexity)]
+    fn begin_log_download(
+        &mut self,
+        proxy_id: u32,
+        cancel: tokio_util::sync::CancellationToken,
+    ) -> anyhow::Result<Option<(
+        std::sync::Arc<dyn crate::log_cache::FileTransfer>,
+        crate::log_cache::LogCache,
+        String,
+        std::sync::Arc<crate::log_cache::DownloadProgress>,
+        usize,
+    )>> {
+        // 이미 받는 중이면 무시
+        if self.traffic_logs.download_status == CollectionStatus::Collecting {
+            return Ok(None);
+        }
+
+        // 프록시 찾기
+        let proxy = self.proxies.iter()
+            .find(|p| p.id == proxy_id)
+            .ok_or_else(|| anyhow::anyhow!("프록시를 찾을 수 없습니다: {}", proxy_id))?;
+
+        proxy.ensure_active()?;
+
+        // traffic_log_path 확인
+        let log_path = proxy.traffic_log_path.clone()
+            .ok_or_else(|| anyhow::anyhow!("프록시에 traffic_log_path가 설정되지 않았습니다"))?;
+
+        self.traffic_logs.last_error = None;
+        self.traffic_logs.download_status = CollectionStatus::Collecting;
+        self.traffic_logs.download_start_time = Some(chrono::Local::now());
+        self.traffic_logs.download_progress = Default::default();
+
+        let transfer = crate::log_cache::transfer_for(proxy, proxy.port, std::time::Duration::from_secs(30), cancel);
+        let cache = crate::log_cache::LogCache::for_proxy(proxy);
+        Ok(Some((transfer, cache, log_path, self.traffic_logs.download_progress.clone(), self.traffic_logs.top_n)))
+    }
+
+    /// 로그 다운로드/분석 결과 반영
+    fn finish_log_download(
+        &mut self,
+        result: anyhow::Result<(Vec<crate::log_cache::CachedLog>, crate::traffic_log_parser::TopNAnalysis)>,
+    ) {
+        match result {
+            Ok((cached, analysis)) => {
+                self.traffic_logs.cached_logs = cached;
+                self.traffic_logs.top_n_analysis = Some(analysis);
+                self.traffic_logs.download_status = CollectionStatus::Success;
+                self.traffic_logs.analysis_status = CollectionStatus::Success;
+                self.traffic_logs.last_analysis_time = Some(chrono::Local::now());
+
+                // CSV 저장
+                if let Some(ref analysis) = self.traffic_logs.top_n_analysis {
+                    if let Err(e) = crate::csv_writer::CsvWriter::save_traffic_analysis(analysis) {
+                        self.traffic_logs.last_error = Some(format!("CSV 저장 실패: {}", e));
+                    }
+                }
+            }
+            Err(e) => {
+                // 받다 만 파일은 캐시에 남아 있어 다음 다운로드에서 이어받음
+                self.traffic_logs.last_error = Some(format!("로그 파일 다운로드 실패: {}", e));
+                self.traffic_logs.download_status = CollectionStatus::Failed;
+            }
+        }
+
+        self.traffic_logs.download_start_time = None;
     }
 
     /// 세션 목록 정렬
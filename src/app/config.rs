@@ -1,3 +1,5 @@
+use anyhow::Context;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 /// 실행 파일의 디렉터리를 기준으로 config 파일 경로를 반환합니다.
@@ -27,3 +29,56 @@ pub fn get_config_path(filename: &str) -> PathBuf {
     // 둘 다 없으면 기본값으로 현재 작업 디렉터리 반환 (에러는 나중에 발생)
     Path::new("config").join(filename)
 }
+
+/// resource_config.json의 `trap` 블록을 읽습니다. (블록이 없으면 비활성화된 기본값)
+pub fn load_trap_config() -> anyhow::Result<crate::snmp::TrapConfig> {
+    let config_path = get_config_path("resource_config.json");
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| anyhow::anyhow!("설정 파일을 찾을 수 없습니다: {} (에러: {})", config_path.display(), e))?;
+    let config: serde_json::Value = serde_json::from_str(&content)?;
+    let Some(trap) = config.get("trap") else {
+        return Ok(crate::snmp::TrapConfig::default());
+    };
+    let trap: crate::snmp::TrapConfig = serde_json::from_value(trap.clone())
+        .map_err(|e| anyhow::anyhow!("trap 설정 형식 오류: {}", e))?;
+    trap.validate()?;
+    Ok(trap)
+}
+
+/// resource_config.json의 OID 항목을 숫자 OID로 변환합니다.
+/// 숫자 OID와 MIB 이름(`UCD-SNMP-MIB::ssCpuIdle.0`)을 받으며, 빈 문자열(수집 안 함)은 그대로 둡니다.
+pub fn resolve_config_oid(field: &str, value: &str) -> anyhow::Result<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(String::new());
+    }
+    crate::snmp::mib::normalize(value).map_err(|e| anyhow::anyhow!("{} OID 설정 오류 ({}): {}", field, value, e))
+}
+
+/// `path`를 임시 파일에 쓴 뒤 이름을 바꿔 교체합니다. (유닉스에서는 소유자만 읽을 수 있게)
+pub fn write_atomic(path: &Path, content: &str) -> anyhow::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(&tmp_path)
+        .with_context(|| format!("파일을 만들 수 없습니다: {}", tmp_path.display()))?;
+    file.write_all(content.as_bytes())
+        .and_then(|_| file.write_all(b"\n"))
+        .and_then(|_| file.sync_all())
+        .with_context(|| format!("파일 쓰기 실패: {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path).with_context(|| format!("파일을 교체할 수 없습니다: {}", path.display()))
+}
+
+/// 기존 파일을 `<이름>.json.bak`으로 백업한 뒤 `content`로 교체하고, 백업 경로를 반환합니다.
+pub fn replace_with_backup(path: &Path, content: &str) -> anyhow::Result<PathBuf> {
+    let backup_path = path.with_extension("json.bak");
+    if path.exists() {
+        std::fs::copy(path, &backup_path)
+            .with_context(|| format!("백업 파일을 만들 수 없습니다: {}", backup_path.display()))?;
+    }
+    write_atomic(path, content)?;
+    Ok(backup_path)
+}
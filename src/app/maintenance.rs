@@ -0,0 +1,172 @@
+//! 프록시 점검 시간
+//!
+//! 점검 시간 중인 프록시는 수집하지 않고 화면에 "점검 중"으로 표시합니다.
+//!
+//! ```json
+//! "maintenance": [
+//!   { "start": "2026-10-20 01:00", "end": "2026-10-20 05:00", "reason": "MWG 업그레이드" },
+//!   { "days": ["sat", "sun"], "start": "23:00", "end": "02:00" }
+//! ]
+//! ```
+//!
+//! 날짜를 쓰면 한 번, 시각만 쓰면 매일(`days`를 쓰면 그 요일마다) 반복합니다.
+//! 반복 점검의 `end`가 `start`보다 이르면 다음 날 `end`까지입니다. 시각은 모두 이 프로그램이 실행되는 곳의 현지 시각입니다.
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+const TIME_FORMAT: &str = "%H:%M";
+
+/// 점검 시간 설정
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    /// "YYYY-MM-DD HH:MM" (한 번) 또는 "HH:MM" (반복)
+    pub start: String,
+    pub end: String,
+    /// 반복할 요일 ("mon" ~ "sun", 비우면 매일)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// 해석한 점검 일정
+enum Schedule {
+    Once { start: NaiveDateTime, end: NaiveDateTime },
+    Recurring { start: NaiveTime, end: NaiveTime, days: Vec<Weekday> },
+}
+
+impl MaintenanceWindow {
+    fn schedule(&self) -> Result<Schedule> {
+        let once = (
+            NaiveDateTime::parse_from_str(self.start.trim(), DATE_TIME_FORMAT),
+            NaiveDateTime::parse_from_str(self.end.trim(), DATE_TIME_FORMAT),
+        );
+        if let (Ok(start), Ok(end)) = once {
+            if end <= start {
+                anyhow::bail!("끝나는 시각({})이 시작 시각({})보다 늦어야 합니다", self.end, self.start);
+            }
+            return Ok(Schedule::Once { start, end });
+        }
+
+        let parse_time = |value: &str| {
+            NaiveTime::parse_from_str(value.trim(), TIME_FORMAT).with_context(|| {
+                format!("시각 형식이 올바르지 않습니다: {} (\"YYYY-MM-DD HH:MM\" 또는 \"HH:MM\")", value)
+            })
+        };
+        let start = parse_time(&self.start)?;
+        let end = parse_time(&self.end)?;
+        if start == end {
+            anyhow::bail!("시작 시각과 끝나는 시각이 같습니다: {}", self.start);
+        }
+        let days = self
+            .days
+            .iter()
+            .map(|day| day.trim().parse::<Weekday>().map_err(|_| anyhow::anyhow!("요일이 올바르지 않습니다: {}", day)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Schedule::Recurring { start, end, days })
+    }
+
+    /// 설정 형식 검사
+    pub fn validate(&self) -> Result<()> {
+        self.schedule().map(|_| ())
+    }
+
+    /// `now`가 점검 시간 안이면 끝나는 시각 (형식이 잘못된 설정은 점검 시간이 아닌 것으로 봄)
+    pub fn active_until(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self.schedule().ok()? {
+            Schedule::Once { start, end } => (start <= now && now < end).then_some(end),
+            Schedule::Recurring { start, end, days } => {
+                let on = |day: Weekday| days.is_empty() || days.contains(&day);
+                let today = now.date();
+                let time = now.time();
+                if start < end {
+                    (on(today.weekday()) && start <= time && time < end).then(|| today.and_time(end))
+                } else if time >= start && on(today.weekday()) {
+                    // 자정을 넘는 점검: 오늘 시작해 내일 끝남
+                    Some((today + Duration::days(1)).and_time(end))
+                } else {
+                    // 어제 시작한 점검이 아직 끝나지 않음
+                    (time < end && on(today.pred_opt()?.weekday())).then(|| today.and_time(end))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(start: &str, end: &str, days: &[&str]) -> MaintenanceWindow {
+        MaintenanceWindow {
+            start: start.to_string(),
+            end: end.to_string(),
+            days: days.iter().map(|d| d.to_string()).collect(),
+            reason: None,
+        }
+    }
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn one_off_and_recurring_windows() {
+        let once = window("2026-10-20 01:00", "2026-10-20 05:00", &[]);
+        assert_eq!(once.active_until(at("2026-10-20 00:59")), None);
+        assert_eq!(once.active_until(at("2026-10-20 01:00")), Some(at("2026-10-20 05:00")));
+        assert_eq!(once.active_until(at("2026-10-20 05:00")), None);
+
+        // 2026-10-17은 토요일
+        let weekend = window("23:00", "02:00", &["sat"]);
+        assert_eq!(weekend.active_until(at("2026-10-17 22:59")), None);
+        assert_eq!(weekend.active_until(at("2026-10-17 23:30")), Some(at("2026-10-18 02:00")));
+        assert_eq!(weekend.active_until(at("2026-10-18 01:59")), Some(at("2026-10-18 02:00")));
+        assert_eq!(weekend.active_until(at("2026-10-18 23:30")), None);
+
+        let daily = window("03:00", "04:00", &[]);
+        assert_eq!(daily.active_until(at("2026-10-21 03:15")), Some(at("2026-10-21 04:00")));
+        assert_eq!(daily.active_until(at("2026-10-21 04:00")), None);
+    }
+
+    #[test]
+    fn proxy_state_follows_enabled_flag_and_windows() {
+        use crate::app::{Proxy, ProxyState};
+        let proxy: Proxy = serde_json::from_str(
+            r#"{ "id": 1, "host": "10.0.0.1", "port": 22, "username": "u", "group": "g",
+                 "maintenance": [{ "start": "02:00", "end": "04:00", "reason": "백업" },
+                                 { "start": "2026-10-20 03:00", "end": "2026-10-20 06:00" }] }"#,
+        )
+        .unwrap();
+        // enabled를 생략하면 활성, 저장할 때도 생략
+        assert!(proxy.enabled);
+        assert!(!serde_json::to_string(&proxy).unwrap().contains("enabled"));
+
+        assert_eq!(proxy.state_at(at("2026-10-19 05:00")), ProxyState::Active);
+        assert_eq!(
+            proxy.state_at(at("2026-10-19 02:30")),
+            ProxyState::Maintenance { until: at("2026-10-19 04:00"), reason: Some("백업".to_string()) }
+        );
+        // 겹치면 늦게 끝나는 점검
+        assert_eq!(proxy.state_at(at("2026-10-20 03:30")), ProxyState::Maintenance { until: at("2026-10-20 06:00"), reason: None });
+
+        let disabled = Proxy { enabled: false, ..proxy };
+        assert_eq!(disabled.state_at(at("2026-10-19 05:00")), ProxyState::Disabled);
+        assert!(serde_json::to_string(&disabled).unwrap().contains(r#""enabled":false"#));
+    }
+
+    #[test]
+    fn invalid_windows_are_rejected() {
+        assert!(window("2026-10-20 05:00", "2026-10-20 01:00", &[]).validate().is_err());
+        assert!(window("02:00", "02:00", &[]).validate().is_err());
+        assert_eq!(
+            window("02:00", "03:00", &["someday"]).validate().unwrap_err().to_string(),
+            "요일이 올바르지 않습니다: someday"
+        );
+        assert!(window("2시", "03:00", &[]).validate().unwrap_err().to_string().starts_with("시각 형식이"));
+        assert_eq!(window("2시", "03:00", &[]).active_until(at("2026-10-20 02:30")), None);
+    }
+}
@@ -1,6 +1,9 @@
 pub mod types;
 pub mod states;
 pub mod config;
+pub mod maintenance;
+pub mod proxy_form;
+#[allow(clippy::module_inception)]
 pub mod app;
 
 pub use types::*;
@@ -0,0 +1,537 @@
+//! 프록시 관리 탭의 추가/편집 입력 창
+//!
+//! 입력 창은 문자열 칸 목록으로 편집하고, 저장할 때 검사해 `Proxy`나 그룹 설정으로 바꿉니다.
+//! 입력 창에 없는 설정(권한 상승, SNMP, 프록시별 점프 호스트, 점검 시간)은 편집 전 값을 그대로 유지합니다.
+
+use crate::app::types::{GroupConfig, Proxy};
+use crate::command_runner::ExecMode;
+use crate::ssh::JumpHost;
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 프록시 관리 목록의 행
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManagedItem {
+    Group(String),
+    Proxy(u32),
+}
+
+/// 그룹 헤더 뒤에 그 그룹의 프록시를 놓은 목록 (그룹은 이름순, 프록시는 ID순)
+/// 프록시가 없어도 그룹 설정에 있는 그룹은 포함합니다.
+pub fn managed_items(proxies: &[Proxy], groups: &BTreeMap<String, GroupConfig>) -> Vec<ManagedItem> {
+    let mut names: BTreeSet<&str> = groups.keys().map(String::as_str).collect();
+    names.extend(proxies.iter().map(|p| p.group.as_str()));
+
+    let mut items = Vec::new();
+    for name in names {
+        items.push(ManagedItem::Group(name.to_string()));
+        let mut ids: Vec<u32> = proxies.iter().filter(|p| p.group == name).map(|p| p.id).collect();
+        ids.sort_unstable();
+        items.extend(ids.into_iter().map(ManagedItem::Proxy));
+    }
+    items
+}
+
+/// 입력 칸 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Text,
+    /// 비밀번호 (`env:`/`cmd:`/`vault:` 참조가 아니면 가려서 표시)
+    Secret,
+    /// 정해진 값 중 하나 (Space/←/→로 변경)
+    Choice(&'static [&'static str]),
+}
+
+const YES_NO: &[&str] = &["no", "yes"];
+const EXEC_MODES: &[&str] = &["ssh", "local"];
+
+/// 입력 칸
+#[derive(Debug, Clone)]
+pub struct FormField {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub value: String,
+    pub kind: FieldKind,
+}
+
+impl FormField {
+    fn text(key: &'static str, label: &'static str, value: impl Into<String>) -> Self {
+        Self { key, label, value: value.into(), kind: FieldKind::Text }
+    }
+
+    fn secret(key: &'static str, label: &'static str, value: impl Into<String>) -> Self {
+        Self { key, label, value: value.into(), kind: FieldKind::Secret }
+    }
+
+    fn choice(key: &'static str, label: &'static str, options: &'static [&'static str], value: &str) -> Self {
+        Self { key, label, value: value.to_string(), kind: FieldKind::Choice(options) }
+    }
+
+    /// 화면에 표시할 값
+    pub fn display_value(&self) -> String {
+        match self.kind {
+            FieldKind::Secret if !crate::credential::is_reference(&self.value) => "*".repeat(self.value.chars().count()),
+            _ => self.value.clone(),
+        }
+    }
+}
+
+/// 입력 창에서 편집하는 대상
+#[derive(Debug, Clone)]
+pub enum FormTarget {
+    /// `original_id`가 없으면 새 프록시 (`base`는 입력 창에 없는 설정의 원본)
+    Proxy { original_id: Option<u32>, base: Box<Proxy> },
+    /// `original_name`이 없으면 새 그룹
+    Group { original_name: Option<String>, base: GroupConfig },
+}
+
+/// 검사를 통과한 입력 결과
+#[derive(Debug, Clone)]
+pub enum FormResult {
+    Proxy { original_id: Option<u32>, proxy: Box<Proxy> },
+    Group { original_name: Option<String>, name: String, config: GroupConfig },
+}
+
+/// 프록시/그룹 추가·편집 입력 창
+#[derive(Debug, Clone)]
+pub struct ProxyForm {
+    pub target: FormTarget,
+    pub fields: Vec<FormField>,
+    pub focused: usize,
+    pub error: Option<String>, // 마지막 저장 시도의 검사 오류
+}
+
+impl ProxyForm {
+    /// 새 프록시 (다음 ID, 선택한 그룹)
+    pub fn new_proxy(proxies: &[Proxy], group: Option<&str>) -> Self {
+        let proxy = Proxy {
+            id: next_id(proxies),
+            port: 22,
+            group: group.unwrap_or_default().to_string(),
+            ..Proxy::default()
+        };
+        Self::for_proxy(None, proxy)
+    }
+
+    pub fn edit_proxy(proxy: &Proxy) -> Self {
+        Self::for_proxy(Some(proxy.id), proxy.clone())
+    }
+
+    /// 설정을 복사한 새 프록시 (다음 ID)
+    pub fn clone_proxy(proxy: &Proxy, proxies: &[Proxy]) -> Self {
+        let copy = Proxy {
+            id: next_id(proxies),
+            alias: proxy.alias.as_ref().map(|alias| format!("{}-복제", alias)),
+            ..proxy.clone()
+        };
+        Self::for_proxy(None, copy)
+    }
+
+    pub fn new_group() -> Self {
+        Self::for_group(None, "", GroupConfig::default())
+    }
+
+    pub fn edit_group(name: &str, config: &GroupConfig) -> Self {
+        Self::for_group(Some(name.to_string()), name, config.clone())
+    }
+
+    fn for_proxy(original_id: Option<u32>, proxy: Proxy) -> Self {
+        let fields = vec![
+            FormField::text("id", "ID", proxy.id.to_string()),
+            FormField::text("alias", "별칭", proxy.alias.clone().unwrap_or_default()),
+            FormField::text("group", "그룹", proxy.group.clone()),
+            FormField::choice("enabled", "사용", YES_NO, if proxy.enabled { "yes" } else { "no" }),
+            FormField::text("host", "호스트", proxy.host.clone()),
+            FormField::text("port", "포트", proxy.port.to_string()),
+            FormField::text("username", "사용자", proxy.username.clone()),
+            FormField::secret("password", "비밀번호", proxy.password.clone()),
+            FormField::text("ssh_key_path", "개인키 경로", proxy.ssh_key_path.clone().unwrap_or_default()),
+            FormField::secret("ssh_key_passphrase", "개인키 암호", proxy.ssh_key_passphrase.clone().unwrap_or_default()),
+            FormField::choice("ssh_agent", "ssh-agent", YES_NO, if proxy.ssh_agent { "yes" } else { "no" }),
+            FormField::choice("exec_mode", "실행 위치", EXEC_MODES, if proxy.exec_mode.is_ssh() { "ssh" } else { "local" }),
+            FormField::text("traffic_log_path", "트래픽 로그 경로", proxy.traffic_log_path.clone().unwrap_or_default()),
+        ];
+        Self {
+            target: FormTarget::Proxy { original_id, base: Box::new(proxy) },
+            fields,
+            focused: 0,
+            error: None,
+        }
+    }
+
+    fn for_group(original_name: Option<String>, name: &str, config: GroupConfig) -> Self {
+        let jump_host = config.jump_host.clone();
+        let jump = |f: fn(&JumpHost) -> String| jump_host.as_ref().map(f).unwrap_or_default();
+        let fields = vec![
+            FormField::text("name", "그룹 이름", name),
+            FormField::text("jump_host", "점프 호스트", jump(|j| j.host.clone())),
+            FormField::text("jump_port", "점프 포트", jump_host.as_ref().map(|j| j.port).unwrap_or(22).to_string()),
+            FormField::text("jump_username", "점프 사용자", jump(|j| j.username.clone())),
+            FormField::secret("jump_password", "점프 비밀번호", jump(|j| j.password.clone())),
+            FormField::text("jump_key_path", "점프 개인키 경로", jump(|j| j.ssh_key_path.clone().unwrap_or_default())),
+            FormField::secret(
+                "jump_key_passphrase",
+                "점프 개인키 암호",
+                jump(|j| j.ssh_key_passphrase.clone().unwrap_or_default()),
+            ),
+            FormField::choice(
+                "jump_agent",
+                "점프 ssh-agent",
+                YES_NO,
+                if jump_host.as_ref().is_some_and(|j| j.ssh_agent) { "yes" } else { "no" },
+            ),
+        ];
+        Self {
+            target: FormTarget::Group { original_name, base: config },
+            fields,
+            focused: 0,
+            error: None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match &self.target {
+            FormTarget::Proxy { original_id: Some(id), .. } => format!("프록시 편집 (ID {})", id),
+            FormTarget::Proxy { original_id: None, .. } => "프록시 추가".to_string(),
+            FormTarget::Group { original_name: Some(name), .. } => format!("그룹 편집 ({})", name),
+            FormTarget::Group { original_name: None, .. } => "그룹 추가".to_string(),
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        self.focused = (self.focused + 1) % self.fields.len();
+    }
+
+    pub fn previous_field(&mut self) {
+        self.focused = (self.focused + self.fields.len() - 1) % self.fields.len();
+    }
+
+    /// 선택한 칸에 문자 입력 (선택 칸은 Space로 값 변경)
+    pub fn insert_char(&mut self, c: char) {
+        match self.fields[self.focused].kind {
+            FieldKind::Choice(_) if c == ' ' => self.cycle_choice(true),
+            FieldKind::Choice(_) => {}
+            _ => self.fields[self.focused].value.push(c),
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        let field = &mut self.fields[self.focused];
+        if !matches!(field.kind, FieldKind::Choice(_)) {
+            field.value.pop();
+        }
+    }
+
+    /// 선택 칸이면 다음(이전) 값으로 변경
+    pub fn cycle_choice(&mut self, forward: bool) {
+        let field = &mut self.fields[self.focused];
+        if let FieldKind::Choice(options) = field.kind {
+            let index = options.iter().position(|o| *o == field.value).unwrap_or(0);
+            let next = if forward { index + 1 } else { index + options.len() - 1 };
+            field.value = options[next % options.len()].to_string();
+        }
+    }
+
+    /// 평문 비밀번호를 입력했는데 볼트에 저장할 수 없으면 경고 문구
+    pub fn plaintext_warning(&self) -> Option<&'static str> {
+        let plaintext = self.fields.iter().any(|f| {
+            let value = f.value.trim();
+            f.kind == FieldKind::Secret && !value.is_empty() && !crate::credential::is_reference(value)
+        });
+        (plaintext && !crate::vault::is_writable())
+            .then_some("볼트가 열려 있지 않아 입력한 비밀번호는 proxies.json에 평문으로 저장됩니다 (vault migrate로 옮길 수 있음)")
+    }
+
+    fn value(&self, key: &str) -> &str {
+        self.fields.iter().find(|f| f.key == key).map(|f| f.value.trim()).unwrap_or_default()
+    }
+
+    /// 비어 있으면 None
+    fn optional(&self, key: &str) -> Option<String> {
+        Some(self.value(key)).filter(|v| !v.is_empty()).map(str::to_string)
+    }
+
+    /// 입력을 검사해 저장할 설정으로 바꿉니다.
+    pub fn submit(&self, proxies: &[Proxy], groups: &BTreeMap<String, GroupConfig>) -> Result<FormResult> {
+        match &self.target {
+            FormTarget::Proxy { original_id, base } => {
+                let proxy = self.proxy_from_fields(base, *original_id, proxies)?;
+                Ok(FormResult::Proxy { original_id: *original_id, proxy: Box::new(proxy) })
+            }
+            FormTarget::Group { original_name, base } => {
+                let name = self.value("name").to_string();
+                if name.is_empty() {
+                    anyhow::bail!("그룹 이름을 입력하세요");
+                }
+                let renamed = original_name.as_deref() != Some(name.as_str());
+                let exists = groups.contains_key(&name) || proxies.iter().any(|p| p.group == name);
+                if renamed && exists {
+                    anyhow::bail!("이미 있는 그룹입니다: {}", name);
+                }
+                let mut config = base.clone();
+                config.jump_host = self.jump_host_from_fields()?;
+                Ok(FormResult::Group { original_name: original_name.clone(), name, config })
+            }
+        }
+    }
+
+    fn proxy_from_fields(&self, base: &Proxy, original_id: Option<u32>, proxies: &[Proxy]) -> Result<Proxy> {
+        let id: u32 = self.value("id").parse().map_err(|_| anyhow::anyhow!("ID는 숫자여야 합니다"))?;
+        if Some(id) != original_id && proxies.iter().any(|p| p.id == id) {
+            anyhow::bail!("이미 사용 중인 ID입니다: {}", id);
+        }
+        let host = self.value("host");
+        if host.is_empty() || host.contains(char::is_whitespace) {
+            anyhow::bail!("호스트를 공백 없이 입력하세요");
+        }
+        let port = parse_port(self.value("port"), "포트")?;
+        let username = self.value("username");
+        if username.is_empty() {
+            anyhow::bail!("사용자를 입력하세요");
+        }
+        let group = self.value("group");
+        if group.is_empty() {
+            anyhow::bail!("그룹을 입력하세요");
+        }
+        let traffic_log_path = self.optional("traffic_log_path");
+        if traffic_log_path.as_ref().is_some_and(|path| !path.starts_with('/')) {
+            anyhow::bail!("트래픽 로그 경로는 절대 경로여야 합니다");
+        }
+
+        let proxy = Proxy {
+            id,
+            host: host.to_string(),
+            port,
+            username: username.to_string(),
+            password: self.value("password").to_string(),
+            group: group.to_string(),
+            alias: self.optional("alias"),
+            traffic_log_path,
+            ssh_key_path: self.optional("ssh_key_path"),
+            ssh_key_passphrase: self.optional("ssh_key_passphrase"),
+            ssh_agent: self.value("ssh_agent") == "yes",
+            exec_mode: if self.value("exec_mode") == "local" { ExecMode::Local } else { ExecMode::Ssh },
+            enabled: self.value("enabled") == "yes",
+            ..base.clone()
+        };
+        if proxy.exec_mode.is_ssh() && proxy.password.is_empty() && proxy.ssh_key_path.is_none() && !proxy.ssh_agent {
+            anyhow::bail!("SSH 인증 방법(비밀번호, 개인키, ssh-agent)을 하나 이상 설정하세요");
+        }
+        proxy.validate_exec_mode()?;
+        Ok(proxy)
+    }
+
+    fn jump_host_from_fields(&self) -> Result<Option<JumpHost>> {
+        let Some(host) = self.optional("jump_host") else {
+            return Ok(None);
+        };
+        if host.contains(char::is_whitespace) {
+            anyhow::bail!("점프 호스트를 공백 없이 입력하세요");
+        }
+        let username = self.value("jump_username");
+        if username.is_empty() {
+            anyhow::bail!("점프 사용자를 입력하세요");
+        }
+        Ok(Some(JumpHost {
+            host,
+            port: parse_port(self.value("jump_port"), "점프 포트")?,
+            username: username.to_string(),
+            password: self.value("jump_password").to_string(),
+            ssh_key_path: self.optional("jump_key_path"),
+            ssh_key_passphrase: self.optional("jump_key_passphrase"),
+            ssh_agent: self.value("jump_agent") == "yes",
+        }))
+    }
+}
+
+fn parse_port(value: &str, label: &str) -> Result<u16> {
+    match value.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port),
+        _ => anyhow::bail!("{}는 1~65535 사이의 숫자여야 합니다", label),
+    }
+}
+
+/// 사용 중인 가장 큰 ID 다음 번호
+fn next_id(proxies: &[Proxy]) -> u32 {
+    proxies.iter().map(|p| p.id).max().map_or(1, |id| id + 1)
+}
+
+/// 볼트가 열려 있으면 입력 결과의 평문 비밀번호를 볼트로 옮기고 `vault:` 참조로 바꿉니다.
+/// 항목 이름은 `vault migrate`와 같습니다. (`proxy-<id>.password`, `group-<이름>.jump_host.password` 등)
+pub fn store_secrets(result: &mut FormResult) -> Result<()> {
+    let mut secrets = Vec::new();
+    let (owner, jump_host) = match result {
+        FormResult::Proxy { proxy, .. } => {
+            let owner = format!("proxy-{}", proxy.id);
+            secrets.push((format!("{}.password", owner), &mut proxy.password));
+            if let Some(passphrase) = &mut proxy.ssh_key_passphrase {
+                secrets.push((format!("{}.ssh_key_passphrase", owner), passphrase));
+            }
+            (owner, None)
+        }
+        FormResult::Group { name, config, .. } => (format!("group-{}", name), config.jump_host.as_mut()),
+    };
+    if let Some(jump_host) = jump_host {
+        secrets.push((format!("{}.jump_host.password", owner), &mut jump_host.password));
+        if let Some(passphrase) = &mut jump_host.ssh_key_passphrase {
+            secrets.push((format!("{}.jump_host.ssh_key_passphrase", owner), passphrase));
+        }
+    }
+    crate::vault::store(secrets)?;
+    Ok(())
+}
+
+/// 입력 결과를 프록시/그룹 목록에 반영합니다. (그룹 이름을 바꾸면 소속 프록시도 함께 옮김)
+pub fn apply(result: FormResult, proxies: &mut Vec<Proxy>, groups: &mut BTreeMap<String, GroupConfig>) {
+    match result {
+        FormResult::Proxy { original_id, proxy } => {
+            match original_id.and_then(|id| proxies.iter().position(|p| p.id == id)) {
+                Some(index) => proxies[index] = *proxy,
+                None => proxies.push(*proxy),
+            }
+        }
+        FormResult::Group { original_name, name, config } => {
+            if let Some(original_name) = original_name.filter(|original| *original != name) {
+                groups.remove(&original_name);
+                for proxy in proxies.iter_mut().filter(|p| p.group == original_name) {
+                    proxy.group = name.clone();
+                }
+            }
+            groups.insert(name, config);
+        }
+    }
+}
+
+/// 프록시나 그룹을 삭제합니다. (프록시가 남아 있는 그룹은 삭제하지 않음)
+pub fn delete(item: &ManagedItem, proxies: &mut Vec<Proxy>, groups: &mut BTreeMap<String, GroupConfig>) -> Result<()> {
+    match item {
+        ManagedItem::Proxy(id) => proxies.retain(|p| p.id != *id),
+        ManagedItem::Group(name) => {
+            let members = proxies.iter().filter(|p| p.group == *name).count();
+            if members > 0 {
+                anyhow::bail!("그룹에 프록시 {}개가 있어 삭제할 수 없습니다. 프록시를 먼저 옮기거나 삭제하세요", members);
+            }
+            groups.remove(name);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxy(id: u32, group: &str) -> Proxy {
+        Proxy {
+            id,
+            host: format!("10.0.0.{}", id),
+            port: 22,
+            username: "admin".to_string(),
+            password: "vault:proxy-1.password".to_string(),
+            group: group.to_string(),
+            alias: Some(format!("p{}", id)),
+            ..Proxy::default()
+        }
+    }
+
+    fn set(form: &mut ProxyForm, key: &str, value: &str) {
+        form.fields.iter_mut().find(|f| f.key == key).unwrap().value = value.to_string();
+    }
+
+    #[test]
+    fn proxy_form_validates_and_keeps_settings_outside_the_form() {
+        let mut proxies = vec![proxy(1, "A"), proxy(3, "B")];
+        proxies[0].escalation = Some(crate::ssh::Escalation::Sudo { password: None, user: None });
+        let mut groups = BTreeMap::new();
+
+        // 복제: 다음 ID, 별칭 표시, 입력 창에 없는 권한 상승 설정 유지
+        let mut form = ProxyForm::clone_proxy(&proxies[0], &proxies);
+        assert_eq!(form.value("id"), "4");
+        assert_eq!(form.value("alias"), "p1-복제");
+        // 참조는 그대로 보이고 평문 비밀번호는 가림
+        let password = form.fields.iter().find(|f| f.key == "password").unwrap();
+        assert_eq!(password.display_value(), "vault:proxy-1.password");
+
+        set(&mut form, "id", "3");
+        assert_eq!(form.submit(&proxies, &groups).unwrap_err().to_string(), "이미 사용 중인 ID입니다: 3");
+        set(&mut form, "id", "4");
+        set(&mut form, "port", "70000");
+        assert_eq!(form.submit(&proxies, &groups).unwrap_err().to_string(), "포트는 1~65535 사이의 숫자여야 합니다");
+        set(&mut form, "port", "2222");
+        set(&mut form, "traffic_log_path", "logs/access.log");
+        assert!(form.submit(&proxies, &groups).is_err());
+        set(&mut form, "traffic_log_path", "");
+        set(&mut form, "password", "");
+        assert!(form.submit(&proxies, &groups).unwrap_err().to_string().contains("인증 방법"));
+        set(&mut form, "password", "secret");
+        assert_eq!(form.fields.iter().find(|f| f.key == "password").unwrap().display_value(), "******");
+
+        // 볼트가 닫혀 있으면 평문 저장을 경고하고 비밀번호는 그대로 저장
+        let _vault = crate::vault::lock_for_test();
+        assert!(form.plaintext_warning().unwrap().contains("평문으로 저장"));
+        let mut result = form.submit(&proxies, &groups).unwrap();
+        store_secrets(&mut result).unwrap();
+        apply(result, &mut proxies, &mut groups);
+        assert_eq!(proxies.iter().find(|p| p.id == 4).unwrap().password, "secret");
+        set(&mut form, "password", "vault:proxy-4.password");
+        assert_eq!(form.plaintext_warning(), None);
+        let added = proxies.iter().find(|p| p.id == 4).unwrap();
+        assert_eq!((added.port, added.traffic_log_path.as_deref()), (2222, None));
+        assert!(added.escalation.is_some());
+
+        // 편집은 같은 ID를 유지해도 되고, 선택 칸은 Space로 변경
+        let mut form = ProxyForm::edit_proxy(&proxies[1]);
+        form.focused = form.fields.iter().position(|f| f.key == "exec_mode").unwrap();
+        form.insert_char(' ');
+        form.backspace();
+        apply(form.submit(&proxies, &groups).unwrap(), &mut proxies, &mut groups);
+        assert_eq!(proxies.len(), 3);
+        assert_eq!(proxies[1].exec_mode, ExecMode::Local);
+
+        // 권한 상승이 있는 프록시는 로컬 실행으로 바꿀 수 없음
+        let mut form = ProxyForm::edit_proxy(&proxies[0]);
+        set(&mut form, "exec_mode", "local");
+        assert!(form.submit(&proxies, &groups).unwrap_err().to_string().contains("exec_mode가 ssh일 때만"));
+    }
+
+    #[test]
+    fn groups_are_renamed_with_their_proxies_and_deleted_only_when_empty() {
+        let mut proxies = vec![proxy(2, "B"), proxy(1, "A"), proxy(3, "A")];
+        let mut groups = BTreeMap::new();
+
+        let mut form = ProxyForm::new_group();
+        set(&mut form, "name", "A");
+        assert_eq!(form.submit(&proxies, &groups).unwrap_err().to_string(), "이미 있는 그룹입니다: A");
+        set(&mut form, "name", "Empty");
+        set(&mut form, "jump_host", "bastion");
+        assert_eq!(form.submit(&proxies, &groups).unwrap_err().to_string(), "점프 사용자를 입력하세요");
+        set(&mut form, "jump_username", "jump");
+        apply(form.submit(&proxies, &groups).unwrap(), &mut proxies, &mut groups);
+        assert_eq!(groups["Empty"].jump_host.as_ref().unwrap().port, 22);
+
+        // 프록시가 없는 그룹도 목록에 나옴
+        assert_eq!(
+            managed_items(&proxies, &groups),
+            vec![
+                ManagedItem::Group("A".to_string()),
+                ManagedItem::Proxy(1),
+                ManagedItem::Proxy(3),
+                ManagedItem::Group("B".to_string()),
+                ManagedItem::Proxy(2),
+                ManagedItem::Group("Empty".to_string()),
+            ]
+        );
+
+        let mut form = ProxyForm::edit_group("A", &GroupConfig::default());
+        set(&mut form, "name", "Core");
+        apply(form.submit(&proxies, &groups).unwrap(), &mut proxies, &mut groups);
+        assert!(proxies.iter().filter(|p| p.id != 2).all(|p| p.group == "Core"));
+
+        let err = delete(&ManagedItem::Group("Core".to_string()), &mut proxies, &mut groups).unwrap_err();
+        assert!(err.to_string().starts_with("그룹에 프록시 2개가 있어"));
+        delete(&ManagedItem::Group("Empty".to_string()), &mut proxies, &mut groups).unwrap();
+        delete(&ManagedItem::Proxy(2), &mut proxies, &mut groups).unwrap();
+        assert!(!groups.contains_key("Empty"));
+        assert_eq!(proxies.len(), 2);
+    }
+}
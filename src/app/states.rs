@@ -1,12 +1,45 @@
 use crate::app::types::{CollectionStatus, Proxy};
 use ratatui::widgets::TableState;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+/// 프록시 관리 탭 상태
+#[derive(Default)]
+pub struct ProxyManagementState {
+    pub table_state: TableState, // managed_items 목록의 선택 행
+    pub form: Option<crate::app::proxy_form::ProxyForm>, // 열려 있는 추가/편집 창
+    pub confirm_delete: Option<crate::app::proxy_form::ManagedItem>, // 삭제 확인 대기 중인 항목
+    pub last_error: Option<String>,
+    pub last_saved: Option<String>, // 마지막 저장 결과 메시지
+}
+
+impl ProxyManagementState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn next(&mut self, count: usize) {
+        let next_idx = match self.table_state.selected() {
+            Some(i) if i + 1 < count => i + 1,
+            _ => 0,
+        };
+        self.table_state.select(if count == 0 { None } else { Some(next_idx) });
+    }
+
+    pub fn previous(&mut self, count: usize) {
+        let prev_idx = match self.table_state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => count.saturating_sub(1),
+        };
+        self.table_state.select(if count == 0 { None } else { Some(prev_idx) });
+    }
+}
 
 /// 자원 사용률 탭 상태
 #[derive(Default)]
 pub struct ResourceUsageState {
     pub table_state: TableState,
     pub data: Vec<crate::app::types::ResourceData>,
-    pub selected_proxy: Option<usize>,
     pub selected_group: Option<String>, // None = 전체보기
     pub available_groups: Vec<String>,
     pub collection_interval_sec: u64, // 수집 주기 (초)
@@ -27,7 +60,6 @@ impl ResourceUsageState {
         Self {
             table_state: TableState::default(),
             data: Vec::new(),
-            selected_proxy: None,
             selected_group: None, // None = 전체보기
             available_groups: Vec::new(),
             collection_interval_sec: 60, // 기본 60초
@@ -43,62 +75,6 @@ impl ResourceUsageState {
         }
     }
 
-    /// 컨트롤 그리드에서 오른쪽으로 이동 (0->1->2, 3->4->5)
-    pub fn move_control_right(&mut self) {
-        self.selected_control = match self.selected_control {
-            None => Some(0),
-            Some(0) => Some(1),
-            Some(1) => Some(2),
-            Some(2) => Some(2), // 오른쪽 끝
-            Some(3) => Some(4),
-            Some(4) => Some(5),
-            Some(5) => Some(5), // 오른쪽 끝
-            _ => Some(0),
-        };
-    }
-
-    /// 컨트롤 그리드에서 왼쪽으로 이동 (2->1->0, 5->4->3)
-    pub fn move_control_left(&mut self) {
-        self.selected_control = match self.selected_control {
-            None => Some(0),
-            Some(0) => Some(0), // 왼쪽 끝
-            Some(1) => Some(0),
-            Some(2) => Some(1),
-            Some(3) => Some(3), // 왼쪽 끝
-            Some(4) => Some(3),
-            Some(5) => Some(4),
-            _ => Some(0),
-        };
-    }
-
-    /// 컨트롤 그리드에서 아래로 이동 (0->3, 1->4, 2->5)
-    pub fn move_control_down(&mut self) {
-        self.selected_control = match self.selected_control {
-            None => Some(0), // 테이블에서 첫 번째 컨트롤로
-            Some(0) => Some(3),
-            Some(1) => Some(4),
-            Some(2) => Some(5),
-            Some(3) => None, // 아래 끝에서 테이블로
-            Some(4) => None, // 아래 끝에서 테이블로
-            Some(5) => None, // 아래 끝에서 테이블로
-            _ => Some(0),
-        };
-    }
-
-    /// 컨트롤 그리드에서 위로 이동 (3->0, 4->1, 5->2)
-    pub fn move_control_up(&mut self) {
-        self.selected_control = match self.selected_control {
-            None => Some(3), // 테이블에서 아래 줄로
-            Some(0) => None, // 위 끝에서 테이블로
-            Some(1) => None, // 위 끝에서 테이블로
-            Some(2) => None, // 위 끝에서 테이블로
-            Some(3) => Some(0),
-            Some(4) => Some(1),
-            Some(5) => Some(2),
-            _ => Some(0),
-        };
-    }
-
     pub fn toggle_auto_collection(&mut self) {
         self.auto_collection_enabled = !self.auto_collection_enabled;
         if self.auto_collection_enabled {
@@ -110,17 +86,6 @@ impl ResourceUsageState {
         }
     }
 
-    pub fn activate_control(&mut self) {
-        match self.selected_control {
-            Some(1) => {
-                // 자동수집 - 토글
-                self.toggle_auto_collection();
-            }
-            // 다른 컨트롤들은 정보 표시용이거나 다른 키로 처리됨
-            _ => {}
-        }
-    }
-    
     /// 자동 수집이 활성화되어 있고 다음 수집 시간이 되었는지 확인
     pub fn should_trigger_auto_collection(&self) -> bool {
         if !self.auto_collection_enabled {
@@ -188,6 +153,10 @@ impl ResourceUsageState {
         }
         self.available_groups = groups.into_iter().collect();
         self.available_groups.sort();
+        // 선택한 그룹이 없어졌으면 전체보기로
+        if self.selected_group.as_ref().is_some_and(|g| !self.available_groups.contains(g)) {
+            self.selected_group = None;
+        }
     }
 
     pub fn next_group(&mut self) {
@@ -275,11 +244,12 @@ impl ResourceUsageState {
 pub struct SessionBrowserState {
     pub table_state: TableState,
     pub sessions: Vec<crate::app::types::SessionData>,
-    pub filter: String,
     pub selected_group: Option<String>, // None = 전체보기
     pub available_groups: Vec<String>,
     pub query_status: CollectionStatus, // 조회 상태
     pub query_progress: Option<(usize, usize)>, // (완료된 수, 전체 수)
+    pub skipped_proxies: usize, // 비활성이거나 점검 중이라 조회하지 않은 프록시 수
+    pub rows_received: Arc<AtomicUsize>, // 조회 중 지금까지 받은 세션 수 (조회 태스크가 갱신)
     pub last_query_time: Option<chrono::DateTime<chrono::Local>>,
     pub last_error: Option<String>, // 마지막 에러 메시지
     pub query_start_time: Option<chrono::DateTime<chrono::Local>>,
@@ -312,11 +282,12 @@ impl SessionBrowserState {
         Self {
             table_state: TableState::default(),
             sessions: Vec::new(),
-            filter: String::new(),
             selected_group: None, // None = 전체보기
             available_groups: Vec::new(),
             query_status: CollectionStatus::Idle,
             query_progress: None,
+            skipped_proxies: 0,
+            rows_received: Arc::default(),
             last_query_time: None,
             last_error: None,
             query_start_time: None,
@@ -335,18 +306,6 @@ impl SessionBrowserState {
         }
     }
 
-    /// 컬럼 오프셋 증가 (오른쪽으로 스크롤)
-    pub fn scroll_right(&mut self) {
-        // 최대 컬럼 수는 19개, 한 번에 10개씩 표시하므로 최대 9까지
-        let max_offset = 19usize.saturating_sub(10);
-        self.column_offset = (self.column_offset + 1).min(max_offset);
-    }
-
-    /// 컬럼 오프셋 감소 (왼쪽으로 스크롤)
-    pub fn scroll_left(&mut self) {
-        self.column_offset = self.column_offset.saturating_sub(1);
-    }
-
     pub fn update_groups(&mut self, proxies: &[Proxy]) {
         use std::collections::HashSet;
         let mut groups: HashSet<String> = HashSet::new();
@@ -355,6 +314,10 @@ impl SessionBrowserState {
         }
         self.available_groups = groups.into_iter().collect();
         self.available_groups.sort();
+        // 선택한 그룹이 없어졌으면 전체보기로
+        if self.selected_group.as_ref().is_some_and(|g| !self.available_groups.contains(g)) {
+            self.selected_group = None;
+        }
     }
 
     pub fn next_group(&mut self) {
@@ -451,16 +414,6 @@ impl SessionBrowserState {
         self.table_state.select(Some(i));
     }
 
-    /// 페이지네이션된 세션 목록 가져오기
-    pub fn get_paginated_sessions<'a>(&self, sessions: &'a [crate::app::types::SessionData]) -> Vec<&'a crate::app::types::SessionData> {
-        let start = self.current_page * self.page_size;
-        let end = (start + self.page_size).min(sessions.len());
-        if start >= sessions.len() {
-            return Vec::new();
-        }
-        sessions[start..end].iter().collect()
-    }
-
     /// 다음 페이지로 이동
     pub fn next_page(&mut self) {
         if self.current_page < self.total_pages.saturating_sub(1) {
@@ -496,7 +449,7 @@ impl SessionBrowserState {
         self.total_pages = if total_items == 0 {
             0
         } else {
-            (total_items + self.page_size - 1) / self.page_size
+            total_items.div_ceil(self.page_size)
         };
         if self.current_page >= self.total_pages && self.total_pages > 0 {
             self.current_page = self.total_pages - 1;
@@ -509,7 +462,6 @@ impl SessionBrowserState {
             if col > 0 {
                 self.selected_column = Some(col - 1);
                 // 선택된 컬럼이 보이도록 가로 스크롤 조정
-                const MAX_VISIBLE: usize = 10;
                 if col - 1 < self.column_offset {
                     // 선택된 컬럼이 현재 보이는 범위 밖이면 스크롤 조정
                     self.column_offset = col - 1;
@@ -551,12 +503,6 @@ impl SessionBrowserState {
         }
     }
 
-    /// 정렬 해제
-    pub fn clear_sort(&mut self) {
-        self.sort_column = None;
-        self.sort_ascending = true;
-    }
-
     /// 상세보기 모달 토글
     pub fn toggle_detail_modal(&mut self) {
         self.show_detail_modal = !self.show_detail_modal;
@@ -589,15 +535,6 @@ impl SessionBrowserState {
         self.search_query.clear();
     }
 
-    /// 검색 모드 토글 (하위 호환성)
-    pub fn toggle_search_mode(&mut self) {
-        if self.search_mode {
-            self.cancel_search_mode();
-        } else {
-            self.start_search_mode();
-        }
-    }
-
     /// 검색어 추가 (검색 모드일 때)
     pub fn add_search_char(&mut self, c: char) {
         if self.search_mode {
@@ -612,40 +549,6 @@ impl SessionBrowserState {
         }
     }
 
-    /// 검색어로 세션 필터링
-    pub fn filter_sessions<'a>(&self, sessions: &'a [&'a crate::app::types::SessionData]) -> Vec<&'a crate::app::types::SessionData> {
-        if self.search_query.is_empty() {
-            return sessions.iter().copied().collect();
-        }
-
-        let query = self.search_query.to_lowercase();
-        sessions.iter()
-            .copied()
-            .filter(|session| {
-                // 모든 필드에서 검색
-                session.host.to_lowercase().contains(&query) ||
-                session.transaction.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.protocol.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.cust_id.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.user_name.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.client_ip.to_lowercase().contains(&query) ||
-                session.client_side_mwg_ip.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.server_side_mwg_ip.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.server_ip.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.status.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.url.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false) ||
-                session.cl_bytes_received.map(|v| format!("{}", v).contains(&query)).unwrap_or(false) ||
-                session.cl_bytes_sent.map(|v| format!("{}", v).contains(&query)).unwrap_or(false) ||
-                session.srv_bytes_received.map(|v| format!("{}", v).contains(&query)).unwrap_or(false) ||
-                session.srv_bytes_sent.map(|v| format!("{}", v).contains(&query)).unwrap_or(false) ||
-                session.trxn_index.map(|v| format!("{}", v).contains(&query)).unwrap_or(false) ||
-                session.age_seconds.map(|v| format!("{}", v).contains(&query)).unwrap_or(false) ||
-                session.in_use.map(|v| format!("{}", v).contains(&query)).unwrap_or(false) ||
-                session.creation_time.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string().to_lowercase().contains(&query)).unwrap_or(false)
-            })
-            .collect::<Vec<_>>()
-    }
-
     /// 컬럼 순서 변경 (왼쪽으로 이동)
     pub fn move_column_left(&mut self, col_idx: usize) {
         if col_idx > 0 && col_idx < self.column_order.len() {
@@ -665,7 +568,6 @@ impl SessionBrowserState {
 #[derive(Default)]
 pub struct TrafficLogsState {
     pub selected_proxy: Option<usize>,
-    pub analysis_result: Option<String>,
     pub top_n_analysis: Option<crate::traffic_log_parser::TopNAnalysis>,
     pub analysis_status: CollectionStatus,
     pub analysis_progress: Option<(usize, usize)>,
@@ -680,8 +582,14 @@ pub struct TrafficLogsState {
     pub log_limit: usize, // 조회할 로그 수 (기본값: 500)
     pub query_status: CollectionStatus,
     pub query_progress: Option<(usize, usize)>,
+    pub lines_received: Arc<AtomicUsize>, // 조회 중 지금까지 받은 로그 라인 수 (조회 태스크가 갱신)
     pub last_query_time: Option<chrono::DateTime<chrono::Local>>,
     pub query_start_time: Option<chrono::DateTime<chrono::Local>>,
+    // 로그 파일 다운로드 (SFTP) 관련 필드
+    pub download_status: CollectionStatus,
+    pub download_progress: Arc<crate::log_cache::DownloadProgress>, // 다운로드 태스크가 갱신
+    pub cached_logs: Vec<crate::log_cache::CachedLog>,
+    pub download_start_time: Option<chrono::DateTime<chrono::Local>>,
     // 페이지네이션
     pub current_page: usize,
     pub page_size: usize,
@@ -712,7 +620,6 @@ impl TrafficLogsState {
     pub fn new() -> Self {
         Self {
             selected_proxy: None,
-            analysis_result: None,
             top_n_analysis: None,
             analysis_status: CollectionStatus::Idle,
             analysis_progress: None,
@@ -727,8 +634,14 @@ impl TrafficLogsState {
             log_limit: 500,
             query_status: CollectionStatus::Idle,
             query_progress: None,
+            lines_received: Arc::default(),
             last_query_time: None,
             query_start_time: None,
+            // 로그 파일 다운로드 관련
+            download_status: CollectionStatus::Idle,
+            download_progress: Arc::default(),
+            cached_logs: Vec::new(),
+            download_start_time: None,
             // 페이지네이션
             current_page: 0,
             page_size: 50,
@@ -834,7 +747,7 @@ impl TrafficLogsState {
         self.total_pages = if total_items == 0 {
             0
         } else {
-            (total_items + self.page_size - 1) / self.page_size
+            total_items.div_ceil(self.page_size)
         };
         // 현재 페이지가 범위를 벗어나면 조정
         if self.current_page >= self.total_pages && self.total_pages > 0 {
@@ -915,3 +828,136 @@ impl TrafficLogsState {
         self.current_page = 0;
     }
 }
+
+/// 이벤트(SNMP 트랩) 탭 상태
+#[derive(Default)]
+pub struct EventsState {
+    pub events: Vec<crate::app::types::EventRecord>, // 최신 이벤트가 앞쪽
+    pub table_state: TableState,
+    pub listener_status: crate::app::types::TrapListenerStatus,
+    pub min_severity: Option<crate::snmp::TrapSeverity>, // None = 전체
+    pub rejected_count: usize, // 커뮤니티/인증 불일치 등으로 버린 메시지 수
+    pub last_error: Option<String>,
+    pub last_export: Option<String>, // 마지막 CSV 저장 경로
+    // 검색 기능
+    pub search_mode: bool,
+    pub search_query: String,
+}
+
+impl EventsState {
+    /// 메모리에 보관하는 최대 이벤트 수
+    pub const MAX_EVENTS: usize = 1000;
+
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            table_state: TableState::default(),
+            listener_status: crate::app::types::TrapListenerStatus::Disabled,
+            min_severity: None,
+            rejected_count: 0,
+            last_error: None,
+            last_export: None,
+            search_mode: false,
+            search_query: String::new(),
+        }
+    }
+
+    /// 새 이벤트를 맨 앞에 추가 (선택된 행이 있으면 같은 이벤트를 계속 가리키도록 보정)
+    pub fn push(&mut self, record: crate::app::types::EventRecord) {
+        self.events.insert(0, record);
+        self.events.truncate(Self::MAX_EVENTS);
+        if let Some(i) = self.table_state.selected() {
+            let visible = self.filtered_events().len();
+            self.table_state.select(Some((i + 1).min(visible.saturating_sub(1))));
+        }
+    }
+
+    /// 심각도 필터와 검색어를 적용한 이벤트 목록
+    pub fn filtered_events(&self) -> Vec<&crate::app::types::EventRecord> {
+        let query = self.search_query.to_lowercase();
+        self.events
+            .iter()
+            .filter(|event| self.min_severity.map(|min| event.severity >= min).unwrap_or(true))
+            .filter(|event| {
+                query.is_empty()
+                    || event.source.to_lowercase().contains(&query)
+                    || event.name.to_lowercase().contains(&query)
+                    || event.trap_oid.contains(&query)
+                    || event.details.to_lowercase().contains(&query)
+                    || event.proxy_name.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false)
+                    || event.group.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// 심각도 필터 순환: 전체 -> 경고 이상 -> 심각 -> 전체
+    pub fn cycle_severity_filter(&mut self) {
+        use crate::snmp::TrapSeverity;
+        self.min_severity = match self.min_severity {
+            None => Some(TrapSeverity::Warning),
+            Some(TrapSeverity::Warning) => Some(TrapSeverity::Critical),
+            Some(_) => None,
+        };
+        self.table_state.select(None);
+    }
+
+    pub fn get_severity_filter_display(&self) -> String {
+        match self.min_severity {
+            None => "전체".to_string(),
+            Some(severity) => format!("{} 이상", severity.label()),
+        }
+    }
+
+    pub fn next(&mut self) {
+        let count = self.filtered_events().len();
+        let next_idx = match self.table_state.selected() {
+            Some(i) if i + 1 < count => i + 1,
+            _ => 0,
+        };
+        self.table_state.select(if count == 0 { None } else { Some(next_idx) });
+    }
+
+    pub fn previous(&mut self) {
+        let count = self.filtered_events().len();
+        let prev_idx = match self.table_state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => count.saturating_sub(1),
+        };
+        self.table_state.select(if count == 0 { None } else { Some(prev_idx) });
+    }
+
+    /// 이벤트 목록 비우기
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.table_state.select(None);
+    }
+
+    /// 검색 모드 시작
+    pub fn start_search_mode(&mut self) {
+        self.search_mode = true;
+    }
+
+    /// 검색 완료 (검색어 유지)
+    pub fn finish_search_mode(&mut self) {
+        self.search_mode = false;
+    }
+
+    /// 검색 취소 (검색어 초기화)
+    pub fn cancel_search_mode(&mut self) {
+        self.search_mode = false;
+        self.search_query.clear();
+        self.table_state.select(None);
+    }
+
+    /// 검색어에 문자 추가
+    pub fn add_search_char(&mut self, c: char) {
+        self.search_query.push(c);
+        self.table_state.select(None);
+    }
+
+    /// 검색어에서 문자 삭제
+    pub fn backspace_search(&mut self) {
+        self.search_query.pop();
+        self.table_state.select(None);
+    }
+}
@@ -7,6 +7,7 @@ pub enum TabIndex {
     ResourceUsage = 1,
     SessionBrowser = 2,
     TrafficLogs = 3,
+    Events = 4,
 }
 
 impl TabIndex {
@@ -15,26 +16,18 @@ impl TabIndex {
             TabIndex::ProxyManagement => TabIndex::ResourceUsage,
             TabIndex::ResourceUsage => TabIndex::SessionBrowser,
             TabIndex::SessionBrowser => TabIndex::TrafficLogs,
-            TabIndex::TrafficLogs => TabIndex::ProxyManagement,
+            TabIndex::TrafficLogs => TabIndex::Events,
+            TabIndex::Events => TabIndex::ProxyManagement,
         }
     }
 
     pub fn previous(&self) -> Self {
         match self {
-            TabIndex::ProxyManagement => TabIndex::TrafficLogs,
+            TabIndex::ProxyManagement => TabIndex::Events,
             TabIndex::ResourceUsage => TabIndex::ProxyManagement,
             TabIndex::SessionBrowser => TabIndex::ResourceUsage,
             TabIndex::TrafficLogs => TabIndex::SessionBrowser,
-        }
-    }
-
-    pub fn from_index(index: usize) -> Self {
-        match index % 4 {
-            0 => TabIndex::ProxyManagement,
-            1 => TabIndex::ResourceUsage,
-            2 => TabIndex::SessionBrowser,
-            3 => TabIndex::TrafficLogs,
-            _ => TabIndex::ProxyManagement,
+            TabIndex::Events => TabIndex::TrafficLogs,
         }
     }
 }
@@ -46,20 +39,280 @@ pub struct Proxy {
     pub host: String,
     pub port: u16,
     pub username: String,
-    pub password: String,
-    pub group: String,
     #[serde(default)]
+    pub password: String, // 개인키나 ssh-agent만 쓰면 비워 둘 수 있음
+    pub group: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub alias: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub traffic_log_path: Option<String>,
+    /// 프록시별 SNMP 설정 (없으면 resource_config.json의 전역 설정 사용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snmp_version: Option<crate::snmp::SnmpVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snmp_community: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snmp_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snmp_v3: Option<crate::snmp::SnmpV3Config>,
+    /// SSH 개인키 파일 경로 (`~/` 사용 가능) - 설정하면 공개키 인증을 시도
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key_passphrase: Option<String>,
+    /// true이면 ssh-agent에 등록된 키로 먼저 인증
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ssh_agent: bool,
+    /// 이 프록시에 접속할 때 거칠 점프 호스트 (없으면 그룹 설정 사용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jump_host: Option<crate::ssh::JumpHost>,
+    /// 명령을 실행할 위치 - "ssh"(기본) 또는 "local" (이 프로그램을 프록시 장비에서 실행할 때)
+    #[serde(default, skip_serializing_if = "crate::command_runner::ExecMode::is_ssh")]
+    pub exec_mode: crate::command_runner::ExecMode,
+    /// MWG 명령과 트래픽 로그 조회를 sudo/su로 실행 (root 권한이 필요한 장비)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation: Option<crate::ssh::Escalation>,
+    /// false이면 수집하지 않음 (목록에는 "비활성"으로 표시)
+    #[serde(default = "default_enabled", skip_serializing_if = "is_enabled")]
+    pub enabled: bool,
+    /// 점검 시간 (이 시간에는 수집하지 않고 "점검 중"으로 표시)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maintenance: Vec<crate::app::maintenance::MaintenanceWindow>,
+    /// 그룹 설정에서 가져온 점프 호스트 (설정 로드 시 채움, 저장하지 않음)
+    #[serde(skip)]
+    pub group_jump_host: Option<crate::ssh::JumpHost>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn is_enabled(enabled: &bool) -> bool {
+    *enabled
+}
+
+impl Default for Proxy {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            host: String::new(),
+            port: 22,
+            username: String::new(),
+            password: String::new(),
+            group: String::new(),
+            alias: None,
+            traffic_log_path: None,
+            snmp_version: None,
+            snmp_community: None,
+            snmp_port: None,
+            snmp_v3: None,
+            ssh_key_path: None,
+            ssh_key_passphrase: None,
+            ssh_agent: false,
+            jump_host: None,
+            exec_mode: Default::default(),
+            escalation: None,
+            enabled: true,
+            maintenance: Vec::new(),
+            group_jump_host: None,
+        }
+    }
+}
+
+/// 프록시의 수집 대상 여부
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProxyState {
+    Active,
+    Disabled,
+    /// 점검 중 (끝나는 시각, 사유)
+    Maintenance { until: chrono::NaiveDateTime, reason: Option<String> },
+}
+
+impl ProxyState {
+    pub fn is_active(&self) -> bool {
+        *self == ProxyState::Active
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            ProxyState::Active => "활성".to_string(),
+            ProxyState::Disabled => "비활성".to_string(),
+            ProxyState::Maintenance { until, reason } => {
+                let reason = reason.as_ref().map(|r| format!(", {}", r)).unwrap_or_default();
+                format!("점검 중 (~{}{})", until.format("%m/%d %H:%M"), reason)
+            }
+        }
+    }
+}
+
+impl Proxy {
+    /// 인증에 쓰는 `env:`/`cmd:`/`vault:` 참조 (비밀번호, 키 암호, 권한 상승, 점프 호스트, SNMP)
+    pub fn credential_references(&self) -> Vec<&str> {
+        let jump_host = self.jump_host.as_ref().or(self.group_jump_host.as_ref());
+        [
+            Some(self.password.as_str()),
+            self.ssh_key_passphrase.as_deref(),
+            self.escalation.as_ref().and_then(|e| e.password()),
+            jump_host.map(|j| j.password.as_str()),
+            jump_host.and_then(|j| j.ssh_key_passphrase.as_deref()),
+            self.snmp_community.as_deref(),
+            self.snmp_v3.as_ref().and_then(|v3| v3.auth_password.as_deref()),
+            self.snmp_v3.as_ref().and_then(|v3| v3.priv_password.as_deref()),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|value| crate::credential::is_reference(value))
+        .collect()
+    }
+
+    /// `now`(현지 시각) 기준 수집 대상 여부
+    pub fn state_at(&self, now: chrono::NaiveDateTime) -> ProxyState {
+        if !self.enabled {
+            return ProxyState::Disabled;
+        }
+        // 겹치는 점검 시간이 있으면 가장 늦게 끝나는 것
+        self.maintenance
+            .iter()
+            .filter_map(|window| Some((window.active_until(now)?, window)))
+            .max_by_key(|(until, _)| *until)
+            .map_or(ProxyState::Active, |(until, window)| ProxyState::Maintenance { until, reason: window.reason.clone() })
+    }
+
+    /// 지금 수집 대상 여부
+    pub fn state(&self) -> ProxyState {
+        self.state_at(chrono::Local::now().naive_local())
+    }
+
+    /// 비활성이거나 점검 중이면 상태를 담은 오류 (직접 조회를 요청한 경우)
+    pub fn ensure_active(&self) -> anyhow::Result<()> {
+        match self.state() {
+            ProxyState::Active => Ok(()),
+            state => anyhow::bail!("프록시 {}은(는) {} 상태라 조회하지 않습니다", self.id, state.label()),
+        }
+    }
+
+    /// 점검 시간 설정 검사
+    pub fn validate_maintenance(&self) -> anyhow::Result<()> {
+        for window in &self.maintenance {
+            window
+                .validate()
+                .map_err(|e| anyhow::anyhow!("프록시 {} 점검 시간 설정 오류: {}", self.id, e))?;
+        }
+        Ok(())
+    }
+
+    /// 실행 위치 설정 검사 (권한 상승은 SSH로 실행할 때만 적용할 수 있음)
+    pub fn validate_exec_mode(&self) -> anyhow::Result<()> {
+        if !self.exec_mode.is_ssh() && self.escalation.is_some() {
+            anyhow::bail!(
+                "프록시 {} 설정 오류: escalation은 exec_mode가 ssh일 때만 쓸 수 있습니다 (local은 이 프로그램의 권한으로 실행)",
+                self.id
+            );
+        }
+        Ok(())
+    }
+
+    /// 마지막 접속에서 가져오지 못한 자격 증명 참조와 이유
+    pub fn credential_error(&self) -> Option<String> {
+        self.credential_references()
+            .into_iter()
+            .find_map(crate::credential::failure)
+    }
+}
+
+/// 인터페이스 운영 상태 (IF-MIB ifOperStatus)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkStatus {
+    Up,
+    Down,       // down(2), notPresent(6), lowerLayerDown(7)
+    Other(i64), // testing(3), unknown(4), dormant(5)
+}
+
+impl LinkStatus {
+    pub fn from_oper_status(value: i64) -> Self {
+        match value {
+            1 => LinkStatus::Up,
+            2 | 6 | 7 => LinkStatus::Down,
+            other => LinkStatus::Other(other),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LinkStatus::Up => "UP",
+            LinkStatus::Down => "DOWN",
+            LinkStatus::Other(3) => "TESTING",
+            LinkStatus::Other(5) => "DORMANT",
+            LinkStatus::Other(_) => "UNKNOWN",
+        }
+    }
 }
 
 /// 인터페이스 트래픽 정보 (bps 단위)
 #[derive(Debug, Clone)]
 pub struct InterfaceTraffic {
     pub name: String,
-    pub in_mbps: f64,  // 실제로는 bps 값이지만 필드명은 유지
-    pub out_mbps: f64, // 실제로는 bps 값이지만 필드명은 유지
+    pub in_bps: Option<f64>, // 첫 수집이거나 카운터 리셋이면 None
+    pub out_bps: Option<f64>,
+    pub speed_bps: Option<f64>, // 회선 속도 (ifHighSpeed 또는 설정의 speed_mbps), 모르면 None
+    pub status: Option<LinkStatus>,
+    pub in_errors: Option<f64>,  // 초당 에러 수 (ifInErrors)
+    pub out_errors: Option<f64>, // 초당 에러 수 (ifOutErrors)
+    pub in_discards: Option<f64>, // 초당 폐기 수 (ifInDiscards)
+    pub out_discards: Option<f64>, // 초당 폐기 수 (ifOutDiscards)
+}
+
+impl InterfaceTraffic {
+    /// 회선 속도 대비 사용률 (%)
+    pub fn utilization(&self, bps: f64) -> Option<f64> {
+        self.speed_bps.filter(|speed| *speed > 0.0).map(|speed| bps / speed * 100.0)
+    }
+
+    /// in/out 중 높은 쪽의 트래픽 (bps)
+    pub fn max_bps(&self) -> Option<f64> {
+        max_of(self.in_bps, self.out_bps)
+    }
+
+    /// in/out 중 높은 쪽의 사용률 (%)
+    pub fn max_utilization(&self) -> Option<f64> {
+        self.utilization(self.max_bps()?)
+    }
+
+    /// in/out 중 높은 쪽의 초당 에러 수
+    pub fn max_errors(&self) -> Option<f64> {
+        max_of(self.in_errors, self.out_errors)
+    }
+
+    /// in/out 중 높은 쪽의 초당 폐기 수
+    pub fn max_discards(&self) -> Option<f64> {
+        max_of(self.in_discards, self.out_discards)
+    }
+
+    pub fn is_down(&self) -> bool {
+        self.status == Some(LinkStatus::Down)
+    }
+}
+
+fn max_of(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// 파일시스템 사용률 (SSH `df`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskUsage {
+    pub mount: String,
+    pub used_percent: f64,
+    pub available_kb: u64,
+}
+
+/// 확인 대상 프로세스의 실행 개수 (SSH `pgrep`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessStatus {
+    pub name: String,
+    pub count: u32, // 0이면 실행 중이 아님
 }
 
 /// 자원 사용률 데이터
@@ -76,9 +329,82 @@ pub struct ResourceData {
     pub https: Option<f64>,
     pub ftp: Option<f64>,
     pub interfaces: Vec<InterfaceTraffic>, // 회선 정보
+    // SSH 호스트 지표 (host_metrics 설정을 켠 경우)
+    pub load_avg: Option<f64>, // 1분 평균 부하
+    pub cpu_cores: Option<u32>,
+    pub swap: Option<f64>, // 스왑 사용률 (%)
+    pub open_files: Option<f64>, // 파일 디스크립터 사용률 (%)
+    pub uptime_secs: Option<u64>,
+    pub disks: Vec<DiskUsage>,
+    pub processes: Vec<ProcessStatus>,
     pub collected_at: chrono::DateTime<chrono::Local>,
     pub collection_failed: bool, // 수집 실패 여부
     pub error_message: Option<String>, // 실패 시 에러 메시지
+    pub skipped: Option<ProxyState>, // 비활성/점검 중이라 수집하지 않은 경우 그 상태
+}
+
+impl ResourceData {
+    /// 비활성이거나 점검 중이라 수집하지 않은 프록시의 행
+    pub fn skipped(proxy: &Proxy, state: ProxyState) -> Self {
+        Self { skipped: Some(state), ..Self::empty(proxy) }
+    }
+
+    /// 수집에 실패한 프록시의 행
+    pub fn failed(proxy: &Proxy, message: impl Into<String>) -> Self {
+        Self { collection_failed: true, error_message: Some(message.into()), ..Self::empty(proxy) }
+    }
+
+    /// 값이 하나도 없는 프록시의 행
+    fn empty(proxy: &Proxy) -> Self {
+        Self {
+            proxy_id: proxy.id,
+            host: proxy.host.clone(),
+            proxy_name: proxy.alias.clone().or_else(|| Some(format!("{}{}", proxy.group, proxy.id))),
+            cpu: None,
+            mem: None,
+            cc: None,
+            cs: None,
+            http: None,
+            https: None,
+            ftp: None,
+            interfaces: Vec::new(),
+            load_avg: None,
+            cpu_cores: None,
+            swap: None,
+            open_files: None,
+            uptime_secs: None,
+            disks: Vec::new(),
+            processes: Vec::new(),
+            collected_at: chrono::Local::now(),
+            collection_failed: false,
+            error_message: None,
+            skipped: None,
+        }
+    }
+
+    /// 운영 상태가 DOWN인 회선이 있는지
+    pub fn has_down_interface(&self) -> bool {
+        self.interfaces.iter().any(InterfaceTraffic::is_down)
+    }
+
+    /// 확인 대상 프로세스 중 실행 중이 아닌 것이 있는지
+    pub fn has_missing_process(&self) -> bool {
+        self.processes.iter().any(|p| p.count == 0)
+    }
+
+    /// 코어당 1분 평균 부하 (코어 수를 모르면 부하 그대로)
+    pub fn load_per_core(&self) -> Option<f64> {
+        let load = self.load_avg?;
+        Some(match self.cpu_cores {
+            Some(cores) if cores > 0 => load / cores as f64,
+            _ => load,
+        })
+    }
+
+    /// 사용률이 가장 높은 파일시스템
+    pub fn fullest_disk(&self) -> Option<&DiskUsage> {
+        self.disks.iter().max_by(|a, b| a.used_percent.total_cmp(&b.used_percent))
+    }
 }
 
 /// 세션 데이터
@@ -106,6 +432,31 @@ pub struct SessionData {
     pub url: Option<String>,
 }
 
+/// SNMP 트랩 이벤트
+#[derive(Debug, Clone)]
+pub struct EventRecord {
+    pub received_at: chrono::DateTime<chrono::Local>,
+    pub source: String, // 트랩을 보낸 장비 주소
+    pub proxy_id: Option<u32>, // 주소가 일치하는 프록시 (없으면 미등록 장비)
+    pub proxy_name: Option<String>, // 프록시 별칭 또는 호스트
+    pub group: Option<String>,
+    pub severity: crate::snmp::TrapSeverity,
+    pub version: String, // v1 / v2c / v3
+    pub kind: String, // trap / inform
+    pub name: String, // 트랩 이름 (표준 트랩 외에는 OID)
+    pub trap_oid: String,
+    pub details: String, // varbind 요약
+}
+
+/// 트랩 수신기 상태
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TrapListenerStatus {
+    #[default]
+    Disabled,          // 설정에서 비활성화
+    Listening(String), // 수신 중 (바인드 주소)
+    Failed(String),    // 시작 실패
+}
+
 /// 수집 상태
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
 pub enum CollectionStatus {
@@ -117,9 +468,35 @@ pub enum CollectionStatus {
     Failed,        // 실패
 }
 
+/// 그룹 공통 설정
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupConfig {
+    /// 그룹의 모든 프록시가 거칠 점프 호스트
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jump_host: Option<crate::ssh::JumpHost>,
+}
+
 /// 프록시 설정 파일 구조
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProxyConfig {
     pub proxies: Vec<Proxy>,
+    /// 그룹 이름 -> 그룹 공통 설정
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub groups: std::collections::BTreeMap<String, GroupConfig>,
+}
+
+impl ProxyConfig {
+    /// 그룹 설정을 각 프록시에 반영한 프록시 목록을 반환합니다.
+    pub fn into_proxies(self) -> Vec<Proxy> {
+        let mut proxies = self.proxies;
+        apply_group_settings(&mut proxies, &self.groups);
+        proxies
+    }
 }
 
+/// 그룹 공통 설정(점프 호스트)을 각 프록시에 반영합니다.
+pub fn apply_group_settings(proxies: &mut [Proxy], groups: &std::collections::BTreeMap<String, GroupConfig>) {
+    for proxy in proxies {
+        proxy.group_jump_host = groups.get(&proxy.group).and_then(|g| g.jump_host.clone());
+    }
+}
@@ -1,47 +1,434 @@
-use crate::app::{Proxy, ResourceData, InterfaceTraffic};
-use crate::snmp::snmp_get_async;
-use crate::ssh::SshClient;
+use crate::app::{Proxy, ResourceData, InterfaceTraffic, LinkStatus};
+use crate::snmp::{SnmpClient, SnmpCredentials, SnmpOptions, SnmpTransport, SnmpV3Config, SnmpVersion, Value};
+use crate::command_runner::{self, CommandRunner};
+use crate::host_metrics::{self, HostMetrics, HostMetricsConfig};
 use anyhow::Result;
 use chrono::Local;
 use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::sync::{Mutex, OnceLock};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio_util::sync::CancellationToken;
 
-/// 인터페이스 카운터 캐시: (proxy_id, interface_name) -> (in_counter, out_counter, timestamp)
-fn get_interface_cache() -> &'static Mutex<HashMap<(u32, String), (u64, u64, f64)>> {
-    static CACHE: OnceLock<Mutex<HashMap<(u32, String), (u64, u64, f64)>>> = OnceLock::new();
+/// 인터페이스 카운터 캐시 타입: (proxy_id, interface_name, counter) -> sample
+type InterfaceCache = Mutex<HashMap<(u32, String, &'static str), CounterSample>>;
+
+/// 카운터 캐시 타입: (proxy_id, metric_name) -> sample
+type CounterCache = Mutex<HashMap<(u32, String), CounterSample>>;
+
+/// ifIndex 캐시 타입: proxy_id -> (interface_name -> ifIndex, 탐색 시각)
+type IfIndexCache = Mutex<HashMap<u32, (HashMap<String, u32>, f64)>>;
+
+/// IF-MIB OID
+const IF_DESCR_OID: &str = "1.3.6.1.2.1.2.2.1.2";
+const IF_NAME_OID: &str = "1.3.6.1.2.1.31.1.1.1.1";
+const IF_HC_IN_OCTETS_OID: &str = "1.3.6.1.2.1.31.1.1.1.6";
+const IF_HC_OUT_OCTETS_OID: &str = "1.3.6.1.2.1.31.1.1.1.10";
+const IF_HIGH_SPEED_OID: &str = "1.3.6.1.2.1.31.1.1.1.15";
+const IF_IN_OCTETS_OID: &str = "1.3.6.1.2.1.2.2.1.10";
+const IF_OUT_OCTETS_OID: &str = "1.3.6.1.2.1.2.2.1.16";
+const IF_OPER_STATUS_OID: &str = "1.3.6.1.2.1.2.2.1.8";
+const IF_IN_DISCARDS_OID: &str = "1.3.6.1.2.1.2.2.1.13";
+const IF_IN_ERRORS_OID: &str = "1.3.6.1.2.1.2.2.1.14";
+const IF_OUT_DISCARDS_OID: &str = "1.3.6.1.2.1.2.2.1.19";
+const IF_OUT_ERRORS_OID: &str = "1.3.6.1.2.1.2.2.1.20";
+
+/// ifIndex를 알 때 회선마다 함께 조회하는 항목: (종류, 컬럼 OID)
+const IF_STATUS_COLUMNS: [(&str, &str); 5] = [
+    ("status", IF_OPER_STATUS_OID),
+    ("in_errors", IF_IN_ERRORS_OID),
+    ("out_errors", IF_OUT_ERRORS_OID),
+    ("in_discards", IF_IN_DISCARDS_OID),
+    ("out_discards", IF_OUT_DISCARDS_OID),
+];
+
+/// sysUpTime.0 - 카운터 리셋(장비 재시작) 판단용으로 카운터와 함께 조회
+const SYS_UPTIME_OID: &str = "1.3.6.1.2.1.1.3.0";
+
+/// 비정상적으로 큰 값 필터링 (100Gbps 이상은 무시)
+const MAX_BPS: f64 = 100_000_000_000.0;
+
+/// 최소 이더넷 프레임 크기 (64바이트, 비트) - 에러/폐기 패킷률 상한 계산용
+const MIN_FRAME_BITS: f64 = 512.0;
+
+/// ifIndex 재탐색 주기 (장비 재부팅 등으로 ifIndex가 바뀔 수 있음)
+const IF_INDEX_REFRESH_SECS: f64 = 1800.0;
+
+/// SSH 지표 명령 실행 제한 시간 (접속 포함)
+const RUNNER_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 인터페이스 카운터 캐시: (proxy_id, interface_name, counter) -> sample
+fn get_interface_cache() -> &'static InterfaceCache {
+    static CACHE: OnceLock<InterfaceCache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// ifIndex 캐시: proxy_id -> (interface_name -> ifIndex, 탐색 시각)
+fn get_if_index_cache() -> &'static IfIndexCache {
+    static CACHE: OnceLock<IfIndexCache> = OnceLock::new();
     CACHE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-/// Counter32 카운터 캐시: (proxy_id, metric_name) -> (counter, timestamp)
-fn get_counter_cache() -> &'static Mutex<HashMap<(u32, String), (u64, f64)>> {
-    static CACHE: OnceLock<Mutex<HashMap<(u32, String), (u64, f64)>>> = OnceLock::new();
+/// 카운터 캐시 (http/https/ftp): (proxy_id, metric_name) -> sample
+fn get_counter_cache() -> &'static CounterCache {
+    static CACHE: OnceLock<CounterCache> = OnceLock::new();
     CACHE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
+/// 카운터 폭 (응답의 SNMP 값 타입으로 결정)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CounterWidth {
+    Bits32, // Counter32 (ifInOctets 등)
+    Bits64, // Counter64 (ifHCInOctets 등)
+}
+
+/// 카운터 측정값
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CounterSample {
+    value: u64,
+    width: CounterWidth,
+    timestamp: f64,
+    uptime: Option<u32>, // 같은 요청에서 받은 sysUpTime (1/100초), 조회 실패 시 None
+}
+
+impl CounterSample {
+    fn new(oid: &str, value: &Value, timestamp: f64, uptime: Option<u32>) -> Result<Self> {
+        let (value, width) = match value {
+            Value::Counter64(c) => (*c, CounterWidth::Bits64),
+            Value::Counter32(c) | Value::Unsigned32(c) => (*c as u64, CounterWidth::Bits32),
+            // 일부 장비는 카운터를 INTEGER로 응답
+            Value::Integer(i) if (0..=u32::MAX as i64).contains(i) => (*i as u64, CounterWidth::Bits32),
+            other => anyhow::bail!("OID {} returned {:?}, expected counter value", oid, other),
+        };
+        Ok(Self { value, width, timestamp, uptime })
+    }
+}
+
+/// 두 측정값으로 초당 증가량을 계산합니다.
+/// 순환(wrap)은 카운터 폭에 맞춰 처리하고, sysUpTime이 줄었으면 장비 재시작으로 카운터가
+/// 리셋된 것으로 보고 None을 반환합니다.
+fn counter_per_sec(prev: &CounterSample, current: &CounterSample) -> Option<f64> {
+    let time_diff = current.timestamp - prev.timestamp;
+    // 1초 이상 5분 이하 차이만 유효 (재시작 시 오래된 캐시 무시)
+    if !(1.0..=300.0).contains(&time_diff) {
+        return None;
+    }
+    // 폭이 바뀌었으면 (OID 설정 변경 등) 비교할 수 없음
+    if prev.width != current.width {
+        return None;
+    }
+
+    match (prev.uptime, current.uptime) {
+        (Some(prev_uptime), Some(uptime)) if uptime < prev_uptime => return None,
+        _ => {}
+    }
+    let uptime_known = prev.uptime.is_some() && current.uptime.is_some();
+    let diff = match current.width {
+        CounterWidth::Bits32 => current.value.wrapping_sub(prev.value) & u32::MAX as u64,
+        CounterWidth::Bits64 if uptime_known => current.value.wrapping_sub(prev.value),
+        // sysUpTime을 모르면 64비트 카운터 감소는 리셋으로 간주 (실제 순환은 사실상 일어나지 않음)
+        CounterWidth::Bits64 => current.value.checked_sub(prev.value)?,
+    };
+
+    Some(diff as f64 / time_diff)
+}
+
+/// 바이트 카운터 두 측정값으로 bps를 계산합니다. (비정상적으로 큰 값은 None)
+fn counter_rate(prev: &CounterSample, current: &CounterSample) -> Option<f64> {
+    let bps = counter_per_sec(prev, current)? * 8.0;
+    (bps <= MAX_BPS).then_some(bps)
+}
+
+/// 에러/폐기 패킷 카운터 두 측정값으로 초당 패킷 수를 계산합니다.
+/// sysUpTime이 놓친 리셋을 순환으로 오인하지 않도록, 감소했는데 순환으로 보기엔 증가량이 범위의
+/// 절반을 넘는 경우와 회선 속도(모르면 MAX_BPS)로 보낼 수 있는 최소 크기 프레임 수를 넘는 경우는 버립니다.
+fn packet_rate(prev: &CounterSample, current: &CounterSample, speed_bps: Option<f64>) -> Option<f64> {
+    if current.value < prev.value {
+        let (max, half_range) = match current.width {
+            CounterWidth::Bits32 => (u32::MAX as u64, 1u64 << 31),
+            CounterWidth::Bits64 => (u64::MAX, 1u64 << 63),
+        };
+        if max - prev.value + current.value + 1 > half_range {
+            return None;
+        }
+    }
+    let rate = counter_per_sec(prev, current)?;
+    let max_rate = speed_bps.unwrap_or(MAX_BPS) / MIN_FRAME_BITS;
+    (rate <= max_rate).then_some(rate)
+}
+
+/// 전역 SNMP 설정 (resource_config.json, 프록시별 재정의가 없을 때 사용)
+#[derive(Debug, Clone)]
+pub struct SnmpSettings {
+    pub version: SnmpVersion,
+    pub community: String,
+    pub v3: Option<SnmpV3Config>,
+    pub port: u16,
+}
+
+/// 회선 설정 (resource_config.json의 interface_oids 항목)
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceConfig {
+    pub in_oid: String, // in/out이 모두 비어 있으면 ifName/ifDescr로 ifIndex 자동 탐색
+    pub out_oid: String,
+    pub speed_mbps: Option<f64>, // 회선 속도 (없으면 ifHighSpeed 조회)
+}
+
+/// 이번 수집에서 요청할 회선 OID
+struct ResolvedInterface {
+    in_oid: String,
+    out_oid: String,
+    if_index: Option<u32>, // ifHighSpeed 조회용 (알 수 없으면 None)
+    speed_mbps: Option<f64>,
+}
+
+/// IF-MIB 옥텟 카운터 OID에서 ifIndex를 추출합니다.
+fn if_index_of(oid: &str) -> Option<u32> {
+    let oid = crate::snmp::mib::normalize(oid).ok()?;
+    [IF_HC_IN_OCTETS_OID, IF_HC_OUT_OCTETS_OID, IF_IN_OCTETS_OID, IF_OUT_OCTETS_OID]
+        .iter()
+        .find_map(|column| oid.strip_prefix(column)?.strip_prefix('.')?.parse().ok())
+}
+
 /// 자원 수집기
+#[derive(Clone)]
 pub struct ResourceCollector {
     oids: HashMap<String, String>, // key -> OID 매핑
-    community: String,
-    interface_oids: HashMap<String, (String, String)>, // interface_name -> (in_oid, out_oid)
+    snmp: SnmpSettings,
+    snmp_options: SnmpOptions,
+    interface_oids: HashMap<String, InterfaceConfig>, // interface_name -> 회선 설정
+    transport: Option<Arc<SnmpTransport>>, // 없으면 공유 소켓 사용
+    host_metrics: Option<HostMetricsConfig>, // 설정하면 SSH로 호스트 지표 수집
+    runner: Option<Arc<dyn CommandRunner>>, // 설정하면 프록시 설정 대신 이 실행기 사용
 }
 
 impl ResourceCollector {
     pub fn new(
         oids: HashMap<String, String>,
-        community: String,
-        interface_oids: HashMap<String, (String, String)>,
+        snmp: SnmpSettings,
+        snmp_options: SnmpOptions,
+        interface_oids: HashMap<String, InterfaceConfig>,
     ) -> Self {
         Self {
             oids,
-            community,
+            snmp,
+            snmp_options,
             interface_oids,
+            transport: None,
+            host_metrics: None,
+            runner: None,
+        }
+    }
+
+    /// SSH 명령 한 번으로 부하/디스크/가동 시간/스왑/파일 디스크립터/프로세스를 함께 수집
+    /// (mem이 "ssh"이면 메모리도 같은 명령에서 가져옴)
+    pub fn with_host_metrics(mut self, config: HostMetricsConfig) -> Self {
+        self.host_metrics = Some(config);
+        self
+    }
+
+    /// 프록시 설정 대신 지정한 실행기로 SSH 지표 수집 (테스트에서 사용)
+    #[cfg(test)]
+    pub fn with_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = Some(runner);
+        self
+    }
+
+    fn runner_for(&self, proxy: &Proxy, cancel: CancellationToken) -> Arc<dyn CommandRunner> {
+        match &self.runner {
+            Some(runner) => runner.clone(),
+            None => command_runner::runner_for(proxy, proxy.port, RUNNER_TIMEOUT, cancel, None),
+        }
+    }
+
+    /// 공유 소켓 대신 지정한 트랜스포트 사용 (테스트 런타임마다 별도 소켓이 필요할 때)
+    #[cfg(test)]
+    pub fn with_transport(mut self, transport: Arc<SnmpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// 프록시에 사용할 SNMP 인증 정보
+    /// 버전/커뮤니티/v3 사용자는 프록시 설정이 있으면 우선하고, 없으면 전역 설정을 사용합니다.
+    /// (snmp_version 없이 snmp_v3만 있는 프록시는 v3로 간주)
+    /// 커뮤니티와 v3 비밀번호의 `env:`/`cmd:`/`vault:` 참조는 그대로 둡니다.
+    /// (로그에 써도 비밀 값이 드러나지 않음, 실제 값은 `resolved_credentials`)
+    fn credentials_for(&self, proxy: &Proxy) -> Result<SnmpCredentials> {
+        let version = proxy.snmp_version.unwrap_or(if proxy.snmp_v3.is_some() {
+            SnmpVersion::V3
+        } else {
+            self.snmp.version
+        });
+        match version {
+            SnmpVersion::V2c => {
+                let community = proxy.snmp_community.as_deref().unwrap_or(&self.snmp.community);
+                Ok(SnmpCredentials::V2c(community.to_string()))
+            }
+            SnmpVersion::V3 => {
+                let v3 = proxy.snmp_v3.clone().or_else(|| self.snmp.v3.clone()).ok_or_else(|| {
+                    anyhow::anyhow!("SNMPv3 설정이 없습니다 (프록시 snmp_v3 또는 resource_config.json의 v3)")
+                })?;
+                Ok(SnmpCredentials::V3(v3))
+            }
+        }
+    }
+
+    /// 참조를 실제 값으로 바꾼 SNMP 인증 정보
+    /// (`cmd:`는 외부 명령을 실행하므로 블로킹 스레드에서 확인)
+    async fn resolved_credentials(&self, proxy: &Proxy) -> Result<SnmpCredentials> {
+        let credentials = self.credentials_for(proxy)?;
+        tokio::task::spawn_blocking(move || credentials.resolve_secrets()).await?
+    }
+
+    /// 프록시용 SNMP 클라이언트 (모든 요청은 공유 소켓 사용)
+    /// 인증 정보는 수집 주기마다 프록시당 한 번 확인한 결과를 받습니다.
+    fn snmp_client_for(&self, credentials: &Result<SnmpCredentials>) -> Result<SnmpClient> {
+        let credentials = credentials.as_ref().map_err(|e| anyhow::anyhow!("{:#}", e))?;
+        let client = SnmpClient::with_credentials(credentials.clone())
+            .with_options(self.snmp_options.clone());
+        Ok(match &self.transport {
+            Some(transport) => client.with_transport(transport.clone()),
+            None => client,
+        })
+    }
+
+    /// 프록시의 SNMP 에이전트 주소 (host:port, 포트는 프록시 설정 -> 전역 설정 순)
+    fn snmp_agent(&self, proxy: &Proxy) -> String {
+        crate::snmp::agent_address(&proxy.host, proxy.snmp_port.unwrap_or(self.snmp.port))
+    }
+
+    /// 프록시 하나의 수집 제한 시간
+    /// (인터페이스 탐색 walk 2회 + GET 1회가 모두 재시도까지 가는 경우를 허용)
+    /// (SSH 지표를 수집하면 SSH 지표 제한 시간 이후 결과를 정리할 여유도 허용)
+    fn proxy_timeout(&self) -> std::time::Duration {
+        let worst_case = self.snmp_options.max_duration() * 3 + std::time::Duration::from_secs(1);
+        let timeout = worst_case.max(std::time::Duration::from_secs(5));
+        if self.host_metrics.is_some() || self.ssh_mem() {
+            timeout.max(Self::ssh_timeout() + std::time::Duration::from_secs(1))
+        } else {
+            timeout
         }
     }
 
+    /// SSH 지표 태스크를 기다리는 시간 (실행기가 먼저 시간 초과를 알릴 수 있도록 1초 여유)
+    fn ssh_timeout() -> Duration {
+        RUNNER_TIMEOUT + Duration::from_secs(1)
+    }
+
+    /// 메모리를 SNMP 대신 SSH로 수집하는지
+    fn ssh_mem(&self) -> bool {
+        self.oids.get("mem").is_some_and(|oid| oid.eq_ignore_ascii_case("ssh"))
+    }
+
+    /// 인터페이스 이름을 ifName/ifDescr과 비교하여 ifIndex를 찾습니다.
+    /// 결과는 프록시별로 캐시하며 IF_INDEX_REFRESH_SECS마다 다시 탐색합니다.
+    async fn discover_if_indexes(
+        &self,
+        proxy: &Proxy,
+        credentials: &Result<SnmpCredentials>,
+        names: &[String],
+        current_time: f64,
+    ) -> Result<HashMap<String, u32>> {
+        if let Some((indexes, discovered_at)) = get_if_index_cache().lock().unwrap().get(&proxy.id) {
+            if current_time - discovered_at < IF_INDEX_REFRESH_SECS {
+                return Ok(indexes.clone());
+            }
+        }
+
+        let client = self.snmp_client_for(credentials)?;
+        let agent = self.snmp_agent(proxy);
+        // ifName(IF-MIB ifXTable)을 우선 사용하고, 없으면 ifDescr로 매칭
+        let (if_names, if_descrs) = tokio::join!(
+            client.bulk_walk(&agent, IF_NAME_OID, 25),
+            client.bulk_walk(&agent, IF_DESCR_OID, 25),
+        );
+        if let (Err(e), Err(_)) = (&if_names, &if_descrs) {
+            anyhow::bail!("인터페이스 탐색 실패: {}", e);
+        }
+
+        let to_entries = |walked: Result<Vec<(Vec<u32>, crate::snmp::Value)>>| -> Vec<(String, u32)> {
+            walked
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|(oid, value)| Some((value.as_text()?, *oid.last()?)))
+                .collect()
+        };
+        let if_names = to_entries(if_names);
+        let if_descrs = to_entries(if_descrs);
+
+        let mut indexes = HashMap::new();
+        for name in names {
+            let found = if_names
+                .iter()
+                .chain(if_descrs.iter())
+                .find(|(label, _)| label.eq_ignore_ascii_case(name));
+            match found {
+                Some((_, if_index)) => {
+                    indexes.insert(name.clone(), *if_index);
+                }
+                None => log_error(&format!("인터페이스 {}을(를) {}에서 찾을 수 없습니다 (ifName/ifDescr)", name, proxy.host)),
+            }
+        }
+
+        get_if_index_cache()
+            .lock()
+            .unwrap()
+            .insert(proxy.id, (indexes.clone(), current_time));
+        Ok(indexes)
+    }
+
+    /// 설정된 인터페이스 OID에 자동 탐색 결과를 합쳐 요청할 회선 목록을 만듭니다.
+    /// 반환값의 두 번째 항목은 자동 탐색으로 찾은 인터페이스 이름입니다.
+    async fn resolve_interface_oids(
+        &self,
+        proxy: &Proxy,
+        credentials: &Result<SnmpCredentials>,
+        current_time: f64,
+    ) -> (HashMap<String, ResolvedInterface>, Vec<String>) {
+        let mut resolved = HashMap::new();
+        let mut to_discover = Vec::new();
+        for (if_name, config) in &self.interface_oids {
+            if config.in_oid.is_empty() && config.out_oid.is_empty() {
+                to_discover.push(if_name.clone());
+            } else {
+                resolved.insert(
+                    if_name.clone(),
+                    ResolvedInterface {
+                        in_oid: config.in_oid.clone(),
+                        out_oid: config.out_oid.clone(),
+                        if_index: if_index_of(&config.in_oid).or_else(|| if_index_of(&config.out_oid)),
+                        speed_mbps: config.speed_mbps,
+                    },
+                );
+            }
+        }
+
+        let mut discovered = Vec::new();
+        if !to_discover.is_empty() {
+            match self.discover_if_indexes(proxy, credentials, &to_discover, current_time).await {
+                Ok(indexes) => {
+                    for (if_name, if_index) in indexes {
+                        resolved.insert(
+                            if_name.clone(),
+                            ResolvedInterface {
+                                in_oid: format!("{}.{}", IF_HC_IN_OCTETS_OID, if_index),
+                                out_oid: format!("{}.{}", IF_HC_OUT_OCTETS_OID, if_index),
+                                if_index: Some(if_index),
+                                speed_mbps: self.interface_oids.get(&if_name).and_then(|c| c.speed_mbps),
+                            },
+                        );
+                        discovered.push(if_name);
+                    }
+                }
+                Err(e) => log_error(&format!("{} for {}", e, proxy.host)),
+            }
+        }
+        (resolved, discovered)
+    }
+
     /// 프록시의 자원 사용률을 수집합니다.
+    /// SNMP 지표와 인터페이스 카운터는 multi-varbind GET으로 한 번에 조회합니다.
     pub async fn collect_for_proxy(&self, proxy: &Proxy) -> Result<ResourceData> {
         let mut cpu: Option<f64> = None;
         let mut mem: Option<f64> = None;
@@ -54,270 +441,280 @@ impl ResourceCollector {
         let mut collection_failed = false;
         let mut error_messages: Vec<String> = Vec::new();
 
-        // 모든 지표를 병렬로 수집
-        let mut tasks: Vec<(String, tokio::task::JoinHandle<Result<f64>>)> = Vec::new();
-
-        // CPU 수집
-        if let Some(cpu_oid) = self.oids.get("cpu") {
-            if !cpu_oid.trim().is_empty() && !cpu_oid.eq_ignore_ascii_case("ssh") {
-                let host = proxy.host.clone();
-                let community = self.community.clone();
-                let oid = cpu_oid.clone();
-                tasks.push(("cpu".to_string(), tokio::spawn(async move {
-                    snmp_get_async(&host, &community, &oid).await
-                })));
-            }
-        }
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
 
-        // 메모리 수집 (SNMP 또는 SSH)
-        if let Some(mem_oid) = self.oids.get("mem") {
-            if mem_oid.eq_ignore_ascii_case("ssh") {
-                // SSH를 통한 메모리 수집
-                let ssh_client = SshClient::new(
-                    proxy.host.clone(),
-                    proxy.port,
-                    proxy.username.clone(),
-                    proxy.password.clone(),
-                );
-                tasks.push(("mem".to_string(), tokio::spawn(async move {
-                    ssh_client.get_memory_percent().await
-                })));
-            } else if !mem_oid.trim().is_empty() {
-                let host = proxy.host.clone();
-                let community = self.community.clone();
-                let oid = mem_oid.clone();
-                tasks.push(("mem".to_string(), tokio::spawn(async move {
-                    snmp_get_async(&host, &community, &oid).await
-                })));
-            }
+        // SSH 지표는 SNMP 요청과 병렬로 실행
+        // (호스트 지표를 켰으면 메모리도 같은 명령에서 가져와 프록시당 SSH 실행은 한 번)
+        // 제한 시간이 지나면 토큰을 취소해 실행 중인 SSH 명령도 정리
+        let ssh_mem = self.ssh_mem();
+        let ssh_cancel = CancellationToken::new();
+        let ssh_deadline = tokio::time::Instant::now() + Self::ssh_timeout();
+        let mut ssh_mem_task = None;
+        let mut host_metrics_task = None;
+        if let Some(config) = self.host_metrics.clone() {
+            let runner = self.runner_for(proxy, ssh_cancel.clone());
+            host_metrics_task = Some(tokio::spawn(async move {
+                host_metrics::collect(runner.as_ref(), &config).await
+            }));
+        } else if ssh_mem {
+            let runner = self.runner_for(proxy, ssh_cancel.clone());
+            ssh_mem_task = Some(tokio::spawn(async move {
+                command_runner::memory_percent(runner.as_ref()).await
+            }));
         }
 
-        // CC 수집
-        if let Some(cc_oid) = self.oids.get("cc") {
-            if !cc_oid.trim().is_empty() {
-                let host = proxy.host.clone();
-                let community = self.community.clone();
-                let oid = cc_oid.clone();
-                tasks.push(("cc".to_string(), tokio::spawn(async move {
-                    snmp_get_async(&host, &community, &oid).await
-                })));
+        // SNMP로 조회할 OID 목록: 지표 (key, oid)
+        let mut metric_keys: Vec<&str> = Vec::new();
+        let mut request_oids: Vec<String> = Vec::new();
+        for key in ["cpu", "mem", "cc", "cs", "http", "https", "ftp"] {
+            if let Some(oid) = self.oids.get(key) {
+                let oid = oid.trim();
+                if !oid.is_empty() && !oid.eq_ignore_ascii_case("ssh") {
+                    metric_keys.push(key);
+                    request_oids.push(oid.to_string());
+                }
             }
         }
 
-        // CS 수집
-        if let Some(cs_oid) = self.oids.get("cs") {
-            if !cs_oid.trim().is_empty() {
-                let host = proxy.host.clone();
-                let community = self.community.clone();
-                let oid = cs_oid.clone();
-                tasks.push(("cs".to_string(), tokio::spawn(async move {
-                    snmp_get_async(&host, &community, &oid).await
-                })));
-            }
-        }
+        // SNMP 인증 정보는 프록시당 한 번만 확인 (SNMP로 조회할 항목이 있을 때만)
+        let credentials = if metric_keys.is_empty() && self.interface_oids.is_empty() {
+            Err(anyhow::anyhow!("SNMP로 조회할 항목이 없습니다"))
+        } else {
+            self.resolved_credentials(proxy).await
+        };
 
-        // HTTP 수집 (Counter32 누적값)
-        if let Some(http_oid) = self.oids.get("http") {
-            if !http_oid.trim().is_empty() {
-                let host = proxy.host.clone();
-                let community = self.community.clone();
-                let oid = http_oid.clone();
-                tasks.push(("http".to_string(), tokio::spawn(async move {
-                    snmp_get_async(&host, &community, &oid).await
-                })));
+        // 인터페이스 (interface_name, direction) - 지표 뒤에 이어서 요청
+        let (interface_oids, discovered_interfaces) =
+            self.resolve_interface_oids(proxy, &credentials, current_time).await;
+        let mut interface_keys: Vec<(String, &str)> = Vec::new();
+        // 회선 속도 (bps) - 설정값이 없으면 ifHighSpeed(Mbps)를 함께 요청
+        let mut interface_speeds: HashMap<String, f64> = HashMap::new();
+        for (if_name, interface) in &interface_oids {
+            if !interface.in_oid.is_empty() {
+                interface_keys.push((if_name.clone(), "in"));
+                request_oids.push(interface.in_oid.clone());
+            }
+            if !interface.out_oid.is_empty() {
+                interface_keys.push((if_name.clone(), "out"));
+                request_oids.push(interface.out_oid.clone());
+            }
+            match (interface.speed_mbps, interface.if_index) {
+                (Some(speed_mbps), _) => {
+                    interface_speeds.insert(if_name.clone(), speed_mbps * 1_000_000.0);
+                }
+                (None, Some(if_index)) => {
+                    interface_keys.push((if_name.clone(), "speed"));
+                    request_oids.push(format!("{}.{}", IF_HIGH_SPEED_OID, if_index));
+                }
+                (None, None) => {}
+            }
+            // 운영 상태와 에러/폐기 카운터 (ifIndex를 알 때만)
+            if let Some(if_index) = interface.if_index {
+                for (kind, column) in IF_STATUS_COLUMNS {
+                    interface_keys.push((if_name.clone(), kind));
+                    request_oids.push(format!("{}.{}", column, if_index));
+                }
             }
         }
 
-        // HTTPS 수집 (Counter32 누적값)
-        if let Some(https_oid) = self.oids.get("https") {
-            if !https_oid.trim().is_empty() {
-                let host = proxy.host.clone();
-                let community = self.community.clone();
-                let oid = https_oid.clone();
-                tasks.push(("https".to_string(), tokio::spawn(async move {
-                    snmp_get_async(&host, &community, &oid).await
-                })));
-            }
+        // 카운터가 있으면 리셋 판단용 sysUpTime을 마지막에 함께 요청
+        let has_counters = !interface_keys.is_empty()
+            || metric_keys.iter().any(|key| matches!(*key, "http" | "https" | "ftp"));
+        if has_counters {
+            request_oids.push(SYS_UPTIME_OID.to_string());
         }
 
-        // FTP 수집 (Counter32 누적값)
-        if let Some(ftp_oid) = self.oids.get("ftp") {
-            if !ftp_oid.trim().is_empty() {
-                let host = proxy.host.clone();
-                let community = self.community.clone();
-                let oid = ftp_oid.clone();
-                tasks.push(("ftp".to_string(), tokio::spawn(async move {
-                    snmp_get_async(&host, &community, &oid).await
-                })));
+        let mut values: Vec<Result<Value>> = Vec::new();
+        if !request_oids.is_empty() {
+            let result = match self.snmp_client_for(&credentials) {
+                Ok(client) => client.get_many_values(&self.snmp_agent(proxy), &request_oids).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(results) => values = results,
+                Err(e) => {
+                    // 요청 자체가 실패하면 모든 SNMP 지표를 실패 처리
+                    // (인증 정보는 참조 그대로 요약해 비밀 값을 다시 확인하거나 남기지 않음)
+                    let err_msg = format!("SNMP 수집 실패: {}", e);
+                    log_error(&format!(
+                        "{} for {} [{}] - SNMP 서버 응답 없음 (방화벽, 네트워크, 또는 SNMP 서비스 확인 필요)",
+                        err_msg,
+                        self.snmp_agent(proxy),
+                        self.credentials_for(proxy).map(|c| c.describe()).unwrap_or_else(|e| e.to_string())
+                    ));
+                    if !metric_keys.is_empty() {
+                        error_messages.push(err_msg);
+                        collection_failed = true;
+                    }
+                }
             }
         }
+        // sysUpTime (Timeticks)은 요청 목록의 마지막 항목
+        let uptime = if has_counters && values.len() == request_oids.len() {
+            match values.pop() {
+                Some(Ok(Value::Timeticks(ticks))) => Some(ticks),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        let mut values = values.into_iter().zip(request_oids.iter());
 
-        // 모든 작업 실행 (각 작업에 개별 타임아웃 적용)
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs_f64();
-
-        for (key, handle) in tasks {
-            let result = tokio::time::timeout(
-                std::time::Duration::from_secs(5),
-                handle
-            ).await;
-
-            match result {
-                Ok(Ok(Ok(value))) => {
-                    // 성공
-                    match key.as_str() {
-                        "cpu" => cpu = Some(value),
-                        "mem" => mem = Some(value),
-                        "cc" => cc = Some(value),
-                        "cs" => cs = Some(value),
-                        "http" | "https" | "ftp" => {
-                            // Counter32 누적값 처리
-                            let counter = value as u64;
-                            let cache_key = (proxy.id, key.clone());
-                            let mut cache = get_counter_cache().lock().unwrap();
-                            
-                            if let Some((prev_counter, prev_time)) = cache.get(&cache_key) {
-                                let time_diff = current_time - prev_time;
-                                // 1초 이상 5분 이하 차이만 유효 (재시작 시 오래된 캐시 무시)
-                                if time_diff >= 1.0 && time_diff <= 300.0 {
-                                    // bps 계산 (바이트/초 * 8 = 비트/초)
-                                    let bps = calculate_bps(*prev_counter, counter, time_diff);
-                                    // 비정상적으로 큰 값 필터링 (예: 100Gbps 이상은 무시)
-                                    const MAX_BPS: f64 = 100_000_000_000.0; // 100Gbps
-                                    if bps <= MAX_BPS {
-                                        match key.as_str() {
-                                            "http" => http = Some(bps),
-                                            "https" => https = Some(bps),
-                                            "ftp" => ftp = Some(bps),
-                                            _ => {}
-                                        }
-                                    }
-                                }
-                            }
-                            
-                            // 캐시 업데이트
-                            cache.insert(cache_key, (counter, current_time));
+        // 지표 결과 처리
+        for (key, (result, oid)) in metric_keys.iter().zip(values.by_ref()) {
+            let result = match *key {
+                // 누적 카운터: 이전 측정값과 비교해 bps 계산
+                "http" | "https" | "ftp" => result
+                    .and_then(|value| CounterSample::new(oid, &value, current_time, uptime))
+                    .map(|sample| {
+                        let mut cache = get_counter_cache().lock().unwrap();
+                        let bps = cache
+                            .insert((proxy.id, key.to_string()), sample)
+                            .and_then(|prev| counter_rate(&prev, &sample));
+                        match *key {
+                            "http" => http = bps,
+                            "https" => https = bps,
+                            _ => ftp = bps,
                         }
-                        _ => {}
-                    }
+                    }),
+                _ => result.and_then(|value| SnmpClient::value_to_f64(oid, value)).map(|value| match *key {
+                    "cpu" => cpu = Some(value),
+                    "mem" => mem = Some(value),
+                    "cc" => cc = Some(value),
+                    _ => cs = Some(value),
+                }),
+            };
+            match result {
+                Ok(()) => {}
+                Err(e) => {
+                    let err_msg = format!("{} 수집 실패: {}", key, e);
+                    log_error(&format!("{} for {}", err_msg, proxy.host));
+                    error_messages.push(err_msg);
+                    collection_failed = true;
                 }
+            }
+        }
+
+        // SSH 메모리 결과 처리
+        if let Some(handle) = ssh_mem_task {
+            match tokio::time::timeout_at(ssh_deadline, handle).await {
+                Ok(Ok(Ok(value))) => mem = Some(value),
                 Ok(Ok(Err(e))) => {
-                    let err_msg = format!("{} 수집 실패: {}", key, e);
+                    let err_msg = format!("mem 수집 실패: {}", e);
                     log_error(&format!("{} for {}", err_msg, proxy.host));
                     error_messages.push(err_msg);
                     collection_failed = true;
                 }
                 Ok(Err(e)) => {
-                    let err_msg = format!("{} 태스크 실패: {}", key, e);
+                    let err_msg = format!("mem 태스크 실패: {}", e);
                     log_error(&format!("{} for {}", err_msg, proxy.host));
                     error_messages.push(err_msg);
                     collection_failed = true;
                 }
                 Err(_) => {
-                    let err_msg = format!("{} 수집 타임아웃 (8초 초과)", key);
-                    log_error(&format!("{} for {} - SNMP 서버 응답 없음 (방화벽, 네트워크, 또는 SNMP 서비스 확인 필요)", err_msg, proxy.host));
+                    ssh_cancel.cancel();
+                    let err_msg = "mem 수집 타임아웃".to_string();
+                    log_error(&format!("{} for {} - SSH 응답 없음", err_msg, proxy.host));
                     error_messages.push(err_msg);
                     collection_failed = true;
                 }
             }
         }
 
-        // 인터페이스 트래픽 수집
-        if !self.interface_oids.is_empty() {
-            let mut interface_tasks = Vec::new();
-            for (if_name, (in_oid, out_oid)) in &self.interface_oids {
-                if !in_oid.is_empty() {
-                    let host = proxy.host.clone();
-                    let community = self.community.clone();
-                    let oid = in_oid.clone();
-                    let if_name_clone = if_name.clone();
-                    interface_tasks.push((
-                        if_name_clone.clone(),
-                        "in".to_string(),
-                        tokio::spawn(async move {
-                            snmp_get_async(&host, &community, &oid).await
-                        }),
-                    ));
-                }
-                if !out_oid.is_empty() {
-                    let host = proxy.host.clone();
-                    let community = self.community.clone();
-                    let oid = out_oid.clone();
-                    let if_name_clone = if_name.clone();
-                    interface_tasks.push((
-                        if_name_clone.clone(),
-                        "out".to_string(),
-                        tokio::spawn(async move {
-                            snmp_get_async(&host, &community, &oid).await
-                        }),
-                    ));
+        // 호스트 지표 결과 처리
+        let mut host = HostMetrics::default();
+        if let Some(handle) = host_metrics_task {
+            let result = match tokio::time::timeout_at(ssh_deadline, handle).await {
+                Ok(Ok(result)) => result.map_err(|e| format!("호스트 지표 수집 실패: {}", e)),
+                Ok(Err(e)) => Err(format!("호스트 지표 태스크 실패: {}", e)),
+                Err(_) => {
+                    ssh_cancel.cancel();
+                    Err("호스트 지표 수집 타임아웃".to_string())
                 }
-            }
-
-            // 인터페이스 카운터 수집
-            let mut interface_counters: HashMap<String, (Option<u64>, Option<u64>)> = HashMap::new();
-            for (if_name, direction, handle) in interface_tasks {
-                let result = tokio::time::timeout(
-                    std::time::Duration::from_secs(5),
-                    handle,
-                )
-                .await;
-
-                if let Ok(Ok(Ok(value))) = result {
-                    let counter = value as u64;
-                    let entry = interface_counters.entry(if_name).or_insert((None, None));
-                    match direction.as_str() {
-                        "in" => entry.0 = Some(counter),
-                        "out" => entry.1 = Some(counter),
-                        _ => {}
+            };
+            match result {
+                Ok(metrics) => {
+                    if ssh_mem {
+                        mem = metrics.mem;
                     }
+                    host = metrics;
+                }
+                Err(err_msg) => {
+                    log_error(&format!("{} for {}", err_msg, proxy.host));
+                    error_messages.push(err_msg);
+                    collection_failed = true;
                 }
             }
+        }
 
-            // bps 계산 (이전 값과 비교)
-            for (if_name, (in_counter, out_counter)) in interface_counters {
-                let cache_key = (proxy.id, if_name.clone());
-                let mut cache = get_interface_cache().lock().unwrap();
-
-                if let Some((prev_in, prev_out, prev_time)) = cache.get(&cache_key) {
-                    let time_diff = current_time - prev_time;
-                    // 1초 이상 5분 이하 차이만 유효 (재시작 시 오래된 캐시 무시)
-                    if time_diff >= 1.0 && time_diff <= 300.0 {
-                        let in_bps = if let Some(current_in) = in_counter {
-                            calculate_bps(*prev_in, current_in, time_diff)
-                        } else {
-                            0.0
-                        };
-                        let out_bps = if let Some(current_out) = out_counter {
-                            calculate_bps(*prev_out, current_out, time_diff)
-                        } else {
-                            0.0
-                        };
-
-                        // 비정상적으로 큰 값 필터링 (예: 100Gbps 이상은 무시)
-                        const MAX_BPS: f64 = 100_000_000_000.0; // 100Gbps
-                        if (in_bps > 0.0 || out_bps > 0.0) && in_bps <= MAX_BPS && out_bps <= MAX_BPS {
-                            interfaces.push(InterfaceTraffic {
-                                name: if_name.clone(),
-                                in_mbps: in_bps, // 필드명은 유지하지만 값은 bps
-                                out_mbps: out_bps,
-                            });
+        // 인터페이스 결과 처리
+        if !interface_keys.is_empty() {
+            let mut stats: HashMap<String, InterfaceTraffic> = HashMap::new();
+            let mut stale_if_index = false;
+            let mut cache = get_interface_cache().lock().unwrap();
+            for ((if_name, kind), (result, oid)) in interface_keys.into_iter().zip(values) {
+                let entry = stats.entry(if_name.clone()).or_insert_with(|| InterfaceTraffic {
+                    name: if_name.clone(),
+                    in_bps: None,
+                    out_bps: None,
+                    speed_bps: interface_speeds.get(&if_name).copied(),
+                    status: None,
+                    in_errors: None,
+                    out_errors: None,
+                    in_discards: None,
+                    out_discards: None,
+                });
+                match kind {
+                    // ifHighSpeed (Mbps, Gauge32) - 0이면 속도를 모르는 회선
+                    "speed" => match result.and_then(|value| SnmpClient::value_to_f64(oid, value)) {
+                        Ok(speed_mbps) if speed_mbps > 0.0 => entry.speed_bps = Some(speed_mbps * 1_000_000.0),
+                        Ok(_) => {}
+                        Err(e) => log_error(&format!("인터페이스 {} 속도 조회 실패: {} for {}", if_name, e, proxy.host)),
+                    },
+                    "status" => match result.and_then(|value| SnmpClient::value_to_f64(oid, value)) {
+                        Ok(status) => entry.status = Some(LinkStatus::from_oper_status(status as i64)),
+                        Err(e) => log_error(&format!("인터페이스 {} 상태 조회 실패: {} for {}", if_name, e, proxy.host)),
+                    },
+                    // 카운터: 이전 측정값과 비교해 초당 증가량 계산
+                    _ => match result.and_then(|value| CounterSample::new(oid, &value, current_time, uptime)) {
+                        Ok(sample) => {
+                            let prev = cache.insert((proxy.id, if_name.clone(), kind), sample);
+                            let prev = prev.as_ref();
+                            // 속도 항목이 에러/폐기 카운터보다 먼저 처리되므로 speed_bps는 이미 채워져 있음
+                            let packets = || prev.and_then(|prev| packet_rate(prev, &sample, entry.speed_bps));
+                            match kind {
+                                "in" => entry.in_bps = prev.and_then(|prev| counter_rate(prev, &sample)),
+                                "out" => entry.out_bps = prev.and_then(|prev| counter_rate(prev, &sample)),
+                                "in_errors" => entry.in_errors = packets(),
+                                "out_errors" => entry.out_errors = packets(),
+                                "in_discards" => entry.in_discards = packets(),
+                                _ => entry.out_discards = packets(),
+                            }
                         }
-                    }
+                        Err(e) => {
+                            log_error(&format!("인터페이스 {} {} 수집 실패: {} for {}", if_name, kind, e, proxy.host));
+                            if matches!(kind, "in" | "out") && discovered_interfaces.contains(&if_name) {
+                                // ifIndex가 바뀌었을 수 있으므로 다음 수집 때 다시 탐색
+                                stale_if_index = true;
+                            }
+                        }
+                    },
                 }
-
-                // 캐시 업데이트
-                cache.insert(
-                    cache_key,
-                    (
-                        in_counter.unwrap_or(0),
-                        out_counter.unwrap_or(0),
-                        current_time,
-                    ),
-                );
             }
+            drop(cache);
+            if stale_if_index {
+                get_if_index_cache().lock().unwrap().remove(&proxy.id);
+            }
+
+            // 트래픽이나 상태 중 하나라도 있는 회선만 표시
+            interfaces = stats
+                .into_values()
+                .filter(|i| i.in_bps.is_some() || i.out_bps.is_some() || i.status.is_some())
+                .collect();
+            interfaces.sort_by(|a, b| a.name.cmp(&b.name));
         }
 
         Ok(ResourceData {
@@ -332,6 +729,13 @@ impl ResourceCollector {
             https,
             ftp,
             interfaces,
+            load_avg: host.load_avg,
+            cpu_cores: host.cpu_cores,
+            swap: host.swap,
+            open_files: host.open_files,
+            uptime_secs: host.uptime_secs,
+            disks: host.disks,
+            processes: host.processes,
             collected_at: Local::now(),
             collection_failed,
             error_message: if !error_messages.is_empty() {
@@ -339,35 +743,39 @@ impl ResourceCollector {
             } else {
                 None
             },
+            skipped: None,
         })
     }
 
     /// 여러 프록시의 자원 사용률을 병렬로 수집합니다.
-    /// 실패한 프록시도 실패 데이터로 포함합니다.
+    /// 실패한 프록시도 실패 데이터로 포함하고, 비활성이거나 점검 중인 프록시는 수집하지 않고 그 상태로 포함합니다.
     pub async fn collect_multiple(&self, proxies: &[Proxy]) -> Result<Vec<ResourceData>> {
         let mut tasks = Vec::new();
         let mut proxy_map: HashMap<u32, Proxy> = HashMap::new();
-        
+        let mut results = Vec::new();
+
         for proxy in proxies {
+            let state = proxy.state();
+            if !state.is_active() {
+                results.push(ResourceData::skipped(proxy, state));
+                continue;
+            }
             proxy_map.insert(proxy.id, proxy.clone());
-            let oids = self.oids.clone();
-            let community = self.community.clone();
-            let interface_oids = self.interface_oids.clone();
+            let collector = self.clone();
             let proxy_clone = proxy.clone();
             
             tasks.push((proxy.id, tokio::spawn(async move {
-                let collector = ResourceCollector::new(oids, community, interface_oids);
                 collector.collect_for_proxy(&proxy_clone).await
             })));
         }
 
-        // 모든 작업을 병렬로 실행하고 타임아웃 적용 (각 프록시당 최대 5초)
-        let mut results = Vec::new();
+        // 모든 작업을 병렬로 실행하고 타임아웃 적용 (SNMP 재시도/SSH 지표 설정에 따라 최소 5초)
+        let proxy_timeout = self.proxy_timeout();
         
         for (proxy_id, task) in tasks {
             // 각 작업에 개별 타임아웃 적용
             match tokio::time::timeout(
-                std::time::Duration::from_secs(5),
+                proxy_timeout,
                 task
             ).await {
                 Ok(Ok(Ok(data))) => {
@@ -377,69 +785,21 @@ impl ResourceCollector {
                 Ok(Ok(Err(e))) => {
                     // 수집 실패 - 실패 데이터 생성
                     if let Some(proxy) = proxy_map.get(&proxy_id) {
-                        let failed_data = ResourceData {
-                            proxy_id: proxy.id,
-                            host: proxy.host.clone(),
-                            proxy_name: proxy.alias.clone().or_else(|| Some(format!("{}{}", proxy.group, proxy.id))),
-                            cpu: None,
-                            mem: None,
-                            cc: None,
-                            cs: None,
-                            http: None,
-                            https: None,
-                            ftp: None,
-                            interfaces: Vec::new(),
-                            collected_at: Local::now(),
-                            collection_failed: true,
-                            error_message: Some(format!("수집 실패: {}", e)),
-                        };
-                        results.push(failed_data);
+                        results.push(ResourceData::failed(proxy, format!("수집 실패: {}", e)));
                     }
                     log_error(&format!("프록시 {} 수집 실패: {}", proxy_id, e));
                 }
                 Ok(Err(e)) => {
                     // 태스크 실행 실패 - 실패 데이터 생성
                     if let Some(proxy) = proxy_map.get(&proxy_id) {
-                        let failed_data = ResourceData {
-                            proxy_id: proxy.id,
-                            host: proxy.host.clone(),
-                            proxy_name: proxy.alias.clone().or_else(|| Some(format!("{}{}", proxy.group, proxy.id))),
-                            cpu: None,
-                            mem: None,
-                            cc: None,
-                            cs: None,
-                            http: None,
-                            https: None,
-                            ftp: None,
-                            interfaces: Vec::new(),
-                            collected_at: Local::now(),
-                            collection_failed: true,
-                            error_message: Some(format!("태스크 실행 실패: {}", e)),
-                        };
-                        results.push(failed_data);
+                        results.push(ResourceData::failed(proxy, format!("태스크 실행 실패: {}", e)));
                     }
                     log_error(&format!("프록시 {} 태스크 실행 실패: {}", proxy_id, e));
                 }
                 Err(_) => {
                     // 타임아웃 - 실패 데이터 생성
                     if let Some(proxy) = proxy_map.get(&proxy_id) {
-                        let failed_data = ResourceData {
-                            proxy_id: proxy.id,
-                            host: proxy.host.clone(),
-                            proxy_name: proxy.alias.clone().or_else(|| Some(format!("{}{}", proxy.group, proxy.id))),
-                            cpu: None,
-                            mem: None,
-                            cc: None,
-                            cs: None,
-                            http: None,
-                            https: None,
-                            ftp: None,
-                            interfaces: Vec::new(),
-                            collected_at: Local::now(),
-                            collection_failed: true,
-                            error_message: Some("수집 타임아웃".to_string()),
-                        };
-                        results.push(failed_data);
+                        results.push(ResourceData::failed(proxy, "수집 타임아웃"));
                     }
                     log_error(&format!("프록시 {} 수집 타임아웃", proxy_id));
                 }
@@ -453,40 +813,11 @@ impl ResourceCollector {
     }
 }
 
-/// bps 계산 함수 (32비트 카운터 오버플로우 처리)
-/// 바이트 카운터를 비트/초로 변환
-fn calculate_bps(prev: u64, current: u64, time_diff_sec: f64) -> f64 {
-    // 32비트 카운터 최대값
-    const COUNTER32_MAX: u64 = 4_294_967_295;
-    
-    let diff = if current >= prev {
-        current - prev
-    } else {
-        // 카운터 오버플로우 처리
-        // 단, 차이가 너무 크면 (예: COUNTER32_MAX의 절반 이상) 오버플로우가 아닐 수 있음
-        // 이 경우는 재시작이나 리셋으로 간주하고 무시
-        let overflow_diff = (COUNTER32_MAX - prev) + current + 1;
-        if overflow_diff > COUNTER32_MAX / 2 {
-            // 차이가 너무 크면 비정상적인 값으로 간주
-            return 0.0;
-        }
-        overflow_diff
-    };
-    
-    // 비정상적으로 큰 차이 필터링 (예: 1초에 10GB 이상 증가는 비정상)
-    if diff > 10_000_000_000 {
-        return 0.0;
-    }
-    
-    // 바이트를 bps로 변환: (bytes * 8) / time_diff_sec
-    (diff as f64 * 8.0) / time_diff_sec
-}
-
 // 로그 파일 쓰기를 위한 뮤텍스 (동시성 보장)
 static LOG_MUTEX: Mutex<()> = Mutex::new(());
 
 /// 에러를 로그 파일에 기록 (스레드 안전)
-fn log_error(message: &str) {
+pub(crate) fn log_error(message: &str) {
     let _guard = LOG_MUTEX.lock().unwrap();
     
     let log_dir = "logs";
@@ -504,3 +835,389 @@ fn log_error(message: &str) {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxy(json: &str) -> Proxy {
+        let base = r#""id": 1, "host": "10.0.0.1", "port": 22, "username": "u", "password": "p", "group": "g""#;
+        serde_json::from_str(&format!("{{{}{}}}", base, json)).unwrap()
+    }
+
+    fn collector(version: SnmpVersion, v3: Option<SnmpV3Config>) -> ResourceCollector {
+        let snmp = SnmpSettings { version, community: "global".to_string(), v3, port: 161 };
+        ResourceCollector::new(HashMap::new(), snmp, SnmpOptions::default(), HashMap::new())
+    }
+
+    fn v3(username: &str) -> SnmpV3Config {
+        SnmpV3Config { username: username.to_string(), ..SnmpV3Config::default() }
+    }
+
+    #[tokio::test]
+    async fn disabled_and_in_maintenance_proxies_are_not_polled() {
+        let disabled = proxy(r#", "enabled": false"#);
+        let mut maintenance = proxy(
+            r#", "maintenance": [{ "start": "2000-01-01 00:00", "end": "2999-01-01 00:00", "reason": "교체" }]"#,
+        );
+        maintenance.id = 2;
+
+        // 접속을 시도하면 타임아웃까지 기다리므로 바로 끝나야 함
+        let started = std::time::Instant::now();
+        let results = collector(SnmpVersion::V2c, None).collect_multiple(&[disabled, maintenance]).await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| !r.collection_failed && r.error_message.is_none()));
+        assert_eq!(results[0].skipped, Some(crate::app::ProxyState::Disabled));
+        assert_eq!(results[1].skipped.as_ref().unwrap().label(), "점검 중 (~01/01 00:00, 교체)");
+    }
+
+    #[test]
+    fn proxy_snmp_settings_fall_back_to_global() {
+        let collector = collector(SnmpVersion::V2c, Some(v3("global-user")));
+
+        let plain = proxy("");
+        assert_eq!(collector.credentials_for(&plain).unwrap(), SnmpCredentials::V2c("global".to_string()));
+        assert_eq!(collector.snmp_agent(&plain), "10.0.0.1:161");
+
+        let overridden = proxy(r#", "snmp_community": "dmz", "snmp_port": 1161"#);
+        assert_eq!(collector.credentials_for(&overridden).unwrap(), SnmpCredentials::V2c("dmz".to_string()));
+        assert_eq!(collector.snmp_agent(&overridden), "10.0.0.1:1161");
+
+        // 버전만 지정하면 전역 v3 사용자 사용
+        let v3_only = proxy(r#", "snmp_version": "3""#);
+        assert_eq!(collector.credentials_for(&v3_only).unwrap(), SnmpCredentials::V3(v3("global-user")));
+
+        // snmp_v3만 있으면 v3로 간주
+        let own_user = proxy(r#", "snmp_v3": {"username": "proxy-user"}"#);
+        assert_eq!(collector.credentials_for(&own_user).unwrap(), SnmpCredentials::V3(v3("proxy-user")));
+    }
+
+    #[tokio::test]
+    async fn snmp_secret_references_are_resolved() {
+        std::env::set_var("MMT_TEST_SNMP_COMMUNITY", "dmz-secret");
+        std::env::set_var("MMT_TEST_SNMP_AUTH", "authpass123");
+        let mut global = v3("global-user");
+        global.auth_protocol = Some(crate::snmp::AuthProtocol::Sha);
+        global.auth_password = Some("env:MMT_TEST_SNMP_AUTH".to_string());
+        // 참조는 길이 검사를 건너뛰고, 실제 값으로 바꾼 뒤 검사
+        global.validate().unwrap();
+        let collector = collector(SnmpVersion::V2c, Some(global.clone()));
+
+        let community = proxy(r#", "snmp_community": "env:MMT_TEST_SNMP_COMMUNITY""#);
+        assert_eq!(
+            collector.resolved_credentials(&community).await.unwrap(),
+            SnmpCredentials::V2c("dmz-secret".to_string())
+        );
+        assert_eq!(community.credential_references(), vec!["env:MMT_TEST_SNMP_COMMUNITY"]);
+        // 로그용 요약은 참조를 그대로 보여줌
+        let described = collector.credentials_for(&community).unwrap().describe();
+        assert_eq!(described, "v2c community=env:MMT_TEST_SNMP_COMMUNITY");
+
+        let v3_proxy = proxy(r#", "snmp_version": "3""#);
+        let SnmpCredentials::V3(resolved) = collector.resolved_credentials(&v3_proxy).await.unwrap() else {
+            panic!("v3 사용자가 있어야 함");
+        };
+        assert_eq!(resolved.auth_password.as_deref(), Some("authpass123"));
+
+        let missing = proxy(
+            r#", "snmp_v3": {"username": "u", "auth_protocol": "SHA", "auth_password": "env:MMT_TEST_SNMP_UNSET"}"#,
+        );
+        let err = collector.resolved_credentials(&missing).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("env:MMT_TEST_SNMP_UNSET"), "{:#}", err);
+    }
+
+    #[test]
+    fn proxy_can_downgrade_to_v2c_or_require_v3_user() {
+        let collector = collector(SnmpVersion::V3, None);
+        let v2c = proxy(r#", "snmp_version": "2c", "snmp_community": "legacy""#);
+        assert_eq!(collector.credentials_for(&v2c).unwrap(), SnmpCredentials::V2c("legacy".to_string()));
+        // 전역/프록시 어디에도 v3 사용자가 없으면 에러
+        assert!(collector.credentials_for(&proxy("")).is_err());
+
+        let invalid: Result<Proxy, _> = serde_json::from_str(
+            r#"{"id": 1, "host": "h", "port": 22, "username": "u", "password": "p", "group": "g", "snmp_version": "1"}"#,
+        );
+        assert!(invalid.is_err());
+    }
+
+    fn sample(value: Value, timestamp: f64, uptime: Option<u32>) -> CounterSample {
+        CounterSample::new("1.3.6.1.2.1.31.1.1.1.6.1", &value, timestamp, uptime).unwrap()
+    }
+
+    #[test]
+    fn counter_rate_wraps_at_the_reported_width() {
+        // Counter32: 4294967000 -> 704 는 1000바이트 증가
+        let prev = sample(Value::Counter32(4_294_967_000), 100.0, Some(1000));
+        let current = sample(Value::Counter32(704), 110.0, Some(2000));
+        assert_eq!(counter_rate(&prev, &current), Some(800.0));
+
+        // Counter64는 2^64에서 순환
+        let prev = sample(Value::Counter64(u64::MAX - 499), 100.0, Some(1000));
+        let current = sample(Value::Counter64(500), 110.0, Some(2000));
+        assert_eq!(counter_rate(&prev, &current), Some(800.0));
+
+        // 40Gbps 회선의 10초 증가량 (50GB)도 그대로 계산
+        let prev = sample(Value::Counter64(1 << 40), 100.0, Some(1000));
+        let current = sample(Value::Counter64((1 << 40) + 50_000_000_000), 110.0, Some(2000));
+        assert_eq!(counter_rate(&prev, &current), Some(40_000_000_000.0));
+    }
+
+    #[test]
+    fn counter_rate_detects_resets_through_sys_uptime() {
+        // sysUpTime이 줄었으면 재시작 - 32비트 순환처럼 보여도 버림
+        let prev = sample(Value::Counter32(4_294_967_000), 100.0, Some(500_000));
+        let current = sample(Value::Counter32(704), 110.0, Some(300));
+        assert_eq!(counter_rate(&prev, &current), None);
+
+        // sysUpTime 없이 64비트 카운터가 줄면 리셋으로 간주
+        let prev = sample(Value::Counter64(1_000_000), 100.0, None);
+        let current = sample(Value::Counter64(10), 110.0, None);
+        assert_eq!(counter_rate(&prev, &current), None);
+
+        // 폭이 바뀌거나 간격이 유효 범위를 벗어나면 계산하지 않음
+        let prev = sample(Value::Counter32(100), 100.0, Some(1000));
+        assert_eq!(counter_rate(&prev, &sample(Value::Counter64(200), 110.0, Some(2000))), None);
+        assert_eq!(counter_rate(&prev, &sample(Value::Counter32(200), 100.5, Some(1050))), None);
+        assert_eq!(counter_rate(&prev, &sample(Value::Counter32(200), 500.0, Some(41000))), None);
+
+        // 카운터가 아닌 값은 거부
+        assert!(CounterSample::new("1.2.3", &Value::OctetString(b"x".to_vec()), 0.0, None).is_err());
+    }
+
+    #[test]
+    fn packet_rate_rejects_resets_and_rates_beyond_the_link_speed() {
+        // 순환 직전 값에서 넘어가면 정상적인 순환
+        let prev = sample(Value::Counter32(u32::MAX - 9), 100.0, Some(1000));
+        let current = sample(Value::Counter32(10), 110.0, Some(2000));
+        assert_eq!(packet_rate(&prev, &current, Some(1_000_000_000.0)), Some(2.0));
+
+        // sysUpTime이 놓친 리셋: 감소폭이 작으면 순환으로 보면 거의 2^32 증가 - 버림
+        let prev = sample(Value::Counter32(5000), 100.0, Some(1000));
+        let current = sample(Value::Counter32(10), 110.0, Some(2000));
+        assert_eq!(packet_rate(&prev, &current, None), None);
+        let prev = sample(Value::Counter64(5000), 100.0, Some(1000));
+        let current = sample(Value::Counter64(10), 110.0, Some(2000));
+        assert_eq!(packet_rate(&prev, &current, None), None);
+
+        // 1Gbps 회선은 초당 최소 프레임 약 195만 개를 넘을 수 없음
+        let prev = sample(Value::Counter32(0), 100.0, Some(1000));
+        let current = sample(Value::Counter32(100_000_000), 110.0, Some(2000));
+        assert_eq!(packet_rate(&prev, &current, Some(1_000_000_000.0)), None);
+        assert_eq!(packet_rate(&prev, &current, Some(10_000_000_000.0)), Some(10_000_000.0));
+    }
+
+    /// 시뮬레이터를 띄우고 (수집기, 시뮬레이터를 가리키는 프록시)를 반환합니다.
+    async fn simulated(
+        proxy_id: u32,
+        fixture: &crate::snmp::SimulatorFixture,
+        oids: &[(&str, &str)],
+        interface_oids: &[(&str, &str, &str)],
+    ) -> (ResourceCollector, Proxy) {
+        let simulator = crate::snmp::SnmpSimulator::bind("127.0.0.1:0", fixture).await.unwrap();
+        let port = simulator.local_addr().unwrap().port();
+        tokio::spawn(simulator.run());
+
+        let snmp = SnmpSettings { version: SnmpVersion::V2c, community: "public".to_string(), v3: None, port };
+        let options = SnmpOptions { timeout: std::time::Duration::from_millis(300), retries: 0, ..SnmpOptions::default() };
+        let oids = oids.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let interface_oids = interface_oids
+            .iter()
+            .map(|(name, in_oid, out_oid)| {
+                let config = InterfaceConfig { in_oid: in_oid.to_string(), out_oid: out_oid.to_string(), speed_mbps: None };
+                (name.to_string(), config)
+            })
+            .collect();
+        // 테스트 런타임마다 별도 소켓 사용
+        let transport = Arc::new(SnmpTransport::bind().await.unwrap());
+        let collector = ResourceCollector::new(oids, snmp, options, interface_oids).with_transport(transport);
+
+        let mut proxy = proxy("");
+        proxy.id = proxy_id;
+        proxy.host = "127.0.0.1".to_string();
+        proxy.snmp_port = Some(port);
+        (collector, proxy)
+    }
+
+    fn fixture(json: &str) -> crate::snmp::SimulatorFixture {
+        serde_json::from_str(json).unwrap()
+    }
+
+    async fn collect_once(collector: &ResourceCollector, proxy: &Proxy) -> ResourceData {
+        collector.collect_multiple(std::slice::from_ref(proxy)).await.unwrap().remove(0)
+    }
+
+    fn interface<'a>(data: &'a ResourceData, name: &str) -> Option<&'a InterfaceTraffic> {
+        data.interfaces.iter().find(|i| i.name == name)
+    }
+
+    #[tokio::test]
+    async fn collect_multiple_against_fixture_agent() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/mwg_agent.json");
+        let fixture = crate::snmp::SimulatorFixture::load(path).unwrap();
+        let (collector, proxy) = simulated(
+            9001,
+            &fixture,
+            &[("cpu", "1.3.6.1.4.1.2021.11.11.0"), ("cc", "1.3.6.1.4.1.2021.4.11.0"), ("https", "1.3.6.1.4.1.1230.2.7.2.3.3.0")],
+            &[("eth0", "", ""), ("eth4", "", ""), ("eth9", "", "")],
+        )
+        .await;
+
+        let first = collect_once(&collector, &proxy).await;
+        assert!(!first.collection_failed, "{:?}", first.error_message);
+        assert_eq!((first.cpu, first.cc), (Some(35.0), Some(8200.0)));
+        // 첫 수집은 비교할 이전 값이 없음
+        assert_eq!(first.https, None);
+        // 상태는 바로 알 수 있지만 트래픽/에러율은 다음 수집부터
+        let eth0 = interface(&first, "eth0").unwrap();
+        assert_eq!((eth0.status, eth0.in_bps, eth0.in_errors), (Some(LinkStatus::Up), None, None));
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let second = collect_once(&collector, &proxy).await;
+        assert_eq!((second.cpu, second.cc), (Some(42.0), Some(9400.0)));
+        let https = second.https.unwrap();
+        assert!(https > 1.0e9 && https < 2.0e9, "https bps {}", https);
+
+        // ifName으로 자동 탐색한 ifHCInOctets (eth4는 2^64에서 순환)
+        let eth0 = interface(&second, "eth0").unwrap();
+        let eth0_in = eth0.in_bps.unwrap();
+        assert!(eth0_in > 50.0e6 && eth0_in < 100.0e6, "eth0 in {}", eth0_in);
+        let eth4 = interface(&second, "eth4").unwrap();
+        let eth4_in = eth4.in_bps.unwrap();
+        assert!(eth4_in > 5.0e9 && eth4_in < 10.0e9, "eth4 in {}", eth4_in);
+
+        // ifHighSpeed(Mbps)로 회선 속도와 사용률 계산
+        assert_eq!(eth0.speed_bps, Some(1.0e9));
+        assert_eq!(eth4.speed_bps, Some(10.0e9));
+        let utilization = eth4.max_utilization().unwrap();
+        assert!(utilization > 50.0 && utilization < 100.0, "eth4 {}%", utilization);
+        assert!(interface(&second, "eth9").is_none());
+
+        // ifInErrors/ifInDiscards 증가량은 초당 개수로 표시
+        let errors = eth4.in_errors.unwrap();
+        assert!(errors > 25.0 && errors < 50.0, "eth4 errors {}", errors);
+        let discards = eth0.max_discards().unwrap();
+        assert!(discards > 15.0 && discards < 30.0, "eth0 discards {}", discards);
+        assert_eq!(eth0.max_errors(), Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn oper_status_marks_links_down_even_without_traffic() {
+        let agent = fixture(
+            r#"{"objects": {
+                "IF-MIB::ifName.7": {"type": "string", "value": "uplink"},
+                "IF-MIB::ifHCInOctets.7": {"type": "counter64", "values": [1000, 1000]},
+                "IF-MIB::ifHCOutOctets.7": {"type": "counter64", "values": [1000, 1000]},
+                "IF-MIB::ifOperStatus.7": {"type": "integer", "values": [1, 7]},
+                "IF-MIB::ifInErrors.7": {"type": "counter32", "values": [4294967290, 10]}
+            }}"#,
+        );
+        let (collector, proxy) = simulated(9005, &agent, &[], &[("uplink", "", "")]).await;
+
+        let first = collect_once(&collector, &proxy).await;
+        assert!(!first.has_down_interface());
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+        // lowerLayerDown(7)도 DOWN으로 취급, 에러 카운터는 32비트에서 순환
+        let second = collect_once(&collector, &proxy).await;
+        assert!(second.has_down_interface());
+        let uplink = interface(&second, "uplink").unwrap();
+        assert_eq!((uplink.status.map(|s| s.label()), uplink.in_bps), (Some("DOWN"), Some(0.0)));
+        let errors = uplink.in_errors.unwrap();
+        assert!(errors > 10.0 && errors < 16.0, "uplink errors {}", errors);
+        // 조회에 실패한 항목은 비워 둠
+        assert_eq!((uplink.out_errors, uplink.in_discards), (None, None));
+    }
+
+    #[tokio::test]
+    async fn collect_multiple_reports_error_status_octet_strings_and_timeouts() {
+        let agent = fixture(
+            r#"{"objects": {
+                "1.3.6.1.4.1.2021.11.11.0": {"error": "genErr"},
+                "1.3.6.1.4.1.2021.4.11.0": {"type": "string", "value": "n/a"},
+                "1.3.6.1.4.1.1230.2.7.2.5.1.0": {"type": "gauge32", "value": 1500}
+            }}"#,
+        );
+        let oids = [("cpu", "1.3.6.1.4.1.2021.11.11.0"), ("cc", "1.3.6.1.4.1.2021.4.11.0"), ("cs", "1.3.6.1.4.1.1230.2.7.2.5.1.0")];
+        let (collector, proxy) = simulated(9002, &agent, &oids, &[]).await;
+        let (_, silent_proxy) = simulated(9003, &fixture(r#"{"silent": true}"#), &oids, &[]).await;
+
+        // 프록시별 snmp_port로 각각의 시뮬레이터를 가리킴
+        let results = collector.collect_multiple(&[silent_proxy, proxy]).await.unwrap();
+        assert_eq!(results.iter().map(|r| r.proxy_id).collect::<Vec<_>>(), vec![9002, 9003]);
+
+        // varbind 하나의 오류는 해당 지표만 실패 처리
+        let data = &results[0];
+        assert!(data.collection_failed);
+        assert_eq!((data.cpu, data.cc, data.cs), (None, None, Some(1500.0)));
+        let message = data.error_message.as_deref().unwrap();
+        assert!(message.contains("cpu") && message.contains("genErr"), "{}", message);
+        assert!(message.contains("cc") && message.contains("OctetString"), "{}", message);
+
+        // 응답이 없으면 요청 전체가 실패
+        let timed_out = &results[1];
+        assert!(timed_out.collection_failed);
+        assert_eq!((timed_out.cpu, timed_out.cc, timed_out.cs), (None, None, None));
+        assert!(timed_out.error_message.as_deref().unwrap().contains("SNMP 수집 실패"));
+    }
+
+    #[tokio::test]
+    async fn counter32_wraps_and_agent_restarts_through_collector() {
+        let agent = fixture(
+            r#"{"objects": {
+                "SNMPv2-MIB::sysUpTime.0": {"type": "timeticks", "values": [100000, 100110, 50]},
+                "IF-MIB::ifInOctets.5": {"type": "counter32", "values": [4294967000, 704, 900]},
+                "IF-MIB::ifOutOctets.5": {"type": "counter32", "values": [10, 20, 30]},
+                "IF-MIB::ifHighSpeed.5": {"type": "gauge32", "value": 100}
+            }}"#,
+        );
+        let (collector, proxy) =
+            simulated(9004, &agent, &[], &[("wan", "IF-MIB::ifInOctets.5", "IF-MIB::ifOutOctets.5")]).await;
+        // 수집기는 MIB 이름 OID도 그대로 요청함
+        let sleep = || tokio::time::sleep(std::time::Duration::from_millis(1100));
+
+        assert!(collect_once(&collector, &proxy).await.interfaces.is_empty());
+        sleep().await;
+        let wrapped = collect_once(&collector, &proxy).await;
+        let wan = interface(&wrapped, "wan").unwrap();
+        // 4294967000 -> 704: 1000바이트 증가 (약 1.1초)
+        let wan_in = wan.in_bps.unwrap();
+        assert!(wan_in > 6000.0 && wan_in < 8000.0, "wan in {}", wan_in);
+        // 수동 지정 OID에서도 ifIndex를 알아내 ifHighSpeed 조회
+        assert_eq!(wan.speed_bps, Some(100.0e6));
+
+        // sysUpTime이 줄었으면 재시작으로 보고 값을 버림
+        sleep().await;
+        let restarted = collect_once(&collector, &proxy).await;
+        assert!(interface(&restarted, "wan").is_none());
+    }
+
+    #[tokio::test]
+    async fn host_metrics_and_ssh_memory_share_one_batched_command() {
+        let recorded = include_str!("../fixtures/mwg_host_metrics.txt");
+        let runner = Arc::new(crate::command_runner::ScriptedRunner::new().respond("echo @load", recorded));
+        let snmp = SnmpSettings { version: SnmpVersion::V2c, community: "public".to_string(), v3: None, port: 161 };
+        let oids = HashMap::from([("mem".to_string(), "ssh".to_string())]);
+        let collector = ResourceCollector::new(oids, snmp.clone(), SnmpOptions::default(), HashMap::new())
+            .with_host_metrics(HostMetricsConfig::default())
+            .with_runner(runner.clone());
+
+        let data = collect_once(&collector, &proxy("")).await;
+        assert!(!data.collection_failed, "{:?}", data.error_message);
+        // 메모리는 별도 명령 없이 같은 출력에서
+        assert_eq!(runner.executed().len(), 1);
+        assert_eq!(data.mem.map(f64::round), Some(75.0));
+        assert_eq!(data.load_per_core(), Some(3.42 / 8.0));
+        assert_eq!(data.fullest_disk().map(|d| d.mount.as_str()), Some("/opt/mwg/log"));
+        assert!(data.has_missing_process());
+
+        // SSH 실패는 수집 실패로 표시
+        let failing = ResourceCollector::new(HashMap::new(), snmp, SnmpOptions::default(), HashMap::new())
+            .with_host_metrics(HostMetricsConfig::default())
+            .with_runner(Arc::new(crate::command_runner::ScriptedRunner::new().fail("echo @load", "Connection refused")));
+        let data = collect_once(&failing, &proxy("")).await;
+        assert!(data.collection_failed);
+        assert_eq!(data.error_message.as_deref(), Some("호스트 지표 수집 실패: Connection refused"));
+        assert!(data.disks.is_empty() && data.load_avg.is_none());
+    }
+}
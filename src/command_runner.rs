@@ -0,0 +1,298 @@
+//! 원격/로컬 명령 실행 추상화
+//!
+//! 세션 조회, 트래픽 로그 조회, SSH 메모리/호스트 지표 수집은 `CommandRunner`로 명령을 실행합니다.
+//! 프록시 설정의 `exec_mode`에 따라 SSH(기본) 또는 로컬 프로세스를 사용하고,
+//! 테스트에서는 기록해 둔 출력을 돌려주는 `ScriptedRunner`를 사용합니다.
+
+use crate::app::Proxy;
+use crate::ssh::{OutputLines, SshClient};
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio_util::sync::CancellationToken;
+
+/// 명령 실행기
+pub trait CommandRunner: Send + Sync {
+    /// 명령을 실행하고 stdout 전체를 반환합니다.
+    fn execute<'a>(&'a self, command: &'a str) -> BoxFuture<'a, Result<String>>;
+
+    /// 명령을 실행하고 출력을 받는 대로 한 줄씩 넘겨줍니다.
+    fn execute_lines(&self, command: &str) -> OutputLines;
+}
+
+/// 프록시 명령을 실행할 위치
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecMode {
+    /// SSH로 접속해 실행
+    #[default]
+    Ssh,
+    /// 이 프로그램이 프록시 장비에서 실행 중일 때 로컬 프로세스로 실행
+    Local,
+}
+
+impl ExecMode {
+    pub fn is_ssh(&self) -> bool {
+        *self == ExecMode::Ssh
+    }
+}
+
+/// 프록시 설정에 맞는 실행기를 만듭니다.
+/// `escalation`은 SSH에서만 사용합니다. (local과 함께 쓴 설정은 `Proxy::validate_exec_mode`가 거부)
+pub fn runner_for(
+    proxy: &Proxy,
+    ssh_port: u16,
+    timeout: Duration,
+    cancel: CancellationToken,
+    escalation: Option<crate::ssh::Escalation>,
+) -> Arc<dyn CommandRunner> {
+    match proxy.exec_mode {
+        ExecMode::Ssh => Arc::new(
+            SshClient::for_proxy(proxy, ssh_port)
+                .with_timeout(timeout)
+                .with_cancel_token(cancel)
+                .with_escalation(escalation),
+        ),
+        ExecMode::Local => Arc::new(LocalRunner::new(timeout, cancel)),
+    }
+}
+
+/// 메모리 사용률 (%)을 /proc/meminfo에서 계산합니다.
+pub async fn memory_percent(runner: &dyn CommandRunner) -> Result<f64> {
+    let command = "awk '/MemTotal/ {total=$2} /MemAvailable/ {available=$2} END {printf \"%.0f\", 100 - (available / total * 100)}' /proc/meminfo";
+    let output = runner.execute(command).await?;
+    let value: f64 = output
+        .trim()
+        .parse()
+        .context("Failed to parse memory percentage")?;
+
+    // 값 범위 제한 (0-100)
+    Ok(value.clamp(0.0, 100.0))
+}
+
+impl CommandRunner for SshClient {
+    fn execute<'a>(&'a self, command: &'a str) -> BoxFuture<'a, Result<String>> {
+        Box::pin(SshClient::execute(self, command))
+    }
+
+    fn execute_lines(&self, command: &str) -> OutputLines {
+        SshClient::execute_lines(self, command)
+    }
+}
+
+/// 로컬 프로세스(`sh -c`)로 명령을 실행합니다.
+pub struct LocalRunner {
+    timeout: Duration,
+    cancel: CancellationToken,
+}
+
+impl LocalRunner {
+    pub fn new(timeout: Duration, cancel: CancellationToken) -> Self {
+        Self { timeout, cancel }
+    }
+
+    fn spawn(command: &str) -> Result<tokio::process::Child> {
+        tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("로컬 명령을 시작할 수 없습니다: {}", command))
+    }
+}
+
+/// 종료 상태가 0이 아니고 stdout이 비어 있으면 실패로 처리 (SSH와 같은 기준)
+fn check_exit(status: std::process::ExitStatus, had_output: bool, stderr: &str) -> Result<()> {
+    if status.success() || had_output {
+        return Ok(());
+    }
+    match status.code() {
+        Some(code) => anyhow::bail!("로컬 명령 실패 (종료 코드 {}): {}", code, stderr.trim()),
+        None => anyhow::bail!("로컬 명령이 시그널로 종료되었습니다: {}", stderr.trim()),
+    }
+}
+
+impl CommandRunner for LocalRunner {
+    fn execute<'a>(&'a self, command: &'a str) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let child = Self::spawn(command)?;
+            let output = tokio::select! {
+                output = tokio::time::timeout(self.timeout, child.wait_with_output()) => match output {
+                    Ok(output) => output.context("로컬 명령 실행 실패")?,
+                    Err(_) => anyhow::bail!("로컬 명령 응답 타임아웃 ({}초)", self.timeout.as_secs()),
+                },
+                _ = self.cancel.cancelled() => anyhow::bail!("명령이 취소되었습니다"),
+            };
+            let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+            check_exit(output.status, !stdout.is_empty(), &String::from_utf8_lossy(&output.stderr))?;
+            Ok(stdout)
+        })
+    }
+
+    fn execute_lines(&self, command: &str) -> OutputLines {
+        let (sender, receiver) = crate::ssh::line_channel();
+        let command = command.to_string();
+        let cancel = self.cancel.clone();
+        let task = tokio::spawn(async move {
+            let mut child = LocalRunner::spawn(&command)?;
+            let mut stdout = BufReader::new(child.stdout.take().context("stdout을 열 수 없습니다")?).lines();
+            // stderr 파이프가 가득 차 명령이 멈추지 않도록 따로 읽음
+            let mut stderr = child.stderr.take().context("stderr를 열 수 없습니다")?;
+            let stderr_task = tokio::spawn(async move {
+                let mut error_output = String::new();
+                stderr.read_to_string(&mut error_output).await.ok();
+                error_output
+            });
+            let mut had_output = false;
+            loop {
+                let line = tokio::select! {
+                    line = stdout.next_line() => line.context("로컬 명령 출력 읽기 실패")?,
+                    _ = cancel.cancelled() => anyhow::bail!("명령이 취소되었습니다"),
+                };
+                let Some(line) = line else { break };
+                had_output = true;
+                // 받는 쪽이 스트림을 버렸으면 중단 (kill_on_drop으로 프로세스 종료)
+                if sender.send(line).await.is_err() {
+                    anyhow::bail!("명령이 취소되었습니다");
+                }
+            }
+            let status = child.wait().await.context("로컬 명령 실행 실패")?;
+            let error_output = stderr_task.await.unwrap_or_default();
+            check_exit(status, had_output, &error_output)
+        });
+        OutputLines::new(receiver, task, self.timeout, self.cancel.clone())
+    }
+}
+
+/// 테스트용 실행기 - 명령에 포함된 문자열로 기록해 둔 출력(또는 오류)을 돌려줍니다.
+#[cfg(test)]
+#[derive(Default)]
+pub struct ScriptedRunner {
+    responses: Vec<(String, std::result::Result<String, String>)>,
+    executed: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl ScriptedRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `pattern`이 들어간 명령에 `output`을 반환
+    pub fn respond(mut self, pattern: &str, output: impl Into<String>) -> Self {
+        self.responses.push((pattern.to_string(), Ok(output.into())));
+        self
+    }
+
+    /// `pattern`이 들어간 명령은 `error`로 실패
+    pub fn fail(mut self, pattern: &str, error: &str) -> Self {
+        self.responses.push((pattern.to_string(), Err(error.to_string())));
+        self
+    }
+
+    /// 지금까지 실행된 명령
+    pub fn executed(&self) -> Vec<String> {
+        self.executed.lock().unwrap().clone()
+    }
+
+    fn run(&self, command: &str) -> Result<String> {
+        self.executed.lock().unwrap().push(command.to_string());
+        match self.responses.iter().find(|(pattern, _)| command.contains(pattern.as_str())) {
+            Some((_, Ok(output))) => Ok(output.clone()),
+            Some((_, Err(error))) => Err(anyhow::anyhow!("{}", error)),
+            None => anyhow::bail!("기록된 출력이 없는 명령: {}", command),
+        }
+    }
+}
+
+#[cfg(test)]
+impl CommandRunner for ScriptedRunner {
+    fn execute<'a>(&'a self, command: &'a str) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move { self.run(command) })
+    }
+
+    fn execute_lines(&self, command: &str) -> OutputLines {
+        let (sender, receiver) = crate::ssh::line_channel();
+        let result = self.run(command);
+        let task = tokio::spawn(async move {
+            for line in result?.lines() {
+                if sender.send(line.to_string()).await.is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+        OutputLines::new(receiver, task, Duration::from_secs(5), CancellationToken::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_runner_executes_and_streams_commands() {
+        let runner = LocalRunner::new(Duration::from_secs(5), CancellationToken::new());
+        assert_eq!(runner.execute("printf 'a\\nb\\n'").await.unwrap(), "a\nb\n");
+
+        let mut output = runner.execute_lines("echo one; echo two >&2; echo three");
+        let mut lines = Vec::new();
+        while let Some(line) = output.next_line().await {
+            lines.push(line);
+        }
+        output.finish().await.unwrap();
+        assert_eq!(lines, vec!["one", "three"]);
+
+        // 출력 없이 실패하면 stderr와 함께 오류
+        let err = runner.execute("echo denied >&2; exit 3").await.unwrap_err();
+        assert_eq!(err.to_string(), "로컬 명령 실패 (종료 코드 3): denied");
+        let output = runner.execute_lines("exit 2");
+        assert!(output.finish().await.is_err());
+
+        // 타임아웃과 취소
+        let slow = LocalRunner::new(Duration::from_millis(200), CancellationToken::new());
+        assert!(slow.execute("sleep 5").await.unwrap_err().to_string().contains("타임아웃"));
+        let cancel = CancellationToken::new();
+        let cancelled = LocalRunner::new(Duration::from_secs(5), cancel.clone());
+        let mut output = cancelled.execute_lines("echo first; sleep 5; echo late");
+        assert_eq!(output.next_line().await.as_deref(), Some("first"));
+        cancel.cancel();
+        assert_eq!(output.next_line().await, None);
+        assert!(output.finish().await.unwrap_err().to_string().contains("취소"));
+    }
+
+    #[test]
+    fn escalation_requires_ssh_exec_mode() {
+        let proxy = |extra: &str| -> Proxy {
+            serde_json::from_str(&format!(
+                r#"{{"id": 7, "host": "127.0.0.1", "port": 22, "username": "u", "password": "", "group": "g"{}}}"#,
+                extra
+            ))
+            .unwrap()
+        };
+        let local = proxy(r#", "exec_mode": "local""#);
+        local.validate_exec_mode().unwrap();
+        let escalated = proxy(r#", "exec_mode": "local", "escalation": {"method": "sudo"}"#);
+        let err = escalated.validate_exec_mode().unwrap_err();
+        assert!(err.to_string().starts_with("프록시 7 설정 오류: escalation"), "{}", err);
+        proxy(r#", "escalation": {"method": "sudo"}"#).validate_exec_mode().unwrap();
+    }
+
+    #[tokio::test]
+    async fn memory_percent_is_parsed_and_clamped() {
+        let runner = ScriptedRunner::new().respond("/proc/meminfo", "37\n");
+        assert_eq!(memory_percent(&runner).await.unwrap(), 37.0);
+        let runner = ScriptedRunner::new().respond("/proc/meminfo", "130");
+        assert_eq!(memory_percent(&runner).await.unwrap(), 100.0);
+        let runner = ScriptedRunner::new().respond("/proc/meminfo", "n/a");
+        assert!(memory_percent(&runner).await.is_err());
+        assert_eq!(runner.executed().len(), 1);
+    }
+}
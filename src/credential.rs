@@ -0,0 +1,155 @@
+//! 자격 증명 참조 (`env:`, `cmd:`, `vault:`)
+//!
+//! 비밀번호 칸에 `env:MWG_PASS_PROD`나 `cmd:pass show mwg/prod`를 쓰면 SSH로 접속할 때마다
+//! 환경 변수나 외부 명령 출력에서 실제 값을 가져옵니다.
+//! 가져온 값은 설정 파일이나 디스크에 남기지 않습니다.
+//! `vault:`는 시작할 때 열어 둔 볼트에서 찾습니다. (`crate::vault`)
+//! 마지막 확인 결과는 참조별로 보관해 프록시 관리 탭에 프록시별로 표시합니다.
+//! `resolve`는 외부 명령이 끝날 때까지 (최대 10초) 스레드를 막습니다.
+//! 비동기 코드에서는 `tokio::task::spawn_blocking` 안에서 호출해야 합니다.
+//! (SSH는 접속 스레드에서, SNMP는 `SnmpCredentials::resolve_secrets`를 블로킹 스레드에서 호출)
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// 환경 변수 참조 접두사
+pub const ENV_PREFIX: &str = "env:";
+/// 외부 명령 참조 접두사 (출력의 첫 줄을 사용)
+pub const CMD_PREFIX: &str = "cmd:";
+
+/// 외부 명령이 이 시간 안에 끝나지 않으면 실패
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 참조 -> 마지막으로 실패한 이유 (성공하면 지움)
+fn failures() -> &'static Mutex<HashMap<String, String>> {
+    static FAILURES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    FAILURES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// `env:`/`cmd:`/`vault:` 참조인지
+pub fn is_reference(value: &str) -> bool {
+    value.starts_with(ENV_PREFIX) || value.starts_with(CMD_PREFIX) || value.starts_with(crate::vault::REF_PREFIX)
+}
+
+/// 참조면 실제 값을, 아니면 값을 그대로 반환합니다.
+/// 블로킹 - 외부 명령을 실행할 수 있으므로 tokio 워커에서 직접 호출하지 말 것
+pub fn resolve(value: &str) -> Result<String> {
+    if !is_reference(value) {
+        return Ok(value.to_string());
+    }
+    let result = resolve_reference(value, COMMAND_TIMEOUT)
+        .with_context(|| format!("자격 증명 참조 실패 ({})", value));
+    let mut failures = failures().lock().unwrap();
+    match &result {
+        Ok(_) => failures.remove(value),
+        Err(e) => failures.insert(value.to_string(), format!("{:#}", e)),
+    };
+    result
+}
+
+/// 참조의 마지막 확인이 실패했으면 그 이유
+pub fn failure(reference: &str) -> Option<String> {
+    failures().lock().unwrap().get(reference).cloned()
+}
+
+fn resolve_reference(value: &str, timeout: Duration) -> Result<String> {
+    if let Some(name) = value.strip_prefix(ENV_PREFIX) {
+        return match std::env::var(name.trim()) {
+            Ok(secret) if !secret.is_empty() => Ok(secret),
+            Ok(_) => anyhow::bail!("환경 변수가 비어 있습니다"),
+            Err(_) => anyhow::bail!("환경 변수가 설정되지 않았습니다"),
+        };
+    }
+    if let Some(name) = value.strip_prefix(crate::vault::REF_PREFIX) {
+        return crate::vault::lookup(name.trim());
+    }
+    let command = value.strip_prefix(CMD_PREFIX).unwrap_or(value).trim();
+    let output = run_command(command, timeout)?;
+    match output.lines().next().map(|line| line.trim_end_matches('\r')) {
+        Some(secret) if !secret.is_empty() => Ok(secret.to_string()),
+        _ => anyhow::bail!("명령 출력이 비어 있습니다"),
+    }
+}
+
+/// `sh -c`로 명령을 실행하고 stdout을 반환합니다. (시간 제한을 넘으면 종료시킴)
+fn run_command(command: &str, timeout: Duration) -> Result<String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("명령을 시작할 수 없습니다")?;
+
+    // 파이프가 가득 차 명령이 멈추지 않도록 따로 읽음
+    let read_all = |mut pipe: Box<dyn Read + Send>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            pipe.read_to_end(&mut buf).ok();
+            String::from_utf8_lossy(&buf).into_owned()
+        })
+    };
+    let stdout = read_all(Box::new(child.stdout.take().context("stdout을 열 수 없습니다")?));
+    let stderr = read_all(Box::new(child.stderr.take().context("stderr를 열 수 없습니다")?));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().context("명령 실행 실패")? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("명령이 {}초 안에 끝나지 않았습니다", timeout.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        match status.code() {
+            Some(code) => anyhow::bail!("명령 실패 (종료 코드 {}): {}", code, stderr.trim()),
+            None => anyhow::bail!("명령이 시그널로 종료되었습니다: {}", stderr.trim()),
+        }
+    }
+    Ok(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn environment_and_command_references_are_resolved() {
+        // 참조가 아니면 그대로
+        assert_eq!(resolve("plain").unwrap(), "plain");
+
+        std::env::set_var("MMT_TEST_CREDENTIAL", "from-env");
+        assert_eq!(resolve("env:MMT_TEST_CREDENTIAL").unwrap(), "from-env");
+        // 명령 출력은 첫 줄만 사용 (pass show 형식)
+        assert_eq!(resolve("cmd:printf 'first\\nlogin: admin\\n'").unwrap(), "first");
+
+        let err = resolve("env:MMT_TEST_CREDENTIAL_MISSING").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "자격 증명 참조 실패 (env:MMT_TEST_CREDENTIAL_MISSING): 환경 변수가 설정되지 않았습니다"
+        );
+        assert!(failure("env:MMT_TEST_CREDENTIAL_MISSING").unwrap().contains("설정되지 않았습니다"));
+
+        let err = resolve("cmd:echo locked >&2; exit 2").unwrap_err();
+        assert!(format!("{:#}", err).ends_with("명령 실패 (종료 코드 2): locked"), "{:#}", err);
+        assert!(resolve("cmd:true").is_err());
+        let err = resolve_reference("cmd:sleep 5", Duration::from_millis(200)).unwrap_err();
+        assert!(err.to_string().contains("끝나지 않았습니다"));
+
+        // 다시 성공하면 실패 기록을 지움
+        std::env::set_var("MMT_TEST_CREDENTIAL_MISSING", "now-set");
+        assert_eq!(resolve("env:MMT_TEST_CREDENTIAL_MISSING").unwrap(), "now-set");
+        assert_eq!(failure("env:MMT_TEST_CREDENTIAL_MISSING"), None);
+    }
+}
@@ -15,6 +15,7 @@ use ratatui::{
     Terminal,
 };
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 
 use crate::{app::App, ui};
 
@@ -24,16 +25,120 @@ fn spawn_collection_task(
     app: Arc<tokio::sync::Mutex<App>>,
     rt: &tokio::runtime::Runtime,
 ) -> tokio::task::JoinHandle<()> {
-    let app_clone = app.clone();
     rt.spawn(async move {
-        let mut app = app_clone.lock().await;
-        if let Err(e) = app.start_collection().await {
+        if let Err(e) = App::run_collection(app).await {
             eprintln!("수집 실패: {}", e);
         }
     })
 }
 
+/// Esc/Ctrl+C로 취소할 수 있는 세션/트래픽 로그 조회와 로그 다운로드 태스크
+///
+/// 조회와 다운로드는 동시에 실행될 수 있으므로 태스크마다 취소 토큰을 보관합니다.
+#[derive(Default)]
+struct CancellableTasks {
+    tasks: Vec<(CancellationToken, tokio::task::JoinHandle<()>)>,
+}
+
+impl CancellableTasks {
+    /// 태스크를 추가합니다. (이미 끝난 태스크는 정리)
+    fn push(&mut self, cancel: CancellationToken, task: tokio::task::JoinHandle<()>) {
+        self.tasks.retain(|(_, task)| !task.is_finished());
+        self.tasks.push((cancel, task));
+    }
+
+    /// 실행 중인 태스크를 모두 취소하고, 취소한 태스크가 있었는지 반환합니다.
+    fn cancel_running(&self) -> bool {
+        let mut cancelled = false;
+        for (cancel, task) in &self.tasks {
+            if !task.is_finished() {
+                cancel.cancel();
+                cancelled = true;
+            }
+        }
+        cancelled
+    }
+}
+
+/// SNMP 트랩 수신 태스크를 시작하는 헬퍼 함수
+///
+/// 수신 루프는 앱 잠금과 무관하게 소켓을 계속 읽고, 받은 이벤트는 채널을 거쳐 앱에 반영합니다.
+fn spawn_trap_listener(app: Arc<Mutex<App>>, rt: &tokio::runtime::Runtime) {
+    rt.spawn(async move {
+        let config = match crate::app::config::load_trap_config() {
+            Ok(config) if config.enabled => config,
+            Ok(_) => return, // 비활성화 (기본값)
+            Err(e) => {
+                app.lock().await.events.listener_status = crate::app::TrapListenerStatus::Failed(format!("{}", e));
+                return;
+            }
+        };
+        let receiver = match crate::snmp::TrapReceiver::bind(&config).await {
+            Ok(receiver) => receiver,
+            Err(e) => {
+                crate::collector::log_error(&format!("{:#}", e));
+                app.lock().await.events.listener_status = crate::app::TrapListenerStatus::Failed(format!("{:#}", e));
+                return;
+            }
+        };
+        let local_addr = receiver
+            .local_addr()
+            .map(|addr| addr.to_string())
+            .unwrap_or_else(|_| format!("{}:{}", config.bind_address, config.port));
+        app.lock().await.events.listener_status = crate::app::TrapListenerStatus::Listening(local_addr);
+
+        let (sender, mut received) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let result = receiver.recv().await.map_err(|e| {
+                    crate::collector::log_error(&format!("SNMP trap: {:#}", e));
+                    format!("{:#}", e)
+                });
+                if sender.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // IP가 아닌 프록시 host는 처음 필요할 때 한 번만 DNS 조회
+        let mut resolved_hosts: std::collections::HashMap<String, Vec<std::net::IpAddr>> = std::collections::HashMap::new();
+        while let Some(result) = received.recv().await {
+            let unresolved: Vec<String> = app
+                .lock()
+                .await
+                .proxies
+                .iter()
+                .map(|p| p.host.clone())
+                .filter(|host| host.parse::<std::net::IpAddr>().is_err() && !resolved_hosts.contains_key(host))
+                .collect();
+            for host in unresolved {
+                let ips = match tokio::net::lookup_host((host.as_str(), 0)).await {
+                    Ok(addrs) => addrs.map(|addr| addr.ip()).collect(),
+                    Err(_) => Vec::new(),
+                };
+                resolved_hosts.insert(host, ips);
+            }
+
+            let mut app_guard = app.lock().await;
+            match result {
+                Ok(event) => app_guard.push_trap_event(&event, &resolved_hosts),
+                Err(e) => {
+                    app_guard.events.rejected_count += 1;
+                    app_guard.events.last_error = Some(e);
+                }
+            }
+        }
+    });
+}
+
 pub fn run(tick_rate: Duration) -> Result<(), Box<dyn Error>> {
+    // 앱 생성 및 프록시 로드 (볼트 마스터 암호를 입력받을 수 있도록 터미널 설정 전에)
+    let mut app = App::new("MWG Monitoring Tool".to_string());
+    if let Err(e) = app.load_proxies() {
+        eprintln!("프록시 설정 파일 로드 실패: {}", e);
+        eprintln!("config/proxies.json 파일을 확인하세요.");
+    }
+
     // 터미널 설정
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -41,16 +146,12 @@ pub fn run(tick_rate: Duration) -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // 앱 생성 및 프록시 로드
-    let mut app = App::new("MWG Monitoring Tool".to_string());
-    if let Err(e) = app.load_proxies() {
-        eprintln!("프록시 설정 파일 로드 실패: {}", e);
-        eprintln!("config/proxies.json 파일을 확인하세요.");
-    }
-
     // 런타임 생성
     let rt = tokio::runtime::Runtime::new()?;
     let app_mutex = Arc::new(Mutex::new(app));
+
+    // SNMP 트랩 수신 (resource_config.json의 trap.enabled가 true일 때)
+    spawn_trap_listener(app_mutex.clone(), &rt);
     
     // 스피너 애니메이션을 위한 주기적 업데이트 태스크
     let app_for_spinner = app_mutex.clone();
@@ -104,12 +205,13 @@ fn run_app<B: Backend>(
 ) -> io::Result<()> {
     let mut last_tick = Instant::now();
     let mut collection_task: Option<tokio::task::JoinHandle<()>> = None;
+    // 실행 중인 세션/트래픽 로그 조회와 로그 다운로드 (Esc로 취소)
+    let mut query_tasks = CancellableTasks::default();
 
     loop {
-        // UI 렌더링
-        {
-            let mut app_guard = rt.block_on(app.lock());
-            terminal.draw(|frame| ui::draw(frame, &mut *app_guard))?;
+        // UI 렌더링 (조회 중이라 앱이 잠겨 있으면 이번 프레임은 건너뛰고 키 입력을 계속 받음)
+        if let Ok(mut app_guard) = app.try_lock() {
+            terminal.draw(|frame| ui::draw(frame, &mut app_guard))?;
         }
 
         let timeout = tick_rate.saturating_sub(last_tick.elapsed());
@@ -117,6 +219,7 @@ fn run_app<B: Backend>(
             if let Event::Key(key) = event::read()? {
                 // Ctrl+C 처리
                 if key.code == KeyCode::Char('c') && key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) {
+                    query_tasks.cancel_running();
                     let mut app_guard = rt.block_on(app.lock());
                     app_guard.should_quit = true;
                     drop(app_guard);
@@ -124,7 +227,55 @@ fn run_app<B: Backend>(
                 }
                 
                 if key.kind == KeyEventKind::Press {
+                    // 조회/다운로드 중 Esc: 앱 잠금을 기다리지 않고 실행 중인 작업을 모두 취소
+                    if key.code == KeyCode::Esc && query_tasks.cancel_running() {
+                        continue;
+                    }
+
                     let mut app_guard = rt.block_on(app.lock());
+
+                    // 호스트 키 확인 창이 열려 있으면 y/n만 처리
+                    if app_guard.host_key_prompt.is_some() {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') => app_guard.accept_host_key(),
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app_guard.reject_host_key(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // 프록시 관리 탭의 삭제 확인 창: y/n만 처리
+                    if app_guard.proxy_management.confirm_delete.is_some() {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') => app_guard.confirm_delete(),
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app_guard.cancel_delete(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // 프록시 관리 탭의 추가/편집 창: 모든 키를 입력 창에서 처리
+                    if app_guard.proxy_management.form.is_some() {
+                        match key.code {
+                            KeyCode::Esc => app_guard.cancel_proxy_form(),
+                            KeyCode::Enter => app_guard.submit_proxy_form(),
+                            code => {
+                                if let Some(form) = &mut app_guard.proxy_management.form {
+                                    match code {
+                                        KeyCode::Tab | KeyCode::Down => form.next_field(),
+                                        KeyCode::BackTab | KeyCode::Up => form.previous_field(),
+                                        KeyCode::Left => form.cycle_choice(false),
+                                        KeyCode::Right => form.cycle_choice(true),
+                                        KeyCode::Backspace => form.backspace(),
+                                        KeyCode::Char(c) => form.insert_char(c),
+                                        _ => {}
+                                    }
+                                }
+                            }
+                        }
+                        continue;
+                    }
+
                     match key.code {
                         KeyCode::Left | KeyCode::Char('h') => {
                             if app_guard.current_tab == crate::app::TabIndex::SessionBrowser
@@ -190,7 +341,10 @@ fn run_app<B: Backend>(
                         }
                         KeyCode::Char(' ') => {
                             // Space 키 처리
-                            if app_guard.current_tab == crate::app::TabIndex::SessionBrowser {
+                            if app_guard.current_tab == crate::app::TabIndex::Events
+                                && app_guard.events.search_mode {
+                                app_guard.events.add_search_char(' ');
+                            } else if app_guard.current_tab == crate::app::TabIndex::SessionBrowser {
                                 // 세션 브라우저 탭: 다음 페이지
                                 app_guard.session_browser.next_page();
                             } else if app_guard.current_tab == crate::app::TabIndex::TrafficLogs {
@@ -207,7 +361,10 @@ fn run_app<B: Backend>(
                             }
                         }
                         KeyCode::Enter => {
-                            if app_guard.current_tab == crate::app::TabIndex::SessionBrowser {
+                            if app_guard.current_tab == crate::app::TabIndex::ProxyManagement {
+                                // 선택한 프록시나 그룹 편집
+                                app_guard.edit_selected();
+                            } else if app_guard.current_tab == crate::app::TabIndex::SessionBrowser {
                                 if app_guard.session_browser.search_mode {
                                     // 검색 모드에서 Enter: 검색 완료 (검색어 유지)
                                     app_guard.session_browser.finish_search_mode();
@@ -223,6 +380,10 @@ fn run_app<B: Backend>(
                                     // 로그 목록 뷰에서 Enter: 상세보기 모달 토글
                                     app_guard.traffic_logs.toggle_detail_modal();
                                 }
+                            } else if app_guard.current_tab == crate::app::TabIndex::Events
+                                && app_guard.events.search_mode {
+                                // 검색 모드에서 Enter: 검색 완료 (검색어 유지)
+                                app_guard.events.finish_search_mode();
                             }
                         }
                         KeyCode::Char('q') | KeyCode::Char('Q') => {
@@ -244,6 +405,10 @@ fn run_app<B: Backend>(
                                     app_guard.traffic_logs.close_detail_modal();
                                 }
                                 // q 키로 종료하지 않음 (Ctrl+C 사용)
+                            } else if app_guard.current_tab == crate::app::TabIndex::Events
+                                && app_guard.events.search_mode {
+                                // 검색 모드에서는 문자 입력
+                                app_guard.events.add_search_char('q');
                             }
                             // q 키로 종료하지 않음 (Ctrl+C 사용)
                         }
@@ -278,19 +443,10 @@ fn run_app<B: Backend>(
                                         app_guard.session_browser.query_status = crate::app::CollectionStatus::Starting;
                                         app_guard.session_browser.query_start_time = Some(chrono::Local::now());
                                         
-                                        let app_clone = app.clone();
-                                        drop(app_guard);
-                                        rt.spawn(async move {
-                                            let mut app_guard = app_clone.lock().await;
-                                            if let Err(e) = app_guard.start_session_query().await {
-                                                eprintln!("세션 조회 실패: {}", e);
-                                                app_guard.session_browser.query_status = crate::app::CollectionStatus::Failed;
-                                                app_guard.session_browser.last_error = Some(format!("{}", e));
-                                                app_guard.session_browser.query_start_time = None;
-                                            }
-                                        });
-                                        // app_guard가 drop되었으므로 다시 lock 필요
-                                        app_guard = rt.block_on(app.lock());
+                                        let cancel = CancellationToken::new();
+                                        let task = rt.spawn(App::run_session_query(app.clone(), cancel.clone()));
+                                        // 태스크는 이 키 처리가 끝나 잠금이 풀린 뒤 실행됨
+                                        query_tasks.push(cancel, task);
                                     }
                                 } else {
                                     // 검색 모드에서는 문자 입력
@@ -317,28 +473,23 @@ fn run_app<B: Backend>(
                                         app_guard.traffic_logs.query_status = crate::app::CollectionStatus::Starting;
                                         app_guard.traffic_logs.query_start_time = Some(chrono::Local::now());
                                         
-                                        let app_clone = app.clone();
-                                        drop(app_guard);
-                                        rt.spawn(async move {
-                                            let mut app_guard = app_clone.lock().await;
-                                            if let Err(e) = app_guard.start_traffic_log_query(proxy_id).await {
-                                                eprintln!("트래픽 로그 조회 실패: {}", e);
-                                                app_guard.traffic_logs.query_status = crate::app::CollectionStatus::Failed;
-                                                app_guard.traffic_logs.last_error = Some(format!("{}", e));
-                                                app_guard.traffic_logs.query_start_time = None;
-                                            }
-                                        });
-                                        // app_guard가 drop되었으므로 다시 lock 필요
-                                        app_guard = rt.block_on(app.lock());
+                                        let cancel = CancellationToken::new();
+                                        let task = rt.spawn(App::run_traffic_log_query(app.clone(), proxy_id, cancel.clone()));
+                                        // 태스크는 이 키 처리가 끝나 잠금이 풀린 뒤 실행됨
+                                        query_tasks.push(cancel, task);
                                     }
                                 } else {
                                     // 검색 모드에서는 문자 입력
                                     app_guard.traffic_logs.add_search_char('r');
                                 }
+                            } else if app_guard.current_tab == crate::app::TabIndex::Events
+                                && app_guard.events.search_mode {
+                                // 검색 모드에서는 문자 입력
+                                app_guard.events.add_search_char('r');
                             }
                         }
-                        KeyCode::Char('s') | KeyCode::Char('S') => {
-                            if app_guard.current_tab == crate::app::TabIndex::SessionBrowser {
+                        KeyCode::Char('s') | KeyCode::Char('S')
+                            if app_guard.current_tab == crate::app::TabIndex::SessionBrowser => {
                                 if !app_guard.session_browser.search_mode {
                                     // S: 정렬 토글 (컬럼이 선택되어 있을 때)
                                     if app_guard.session_browser.selected_column.is_some() {
@@ -357,9 +508,18 @@ fn run_app<B: Backend>(
                                     app_guard.session_browser.add_search_char('s');
                                 }
                             }
-                        }
                         KeyCode::Char(c) => {
-                            if app_guard.current_tab == crate::app::TabIndex::SessionBrowser {
+                            if app_guard.current_tab == crate::app::TabIndex::ProxyManagement {
+                                // 프록시 관리 탭: 추가/편집/복제/삭제
+                                match c {
+                                    'a' | 'A' => app_guard.add_proxy(),
+                                    'g' | 'G' => app_guard.add_group(),
+                                    'e' | 'E' => app_guard.edit_selected(),
+                                    'c' | 'C' => app_guard.clone_selected(),
+                                    'd' | 'D' => app_guard.request_delete_selected(),
+                                    _ => app_guard.on_key(c),
+                                }
+                            } else if app_guard.current_tab == crate::app::TabIndex::SessionBrowser {
                                 if app_guard.session_browser.search_mode {
                                     // 검색 모드일 때 - 모든 문자를 검색어로 입력
                                     app_guard.session_browser.add_search_char(c);
@@ -386,10 +546,48 @@ fn run_app<B: Backend>(
                                         app_guard.traffic_logs.start_search_mode();
                                     } else if c == 'b' || c == 'B' {
                                         app_guard.traffic_logs.previous_page();
+                                    } else if c == 'd' || c == 'D' {
+                                        // D: 로그 파일 다운로드(SFTP) 후 전체 분석
+                                        let should_download = app_guard.traffic_logs.download_status != crate::app::CollectionStatus::Collecting
+                                            && !app_guard.proxies.is_empty();
+                                        if should_download {
+                                            let proxy_id = match app_guard.traffic_logs.selected_proxy {
+                                                Some(id) => id as u32,
+                                                None => {
+                                                    let id = app_guard.proxies[app_guard.traffic_logs.proxy_list_index].id;
+                                                    app_guard.traffic_logs.selected_proxy = Some(id as usize);
+                                                    id
+                                                }
+                                            };
+                                            app_guard.traffic_logs.download_status = crate::app::CollectionStatus::Starting;
+                                            app_guard.traffic_logs.download_start_time = Some(chrono::Local::now());
+
+                                            let cancel = CancellationToken::new();
+                                            let task = rt.spawn(App::run_log_download(app.clone(), proxy_id, cancel.clone()));
+                                            query_tasks.push(cancel, task);
+                                        }
                                     } else {
                                         app_guard.on_key(c);
                                     }
                                 }
+                            } else if app_guard.current_tab == crate::app::TabIndex::Events {
+                                // 이벤트 탭에서의 문자 키 처리
+                                if app_guard.events.search_mode {
+                                    app_guard.events.add_search_char(c);
+                                } else if c == '/' {
+                                    app_guard.events.start_search_mode();
+                                } else if c == 'f' || c == 'F' {
+                                    // F: 심각도 필터 순환
+                                    app_guard.events.cycle_severity_filter();
+                                } else if c == 'e' || c == 'E' {
+                                    // E: 현재 필터 결과를 CSV로 저장
+                                    app_guard.export_events();
+                                } else if c == 'x' || c == 'X' {
+                                    // X: 이벤트 목록 비우기
+                                    app_guard.events.clear();
+                                } else {
+                                    app_guard.on_key(c);
+                                }
                             } else if c == 'a' || c == 'A' {
                                 // A 키로 트래픽 로그 분석 시작
                                 if app_guard.current_tab == crate::app::TabIndex::TrafficLogs {
@@ -425,6 +623,9 @@ fn run_app<B: Backend>(
                             } else if app_guard.current_tab == crate::app::TabIndex::TrafficLogs
                                 && app_guard.traffic_logs.search_mode {
                                 app_guard.traffic_logs.backspace_search();
+                            } else if app_guard.current_tab == crate::app::TabIndex::Events
+                                && app_guard.events.search_mode {
+                                app_guard.events.backspace_search();
                             }
                         }
                         KeyCode::Esc => {
@@ -449,9 +650,16 @@ fn run_app<B: Backend>(
                                     app_guard.traffic_logs.close_detail_modal();
                                 }
                                 // Esc로 종료하지 않음 (Ctrl+C 사용)
+                            } else if app_guard.current_tab == crate::app::TabIndex::Events
+                                && app_guard.events.search_mode {
+                                // 검색 취소 (검색어 초기화)
+                                app_guard.events.cancel_search_mode();
                             }
                             // Esc로 종료하지 않음 (Ctrl+C 사용)
                         }
+                        KeyCode::Delete if app_guard.current_tab == crate::app::TabIndex::ProxyManagement => {
+                            app_guard.request_delete_selected();
+                        }
                         KeyCode::PageDown => {
                             if app_guard.current_tab == crate::app::TabIndex::SessionBrowser {
                                 app_guard.session_browser.next_page();
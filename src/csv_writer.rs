@@ -1,4 +1,4 @@
-use crate::app::ResourceData;
+use crate::app::{InterfaceTraffic, ResourceData};
 use anyhow::{Context, Result};
 use chrono::Local;
 use std::collections::HashMap;
@@ -87,7 +87,8 @@ impl CsvWriter {
         // 헤더는 파일이 없을 때만 작성
         if !file_exists {
             let mut header = vec![
-                "timestamp", "proxy_id", "host", "cpu", "mem", "cc", "cs", "http", "https", "ftp"
+                "timestamp", "proxy_id", "host", "cpu", "mem", "cc", "cs", "http", "https", "ftp",
+                "load", "swap", "open_files", "uptime_secs", "disks", "processes"
             ];
             
             // 각 회선에 대해 컬럼 추가
@@ -104,7 +105,7 @@ impl CsvWriter {
         // 데이터 작성
         for record in data {
             let format_value = |v: Option<f64>| -> String {
-                v.map(|val| format!("{:.2}", val)).unwrap_or_else(|| "".to_string())
+                v.map(|val| format!("{:.2}", val)).unwrap_or_default()
             };
 
             let cpu_str = format_value(record.cpu);
@@ -116,17 +117,18 @@ impl CsvWriter {
             let ftp_str = format_value(record.ftp);
             
             // 회선 정보를 HashMap으로 변환 (빠른 조회를 위해)
-            let interface_map: HashMap<String, (f64, f64)> = record.interfaces
+            let interface_map: HashMap<String, &InterfaceTraffic> = record.interfaces
                 .iter()
-                .map(|iface| (iface.name.clone(), (iface.in_mbps, iface.out_mbps)))
+                .map(|iface| (iface.name.clone(), iface))
                 .collect();
 
-            let status = if record.collection_failed {
-                record.error_message.as_ref()
-                    .map(|s| s.as_str())
-                    .unwrap_or("실패")
+            let status = if let Some(state) = &record.skipped {
+                state.label()
+            } else if record.collection_failed {
+                record.error_message.clone()
+                    .unwrap_or_else(|| "실패".to_string())
             } else {
-                "성공"
+                "성공".to_string()
             };
 
             // 기본 필드들
@@ -141,12 +143,34 @@ impl CsvWriter {
                 http_str,
                 https_str,
                 ftp_str,
+                format_value(record.load_avg),
+                format_value(record.swap),
+                format_value(record.open_files),
+                record.uptime_secs.map(|secs| secs.to_string()).unwrap_or_default(),
+                // "마운트=사용률" / "프로세스=개수"를 ;로 구분
+                record.disks
+                    .iter()
+                    .map(|disk| format!("{}={:.0}", disk.mount, disk.used_percent))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+                record.processes
+                    .iter()
+                    .map(|process| format!("{}={}", process.name, process.count))
+                    .collect::<Vec<_>>()
+                    .join(";"),
             ];
             
             // 각 회선에 대해 값 추가 (In/Out 형식)
             for if_name in &interface_names {
-                if let Some((in_mbps, out_mbps)) = interface_map.get(if_name) {
-                    record_fields.push(format!("{:.2}/{:.2}", in_mbps, out_mbps));
+                if let Some(iface) = interface_map.get(if_name) {
+                    // DOWN 회선은 트래픽 대신 상태를 기록
+                    if iface.is_down() {
+                        record_fields.push("DOWN".to_string());
+                    } else if iface.in_bps.is_none() && iface.out_bps.is_none() {
+                        record_fields.push("".to_string());
+                    } else {
+                        record_fields.push(format!("{}/{}", format_value(iface.in_bps), format_value(iface.out_bps)));
+                    }
                 } else {
                     record_fields.push("".to_string());
                 }
@@ -179,7 +203,7 @@ impl CsvWriter {
             .context("Failed to create CSV file")?;
 
         // 헤더 작성 (모든 필드 포함)
-        wtr.write_record(&[
+        wtr.write_record([
             "timestamp",
             "proxy_id",
             "host",
@@ -258,7 +282,7 @@ impl CsvWriter {
             .context("Failed to create CSV file")?;
 
         // 요약 정보 저장
-        wtr.write_record(&[
+        wtr.write_record([
             "type", "category", "rank", "value", "request_count", "recv_bytes", "sent_bytes"
         ])
         .context("Failed to write CSV header")?;
@@ -365,5 +389,56 @@ impl CsvWriter {
 
         Ok(filepath)
     }
-}
 
+    /// 트랩 이벤트 목록을 CSV 파일로 저장합니다.
+    pub fn save_events(events: &[crate::app::EventRecord]) -> Result<PathBuf> {
+        // logs 디렉토리 생성
+        let logs_dir = PathBuf::from("logs");
+        fs::create_dir_all(&logs_dir).context("Failed to create logs directory")?;
+
+        // 파일명 생성 (타임스탬프 포함)
+        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+        let filename = format!("events_{}.csv", timestamp);
+        let filepath = logs_dir.join(&filename);
+
+        // CSV 작성
+        let mut wtr = csv::Writer::from_path(&filepath)
+            .context("Failed to create CSV file")?;
+
+        wtr.write_record([
+            "received_at",
+            "severity",
+            "source",
+            "proxy_id",
+            "proxy_name",
+            "group",
+            "version",
+            "kind",
+            "name",
+            "trap_oid",
+            "details",
+        ])
+        .context("Failed to write CSV header")?;
+
+        for event in events {
+            wtr.write_record(&[
+                event.received_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                event.severity.label().to_string(),
+                event.source.clone(),
+                event.proxy_id.map(|id| id.to_string()).unwrap_or_default(),
+                event.proxy_name.clone().unwrap_or_default(),
+                event.group.clone().unwrap_or_default(),
+                event.version.clone(),
+                event.kind.clone(),
+                event.name.clone(),
+                event.trap_oid.clone(),
+                event.details.clone(),
+            ])
+            .context("Failed to write CSV record")?;
+        }
+
+        wtr.flush().context("Failed to flush CSV file")?;
+
+        Ok(filepath)
+    }
+}
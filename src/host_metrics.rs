@@ -0,0 +1,244 @@
+//! SSH로 수집하는 호스트 지표 (부하, 디스크, 가동 시간, 스왑, 파일 디스크립터, 프로세스)
+//!
+//! 프록시마다 명령 한 번으로 /proc 파일과 `df`, `pgrep` 결과를 구역 표시 줄(`@load` 등)로
+//! 나눠 출력하고, 받은 출력을 구역별로 해석합니다. 일부 구역이 실패해도 나머지 지표는 사용합니다.
+
+use crate::app::{DiskUsage, ProcessStatus};
+use crate::command_runner::CommandRunner;
+use anyhow::Result;
+
+/// 기본으로 확인하는 MWG 프로세스
+pub const DEFAULT_PROCESSES: [&str; 2] = ["mwg-core", "coordinator"];
+
+/// resource_config.json의 `host_metrics` 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostMetricsConfig {
+    /// 사용률을 볼 경로 (비어 있으면 tmpfs를 제외한 모든 파일시스템)
+    pub mounts: Vec<String>,
+    /// 실행 여부를 확인할 프로세스 이름 (`pgrep -x`로 비교)
+    pub processes: Vec<String>,
+}
+
+impl Default for HostMetricsConfig {
+    fn default() -> Self {
+        Self {
+            mounts: Vec::new(),
+            processes: DEFAULT_PROCESSES.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+}
+
+/// 한 번의 명령으로 수집한 호스트 지표
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostMetrics {
+    pub load_avg: Option<f64>, // 1분 평균 부하
+    pub cpu_cores: Option<u32>,
+    pub uptime_secs: Option<u64>,
+    pub mem: Option<f64>,        // 메모리 사용률 (%)
+    pub swap: Option<f64>,       // 스왑 사용률 (%), 스왑이 없으면 None
+    pub open_files: Option<f64>, // 열린 파일 디스크립터 / fs.file-max (%)
+    pub disks: Vec<DiskUsage>,
+    pub processes: Vec<ProcessStatus>,
+}
+
+/// sh에서 한 인자로 쓰이도록 작은따옴표로 감쌈
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// 모든 지표를 출력하는 명령 (구역마다 `@이름` 줄로 시작)
+pub fn batch_command(config: &HostMetricsConfig) -> String {
+    let df = if config.mounts.is_empty() {
+        "df -P -k -x tmpfs -x devtmpfs".to_string()
+    } else {
+        let mounts: Vec<String> = config.mounts.iter().map(|mount| quote(mount)).collect();
+        format!("df -P -k {}", mounts.join(" "))
+    };
+    let mut command = [
+        "echo @load; cat /proc/loadavg",
+        "echo @cores; getconf _NPROCESSORS_ONLN",
+        "echo @uptime; cat /proc/uptime",
+        "echo @meminfo; grep -E '^(MemTotal|MemAvailable|SwapTotal|SwapFree):' /proc/meminfo",
+        "echo @files; cat /proc/sys/fs/file-nr",
+    ]
+    .map(|part| format!("{} 2>/dev/null", part))
+    .join("; ");
+    command.push_str(&format!("; echo @df; {} 2>/dev/null", df));
+    if !config.processes.is_empty() {
+        let names: Vec<String> = config.processes.iter().map(|name| quote(name)).collect();
+        // 줄마다 "개수 이름" (이름에 공백이 있어도 구분되도록 개수를 앞에)
+        command.push_str(&format!(
+            "; echo @processes; for p in {}; do echo \"$(pgrep -x -- \"$p\" | wc -l) $p\"; done",
+            names.join(" ")
+        ));
+    }
+    // pgrep/df의 종료 코드가 전체 결과가 되지 않도록
+    command.push_str("; true");
+    command
+}
+
+/// 명령 출력을 해석합니다. 해석할 수 있는 구역이 하나도 없으면 오류입니다.
+pub fn parse(output: &str, config: &HostMetricsConfig) -> Result<HostMetrics> {
+    let mut metrics = HostMetrics::default();
+    let mut meminfo: Vec<(String, f64)> = Vec::new();
+    let mut section = "";
+    let mut parsed_any = false;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('@') {
+            section = name;
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let parsed = match section {
+            // "0.52 0.58 0.59 1/467 12345"
+            "load" => fields.first().and_then(|v| v.parse().ok()).map(|v| metrics.load_avg = Some(v)),
+            "cores" => line.parse().ok().filter(|n| *n > 0).map(|n| metrics.cpu_cores = Some(n)),
+            // "350735.47 1381033.37" (가동 시간, 유휴 시간)
+            "uptime" => fields
+                .first()
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(|secs| metrics.uptime_secs = Some(secs as u64)),
+            // "MemTotal:       16318480 kB"
+            "meminfo" => match (fields.first(), fields.get(1).and_then(|v| v.parse().ok())) {
+                (Some(key), Some(kb)) => {
+                    meminfo.push((key.trim_end_matches(':').to_string(), kb));
+                    Some(())
+                }
+                _ => None,
+            },
+            // "할당 미사용 최대" (최근 커널은 미사용이 항상 0)
+            "files" => match fields.iter().map(|v| v.parse::<f64>().ok()).collect::<Option<Vec<_>>>().as_deref() {
+                Some([allocated, unused, max]) if *max > 0.0 => {
+                    metrics.open_files = Some(((allocated - unused) / max * 100.0).clamp(0.0, 100.0));
+                    Some(())
+                }
+                _ => None,
+            },
+            "df" => parse_df_line(&fields).map(|disk| {
+                // 여러 경로가 같은 파일시스템에 있으면 한 번만
+                if !metrics.disks.iter().any(|d| d.mount == disk.mount) {
+                    metrics.disks.push(disk);
+                }
+            }),
+            "processes" => line.split_once(' ').and_then(|(count, name)| {
+                let count = count.parse().ok()?;
+                metrics.processes.push(ProcessStatus { name: name.to_string(), count });
+                Some(())
+            }),
+            _ => None,
+        };
+        parsed_any |= parsed.is_some();
+    }
+
+    let kb = |key: &str| meminfo.iter().find(|(k, _)| k == key).map(|(_, v)| *v);
+    if let (Some(total), Some(available)) = (kb("MemTotal"), kb("MemAvailable")) {
+        if total > 0.0 {
+            metrics.mem = Some((100.0 - available / total * 100.0).clamp(0.0, 100.0));
+        }
+    }
+    if let (Some(total), Some(free)) = (kb("SwapTotal"), kb("SwapFree")) {
+        if total > 0.0 {
+            metrics.swap = Some(((total - free) / total * 100.0).clamp(0.0, 100.0));
+        }
+    }
+
+    // 출력이 없는 프로세스(명령 실패)도 확인 대상이면 0개로 표시
+    for name in &config.processes {
+        if parsed_any && !metrics.processes.iter().any(|p| &p.name == name) {
+            metrics.processes.push(ProcessStatus { name: name.clone(), count: 0 });
+        }
+    }
+
+    if !parsed_any {
+        anyhow::bail!("호스트 지표 출력을 해석할 수 없습니다: {}", output.trim());
+    }
+    Ok(metrics)
+}
+
+/// `df -P -k` 한 줄: "파일시스템 1024-blocks Used Available Capacity 마운트"
+fn parse_df_line(fields: &[&str]) -> Option<DiskUsage> {
+    if fields.len() < 6 {
+        return None;
+    }
+    let used_percent = fields[4].strip_suffix('%')?.parse().ok()?;
+    let available_kb = fields[3].parse().ok()?;
+    Some(DiskUsage {
+        // 마운트 경로에 공백이 있을 수 있음
+        mount: fields[5..].join(" "),
+        used_percent,
+        available_kb,
+    })
+}
+
+/// 호스트 지표를 한 번의 명령으로 수집합니다.
+pub async fn collect(runner: &dyn CommandRunner, config: &HostMetricsConfig) -> Result<HostMetrics> {
+    let output = runner.execute(&batch_command(config)).await?;
+    parse(&output, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_runner::ScriptedRunner;
+
+    const RECORDED: &str = include_str!("../fixtures/mwg_host_metrics.txt");
+
+    fn config(mounts: &[&str]) -> HostMetricsConfig {
+        HostMetricsConfig { mounts: mounts.iter().map(|m| m.to_string()).collect(), ..HostMetricsConfig::default() }
+    }
+
+    #[tokio::test]
+    async fn recorded_batch_output_is_parsed_into_metrics() {
+        let runner = ScriptedRunner::new().respond("echo @load", RECORDED);
+        let metrics = collect(&runner, &config(&["/", "/opt/mwg/log"])).await.unwrap();
+
+        // 명령은 한 번만 실행하고 경로와 프로세스 이름은 따옴표로 감쌈
+        let executed = runner.executed();
+        assert_eq!(executed.len(), 1);
+        assert!(executed[0].contains("df -P -k '/' '/opt/mwg/log'"), "{}", executed[0]);
+        assert!(executed[0].contains("for p in 'mwg-core' 'coordinator'"), "{}", executed[0]);
+
+        assert_eq!(metrics.load_avg, Some(3.42));
+        assert_eq!(metrics.cpu_cores, Some(8));
+        assert_eq!(metrics.uptime_secs, Some(350735));
+        assert_eq!(metrics.mem.map(f64::round), Some(75.0));
+        assert_eq!(metrics.swap, Some(25.0));
+        assert_eq!(metrics.open_files, Some(10.0));
+        assert_eq!(
+            metrics.disks,
+            vec![
+                DiskUsage { mount: "/".to_string(), used_percent: 41.0, available_kb: 29_360_128 },
+                DiskUsage { mount: "/opt/mwg/log".to_string(), used_percent: 93.0, available_kb: 7_340_032 },
+            ]
+        );
+        assert_eq!(
+            metrics.processes,
+            vec![
+                ProcessStatus { name: "mwg-core".to_string(), count: 1 },
+                ProcessStatus { name: "coordinator".to_string(), count: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn partial_output_keeps_available_sections() {
+        // /proc/meminfo에 스왑이 없고 df, pgrep 구역이 비어 있는 장비
+        let output = "@load\n0.10 0.20 0.30 1/100 42\n@meminfo\nMemTotal: 1000 kB\nMemAvailable: 900 kB\nSwapTotal: 0 kB\nSwapFree: 0 kB\n@df\n@processes\n";
+        let metrics = parse(output, &config(&[])).unwrap();
+        assert_eq!(metrics.load_avg, Some(0.1));
+        assert_eq!(metrics.mem.map(f64::round), Some(10.0));
+        assert_eq!((metrics.swap, metrics.cpu_cores, metrics.open_files), (None, None, None));
+        assert!(metrics.disks.is_empty());
+        // 출력이 없어도 확인 대상 프로세스는 0개로 표시
+        assert!(metrics.processes.iter().all(|p| p.count == 0));
+        assert_eq!(metrics.processes.len(), 2);
+
+        assert!(batch_command(&config(&[])).contains("df -P -k -x tmpfs"));
+        assert!(parse("sh: not found\n", &config(&[])).is_err());
+    }
+}
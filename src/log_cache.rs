@@ -0,0 +1,645 @@
+//! 트래픽 로그 파일 다운로드 캐시
+//!
+//! 현재 로그와 회전된 로그(`access.log.1`, `access.log.2.gz`, `access.log-20261015` 등)를
+//! SFTP로 `logs/cache/` 아래에 받아 두고, 분석기는 받은 파일을 읽습니다.
+//! 받다가 끊긴 파일은 다음에 이어받고, 받은 뒤에는 원격 파일의 SHA-256과 비교합니다.
+//! 프록시에 `escalation`이 있으면 SFTP 대신 권한 상승 명령(`find`, `tail -c`, `sha256sum`)으로 읽습니다.
+
+use crate::app::Proxy;
+use crate::command_runner::{CommandRunner, ExecMode};
+use crate::ssh::{RemoteFile, SshClient};
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// 원격 파일 조회/다운로드
+pub trait FileTransfer: Send + Sync {
+    /// 디렉터리의 일반 파일 목록
+    fn list_dir<'a>(&'a self, dir: &'a str) -> BoxFuture<'a, Result<Vec<RemoteFile>>>;
+
+    /// 원격 파일의 (시작, 끝) 구간을 로컬 파일 끝에 이어 쓰고, 받은 바이트를 `progress`에 더합니다.
+    fn download<'a>(
+        &'a self,
+        remote: &'a str,
+        local: &'a Path,
+        range: (u64, u64),
+        progress: Arc<AtomicU64>,
+    ) -> BoxFuture<'a, Result<()>>;
+
+    /// 원격 파일 앞 `len`바이트의 SHA-256 (16진수)
+    fn checksum<'a>(&'a self, remote: &'a str, len: u64) -> BoxFuture<'a, Result<String>>;
+}
+
+/// 프록시 설정에 맞는 파일 전송기를 만듭니다.
+/// SFTP는 로그인 사용자 권한으로만 읽으므로 `escalation`이 있으면 권한 상승 명령으로 읽습니다.
+pub fn transfer_for(proxy: &Proxy, ssh_port: u16, timeout: Duration, cancel: CancellationToken) -> Arc<dyn FileTransfer> {
+    match proxy.exec_mode {
+        ExecMode::Ssh => {
+            let client = SshClient::for_proxy(proxy, ssh_port)
+                .with_timeout(timeout)
+                .with_cancel_token(cancel);
+            match &proxy.escalation {
+                Some(escalation) => {
+                    Arc::new(EscalatedFiles::new(Arc::new(client.with_escalation(Some(escalation.clone())))))
+                }
+                None => Arc::new(client),
+            }
+        }
+        ExecMode::Local => Arc::new(LocalFiles::new(cancel)),
+    }
+}
+
+/// sh에서 한 인자로 쓰이도록 작은따옴표로 감쌈
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+impl FileTransfer for SshClient {
+    fn list_dir<'a>(&'a self, dir: &'a str) -> BoxFuture<'a, Result<Vec<RemoteFile>>> {
+        Box::pin(SshClient::list_dir(self, dir))
+    }
+
+    fn download<'a>(
+        &'a self,
+        remote: &'a str,
+        local: &'a Path,
+        (offset, end): (u64, u64),
+        progress: Arc<AtomicU64>,
+    ) -> BoxFuture<'a, Result<()>> {
+        Box::pin(SshClient::download(self, remote, local, offset, end, progress))
+    }
+
+    fn checksum<'a>(&'a self, remote: &'a str, len: u64) -> BoxFuture<'a, Result<String>> {
+        Box::pin(remote_checksum(self, remote, len))
+    }
+}
+
+/// 원격에서 `sha256sum`으로 파일 앞 `len`바이트의 SHA-256을 계산
+async fn remote_checksum(runner: &dyn CommandRunner, remote: &str, len: u64) -> Result<String> {
+    // 받는 동안 계속 쓰이는 현재 로그도 받은 길이만큼만 비교
+    let command = format!("head -c {} -- {} | sha256sum", len, quote(remote));
+    let output = runner.execute(&command).await?;
+    parse_sha256sum(&output).with_context(|| format!("원격 체크섬을 확인할 수 없습니다: {}", output.trim()))
+}
+
+/// 권한 상승 명령 한 번에 받는 크기 (명령마다 실행 타임아웃이 적용되므로 나눠 받음)
+const ESCALATED_CHUNK: u64 = 4 * 1024 * 1024;
+
+/// sudo/su로 권한을 올린 명령으로 파일을 조회하고 받음 (바이너리는 base64로 옮김)
+pub struct EscalatedFiles {
+    runner: Arc<dyn CommandRunner>,
+}
+
+impl EscalatedFiles {
+    pub fn new(runner: Arc<dyn CommandRunner>) -> Self {
+        Self { runner }
+    }
+
+    /// `offset`부터 `len`바이트를 받아 `target`에 이어 쓰고 받은 바이트 수를 반환합니다.
+    async fn download_chunk(
+        &self,
+        remote: &str,
+        target: &mut std::fs::File,
+        (offset, len): (u64, u64),
+        progress: &AtomicU64,
+    ) -> Result<u64> {
+        use base64::Engine;
+        let command = format!("tail -c +{} -- {} | head -c {} | base64", offset + 1, quote(remote), len);
+        let mut output = self.runner.execute_lines(&command);
+        let mut received = 0;
+        // base64 출력은 76자(57바이트) 단위로 줄을 나누므로 줄마다 따로 풀 수 있음
+        while let Some(line) = output.next_line().await {
+            let data = base64::engine::general_purpose::STANDARD
+                .decode(line.trim())
+                .with_context(|| format!("파일 내용을 해석할 수 없습니다: {}", remote))?;
+            target.write_all(&data)?;
+            received += data.len() as u64;
+            progress.fetch_add(data.len() as u64, Ordering::Relaxed);
+        }
+        output.finish().await.with_context(|| format!("파일 읽기 실패: {}", remote))?;
+        Ok(received)
+    }
+}
+
+impl FileTransfer for EscalatedFiles {
+    fn list_dir<'a>(&'a self, dir: &'a str) -> BoxFuture<'a, Result<Vec<RemoteFile>>> {
+        Box::pin(async move {
+            // 한 줄에 "크기 수정시각 경로"
+            let command = format!("find {} -maxdepth 1 -type f -printf '%s %T@ %p\\n'", quote(dir));
+            let output = self.runner.execute(&command).await.with_context(|| format!("디렉터리 조회 실패: {}", dir))?;
+            Ok(output
+                .lines()
+                .filter_map(|line| {
+                    let mut fields = line.splitn(3, ' ');
+                    let size = fields.next()?.parse().ok()?;
+                    let modified = fields.next()?.parse::<f64>().ok().map(|secs| secs as u64);
+                    let path = fields.next()?.to_string();
+                    let name = path.rsplit('/').next()?.to_string();
+                    Some(RemoteFile { name, path, size, modified })
+                })
+                .collect())
+        })
+    }
+
+    fn download<'a>(
+        &'a self,
+        remote: &'a str,
+        local: &'a Path,
+        (offset, end): (u64, u64),
+        progress: Arc<AtomicU64>,
+    ) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut target = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(local)
+                .with_context(|| format!("로컬 파일을 열 수 없습니다: {}", local.display()))?;
+            let mut position = offset;
+            while position < end {
+                let len = (end - position).min(ESCALATED_CHUNK);
+                let received = self.download_chunk(remote, &mut target, (position, len), &progress).await?;
+                if received == 0 {
+                    // 그 사이 파일이 줄어듦 (체크섬 확인에서 다시 받음)
+                    break;
+                }
+                position += received;
+            }
+            Ok(())
+        })
+    }
+
+    fn checksum<'a>(&'a self, remote: &'a str, len: u64) -> BoxFuture<'a, Result<String>> {
+        Box::pin(remote_checksum(self.runner.as_ref(), remote, len))
+    }
+}
+
+/// `sha256sum` 출력의 첫 단어 (64자리 16진수)
+fn parse_sha256sum(output: &str) -> Option<String> {
+    let hash = output.split_whitespace().next()?;
+    (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())).then(|| hash.to_ascii_lowercase())
+}
+
+/// 로컬 파일 앞 `len`바이트의 SHA-256 (16진수)
+pub fn sha256_file(path: &Path, len: u64) -> Result<String> {
+    let file = std::fs::File::open(path).with_context(|| format!("파일을 열 수 없습니다: {}", path.display()))?;
+    let mut reader = BufReader::new(file).take(len);
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf).with_context(|| format!("파일 읽기 실패: {}", path.display()))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// 이 프로그램이 프록시 장비에서 실행될 때 로컬 파일을 그대로 복사
+pub struct LocalFiles {
+    cancel: CancellationToken,
+}
+
+impl LocalFiles {
+    pub fn new(cancel: CancellationToken) -> Self {
+        Self { cancel }
+    }
+}
+
+impl FileTransfer for LocalFiles {
+    fn list_dir<'a>(&'a self, dir: &'a str) -> BoxFuture<'a, Result<Vec<RemoteFile>>> {
+        Box::pin(async move {
+            let entries = std::fs::read_dir(dir).with_context(|| format!("디렉터리 조회 실패: {}", dir))?;
+            let mut files = Vec::new();
+            for entry in entries.flatten() {
+                let Ok(metadata) = entry.metadata() else { continue };
+                if !metadata.is_file() {
+                    continue;
+                }
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|since| since.as_secs());
+                files.push(RemoteFile {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    path: entry.path().to_string_lossy().into_owned(),
+                    size: metadata.len(),
+                    modified,
+                });
+            }
+            Ok(files)
+        })
+    }
+
+    fn download<'a>(
+        &'a self,
+        remote: &'a str,
+        local: &'a Path,
+        (offset, end): (u64, u64),
+        progress: Arc<AtomicU64>,
+    ) -> BoxFuture<'a, Result<()>> {
+        let remote = remote.to_string();
+        let local = local.to_path_buf();
+        let cancel = self.cancel.clone();
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || -> Result<()> {
+                let mut source = std::fs::File::open(&remote).with_context(|| format!("파일을 열 수 없습니다: {}", remote))?;
+                source.seek(SeekFrom::Start(offset))?;
+                let mut target = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&local)
+                    .with_context(|| format!("로컬 파일을 열 수 없습니다: {}", local.display()))?;
+                let mut source = source.take(end.saturating_sub(offset));
+                let mut buf = [0u8; 64 * 1024];
+                loop {
+                    if cancel.is_cancelled() {
+                        anyhow::bail!("다운로드가 취소되었습니다");
+                    }
+                    let n = source.read(&mut buf)?;
+                    if n == 0 {
+                        break;
+                    }
+                    target.write_all(&buf[..n])?;
+                    progress.fetch_add(n as u64, Ordering::Relaxed);
+                }
+                Ok(())
+            })
+            .await
+            .context("파일 복사 태스크 실패")?
+        })
+    }
+
+    fn checksum<'a>(&'a self, remote: &'a str, len: u64) -> BoxFuture<'a, Result<String>> {
+        let remote = PathBuf::from(remote);
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || sha256_file(&remote, len))
+                .await
+                .context("체크섬 태스크 실패")?
+        })
+    }
+}
+
+/// 다운로드 진행 상황 (다운로드 태스크가 갱신하고 화면이 읽음)
+#[derive(Debug, Default)]
+pub struct DownloadProgress {
+    pub downloaded: Arc<AtomicU64>, // 이번에 받은 바이트
+    pub total: AtomicU64,           // 이번에 받아야 할 바이트 (이어받는 파일은 남은 부분만)
+    pub files_done: AtomicUsize,
+    pub files_total: AtomicUsize,
+}
+
+/// 캐시에 받아 둔 로그 파일
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedLog {
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub downloaded: u64, // 이번에 받은 바이트 (0이면 캐시 그대로 사용)
+    pub resumed: bool,   // 이전에 받은 부분에 이어받았는지
+}
+
+/// `name`이 `base`의 회전된 로그인지 (`base.1`, `base.2.gz`, `base.gz`, `base-20261015`, `base-20261015.gz`)
+pub fn is_rotation(base: &str, name: &str) -> bool {
+    let Some(rest) = name.strip_prefix(base) else { return false };
+    let rest = rest.strip_suffix(".gz").unwrap_or(rest);
+    let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    match rest {
+        "" => name != base, // base.gz
+        _ => rest.strip_prefix('.').is_some_and(digits) || rest.strip_prefix('-').is_some_and(digits),
+    }
+}
+
+/// 로그 파일을 줄 단위로 읽는 reader (`.gz`면 압축을 풀며 읽음)
+pub fn open_log(path: &Path) -> Result<Box<dyn BufRead>> {
+    let file = std::fs::File::open(path).with_context(|| format!("로그 파일을 열 수 없습니다: {}", path.display()))?;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        Ok(Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// 프록시별 로그 캐시 디렉터리
+pub struct LogCache {
+    root: PathBuf,
+}
+
+impl LogCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// `logs/cache/<프록시 ID>_<호스트>/`
+    pub fn for_proxy(proxy: &Proxy) -> Self {
+        let host: String = proxy
+            .host
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+            .collect();
+        Self::new(Path::new("logs").join("cache").join(format!("{}_{}", proxy.id, host)))
+    }
+
+    fn paths(&self, name: &str) -> (PathBuf, PathBuf, PathBuf) {
+        (
+            self.root.join(name),
+            self.root.join(format!("{}.part", name)),
+            self.root.join(format!("{}.sha256", name)),
+        )
+    }
+
+    /// 검증을 마친 크기와 그때의 원격 수정 시각 (`<이름>.sha256`에 "크기 수정시각 해시"로 기록, 수정 시각을 모르면 "-")
+    fn verified(&self, name: &str) -> Option<(u64, Option<u64>)> {
+        let (_, _, sum_path) = self.paths(name);
+        let content = std::fs::read_to_string(sum_path).ok()?;
+        let [size, modified, _hash] = content.split_whitespace().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        Some((size.parse().ok()?, modified.parse().ok()))
+    }
+
+    /// 이어받을 위치 (None이면 캐시가 최신이라 받지 않음)
+    fn resume_offset(&self, file: &RemoteFile) -> Option<u64> {
+        let (final_path, part_path, _) = self.paths(&file.name);
+        let len = |path: &Path| std::fs::metadata(path).map(|m| m.len()).ok();
+        if let Some(part_len) = len(&part_path) {
+            return Some(if part_len > file.size { 0 } else { part_len });
+        }
+        match (len(&final_path), self.verified(&file.name)) {
+            // 크기가 같아도 수정 시각이 다르면 (같은 크기의 다른 파일로 바뀜) 처음부터
+            (Some(final_len), Some((verified, modified))) if final_len == verified && verified == file.size => {
+                (modified.is_none() || modified != file.modified).then_some(0)
+            }
+            // 현재 로그가 커졌으면 검증한 부분 뒤부터 (앞부분이 바뀌었으면 체크섬이 달라 처음부터 다시 받음)
+            (Some(final_len), Some((verified, _))) if final_len == verified && verified < file.size => Some(verified),
+            _ => Some(0),
+        }
+    }
+
+    /// `log_path`와 회전된 로그를 캐시에 받고, 오래된 순(현재 로그가 마지막)으로 반환합니다.
+    /// `since`(유닉스 초)가 있으면 그 뒤에 수정된 회전 로그만 받습니다. (현재 로그는 항상 포함)
+    pub async fn sync(
+        &self,
+        transfer: &dyn FileTransfer,
+        log_path: &str,
+        since: Option<u64>,
+        progress: &DownloadProgress,
+    ) -> Result<Vec<CachedLog>> {
+        let (dir, base) = match log_path.rsplit_once('/') {
+            Some(("", base)) => ("/", base),
+            Some((dir, base)) => (dir, base),
+            None => (".", log_path),
+        };
+        let listing = transfer.list_dir(dir).await?;
+        let mut files: Vec<RemoteFile> = listing
+            .iter()
+            .filter(|f| {
+                f.name == base
+                    || (is_rotation(base, &f.name) && since.is_none_or(|since| f.modified.unwrap_or(0) >= since))
+            })
+            .cloned()
+            .collect();
+        if files.is_empty() {
+            anyhow::bail!("로그 파일이 없습니다: {}", log_path);
+        }
+        files.sort_by_key(|f| (f.name == base, f.modified.unwrap_or(0), std::cmp::Reverse(f.name.clone())));
+
+        std::fs::create_dir_all(&self.root)
+            .with_context(|| format!("캐시 디렉터리를 만들 수 없습니다: {}", self.root.display()))?;
+        self.prune(&listing, base);
+
+        let plan: Vec<(RemoteFile, Option<u64>)> = files.into_iter().map(|f| {
+            let offset = self.resume_offset(&f);
+            (f, offset)
+        }).collect();
+        let total: u64 = plan.iter().filter_map(|(f, offset)| offset.map(|o| f.size - o)).sum();
+        progress.total.store(total, Ordering::Relaxed);
+        progress.files_total.store(plan.len(), Ordering::Relaxed);
+
+        let mut cached = Vec::new();
+        for (file, offset) in plan {
+            let (final_path, _, _) = self.paths(&file.name);
+            let entry = match offset {
+                None => CachedLog { name: file.name.clone(), path: final_path, size: file.size, downloaded: 0, resumed: false },
+                Some(offset) => self.fetch(transfer, &file, offset, progress).await?,
+            };
+            cached.push(entry);
+            progress.files_done.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(cached)
+    }
+
+    /// 파일 하나를 받고 체크섬을 확인합니다.
+    /// 이어받은 파일의 체크섬이 다르면 (그 사이 로그가 회전됨) 처음부터 다시 받습니다.
+    async fn fetch(&self, transfer: &dyn FileTransfer, file: &RemoteFile, offset: u64, progress: &DownloadProgress) -> Result<CachedLog> {
+        let (final_path, part_path, sum_path) = self.paths(&file.name);
+        // 검증한 파일에 이어받을 때는 .part로 옮겨서 받음
+        if offset > 0 && !part_path.exists() {
+            std::fs::rename(&final_path, &part_path)?;
+        }
+        if offset == 0 {
+            let _ = std::fs::remove_file(&part_path);
+        }
+        transfer.download(&file.path, &part_path, (offset, file.size), progress.downloaded.clone()).await?;
+        let mut downloaded = file.size - offset;
+
+        let remote_sum = transfer.checksum(&file.path, file.size).await?;
+        let mut local_sum = sha256_file(&part_path, file.size)?;
+        if local_sum != remote_sum && offset > 0 {
+            std::fs::remove_file(&part_path)?;
+            progress.total.fetch_add(offset, Ordering::Relaxed);
+            transfer.download(&file.path, &part_path, (0, file.size), progress.downloaded.clone()).await?;
+            downloaded += file.size;
+            local_sum = sha256_file(&part_path, file.size)?;
+        }
+        if local_sum != remote_sum {
+            let _ = std::fs::remove_file(&part_path);
+            anyhow::bail!("체크섬 불일치: {} (원격 {}, 로컬 {})", file.name, remote_sum, local_sum);
+        }
+
+        std::fs::rename(&part_path, &final_path)?;
+        let modified = file.modified.map_or("-".to_string(), |modified| modified.to_string());
+        std::fs::write(&sum_path, format!("{} {} {}\n", file.size, modified, local_sum))?;
+        Ok(CachedLog { name: file.name.clone(), path: final_path, size: file.size, downloaded, resumed: offset > 0 })
+    }
+
+    /// 서버에서 사라진 (회전되어 삭제된) 로그의 캐시를 지웁니다.
+    fn prune(&self, listing: &[RemoteFile], base: &str) {
+        let Ok(entries) = std::fs::read_dir(&self.root) else { return };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let log_name = name
+                .strip_suffix(".part")
+                .or_else(|| name.strip_suffix(".sha256"))
+                .unwrap_or(&name);
+            let is_log = log_name == base || is_rotation(base, log_name);
+            if is_log && !listing.iter().any(|f| f.name == log_name) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic_log_parser::TrafficLogAnalyzer;
+
+    const RECORDED: &str = include_str!("../fixtures/mwg_access.log");
+
+    /// 테스트마다 비어 있는 임시 디렉터리 (원격, 캐시)
+    fn temp_dirs(name: &str) -> (PathBuf, PathBuf) {
+        let root = std::env::temp_dir().join(format!("mmt-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let (remote, cache) = (root.join("remote"), root.join("cache"));
+        std::fs::create_dir_all(&remote).unwrap();
+        (remote, cache)
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn rotated_names_are_recognized() {
+        for name in ["access.log.1", "access.log.12.gz", "access.log.gz", "access.log-20261015", "access.log-20261015.gz"] {
+            assert!(is_rotation("access.log", name), "{}", name);
+        }
+        for name in ["access.log", "access.log.bak", "access.log.1.tmp", "access.log2", "error.log.1", "access.log.-1"] {
+            assert!(!is_rotation("access.log", name), "{}", name);
+        }
+        assert_eq!(parse_sha256sum(&format!("{}  -\n", "AB".repeat(32))), Some("ab".repeat(32)));
+        assert_eq!(parse_sha256sum("sha256sum: not found"), None);
+    }
+
+    #[tokio::test]
+    async fn sync_resumes_verifies_and_analyzes_rotated_logs() {
+        let (remote, cache_dir) = temp_dirs("log-cache");
+        let lines: Vec<&str> = RECORDED.lines().filter(|l| !l.is_empty()).collect();
+        std::fs::write(remote.join("access.log"), format!("{}\n", lines[0])).unwrap();
+        std::fs::write(remote.join("access.log.1"), format!("{}\n", lines[1])).unwrap();
+        std::fs::write(remote.join("access.log.2.gz"), gzip(format!("{}\n", lines[2]).as_bytes())).unwrap();
+        std::fs::write(remote.join("error.log"), "unrelated\n").unwrap();
+
+        let transfer = LocalFiles::new(CancellationToken::new());
+        let cache = LogCache::new(&cache_dir);
+        let log_path = remote.join("access.log").to_string_lossy().into_owned();
+        let progress = DownloadProgress::default();
+        let cached = cache.sync(&transfer, &log_path, None, &progress).await.unwrap();
+
+        // 현재 로그가 마지막, 관계없는 파일은 받지 않음
+        let names: Vec<&str> = cached.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names.last(), Some(&"access.log"));
+        assert_eq!(names.len(), 3);
+        let total: u64 = cached.iter().map(|c| c.size).sum();
+        assert_eq!(progress.downloaded.load(Ordering::Relaxed), total);
+        assert_eq!(progress.total.load(Ordering::Relaxed), total);
+        assert_eq!(progress.files_done.load(Ordering::Relaxed), 3);
+        assert!(!cache_dir.join("error.log").exists());
+
+        // 압축된 회전 로그까지 세 줄 모두 분석
+        let paths: Vec<PathBuf> = cached.iter().map(|c| c.path.clone()).collect();
+        let analysis = TrafficLogAnalyzer::new(10).analyze_files(&paths).unwrap();
+        assert_eq!((analysis.parsed_records, analysis.unparsed_records), (3, 0));
+
+        // 바뀐 것이 없으면 다시 받지 않음
+        let progress = DownloadProgress::default();
+        let again = cache.sync(&transfer, &log_path, None, &progress).await.unwrap();
+        assert!(again.iter().all(|c| c.downloaded == 0));
+        assert_eq!(progress.downloaded.load(Ordering::Relaxed), 0);
+
+        // 현재 로그에 줄이 추가되면 늘어난 부분만 이어받음
+        let grown = format!("{}\n{}\n", lines[0], lines[1]);
+        std::fs::write(remote.join("access.log"), &grown).unwrap();
+        let progress = DownloadProgress::default();
+        let current = cache.sync(&transfer, &log_path, None, &progress).await.unwrap().pop().unwrap();
+        assert!(current.resumed);
+        assert_eq!(current.downloaded, lines[1].len() as u64 + 1);
+        assert_eq!(std::fs::read_to_string(&current.path).unwrap(), grown);
+
+        // 끊긴 다운로드(.part)는 남은 부분만 받음
+        std::fs::write(cache_dir.join("access.log.1.part"), &lines[1][..10]).unwrap();
+        std::fs::remove_file(cache_dir.join("access.log.1.sha256")).unwrap();
+        // 그 사이 회전되어 내용이 바뀐 파일은 체크섬이 달라 처음부터 다시 받음
+        let rotated = format!("{}\n{}\n{}\n", lines[2], lines[0], lines[1]);
+        std::fs::write(remote.join("access.log"), &rotated).unwrap();
+        let progress = DownloadProgress::default();
+        let cached = cache.sync(&transfer, &log_path, None, &progress).await.unwrap();
+        assert!(cached[1].resumed);
+        assert_eq!(cached[1].downloaded, lines[1].len() as u64 + 1 - 10);
+        let current = cached.last().unwrap();
+        assert_eq!(std::fs::read_to_string(&current.path).unwrap(), rotated);
+        assert_eq!(current.downloaded, (rotated.len() - grown.len() + rotated.len()) as u64);
+        assert_eq!(std::fs::read_to_string(cache_dir.join("access.log.1")).unwrap(), format!("{}\n", lines[1]));
+        assert!(!cache_dir.join("access.log.1.part").exists());
+
+        // 크기가 같아도 수정 시각이 바뀐 파일은 처음부터 다시 받음
+        let replaced = format!("{}\n", lines[1].replace('/', "_"));
+        assert_eq!(replaced.len(), lines[1].len() + 1);
+        std::fs::write(remote.join("access.log.1"), &replaced).unwrap();
+        let touched = std::time::SystemTime::now() - Duration::from_secs(3600);
+        std::fs::File::options().write(true).open(remote.join("access.log.1")).unwrap().set_modified(touched).unwrap();
+        let progress = DownloadProgress::default();
+        let cached = cache.sync(&transfer, &log_path, None, &progress).await.unwrap();
+        let rotated_log = cached.iter().find(|c| c.name == "access.log.1").unwrap();
+        assert_eq!(rotated_log.downloaded, replaced.len() as u64);
+        assert!(!rotated_log.resumed);
+        assert_eq!(std::fs::read_to_string(cache_dir.join("access.log.1")).unwrap(), replaced);
+        assert!(cached.iter().filter(|c| c.name != "access.log.1").all(|c| c.downloaded == 0));
+
+        // 서버에서 지워진 회전 로그는 캐시에서도 지움
+        std::fs::remove_file(remote.join("access.log.2.gz")).unwrap();
+        let cached = cache.sync(&transfer, &log_path, None, &DownloadProgress::default()).await.unwrap();
+        assert_eq!(cached.len(), 2);
+        assert!(!cache_dir.join("access.log.2.gz").exists());
+
+        // 로그가 없으면 오류
+        let missing = remote.join("missing.log").to_string_lossy().into_owned();
+        assert!(cache.sync(&transfer, &missing, None, &DownloadProgress::default()).await.is_err());
+        let _ = std::fs::remove_dir_all(cache_dir.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn escalated_files_read_through_runner_commands() {
+        let (remote, cache_dir) = temp_dirs("log-cache-escalated");
+        let lines: Vec<&str> = RECORDED.lines().filter(|l| !l.is_empty()).collect();
+        let compressed = gzip(format!("{}\n", lines[1]).as_bytes());
+        std::fs::write(remote.join("access.log"), format!("{}\n", lines[0])).unwrap();
+        std::fs::write(remote.join("access.log.1.gz"), &compressed).unwrap();
+
+        // sudo/su로 감싸는 SSH 실행기 대신 같은 명령을 로컬에서 실행
+        let runner = crate::command_runner::LocalRunner::new(Duration::from_secs(10), CancellationToken::new());
+        let transfer = EscalatedFiles::new(Arc::new(runner));
+        let listing = transfer.list_dir(&remote.to_string_lossy()).await.unwrap();
+        let current = listing.iter().find(|f| f.name == "access.log").unwrap();
+        assert_eq!(current.size, lines[0].len() as u64 + 1);
+        assert!(current.modified.is_some());
+
+        let cache = LogCache::new(&cache_dir);
+        let log_path = remote.join("access.log").to_string_lossy().into_owned();
+        let progress = DownloadProgress::default();
+        let cached = cache.sync(&transfer, &log_path, None, &progress).await.unwrap();
+        assert_eq!(cached.len(), 2);
+        // 바이너리(gzip)도 그대로 받음
+        assert_eq!(std::fs::read(cache_dir.join("access.log.1.gz")).unwrap(), compressed);
+        assert_eq!(progress.downloaded.load(Ordering::Relaxed), cached.iter().map(|c| c.size).sum::<u64>());
+
+        // 늘어난 부분만 이어받음
+        let grown = format!("{}\n{}\n", lines[0], lines[2]);
+        std::fs::write(remote.join("access.log"), &grown).unwrap();
+        let current = cache.sync(&transfer, &log_path, None, &DownloadProgress::default()).await.unwrap().pop().unwrap();
+        assert!(current.resumed);
+        assert_eq!(current.downloaded, lines[2].len() as u64 + 1);
+        assert_eq!(std::fs::read_to_string(&current.path).unwrap(), grown);
+        let _ = std::fs::remove_dir_all(cache_dir.parent().unwrap());
+    }
+}
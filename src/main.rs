@@ -4,6 +4,11 @@ mod ui;
 mod snmp;
 mod ssh;
 mod collector;
+mod command_runner;
+mod credential;
+mod host_metrics;
+mod log_cache;
+mod vault;
 mod csv_writer;
 mod session_collector;
 mod traffic_log_parser;
@@ -12,7 +17,48 @@ mod traffic_log_collector;
 use std::time::Duration;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("simulate") {
+        run_simulator(&args[1..])?;
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("vault") {
+        run_vault(&args[1..])?;
+        return Ok(());
+    }
+
     let tick_rate = Duration::from_millis(50); // 250ms -> 50ms로 변경하여 더 빠른 반응
     crossterm::run(tick_rate)?;
     Ok(())
 }
+
+/// `vault migrate [proxies.json 경로]` - 평문 비밀번호를 암호화된 볼트로 옮김
+fn run_vault(args: &[String]) -> anyhow::Result<()> {
+    match args.first().map(String::as_str) {
+        Some("migrate") => vault::run_migrate(&args[1..]),
+        _ => anyhow::bail!("사용법: rust-mmt vault migrate [proxies.json 경로]"),
+    }
+}
+
+/// `simulate <픽스처 파일> [바인드 주소]` - 로컬 SNMP 에이전트 시뮬레이터 실행 (시연/테스트용)
+fn run_simulator(args: &[String]) -> anyhow::Result<()> {
+    let Some(fixture_path) = args.first() else {
+        anyhow::bail!("사용법: rust-mmt simulate <픽스처 파일> [바인드 주소, 기본값 127.0.0.1:1161]");
+    };
+    let bind_addr = args.get(1).map(String::as_str).unwrap_or("127.0.0.1:1161");
+    let fixture = snmp::SimulatorFixture::load(fixture_path)?;
+
+    tokio::runtime::Runtime::new()?.block_on(async {
+        let simulator = snmp::SnmpSimulator::bind(bind_addr, &fixture).await?;
+        println!(
+            "SNMP 시뮬레이터 실행 중: {} (community: {}, OID {}개) - Ctrl+C로 종료",
+            simulator.local_addr()?,
+            fixture.community,
+            fixture.objects.len()
+        );
+        tokio::select! {
+            result = simulator.run() => result,
+            _ = tokio::signal::ctrl_c() => Ok(()),
+        }
+    })
+}
@@ -1,9 +1,14 @@
 use crate::app::{Proxy, SessionData};
-use crate::ssh::SshClient;
+use crate::command_runner::{self, CommandRunner};
+use crate::ssh::SshError;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
 use regex::Regex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
 
 /// 세션 브라우저 설정
 #[derive(Debug, Clone)]
@@ -30,230 +35,125 @@ impl Default for SessionBrowserConfig {
 /// 세션 조회기
 pub struct SessionCollector {
     config: SessionBrowserConfig,
+    cancel: CancellationToken,
+    rows_received: Arc<AtomicUsize>, // 지금까지 파싱한 세션 수 (모든 프록시 합계)
+    runner: Option<Arc<dyn CommandRunner>>, // 설정하면 프록시 설정 대신 이 실행기 사용
 }
 
 impl SessionCollector {
     pub fn new(config: SessionBrowserConfig) -> Self {
-        Self { config }
+        Self { config, cancel: CancellationToken::new(), rows_received: Arc::default(), runner: None }
     }
 
-    /// 프록시에서 세션 목록을 조회합니다.
-    pub async fn query_sessions(&self, proxy: &Proxy) -> Result<Vec<SessionData>> {
-        let ssh_client = SshClient::new(
-            proxy.host.clone(),
-            self.config.ssh_port,
-            proxy.username.clone(),
-            proxy.password.clone(),
-        )
-        .with_timeout(Duration::from_secs(self.config.timeout_sec));
-
-        // MWG 명령어 실행: command_path + command_args
-        let command = format!("{} {}", self.config.command_path, self.config.command_args).trim().to_string();
-        
-        let output = ssh_client.execute(&command).await
-            .context(format!("SSH 명령어 실행 실패: {}", command))?;
+    /// 토큰이 취소되면 진행 중인 SSH 명령을 모두 중단
+    pub fn with_cancel_token(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
 
-        // 출력 파싱
-        Self::parse_sessions(&output, proxy)
+    /// 조회 중 파싱한 세션 수를 이 카운터에 더함 (조회가 끝나기 전에 진행 상황 표시용)
+    pub fn with_row_counter(mut self, rows_received: Arc<AtomicUsize>) -> Self {
+        self.rows_received = rows_received;
+        self
     }
 
-    /// 세션 출력을 파싱하여 SessionData 벡터로 변환합니다.
-    /// 기존 Python 앱의 _parse_sessions 함수를 Rust로 포팅
-    fn parse_sessions(output: &str, proxy: &Proxy) -> Result<Vec<SessionData>> {
-        let lines: Vec<&str> = output
-            .lines()
-            .map(|l| l.trim())
-            .filter(|l| !l.is_empty())
-            .collect();
-
-        if lines.is_empty() {
-            return Ok(Vec::new());
-        }
+    /// 프록시 설정(SSH/로컬) 대신 주어진 실행기로 명령 실행
+    #[cfg(test)]
+    pub fn with_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = Some(runner);
+        self
+    }
 
-        // 첫 줄이 요약 줄인지 확인 ("There are currently"로 시작)
-        let mut start_idx = 0;
-        if lines[0].to_lowercase().starts_with("there are currently") {
-            start_idx = 1;
+    fn runner_for(&self, proxy: &Proxy) -> Arc<dyn CommandRunner> {
+        match &self.runner {
+            Some(runner) => runner.clone(),
+            None => command_runner::runner_for(
+                proxy,
+                self.config.ssh_port,
+                Duration::from_secs(self.config.timeout_sec),
+                self.cancel.clone(),
+                proxy.escalation.clone(),
+            ),
         }
+    }
 
-        // 두 번째 줄이 헤더 줄인지 확인 (Transaction과 URL 포함)
-        if start_idx < lines.len()
-            && lines[start_idx].contains("Transaction")
-            && lines[start_idx].contains("URL")
-        {
-            start_idx += 1;
-        }
+    /// 프록시에서 세션 목록을 조회합니다.
+    pub async fn query_sessions(&self, proxy: &Proxy) -> Result<Vec<SessionData>> {
+        let runner = self.runner_for(proxy);
 
+        // MWG 명령어 실행: command_path + command_args
+        let command = format!("{} {}", self.config.command_path, self.config.command_args).trim().to_string();
+        
+        // 출력을 받는 대로 한 줄씩 파싱
+        let mut output = runner.execute_lines(&command);
+        let mut parser = SessionParser::new(proxy);
         let mut sessions = Vec::new();
-        let dt_regex = Regex::new(r"^\d{4}-\d{2}-\d{2}\s+\d{2}:\d{2}:\d{2}$").unwrap();
-
-        for line in lines.iter().skip(start_idx) {
-            // 파이프로 구분된 필드 파싱
-            let parts: Vec<&str> = line.split('|').map(|p| p.trim()).collect();
-
-            if parts.is_empty() {
-                continue;
-            }
-
-            // Transaction은 항상 첫 번째 필드
-            let transaction = parts.get(0).and_then(|s| {
-                let s = s.trim();
-                if s.is_empty() {
-                    None
-                } else {
-                    Some(s.to_string())
-                }
-            });
-
-            // Creation Time 찾기 (다음 몇 개 필드 중에서 날짜 형식 찾기)
-            let mut creation_time_idx = None;
-            for i in 1..parts.len().min(6) {
-                if let Some(part) = parts.get(i) {
-                    if dt_regex.is_match(part) {
-                        creation_time_idx = Some(i);
-                        break;
-                    }
-                }
-            }
-
-            // Creation Time 파싱
-            let creation_time = creation_time_idx.and_then(|idx| {
-                parts.get(idx).and_then(|ct_str| {
-                    // NaiveDateTime로 파싱 후 Local 시간대로 변환
-                    if let Ok(naive_dt) = NaiveDateTime::parse_from_str(ct_str, "%Y-%m-%d %H:%M:%S") {
-                        // Local 시간대로 변환 (ambiguous time은 첫 번째로 선택)
-                        return Some(Local.from_local_datetime(&naive_dt)
-                            .single()
-                            .unwrap_or_else(|| naive_dt.and_utc().with_timezone(&Local)));
-                    }
-                    // DateTime::parse_from_str 시도 (이미 시간대 포함된 경우)
-                    if let Ok(dt) = DateTime::parse_from_str(ct_str, "%Y-%m-%d %H:%M:%S %z") {
-                        return Some(dt.with_timezone(&Local));
-                    }
-                    None
-                })
-            });
-
-            // Creation Time 이후 필드들의 인덱스 조정
-            let shift_after = creation_time_idx.map(|idx| idx - 1).unwrap_or(0);
-
-            // 필드 추출 헬퍼 함수
-            let get_after = |expected_index: usize| -> Option<String> {
-                let idx = expected_index + shift_after;
-                parts.get(idx).and_then(|s| {
-                    let s = s.trim();
-                    if s.is_empty() {
-                        None
-                    } else {
-                        Some(s.to_string())
-                    }
-                })
-            };
-
-            // 정수 파싱 헬퍼 함수
-            let to_int = |value: Option<String>| -> Option<i64> {
-                value?.parse().ok()
-            };
-
-            // 필드 추출 (기존 Python 코드의 순서대로)
-            let protocol = get_after(2);
-            let cust_id = get_after(3);
-            let user_name = get_after(4);
-            let client_ip_raw = get_after(5);
-            
-            // Client IP에서 포트 제거 (예: "1.2.3.4:56789" -> "1.2.3.4")
-            let client_ip = client_ip_raw.and_then(|ip| {
-                let ip_regex = Regex::new(r"^(\d+\.\d+\.\d+\.\d+):\d+$").unwrap();
-                if let Some(caps) = ip_regex.captures(&ip) {
-                    caps.get(1).map(|m| m.as_str().to_string())
-                } else {
-                    Some(ip)
-                }
-            });
-
-            let client_side_mwg_ip = get_after(6);
-            let server_side_mwg_ip = get_after(7);
-            let server_ip = get_after(8);
-            let cl_bytes_received = to_int(get_after(9));
-            let cl_bytes_sent = to_int(get_after(10));
-            let srv_bytes_received = to_int(get_after(11));
-            let srv_bytes_sent = to_int(get_after(12));
-            let trxn_index = to_int(get_after(13));
-            let age_seconds = to_int(get_after(14));
-            let status = get_after(15);
-            let in_use = to_int(get_after(16));
-            let mut url = get_after(17);
-
-            // URL이 없으면 마지막 필드에서 찾기
-            if url.is_none() && !parts.is_empty() {
-                if let Some(last) = parts.last() {
-                    let last = last.trim();
-                    if last.starts_with("http://") || last.starts_with("https://") {
-                        url = Some(last.to_string());
-                    }
-                }
-            }
-
-            // Client IP가 있어야 세션으로 인정
-            if let Some(client_ip) = client_ip {
-                sessions.push(SessionData {
-                    proxy_id: proxy.id,
-                    host: proxy.host.clone(),
-                    transaction,
-                    creation_time,
-                    protocol,
-                    cust_id,
-                    user_name,
-                    client_ip,
-                    client_side_mwg_ip,
-                    server_side_mwg_ip,
-                    server_ip,
-                    cl_bytes_received,
-                    cl_bytes_sent,
-                    srv_bytes_received,
-                    srv_bytes_sent,
-                    trxn_index,
-                    age_seconds,
-                    status,
-                    in_use,
-                    url,
-                });
+        while let Some(line) = output.next_line().await {
+            if let Some(session) = parser.parse_line(&line) {
+                sessions.push(session);
+                self.rows_received.fetch_add(1, Ordering::Relaxed);
             }
         }
+        output.finish().await
+            .context(format!("SSH 명령어 실행 실패: {}", command))?;
 
         Ok(sessions)
     }
 
-    /// 여러 프록시에서 세션을 병렬로 조회합니다.
-    pub async fn query_multiple(&self, proxies: &[Proxy]) -> Result<Vec<SessionData>> {
+    /// 여러 프록시에서 세션을 병렬로 조회합니다. (동시에 최대 `max_workers`개)
+    /// 일부 프록시가 실패해도 나머지 결과와 함께 실패한 프록시와 오류를 돌려줍니다.
+    pub async fn query_multiple(&self, proxies: &[Proxy]) -> Result<SessionQueryResult> {
         let mut tasks = Vec::new();
+        let workers = Arc::new(Semaphore::new(self.config.max_workers.max(1)));
 
         for proxy in proxies {
             let proxy_clone = proxy.clone();
             let collector = self.clone();
-            tasks.push(tokio::spawn(async move {
+            let workers = workers.clone();
+            tasks.push((proxy.host.clone(), tokio::spawn(async move {
+                let _permit = workers.acquire_owned().await?;
                 collector.query_sessions(&proxy_clone).await
-            }));
+            })));
         }
 
-        let mut all_sessions = Vec::new();
+        let mut result = SessionQueryResult::default();
 
-        for task in tasks {
+        for (host, task) in tasks {
             match task.await {
                 Ok(Ok(sessions)) => {
-                    all_sessions.extend(sessions);
+                    result.sessions.extend(sessions);
                 }
                 Ok(Err(e)) => {
-                    // 개별 프록시 조회 실패는 무시하고 계속 진행
-                    eprintln!("세션 조회 실패: {}", e);
+                    // 개별 프록시 조회 실패는 모아 두고 계속 진행
+                    result.failures.push((host, e));
                 }
                 Err(e) => {
-                    eprintln!("태스크 실행 실패: {}", e);
+                    result.failures.push((host, anyhow::anyhow!("태스크 실행 실패: {}", e)));
                 }
             }
         }
 
-        Ok(all_sessions)
+        // 취소되었으면 일부 결과 대신 취소로 처리
+        if self.cancel.is_cancelled() {
+            return Err(SshError::Cancelled.into());
+        }
+
+        Ok(result)
+    }
+}
+
+/// 여러 프록시 세션 조회 결과
+#[derive(Debug, Default)]
+pub struct SessionQueryResult {
+    pub sessions: Vec<SessionData>,
+    /// 조회에 실패한 프록시 (호스트, 오류)
+    pub failures: Vec<(String, anyhow::Error)>,
+}
+
+impl SessionQueryResult {
+    /// 실패한 프록시별 오류를 한 줄로 (예: "10.0.0.1: 권한 상승 실패 ...; 10.0.0.2: ...")
+    pub fn failure_summary(&self) -> String {
+        self.failures.iter().map(|(host, e)| format!("{}: {:#}", host, e)).collect::<Vec<_>>().join("; ")
     }
 }
 
@@ -261,6 +161,266 @@ impl Clone for SessionCollector {
     fn clone(&self) -> Self {
         Self {
             config: self.config.clone(),
+            cancel: self.cancel.clone(),
+            rows_received: self.rows_received.clone(),
+            runner: self.runner.clone(),
         }
     }
 }
+
+/// `mwg-core -S connections` 출력을 한 줄씩 SessionData로 변환하는 파서
+/// 기존 Python 앱의 _parse_sessions 함수를 Rust로 포팅
+pub struct SessionParser<'a> {
+    proxy: &'a Proxy,
+    lines_seen: usize, // 지금까지 받은 줄 수 (빈 줄 제외)
+    header_index: usize, // 헤더 줄이 올 수 있는 위치 (요약 줄이 있으면 1)
+    dt_regex: Regex,
+    ip_regex: Regex,
+}
+
+impl<'a> SessionParser<'a> {
+    pub fn new(proxy: &'a Proxy) -> Self {
+        Self {
+            proxy,
+            lines_seen: 0,
+            header_index: 0,
+            dt_regex: Regex::new(r"^\d{4}-\d{2}-\d{2}\s+\d{2}:\d{2}:\d{2}$").unwrap(),
+            ip_regex: Regex::new(r"^(\d+\.\d+\.\d+\.\d+):\d+$").unwrap(),
+        }
+    }
+
+    /// 출력 한 줄을 해석합니다. 요약/헤더 줄이나 세션이 아닌 줄이면 None
+    pub fn parse_line(&mut self, line: &str) -> Option<SessionData> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+        let index = self.lines_seen;
+        self.lines_seen += 1;
+
+        // 첫 줄이 요약 줄인지 확인 ("There are currently"로 시작)
+        if index == 0 && line.to_lowercase().starts_with("there are currently") {
+            self.header_index = 1;
+            return None;
+        }
+
+        // 요약 줄 다음 줄이 헤더 줄인지 확인 (Transaction과 URL 포함)
+        if index == self.header_index && line.contains("Transaction") && line.contains("URL") {
+            return None;
+        }
+
+        let proxy = self.proxy;
+        // 파이프로 구분된 필드 파싱
+        let parts: Vec<&str> = line.split('|').map(|p| p.trim()).collect();
+
+        if parts.is_empty() {
+            return None;
+        }
+
+        // Transaction은 항상 첫 번째 필드
+        let transaction = parts.first().and_then(|s| {
+            let s = s.trim();
+            if s.is_empty() {
+                None
+            } else {
+                Some(s.to_string())
+            }
+        });
+
+        // Creation Time 찾기 (다음 몇 개 필드 중에서 날짜 형식 찾기)
+        let mut creation_time_idx = None;
+        for i in 1..parts.len().min(6) {
+            if let Some(part) = parts.get(i) {
+                if self.dt_regex.is_match(part) {
+                    creation_time_idx = Some(i);
+                    break;
+                }
+            }
+        }
+
+        // Creation Time 파싱
+        let creation_time = creation_time_idx.and_then(|idx| {
+            parts.get(idx).and_then(|ct_str| {
+                // NaiveDateTime로 파싱 후 Local 시간대로 변환
+                if let Ok(naive_dt) = NaiveDateTime::parse_from_str(ct_str, "%Y-%m-%d %H:%M:%S") {
+                    // Local 시간대로 변환 (ambiguous time은 첫 번째로 선택)
+                    return Some(Local.from_local_datetime(&naive_dt)
+                        .single()
+                        .unwrap_or_else(|| naive_dt.and_utc().with_timezone(&Local)));
+                }
+                // DateTime::parse_from_str 시도 (이미 시간대 포함된 경우)
+                if let Ok(dt) = DateTime::parse_from_str(ct_str, "%Y-%m-%d %H:%M:%S %z") {
+                    return Some(dt.with_timezone(&Local));
+                }
+                None
+            })
+        });
+
+        // Creation Time 이후 필드들의 인덱스 조정
+        let shift_after = creation_time_idx.map(|idx| idx - 1).unwrap_or(0);
+
+        // 필드 추출 헬퍼 함수
+        let get_after = |expected_index: usize| -> Option<String> {
+            let idx = expected_index + shift_after;
+            parts.get(idx).and_then(|s| {
+                let s = s.trim();
+                if s.is_empty() {
+                    None
+                } else {
+                    Some(s.to_string())
+                }
+            })
+        };
+
+        // 정수 파싱 헬퍼 함수
+        let to_int = |value: Option<String>| -> Option<i64> {
+            value?.parse().ok()
+        };
+
+        // 필드 추출 (기존 Python 코드의 순서대로)
+        let protocol = get_after(2);
+        let cust_id = get_after(3);
+        let user_name = get_after(4);
+        let client_ip_raw = get_after(5);
+        
+        // Client IP에서 포트 제거 (예: "1.2.3.4:56789" -> "1.2.3.4")
+        let client_ip = client_ip_raw.and_then(|ip| {
+            if let Some(caps) = self.ip_regex.captures(&ip) {
+                caps.get(1).map(|m| m.as_str().to_string())
+            } else {
+                Some(ip)
+            }
+        });
+
+        let client_side_mwg_ip = get_after(6);
+        let server_side_mwg_ip = get_after(7);
+        let server_ip = get_after(8);
+        let cl_bytes_received = to_int(get_after(9));
+        let cl_bytes_sent = to_int(get_after(10));
+        let srv_bytes_received = to_int(get_after(11));
+        let srv_bytes_sent = to_int(get_after(12));
+        let trxn_index = to_int(get_after(13));
+        let age_seconds = to_int(get_after(14));
+        let status = get_after(15);
+        let in_use = to_int(get_after(16));
+        let mut url = get_after(17);
+
+        // URL이 없으면 마지막 필드에서 찾기
+        if url.is_none() && !parts.is_empty() {
+            if let Some(last) = parts.last() {
+                let last = last.trim();
+                if last.starts_with("http://") || last.starts_with("https://") {
+                    url = Some(last.to_string());
+                }
+            }
+        }
+
+        // Client IP가 있어야 세션으로 인정
+        let client_ip = client_ip?;
+        Some(SessionData {
+            proxy_id: proxy.id,
+            host: proxy.host.clone(),
+            transaction,
+            creation_time,
+            protocol,
+            cust_id,
+            user_name,
+            client_ip,
+            client_side_mwg_ip,
+            server_side_mwg_ip,
+            server_ip,
+            cl_bytes_received,
+            cl_bytes_sent,
+            srv_bytes_received,
+            srv_bytes_sent,
+            trxn_index,
+            age_seconds,
+            status,
+            in_use,
+            url,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_runner::ScriptedRunner;
+
+    const RECORDED: &str = include_str!("../fixtures/mwg_connections.txt");
+
+    fn proxy() -> Proxy {
+        serde_json::from_str(r#"{"id": 7, "host": "10.0.0.1", "port": 22, "username": "u", "password": "p", "group": "g"}"#).unwrap()
+    }
+
+    #[test]
+    fn parser_reads_recorded_connections_output() {
+        let proxy = proxy();
+        let mut parser = SessionParser::new(&proxy);
+        let sessions: Vec<SessionData> = RECORDED.lines().filter_map(|line| parser.parse_line(line)).collect();
+
+        // 요약/헤더/빈 줄과 Client IP가 없는 줄은 건너뜀
+        assert_eq!(sessions.len(), 3);
+        let first = &sessions[0];
+        assert_eq!((first.proxy_id, first.host.as_str()), (7, "10.0.0.1"));
+        assert_eq!(first.transaction.as_deref(), Some("0x1a2b3c"));
+        assert_eq!(first.creation_time.unwrap().format("%H:%M:%S").to_string(), "09:12:01");
+        assert_eq!(first.client_ip, "10.1.2.3"); // 포트 제거
+        assert_eq!(first.user_name.as_deref(), Some("alice"));
+        assert_eq!((first.cl_bytes_received, first.srv_bytes_sent), (Some(1200), Some(1100)));
+        assert_eq!((first.age_seconds, first.in_use), (Some(42), Some(1)));
+        assert_eq!(first.url.as_deref(), Some("https://www.example.com/"));
+
+        // 생성 시간이 한 칸 뒤에 있으면 이후 필드도 한 칸씩 밀어서 읽음
+        let shifted = &sessions[2];
+        assert_eq!(shifted.protocol.as_deref(), Some("HTTPS"));
+        assert_eq!(shifted.cust_id, None);
+        assert_eq!(shifted.user_name.as_deref(), Some("carol"));
+        assert_eq!(shifted.client_ip, "10.1.2.5");
+        assert_eq!(shifted.status.as_deref(), Some("CONNECTING"));
+        assert_eq!(shifted.url, None);
+
+        // 요약 줄이 없으면 첫 줄이 헤더, 헤더가 아닌 줄은 데이터로 처리
+        let mut parser = SessionParser::new(&proxy);
+        let without_summary: Vec<_> = RECORDED.lines().skip(1).filter_map(|line| parser.parse_line(line)).collect();
+        assert_eq!(without_summary.len(), 3);
+        let mut parser = SessionParser::new(&proxy);
+        assert!(RECORDED.lines().skip(2).filter_map(|line| parser.parse_line(line)).next().is_some());
+    }
+
+    #[tokio::test]
+    async fn query_multiple_runs_mwg_command_and_counts_rows() {
+        let runner = Arc::new(ScriptedRunner::new().respond("mwg-core -S connections", RECORDED));
+        let rows = Arc::new(AtomicUsize::new(0));
+        let collector = SessionCollector::new(SessionBrowserConfig::default())
+            .with_runner(runner.clone())
+            .with_row_counter(rows.clone());
+
+        let mut second = proxy();
+        second.id = 8;
+        let result = collector.query_multiple(&[proxy(), second]).await.unwrap();
+        assert!(result.failures.is_empty());
+        let sessions = result.sessions;
+        assert_eq!(sessions.len(), 6);
+        assert_eq!(sessions.iter().filter(|s| s.proxy_id == 8).count(), 3);
+        assert_eq!(rows.load(Ordering::Relaxed), 6);
+        assert_eq!(runner.executed(), vec!["/opt/mwg/bin/mwg-core -S connections"; 2]);
+
+        // 개별 프록시 실패는 호스트별 오류로 모으고, 취소되면 결과 대신 취소 오류
+        let failing = SessionCollector::new(SessionBrowserConfig::default())
+            .with_runner(Arc::new(ScriptedRunner::new().fail("mwg-core", "permission denied")));
+        assert!(failing.query_sessions(&proxy()).await.unwrap_err().to_string().contains("mwg-core"));
+        let result = failing.query_multiple(&[proxy()]).await.unwrap();
+        assert!(result.sessions.is_empty());
+        assert_eq!(result.failures.len(), 1);
+        let summary = result.failure_summary();
+        assert!(summary.starts_with(&format!("{}: SSH 명령어 실행 실패", proxy().host)), "{}", summary);
+        assert!(summary.contains("permission denied"), "{}", summary);
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let cancelled = collector.clone().with_cancel_token(cancel);
+        let err = cancelled.query_multiple(&[proxy()]).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<SshError>(), Some(SshError::Cancelled)));
+    }
+}
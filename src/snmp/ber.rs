@@ -0,0 +1,259 @@
+use anyhow::{bail, Result};
+
+/// ASN.1 / SNMP 타입 태그
+pub const TYPE_INTEGER: u8 = 0x02;
+pub const TYPE_OCTETSTRING: u8 = 0x04;
+pub const TYPE_NULL: u8 = 0x05;
+pub const TYPE_OBJECTIDENTIFIER: u8 = 0x06;
+pub const TYPE_SEQUENCE: u8 = 0x30;
+pub const TYPE_IPADDRESS: u8 = 0x40;
+pub const TYPE_COUNTER32: u8 = 0x41;
+pub const TYPE_UNSIGNED32: u8 = 0x42;
+pub const TYPE_TIMETICKS: u8 = 0x43;
+pub const TYPE_OPAQUE: u8 = 0x44;
+pub const TYPE_COUNTER64: u8 = 0x46;
+pub const TYPE_NOSUCHOBJECT: u8 = 0x80;
+pub const TYPE_NOSUCHINSTANCE: u8 = 0x81;
+pub const TYPE_ENDOFMIBVIEW: u8 = 0x82;
+
+/// BER 길이 필드를 인코딩합니다.
+fn encode_length(len: usize, out: &mut Vec<u8>) {
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = bytes.iter().take_while(|&&b| b == 0).count();
+        out.push(0x80 | (bytes.len() - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+}
+
+/// 태그와 값으로 TLV를 만듭니다.
+pub fn encode_tlv(tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(value.len() + 4);
+    out.push(tag);
+    encode_length(value.len(), &mut out);
+    out.extend_from_slice(value);
+    out
+}
+
+/// 여러 TLV를 하나의 구조체(SEQUENCE, PDU 등)로 묶습니다.
+pub fn encode_constructed(tag: u8, parts: &[Vec<u8>]) -> Vec<u8> {
+    encode_tlv(tag, &parts.concat())
+}
+
+pub fn encode_integer(n: i64) -> Vec<u8> {
+    let bytes = n.to_be_bytes();
+    // 부호를 유지하는 최소 길이로 자르기
+    let mut start = 0;
+    while start < 7 {
+        let (cur, next) = (bytes[start], bytes[start + 1]);
+        if (cur == 0x00 && next & 0x80 == 0) || (cur == 0xff && next & 0x80 != 0) {
+            start += 1;
+        } else {
+            break;
+        }
+    }
+    encode_tlv(TYPE_INTEGER, &bytes[start..])
+}
+
+/// Counter32/Gauge32/TimeTicks/Counter64 같은 부호 없는 정수를 인코딩합니다.
+pub fn encode_unsigned(tag: u8, n: u64) -> Vec<u8> {
+    let bytes = n.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    let mut value = Vec::with_capacity(9);
+    if bytes[skip] & 0x80 != 0 {
+        value.push(0);
+    }
+    value.extend_from_slice(&bytes[skip..]);
+    encode_tlv(tag, &value)
+}
+
+pub fn encode_octet_string(bytes: &[u8]) -> Vec<u8> {
+    encode_tlv(TYPE_OCTETSTRING, bytes)
+}
+
+pub fn encode_null() -> Vec<u8> {
+    vec![TYPE_NULL, 0]
+}
+
+pub fn encode_oid(oid: &[u32]) -> Result<Vec<u8>> {
+    if oid.len() < 2 || oid[0] > 2 || (oid[0] < 2 && oid[1] >= 40) {
+        bail!("Invalid OID: {}", format_oid(oid));
+    }
+    let mut value = Vec::new();
+    let mut push_subid = |subid: u64| {
+        let mut chunk = [0u8; 10];
+        let mut i = chunk.len();
+        let mut rest = subid;
+        loop {
+            i -= 1;
+            chunk[i] = (rest & 0x7f) as u8 | if i == chunk.len() - 1 { 0 } else { 0x80 };
+            rest >>= 7;
+            if rest == 0 {
+                break;
+            }
+        }
+        value.extend_from_slice(&chunk[i..]);
+    };
+    push_subid(oid[0] as u64 * 40 + oid[1] as u64);
+    for &subid in &oid[2..] {
+        push_subid(subid as u64);
+    }
+    Ok(encode_tlv(TYPE_OBJECTIDENTIFIER, &value))
+}
+
+pub fn decode_integer(bytes: &[u8]) -> Result<i64> {
+    if bytes.is_empty() || bytes.len() > 8 {
+        bail!("Invalid INTEGER length: {}", bytes.len());
+    }
+    let mut n: i64 = if bytes[0] & 0x80 != 0 { -1 } else { 0 };
+    for &b in bytes {
+        n = (n << 8) | b as i64;
+    }
+    Ok(n)
+}
+
+pub fn decode_unsigned(bytes: &[u8]) -> Result<u64> {
+    // 선행 0 바이트 하나는 부호 비트를 위한 것이므로 허용
+    let bytes = if bytes.len() == 9 && bytes[0] == 0 { &bytes[1..] } else { bytes };
+    if bytes.is_empty() || bytes.len() > 8 {
+        bail!("Invalid unsigned integer length: {}", bytes.len());
+    }
+    Ok(bytes.iter().fold(0u64, |n, &b| (n << 8) | b as u64))
+}
+
+pub fn decode_oid(bytes: &[u8]) -> Result<Vec<u32>> {
+    if bytes.is_empty() {
+        bail!("Empty OBJECT IDENTIFIER");
+    }
+    let mut subids = Vec::new();
+    let mut current: u64 = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        current = (current << 7) | (b & 0x7f) as u64;
+        if current > u32::MAX as u64 * 40 {
+            bail!("OBJECT IDENTIFIER sub-identifier overflow");
+        }
+        if b & 0x80 == 0 {
+            if subids.is_empty() {
+                let first = (current / 40).min(2);
+                subids.push(first as u32);
+                subids.push((current - first * 40) as u32);
+            } else {
+                subids.push(current as u32);
+            }
+            current = 0;
+        } else if i == bytes.len() - 1 {
+            bail!("Truncated OBJECT IDENTIFIER");
+        }
+    }
+    Ok(subids)
+}
+
+/// OID를 점 표기 문자열로 변환합니다.
+pub fn format_oid(oid: &[u32]) -> String {
+    oid.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(".")
+}
+
+/// 디코딩된 TLV 하나
+pub struct Tlv<'a> {
+    pub tag: u8,
+    /// 전체 메시지 기준 값(value) 시작 위치
+    pub offset: usize,
+    pub value: &'a [u8],
+    /// 태그와 길이를 포함한 TLV 전체
+    pub raw: &'a [u8],
+}
+
+/// BER 디코더. 하위 구조체를 읽어도 오프셋은 항상 전체 메시지 기준입니다.
+pub struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0, end: data.len() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.end
+    }
+
+    pub fn peek_tag(&self) -> Option<u8> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.data[self.pos])
+        }
+    }
+
+    fn read_byte(&mut self) -> Result<u8> {
+        if self.pos >= self.end {
+            bail!("Unexpected end of BER data");
+        }
+        let b = self.data[self.pos];
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn read_length(&mut self) -> Result<usize> {
+        let first = self.read_byte()?;
+        if first & 0x80 == 0 {
+            return Ok(first as usize);
+        }
+        let num_bytes = (first & 0x7f) as usize;
+        if num_bytes == 0 || num_bytes > std::mem::size_of::<usize>() {
+            bail!("Unsupported BER length encoding");
+        }
+        let mut len = 0usize;
+        for _ in 0..num_bytes {
+            len = (len << 8) | self.read_byte()? as usize;
+        }
+        Ok(len)
+    }
+
+    pub fn read_tlv(&mut self) -> Result<Tlv<'a>> {
+        let start = self.pos;
+        let tag = self.read_byte()?;
+        let len = self.read_length()?;
+        if len > self.end - self.pos {
+            bail!("BER length {} exceeds remaining data", len);
+        }
+        let offset = self.pos;
+        self.pos += len;
+        Ok(Tlv {
+            tag,
+            offset,
+            value: &self.data[offset..offset + len],
+            raw: &self.data[start..offset + len],
+        })
+    }
+
+    pub fn read_expected(&mut self, tag: u8) -> Result<Tlv<'a>> {
+        let tlv = self.read_tlv()?;
+        if tlv.tag != tag {
+            bail!("Unexpected BER tag 0x{:02x} (expected 0x{:02x})", tlv.tag, tag);
+        }
+        Ok(tlv)
+    }
+
+    /// 구조체 TLV를 읽고 그 내부를 읽는 Reader를 반환합니다.
+    pub fn read_constructed(&mut self, tag: u8) -> Result<Reader<'a>> {
+        let tlv = self.read_expected(tag)?;
+        Ok(Reader { data: self.data, pos: tlv.offset, end: tlv.offset + tlv.value.len() })
+    }
+
+    pub fn read_integer(&mut self) -> Result<i64> {
+        decode_integer(self.read_expected(TYPE_INTEGER)?.value)
+    }
+
+    pub fn read_octet_string(&mut self) -> Result<&'a [u8]> {
+        Ok(self.read_expected(TYPE_OCTETSTRING)?.value)
+    }
+
+    pub fn read_oid(&mut self) -> Result<Vec<u32>> {
+        decode_oid(self.read_expected(TYPE_OBJECTIDENTIFIER)?.value)
+    }
+}
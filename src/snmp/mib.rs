@@ -0,0 +1,207 @@
+//! 설정 파일에서 사용할 수 있는 MIB 심볼 이름 (UCD-SNMP-MIB, IF-MIB, HOST-RESOURCES-MIB, MWG 엔터프라이스 MIB 일부)
+//!
+//! `UCD-SNMP-MIB::ssCpuIdle.0`, `IF-MIB::ifHCInOctets.3` 같은 이름이나 모듈을 생략한 `ssCpuIdle.0`,
+//! 숫자 OID(`1.3.6.1.4.1.2021.11.11.0`)를 받아 숫자 OID로 변환합니다.
+
+use super::ber::format_oid;
+use anyhow::{bail, Result};
+
+/// (모듈, 객체 이름, OID)
+const MIB_OBJECTS: &[(&str, &str, &str)] = &[
+    // SNMPv2-MIB system 그룹
+    ("SNMPv2-MIB", "sysDescr", "1.3.6.1.2.1.1.1"),
+    ("SNMPv2-MIB", "sysObjectID", "1.3.6.1.2.1.1.2"),
+    ("SNMPv2-MIB", "sysUpTime", "1.3.6.1.2.1.1.3"),
+    ("SNMPv2-MIB", "sysContact", "1.3.6.1.2.1.1.4"),
+    ("SNMPv2-MIB", "sysName", "1.3.6.1.2.1.1.5"),
+    ("SNMPv2-MIB", "sysLocation", "1.3.6.1.2.1.1.6"),
+    // IF-MIB ifTable
+    ("IF-MIB", "ifNumber", "1.3.6.1.2.1.2.1"),
+    ("IF-MIB", "ifIndex", "1.3.6.1.2.1.2.2.1.1"),
+    ("IF-MIB", "ifDescr", "1.3.6.1.2.1.2.2.1.2"),
+    ("IF-MIB", "ifType", "1.3.6.1.2.1.2.2.1.3"),
+    ("IF-MIB", "ifMtu", "1.3.6.1.2.1.2.2.1.4"),
+    ("IF-MIB", "ifSpeed", "1.3.6.1.2.1.2.2.1.5"),
+    ("IF-MIB", "ifPhysAddress", "1.3.6.1.2.1.2.2.1.6"),
+    ("IF-MIB", "ifAdminStatus", "1.3.6.1.2.1.2.2.1.7"),
+    ("IF-MIB", "ifOperStatus", "1.3.6.1.2.1.2.2.1.8"),
+    ("IF-MIB", "ifLastChange", "1.3.6.1.2.1.2.2.1.9"),
+    ("IF-MIB", "ifInOctets", "1.3.6.1.2.1.2.2.1.10"),
+    ("IF-MIB", "ifInUcastPkts", "1.3.6.1.2.1.2.2.1.11"),
+    ("IF-MIB", "ifInNUcastPkts", "1.3.6.1.2.1.2.2.1.12"),
+    ("IF-MIB", "ifInDiscards", "1.3.6.1.2.1.2.2.1.13"),
+    ("IF-MIB", "ifInErrors", "1.3.6.1.2.1.2.2.1.14"),
+    ("IF-MIB", "ifInUnknownProtos", "1.3.6.1.2.1.2.2.1.15"),
+    ("IF-MIB", "ifOutOctets", "1.3.6.1.2.1.2.2.1.16"),
+    ("IF-MIB", "ifOutUcastPkts", "1.3.6.1.2.1.2.2.1.17"),
+    ("IF-MIB", "ifOutNUcastPkts", "1.3.6.1.2.1.2.2.1.18"),
+    ("IF-MIB", "ifOutDiscards", "1.3.6.1.2.1.2.2.1.19"),
+    ("IF-MIB", "ifOutErrors", "1.3.6.1.2.1.2.2.1.20"),
+    ("IF-MIB", "ifOutQLen", "1.3.6.1.2.1.2.2.1.21"),
+    // IF-MIB ifXTable
+    ("IF-MIB", "ifName", "1.3.6.1.2.1.31.1.1.1.1"),
+    ("IF-MIB", "ifInMulticastPkts", "1.3.6.1.2.1.31.1.1.1.2"),
+    ("IF-MIB", "ifInBroadcastPkts", "1.3.6.1.2.1.31.1.1.1.3"),
+    ("IF-MIB", "ifOutMulticastPkts", "1.3.6.1.2.1.31.1.1.1.4"),
+    ("IF-MIB", "ifOutBroadcastPkts", "1.3.6.1.2.1.31.1.1.1.5"),
+    ("IF-MIB", "ifHCInOctets", "1.3.6.1.2.1.31.1.1.1.6"),
+    ("IF-MIB", "ifHCInUcastPkts", "1.3.6.1.2.1.31.1.1.1.7"),
+    ("IF-MIB", "ifHCInMulticastPkts", "1.3.6.1.2.1.31.1.1.1.8"),
+    ("IF-MIB", "ifHCInBroadcastPkts", "1.3.6.1.2.1.31.1.1.1.9"),
+    ("IF-MIB", "ifHCOutOctets", "1.3.6.1.2.1.31.1.1.1.10"),
+    ("IF-MIB", "ifHCOutUcastPkts", "1.3.6.1.2.1.31.1.1.1.11"),
+    ("IF-MIB", "ifHCOutMulticastPkts", "1.3.6.1.2.1.31.1.1.1.12"),
+    ("IF-MIB", "ifHCOutBroadcastPkts", "1.3.6.1.2.1.31.1.1.1.13"),
+    ("IF-MIB", "ifHighSpeed", "1.3.6.1.2.1.31.1.1.1.15"),
+    ("IF-MIB", "ifAlias", "1.3.6.1.2.1.31.1.1.1.18"),
+    ("IF-MIB", "linkDown", "1.3.6.1.6.3.1.1.5.3"),
+    ("IF-MIB", "linkUp", "1.3.6.1.6.3.1.1.5.4"),
+    // HOST-RESOURCES-MIB
+    ("HOST-RESOURCES-MIB", "hrSystemUptime", "1.3.6.1.2.1.25.1.1"),
+    ("HOST-RESOURCES-MIB", "hrSystemNumUsers", "1.3.6.1.2.1.25.1.5"),
+    ("HOST-RESOURCES-MIB", "hrSystemProcesses", "1.3.6.1.2.1.25.1.6"),
+    ("HOST-RESOURCES-MIB", "hrSystemMaxProcesses", "1.3.6.1.2.1.25.1.7"),
+    ("HOST-RESOURCES-MIB", "hrMemorySize", "1.3.6.1.2.1.25.2.2"),
+    ("HOST-RESOURCES-MIB", "hrStorageIndex", "1.3.6.1.2.1.25.2.3.1.1"),
+    ("HOST-RESOURCES-MIB", "hrStorageType", "1.3.6.1.2.1.25.2.3.1.2"),
+    ("HOST-RESOURCES-MIB", "hrStorageDescr", "1.3.6.1.2.1.25.2.3.1.3"),
+    ("HOST-RESOURCES-MIB", "hrStorageAllocationUnits", "1.3.6.1.2.1.25.2.3.1.4"),
+    ("HOST-RESOURCES-MIB", "hrStorageSize", "1.3.6.1.2.1.25.2.3.1.5"),
+    ("HOST-RESOURCES-MIB", "hrStorageUsed", "1.3.6.1.2.1.25.2.3.1.6"),
+    ("HOST-RESOURCES-MIB", "hrStorageAllocationFailures", "1.3.6.1.2.1.25.2.3.1.7"),
+    ("HOST-RESOURCES-MIB", "hrDeviceIndex", "1.3.6.1.2.1.25.3.2.1.1"),
+    ("HOST-RESOURCES-MIB", "hrDeviceType", "1.3.6.1.2.1.25.3.2.1.2"),
+    ("HOST-RESOURCES-MIB", "hrDeviceDescr", "1.3.6.1.2.1.25.3.2.1.3"),
+    ("HOST-RESOURCES-MIB", "hrDeviceStatus", "1.3.6.1.2.1.25.3.2.1.5"),
+    ("HOST-RESOURCES-MIB", "hrDeviceErrors", "1.3.6.1.2.1.25.3.2.1.6"),
+    ("HOST-RESOURCES-MIB", "hrProcessorLoad", "1.3.6.1.2.1.25.3.3.1.2"),
+    ("HOST-RESOURCES-MIB", "hrSWRunIndex", "1.3.6.1.2.1.25.4.2.1.1"),
+    ("HOST-RESOURCES-MIB", "hrSWRunName", "1.3.6.1.2.1.25.4.2.1.2"),
+    ("HOST-RESOURCES-MIB", "hrSWRunPath", "1.3.6.1.2.1.25.4.2.1.4"),
+    ("HOST-RESOURCES-MIB", "hrSWRunParameters", "1.3.6.1.2.1.25.4.2.1.5"),
+    ("HOST-RESOURCES-MIB", "hrSWRunStatus", "1.3.6.1.2.1.25.4.2.1.7"),
+    ("HOST-RESOURCES-MIB", "hrSWRunPerfCPU", "1.3.6.1.2.1.25.5.1.1.1"),
+    ("HOST-RESOURCES-MIB", "hrSWRunPerfMem", "1.3.6.1.2.1.25.5.1.1.2"),
+    // UCD-SNMP-MIB prTable
+    ("UCD-SNMP-MIB", "prIndex", "1.3.6.1.4.1.2021.2.1.1"),
+    ("UCD-SNMP-MIB", "prNames", "1.3.6.1.4.1.2021.2.1.2"),
+    ("UCD-SNMP-MIB", "prMin", "1.3.6.1.4.1.2021.2.1.3"),
+    ("UCD-SNMP-MIB", "prMax", "1.3.6.1.4.1.2021.2.1.4"),
+    ("UCD-SNMP-MIB", "prCount", "1.3.6.1.4.1.2021.2.1.5"),
+    // UCD-SNMP-MIB memory
+    ("UCD-SNMP-MIB", "memTotalSwap", "1.3.6.1.4.1.2021.4.3"),
+    ("UCD-SNMP-MIB", "memAvailSwap", "1.3.6.1.4.1.2021.4.4"),
+    ("UCD-SNMP-MIB", "memTotalReal", "1.3.6.1.4.1.2021.4.5"),
+    ("UCD-SNMP-MIB", "memAvailReal", "1.3.6.1.4.1.2021.4.6"),
+    ("UCD-SNMP-MIB", "memTotalFree", "1.3.6.1.4.1.2021.4.11"),
+    ("UCD-SNMP-MIB", "memMinimumSwap", "1.3.6.1.4.1.2021.4.12"),
+    ("UCD-SNMP-MIB", "memShared", "1.3.6.1.4.1.2021.4.13"),
+    ("UCD-SNMP-MIB", "memBuffer", "1.3.6.1.4.1.2021.4.14"),
+    ("UCD-SNMP-MIB", "memCached", "1.3.6.1.4.1.2021.4.15"),
+    // UCD-SNMP-MIB dskTable
+    ("UCD-SNMP-MIB", "dskIndex", "1.3.6.1.4.1.2021.9.1.1"),
+    ("UCD-SNMP-MIB", "dskPath", "1.3.6.1.4.1.2021.9.1.2"),
+    ("UCD-SNMP-MIB", "dskDevice", "1.3.6.1.4.1.2021.9.1.3"),
+    ("UCD-SNMP-MIB", "dskTotal", "1.3.6.1.4.1.2021.9.1.6"),
+    ("UCD-SNMP-MIB", "dskAvail", "1.3.6.1.4.1.2021.9.1.7"),
+    ("UCD-SNMP-MIB", "dskUsed", "1.3.6.1.4.1.2021.9.1.8"),
+    ("UCD-SNMP-MIB", "dskPercent", "1.3.6.1.4.1.2021.9.1.9"),
+    ("UCD-SNMP-MIB", "dskPercentNode", "1.3.6.1.4.1.2021.9.1.10"),
+    // UCD-SNMP-MIB laTable
+    ("UCD-SNMP-MIB", "laIndex", "1.3.6.1.4.1.2021.10.1.1"),
+    ("UCD-SNMP-MIB", "laNames", "1.3.6.1.4.1.2021.10.1.2"),
+    ("UCD-SNMP-MIB", "laLoad", "1.3.6.1.4.1.2021.10.1.3"),
+    ("UCD-SNMP-MIB", "laLoadInt", "1.3.6.1.4.1.2021.10.1.5"),
+    // UCD-SNMP-MIB systemStats
+    ("UCD-SNMP-MIB", "ssSwapIn", "1.3.6.1.4.1.2021.11.3"),
+    ("UCD-SNMP-MIB", "ssSwapOut", "1.3.6.1.4.1.2021.11.4"),
+    ("UCD-SNMP-MIB", "ssIOSent", "1.3.6.1.4.1.2021.11.5"),
+    ("UCD-SNMP-MIB", "ssIOReceive", "1.3.6.1.4.1.2021.11.6"),
+    ("UCD-SNMP-MIB", "ssSysInterrupts", "1.3.6.1.4.1.2021.11.7"),
+    ("UCD-SNMP-MIB", "ssSysContext", "1.3.6.1.4.1.2021.11.8"),
+    ("UCD-SNMP-MIB", "ssCpuUser", "1.3.6.1.4.1.2021.11.9"),
+    ("UCD-SNMP-MIB", "ssCpuSystem", "1.3.6.1.4.1.2021.11.10"),
+    ("UCD-SNMP-MIB", "ssCpuIdle", "1.3.6.1.4.1.2021.11.11"),
+    ("UCD-SNMP-MIB", "ssCpuRawUser", "1.3.6.1.4.1.2021.11.50"),
+    ("UCD-SNMP-MIB", "ssCpuRawNice", "1.3.6.1.4.1.2021.11.51"),
+    ("UCD-SNMP-MIB", "ssCpuRawSystem", "1.3.6.1.4.1.2021.11.52"),
+    ("UCD-SNMP-MIB", "ssCpuRawIdle", "1.3.6.1.4.1.2021.11.53"),
+    ("UCD-SNMP-MIB", "ssCpuRawWait", "1.3.6.1.4.1.2021.11.54"),
+    ("UCD-SNMP-MIB", "ssCpuRawKernel", "1.3.6.1.4.1.2021.11.55"),
+    ("UCD-SNMP-MIB", "ssCpuRawInterrupt", "1.3.6.1.4.1.2021.11.56"),
+    ("UCD-SNMP-MIB", "ssCpuRawSoftIRQ", "1.3.6.1.4.1.2021.11.61"),
+    // MCAFEE-MWG-MIB (Web Gateway 통계, enterprise 1230)
+    ("MCAFEE-MWG-MIB", "mwg", "1.3.6.1.4.1.1230.2.7"),
+    ("MCAFEE-MWG-MIB", "stMalwareDetected", "1.3.6.1.4.1.1230.2.7.2.1.2"),
+    ("MCAFEE-MWG-MIB", "stHttpRequests", "1.3.6.1.4.1.1230.2.7.2.2.1"),
+    ("MCAFEE-MWG-MIB", "stHttpTraffic", "1.3.6.1.4.1.1230.2.7.2.2.3"),
+    ("MCAFEE-MWG-MIB", "stHttpsRequests", "1.3.6.1.4.1.1230.2.7.2.3.1"),
+    ("MCAFEE-MWG-MIB", "stHttpsTraffic", "1.3.6.1.4.1.1230.2.7.2.3.3"),
+    ("MCAFEE-MWG-MIB", "stFtpTraffic", "1.3.6.1.4.1.1230.2.7.2.4.2"),
+    ("MCAFEE-MWG-MIB", "stClientCount", "1.3.6.1.4.1.1230.2.7.2.5.1"),
+    ("MCAFEE-MWG-MIB", "stConnectedSockets", "1.3.6.1.4.1.1230.2.7.2.5.6"),
+];
+
+/// 점으로 구분된 숫자 목록을 파싱합니다. 빈 항목이나 숫자가 아닌 항목이 있으면 None
+fn parse_arcs(arcs: &str) -> Option<Vec<u32>> {
+    arcs.split('.')
+        .map(|arc| arc.parse::<u32>().ok().filter(|_| arc.bytes().all(|b| b.is_ascii_digit())))
+        .collect()
+}
+
+/// 숫자 OID 문자열을 파싱합니다. 앞의 '.'은 허용합니다.
+fn parse_numeric(oid: &str) -> Result<Vec<u32>> {
+    match parse_arcs(oid.strip_prefix('.').unwrap_or(oid)) {
+        Some(parts) if parts.len() >= 2 && parts[0] <= 2 => Ok(parts),
+        _ => bail!("Invalid OID format: {}", oid),
+    }
+}
+
+/// 객체 이름으로 OID를 찾습니다. (모듈을 지정하지 않으면 모든 모듈에서 검색)
+fn lookup(module: Option<&str>, name: &str) -> Result<&'static str> {
+    if let Some(module) = module {
+        if !MIB_OBJECTS.iter().any(|(m, _, _)| *m == module) {
+            bail!("알 수 없는 MIB 모듈: {}", module);
+        }
+    }
+    MIB_OBJECTS
+        .iter()
+        .find(|(m, n, _)| *n == name && module.is_none_or(|module| *m == module))
+        .map(|(_, _, oid)| *oid)
+        .ok_or_else(|| match module {
+            Some(module) => anyhow::anyhow!("{}에 {} 객체가 없습니다", module, name),
+            None => anyhow::anyhow!("알 수 없는 MIB 이름: {}", name),
+        })
+}
+
+/// OID 문자열(숫자 또는 `MODULE::name.index` 형식)을 숫자 배열로 변환합니다.
+pub fn resolve(oid: &str) -> Result<Vec<u32>> {
+    let oid = oid.trim();
+    if oid.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return parse_numeric(oid);
+    }
+
+    let (module, symbol) = match oid.split_once("::") {
+        Some((module, symbol)) => (Some(module), symbol),
+        None => (None, oid),
+    };
+    let (name, index) = match symbol.split_once('.') {
+        Some((name, index)) => (name, Some(index)),
+        None => (symbol, None),
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        bail!("Invalid OID format: {}", oid);
+    }
+
+    let mut resolved = parse_numeric(lookup(module, name)?)?;
+    if let Some(index) = index {
+        let suffix = parse_arcs(index).ok_or_else(|| anyhow::anyhow!("Invalid OID index: {}", oid))?;
+        resolved.extend(suffix);
+    }
+    Ok(resolved)
+}
+
+/// OID 문자열을 숫자 OID 문자열(`1.3.6...`)로 변환합니다. 설정 파일 검증에 사용합니다.
+pub fn normalize(oid: &str) -> Result<String> {
+    Ok(format_oid(&resolve(oid)?))
+}
@@ -0,0 +1,639 @@
+mod ber;
+pub mod mib;
+mod pdu;
+mod simulator;
+mod transport;
+mod trap;
+mod usm;
+
+#[cfg(test)]
+mod tests;
+
+use anyhow::{bail, Context, Result};
+use pdu::{Pdu, ScopedPdu, FLAG_REPORTABLE, MSG_GET, MSG_REPORT};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use usm::{EngineState, UsmReport, UsmUser};
+
+pub use pdu::Value;
+pub use simulator::{SimulatorFixture, SnmpSimulator};
+pub use transport::SnmpTransport;
+pub use trap::{TrapConfig, TrapEvent, TrapReceiver, TrapSeverity};
+
+/// SNMPv3 인증 프로토콜
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuthProtocol {
+    #[serde(rename = "MD5")]
+    Md5,
+    #[serde(rename = "SHA", alias = "SHA1")]
+    Sha,
+}
+
+/// SNMPv3 프라이버시(암호화) 프로토콜
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PrivProtocol {
+    #[serde(rename = "DES")]
+    Des,
+    #[serde(rename = "AES", alias = "AES128")]
+    Aes,
+}
+
+/// SNMPv3 USM 사용자 설정
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnmpV3Config {
+    pub username: String,
+    pub auth_protocol: Option<AuthProtocol>,
+    pub auth_password: Option<String>,
+    pub priv_protocol: Option<PrivProtocol>,
+    pub priv_password: Option<String>,
+    pub context_name: Option<String>,
+}
+
+impl SnmpV3Config {
+    /// 보안 수준 조합과 비밀번호를 검증합니다.
+    pub fn validate(&self) -> Result<()> {
+        if self.username.is_empty() {
+            bail!("SNMPv3 username is empty");
+        }
+        if self.priv_protocol.is_some() && self.auth_protocol.is_none() {
+            bail!("SNMPv3 privacy requires an auth protocol (user: {})", self.username);
+        }
+        // RFC 3414: 비밀번호는 최소 8자 (env:/cmd:/vault: 참조는 실제 값으로 바꾼 뒤 다시 확인)
+        let check_password = |kind: &str, password: &Option<String>| -> Result<()> {
+            match password {
+                None => bail!("SNMPv3 {} password is missing (user: {})", kind, self.username),
+                Some(p) if p.len() < 8 && !crate::credential::is_reference(p) => {
+                    bail!("SNMPv3 {} password must be at least 8 characters (user: {})", kind, self.username)
+                }
+                Some(_) => Ok(()),
+            }
+        };
+        if self.auth_protocol.is_some() {
+            check_password("auth", &self.auth_password)?;
+        }
+        if self.priv_protocol.is_some() {
+            check_password("priv", &self.priv_password)?;
+        }
+        Ok(())
+    }
+
+    /// 보안 수준 이름 (noAuthNoPriv / authNoPriv / authPriv)
+    pub fn security_level(&self) -> &'static str {
+        match (self.auth_protocol, self.priv_protocol) {
+            (None, _) => "noAuthNoPriv",
+            (Some(_), None) => "authNoPriv",
+            (Some(_), Some(_)) => "authPriv",
+        }
+    }
+}
+
+/// SNMP 버전 (설정 파일의 "2c" / "3")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnmpVersion {
+    #[serde(rename = "2c", alias = "v2c")]
+    V2c,
+    #[serde(rename = "3", alias = "v3")]
+    V3,
+}
+
+/// SNMP 인증 정보 (v2c 커뮤니티 또는 v3 USM 사용자)
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnmpCredentials {
+    V2c(String),
+    V3(SnmpV3Config),
+}
+
+impl SnmpCredentials {
+    /// 로그용 요약 (비밀번호는 포함하지 않음)
+    pub fn describe(&self) -> String {
+        match self {
+            SnmpCredentials::V2c(community) => format!("v2c community={}", community),
+            SnmpCredentials::V3(config) => {
+                format!("v3 user={} level={}", config.username, config.security_level())
+            }
+        }
+    }
+
+    /// 커뮤니티와 v3 비밀번호의 `env:`/`cmd:`/`vault:` 참조를 실제 값으로 바꿉니다.
+    /// (블로킹 - `crate::credential::resolve` 참고)
+    pub fn resolve_secrets(self) -> Result<Self> {
+        match self {
+            SnmpCredentials::V2c(community) => Ok(SnmpCredentials::V2c(crate::credential::resolve(&community)?)),
+            SnmpCredentials::V3(mut config) => {
+                for password in [&mut config.auth_password, &mut config.priv_password].into_iter().flatten() {
+                    *password = crate::credential::resolve(password)?;
+                }
+                Ok(SnmpCredentials::V3(config))
+            }
+        }
+    }
+}
+
+/// 호스트와 포트를 에이전트 주소로 합칩니다. (대괄호 없는 IPv6 주소는 `[addr]:port`)
+pub fn agent_address(host: &str, port: u16) -> String {
+    if !host.starts_with('[') && host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+/// SNMP 요청 타임아웃/재시도 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnmpOptions {
+    /// 첫 시도의 응답 대기 시간
+    pub timeout: Duration,
+    /// 응답이 없을 때 재전송 횟수
+    pub retries: u32,
+    /// 재시도마다 대기 시간에 곱하는 값
+    pub backoff: f64,
+}
+
+impl Default for SnmpOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(2),
+            retries: 1,
+            backoff: 2.0,
+        }
+    }
+}
+
+impl SnmpOptions {
+    /// 모든 재시도를 포함한 요청 하나의 최대 대기 시간
+    pub fn max_duration(&self) -> Duration {
+        let mut wait = self.timeout;
+        let mut total = Duration::ZERO;
+        for _ in 0..=self.retries {
+            total += wait;
+            wait = wait.mul_f64(self.backoff.max(1.0));
+        }
+        total
+    }
+}
+
+/// 요청 하나에 담는 최대 varbind 수 (너무 크면 tooBig이 발생할 수 있음)
+const MAX_VARBINDS_PER_REQUEST: usize = 32;
+
+/// 요청 ID 카운터 (프로세스마다 다른 값에서 시작)
+fn next_request_id() -> i32 {
+    static REQUEST_ID: OnceLock<AtomicI32> = OnceLock::new();
+    REQUEST_ID
+        .get_or_init(|| {
+            let seed = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or(1);
+            AtomicI32::new((seed & 0x3fff_ffff) as i32)
+        })
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| {
+            Some(if id >= i32::MAX - 1 { 1 } else { id + 1 })
+        })
+        .unwrap_or(1)
+}
+
+/// SNMP 클라이언트 (SNMP v2c / v3)
+///
+/// 별도 트랜스포트를 지정하지 않으면 프로세스 공유 소켓(`SnmpTransport::shared`)을 사용합니다.
+pub struct SnmpClient {
+    credentials: SnmpCredentials,
+    options: SnmpOptions,
+    transport: Option<Arc<SnmpTransport>>,
+}
+
+impl SnmpClient {
+    #[cfg(test)]
+    pub fn new(community: String) -> Self {
+        Self::with_credentials(SnmpCredentials::V2c(community))
+    }
+
+    pub fn with_credentials(credentials: SnmpCredentials) -> Self {
+        Self {
+            credentials,
+            options: SnmpOptions::default(),
+            transport: None,
+        }
+    }
+
+    pub fn with_options(mut self, options: SnmpOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = timeout;
+        self
+    }
+
+    pub fn with_transport(mut self, transport: Arc<SnmpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    async fn transport(&self) -> Result<Arc<SnmpTransport>> {
+        match &self.transport {
+            Some(transport) => Ok(transport.clone()),
+            None => SnmpTransport::shared().await,
+        }
+    }
+
+    /// OID 문자열을 u32 배열로 변환 (숫자 OID 또는 `MODULE::name.index`)
+    fn parse_oid(oid: &str) -> Result<Vec<u32>> {
+        mib::resolve(oid)
+    }
+
+    /// 호스트 주소에 포트 추가 (없으면 기본값 161)
+    /// IPv6는 `[addr]:port`로 포트를 붙이고, 대괄호 없는 주소는 포트가 없는 것으로 봅니다.
+    fn agent_addr(host: &str) -> String {
+        let has_port = match host.strip_prefix('[') {
+            Some(rest) => rest.contains("]:"),
+            None => host.matches(':').count() == 1,
+        };
+        if has_port {
+            host.to_string()
+        } else {
+            agent_address(host, 161)
+        }
+    }
+
+    fn error_status_name(error_status: u32) -> &'static str {
+        match error_status {
+            1 => "tooBig",
+            2 => "noSuchName",
+            3 => "badValue",
+            4 => "readOnly",
+            5 => "genErr",
+            _ => "unknown",
+        }
+    }
+
+    /// SNMP GET 요청을 보내고 값을 반환합니다.
+    #[cfg(test)]
+    pub async fn get(&self, host: &str, oid: &str) -> Result<f64> {
+        self.get_many(host, &[oid.to_string()])
+            .await?
+            .pop()
+            .unwrap_or_else(|| Err(anyhow::anyhow!("Empty SNMP response from {} for OID {}", host, oid)))
+    }
+
+    /// 여러 OID를 multi-varbind GET으로 조회합니다.
+    /// 결과는 `oids`와 같은 순서이며, varbind별 오류(noSuchObject 등)는 해당 항목에만 반영됩니다.
+    /// 요청 자체가 실패(타임아웃, 인증 오류 등)하면 Err를 반환합니다.
+    #[cfg(test)]
+    pub async fn get_many(&self, host: &str, oids: &[String]) -> Result<Vec<Result<f64>>> {
+        Ok(self
+            .get_many_values(host, oids)
+            .await?
+            .into_iter()
+            .zip(oids)
+            .map(|(result, oid)| result.and_then(|value| Self::value_to_f64(oid, value)))
+            .collect())
+    }
+
+    /// `get_many`와 같지만 숫자로 변환하지 않고 SNMP 값 타입을 그대로 돌려줍니다.
+    /// 카운터 폭(Counter32/Counter64)을 구분해야 하는 트래픽 계산에서 사용합니다.
+    /// noSuchObject/noSuchInstance/endOfMibView/NULL은 해당 항목의 오류로 반환합니다.
+    pub async fn get_many_values(&self, host: &str, oids: &[String]) -> Result<Vec<Result<Value>>> {
+        let agent_addr = Self::agent_addr(host);
+        let mut results: Vec<Option<Result<Value>>> = oids.iter().map(|_| None).collect();
+
+        let mut pending = Vec::new();
+        for (i, oid) in oids.iter().enumerate() {
+            match Self::parse_oid(oid) {
+                Ok(oid_vec) => pending.push((i, oid_vec)),
+                Err(e) => results[i] = Some(Err(e)),
+            }
+        }
+
+        if !pending.is_empty() {
+            let target = transport::resolve(&agent_addr).await?;
+            // tooBig이면 묶음을 나눠 큐에 다시 넣음
+            let mut queue: Vec<Vec<(usize, Vec<u32>)>> =
+                pending.chunks(MAX_VARBINDS_PER_REQUEST).map(|batch| batch.to_vec()).collect();
+            while let Some(batch) = queue.pop() {
+                self.get_batch(target, &agent_addr, oids, batch, &mut results, &mut queue)
+                    .await
+                    .with_context(|| format!("SNMP GET failed for host: {}", host))?;
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .zip(oids)
+            .map(|(result, oid)| {
+                result.unwrap_or_else(|| Err(anyhow::anyhow!("No SNMP value returned for OID {}", oid)))
+            })
+            .collect())
+    }
+
+    /// varbind 묶음 하나를 요청합니다. (index, oid) 목록을 받아 `results[index]`를 채웁니다.
+    async fn get_batch(
+        &self,
+        target: SocketAddr,
+        agent_addr: &str,
+        oids: &[String],
+        mut batch: Vec<(usize, Vec<u32>)>,
+        results: &mut [Option<Result<Value>>],
+        queue: &mut Vec<Vec<(usize, Vec<u32>)>>,
+    ) -> Result<()> {
+        while !batch.is_empty() {
+            let oid_list: Vec<Vec<u32>> = batch.iter().map(|(_, oid)| oid.clone()).collect();
+            let response = self
+                .request(target, agent_addr, |id| Pdu::request(MSG_GET, id, &oid_list))
+                .await?;
+
+            match response.error_status {
+                0 => {
+                    if response.varbinds.len() != batch.len() {
+                        anyhow::bail!(
+                            "SNMP response has {} varbinds, expected {}",
+                            response.varbinds.len(),
+                            batch.len()
+                        );
+                    }
+                    for ((i, _), (_, value)) in batch.iter().zip(response.varbinds) {
+                        results[*i] = Some(Self::check_exception(&oids[*i], value));
+                    }
+                    return Ok(());
+                }
+                // 응답이 너무 크면 절반씩 나눠서 다시 요청
+                1 if batch.len() > 1 => {
+                    let rest = batch.split_off(batch.len() / 2);
+                    queue.push(rest);
+                    queue.push(batch);
+                    return Ok(());
+                }
+                status => {
+                    let error_index = response.error_index as usize;
+                    let describe = |oid: &str| {
+                        anyhow::anyhow!(
+                            "SNMP error for OID {}: {} (error-status: {}, error-index: {})",
+                            oid,
+                            Self::error_status_name(status),
+                            status,
+                            error_index
+                        )
+                    };
+                    if error_index == 0 || error_index > batch.len() {
+                        // 어느 varbind의 오류인지 알 수 없으면 묶음 전체를 실패 처리
+                        for (i, _) in &batch {
+                            results[*i] = Some(Err(describe(&oids[*i])));
+                        }
+                        return Ok(());
+                    }
+                    // error-index(1부터)가 가리키는 varbind만 실패 처리하고 나머지는 다시 요청
+                    let (i, _) = batch.remove(error_index - 1);
+                    results[i] = Some(Err(describe(&oids[i])));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 값이 없음을 나타내는 응답(noSuchObject 등)을 오류로 바꿉니다.
+    fn check_exception(oid: &str, value: Value) -> Result<Value> {
+        match value {
+            Value::Null => anyhow::bail!("OID {} returned NULL value", oid),
+            Value::NoSuchObject => anyhow::bail!("OID {} returned noSuchObject", oid),
+            Value::NoSuchInstance => anyhow::bail!("OID {} returned noSuchInstance", oid),
+            Value::EndOfMibView => anyhow::bail!("OID {} returned endOfMibView", oid),
+            value => Ok(value),
+        }
+    }
+
+    /// 응답 값을 숫자로 변환합니다.
+    pub(crate) fn value_to_f64(oid: &str, value: Value) -> Result<f64> {
+        match value {
+            Value::Integer(i) => Ok(i as f64),
+            Value::Counter32(c) => Ok(c as f64),
+            Value::Unsigned32(u) => Ok(u as f64), // Gauge32는 Unsigned32로 처리
+            Value::Timeticks(t) => Ok(t as f64),
+            Value::Counter64(c) => Ok(c as f64),
+            Value::OctetString(_) => {
+                anyhow::bail!("OID {} returned OctetString, expected numeric value", oid)
+            }
+            _ => {
+                anyhow::bail!("OID {} returned unsupported value type: {:?}", oid, value)
+            }
+        }
+    }
+
+    /// GETNEXT로 OID 하위 트리 전체를 순회합니다.
+    #[cfg(test)]
+    pub async fn walk(&self, host: &str, oid: &str) -> Result<Vec<(Vec<u32>, Value)>> {
+        self.walk_with(host, oid, |id, next| Pdu::request(pdu::MSG_GET_NEXT, id, &[next.to_vec()]))
+            .await
+    }
+
+    /// GETBULK로 OID 하위 트리 전체를 순회합니다. (요청당 최대 `max_repetitions`개)
+    pub async fn bulk_walk(&self, host: &str, oid: &str, max_repetitions: u32) -> Result<Vec<(Vec<u32>, Value)>> {
+        self.walk_with(host, oid, |id, next| Pdu::bulk_request(id, &[next.to_vec()], 0, max_repetitions))
+            .await
+    }
+
+    async fn walk_with(
+        &self,
+        host: &str,
+        oid: &str,
+        make_pdu: impl Fn(i32, &[u32]) -> Pdu,
+    ) -> Result<Vec<(Vec<u32>, Value)>> {
+        let root = Self::parse_oid(oid)
+            .with_context(|| format!("Failed to parse OID: {}", oid))?;
+        let agent_addr = Self::agent_addr(host);
+        let target = transport::resolve(&agent_addr).await?;
+
+        let mut results = Vec::new();
+        let mut next = root.clone();
+        loop {
+            let response = self
+                .request(target, &agent_addr, |id| make_pdu(id, &next))
+                .await
+                .with_context(|| format!("SNMP walk failed for host: {}, OID: {}", host, oid))?;
+
+            // noSuchName은 (v1 방식의) MIB 끝
+            if response.error_status == 2 {
+                return Ok(results);
+            }
+            if response.error_status != 0 {
+                anyhow::bail!(
+                    "SNMP error during walk for host: {}, OID: {}: {} (error-status: {}, error-index: {})",
+                    host,
+                    oid,
+                    Self::error_status_name(response.error_status),
+                    response.error_status,
+                    response.error_index
+                );
+            }
+            if response.varbinds.is_empty() {
+                return Ok(results);
+            }
+
+            for (vb_oid, value) in response.varbinds {
+                // 하위 트리를 벗어나거나 MIB 끝이면 종료
+                if !vb_oid.starts_with(&root) || value == Value::EndOfMibView {
+                    return Ok(results);
+                }
+                // 잘못된 에이전트가 같은 OID를 반복해서 주는 경우 무한 루프 방지
+                if vb_oid <= next {
+                    anyhow::bail!(
+                        "SNMP walk for host: {} returned non-increasing OID {}",
+                        host,
+                        ber::format_oid(&vb_oid)
+                    );
+                }
+                next = vb_oid.clone();
+                results.push((vb_oid, value));
+            }
+        }
+    }
+
+    /// 요청 PDU를 보내고 응답 PDU를 받습니다. `make_pdu`는 요청 ID를 받아 PDU를 만듭니다.
+    async fn request(&self, target: SocketAddr, agent_addr: &str, make_pdu: impl Fn(i32) -> Pdu) -> Result<Pdu> {
+        let transport = self.transport().await?;
+        match &self.credentials {
+            SnmpCredentials::V2c(community) => {
+                let request_id = next_request_id();
+                let message = pdu::encode_community_message(
+                    community.as_bytes(),
+                    &make_pdu(request_id),
+                )?;
+                let response = transport.exchange(target, request_id, &message, &self.options).await?;
+                let (_, _, pdu) = pdu::decode_community_message(&response)?;
+                Ok(pdu)
+            }
+            SnmpCredentials::V3(config) => {
+                self.request_v3(&transport, target, agent_addr, config, &make_pdu).await
+            }
+        }
+    }
+
+    async fn request_v3(
+        &self,
+        transport: &SnmpTransport,
+        target: SocketAddr,
+        agent_addr: &str,
+        config: &SnmpV3Config,
+        make_pdu: &impl Fn(i32) -> Pdu,
+    ) -> Result<Pdu> {
+        config.validate()?;
+
+        // notInTimeWindow / unknownEngineID는 엔진 정보를 갱신하고 한 번만 재시도
+        let mut resynced = false;
+        loop {
+            let engine = match usm::cached_engine(agent_addr) {
+                Some(engine) => engine,
+                None => {
+                    let engine = self.discover_engine(transport, target).await?;
+                    usm::cache_engine(agent_addr, engine.clone());
+                    engine
+                }
+            };
+            let user = UsmUser::localize(config, &engine.engine_id)?;
+
+            let request_id = next_request_id();
+            let scoped = ScopedPdu {
+                context_engine_id: engine.engine_id.clone(),
+                context_name: config.context_name.clone().unwrap_or_default().into_bytes(),
+                pdu: make_pdu(request_id),
+            };
+            let message = usm::seal(
+                &user,
+                &engine.engine_id,
+                engine.boots,
+                engine.current_time(),
+                request_id,
+                FLAG_REPORTABLE,
+                &scoped,
+            )?;
+            let response = transport.exchange(target, request_id, &message, &self.options).await?;
+
+            // 응답은 요청과 같은 보안 수준이어야 함. 더 낮은 수준은 Report(discovery/재동기화 힌트,
+            // 인증 오류 알림)만 받아들이고, 인증하지 못하는 Response는 위조일 수 있으므로 거부
+            let level = pdu::V3Message::decode(&response)?.0.flags & (pdu::FLAG_AUTH | pdu::FLAG_PRIV);
+            let (message, scoped) = if level == user.security_flags() {
+                usm::unseal(&user, &response)?
+            } else {
+                let reply_user = match level {
+                    0 => UsmUser { name: user.name.clone(), auth: None, privacy: None },
+                    pdu::FLAG_AUTH if user.auth.is_some() => UsmUser { privacy: None, ..user },
+                    _ => bail!(
+                        "SNMPv3 reply security level (flags {:#04x}) does not match {} (user: {})",
+                        level,
+                        config.security_level(),
+                        config.username
+                    ),
+                };
+                let (message, scoped) = usm::unseal(&reply_user, &response)?;
+                if scoped.pdu.pdu_type != MSG_REPORT {
+                    bail!(
+                        "SNMPv3 reply rejected: {} response for {} user {}",
+                        if level == 0 { "unauthenticated" } else { "unencrypted" },
+                        config.security_level(),
+                        config.username
+                    );
+                }
+                (message, scoped)
+            };
+
+            if scoped.pdu.pdu_type != MSG_REPORT {
+                return Ok(scoped.pdu);
+            }
+
+            let report = scoped
+                .pdu
+                .varbinds
+                .first()
+                .and_then(|(oid, _)| UsmReport::from_oid(oid));
+            match report {
+                Some(UsmReport::NotInTimeWindow) if !resynced => {
+                    let security = &message.security;
+                    usm::cache_engine(
+                        agent_addr,
+                        EngineState::new(engine.engine_id.clone(), security.engine_boots, security.engine_time),
+                    );
+                    resynced = true;
+                }
+                Some(UsmReport::UnknownEngineId) if !resynced => {
+                    usm::forget_engine(agent_addr);
+                    resynced = true;
+                }
+                Some(report) => {
+                    bail!("SNMPv3 request rejected by agent: {} (user: {})", report.description(), config.username)
+                }
+                None => bail!("SNMPv3 agent returned an unknown report PDU"),
+            }
+        }
+    }
+
+    /// 에이전트의 engineID/boots/time을 알아냅니다. (RFC 3414 4. Discovery)
+    async fn discover_engine(&self, transport: &SnmpTransport, target: SocketAddr) -> Result<EngineState> {
+        let request_id = next_request_id();
+        let scoped = ScopedPdu {
+            context_engine_id: Vec::new(),
+            context_name: Vec::new(),
+            pdu: Pdu::request(MSG_GET, request_id, &[]),
+        };
+        let anonymous = UsmUser { name: Vec::new(), auth: None, privacy: None };
+        let message = usm::seal(&anonymous, &[], 0, 0, request_id, FLAG_REPORTABLE, &scoped)?;
+
+        let response = transport
+            .exchange(target, request_id, &message, &self.options)
+            .await
+            .context("SNMPv3 engine discovery failed")?;
+        let (message, _, _) = pdu::V3Message::decode(&response)?;
+
+        if message.security.engine_id.is_empty() {
+            bail!("SNMPv3 engine discovery failed: agent returned an empty engine ID");
+        }
+        Ok(EngineState::new(
+            message.security.engine_id,
+            message.security.engine_boots,
+            message.security.engine_time,
+        ))
+    }
+}
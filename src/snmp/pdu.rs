@@ -0,0 +1,468 @@
+use super::ber::{self, Reader};
+use anyhow::{bail, Context, Result};
+
+/// PDU 타입 태그
+pub const MSG_GET: u8 = 0xa0;
+pub const MSG_GET_NEXT: u8 = 0xa1;
+pub const MSG_RESPONSE: u8 = 0xa2;
+pub const MSG_TRAP_V1: u8 = 0xa4;
+pub const MSG_GET_BULK: u8 = 0xa5;
+pub const MSG_INFORM: u8 = 0xa6;
+pub const MSG_TRAP: u8 = 0xa7;
+pub const MSG_REPORT: u8 = 0xa8;
+
+/// 메시지 버전 필드 값
+pub const VERSION_1: i64 = 0;
+pub const VERSION_2C: i64 = 1;
+pub const VERSION_3: i64 = 3;
+
+/// SNMPv3 msgFlags
+pub const FLAG_AUTH: u8 = 0x01;
+pub const FLAG_PRIV: u8 = 0x02;
+pub const FLAG_REPORTABLE: u8 = 0x04;
+
+/// User-based Security Model 번호
+const SECURITY_MODEL_USM: i64 = 3;
+
+/// 우리가 받을 수 있는 최대 메시지 크기
+pub const MAX_MESSAGE_SIZE: i64 = 65507;
+
+/// SNMP 변수 값
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    OctetString(Vec<u8>),
+    Null,
+    ObjectIdentifier(Vec<u32>),
+    IpAddress([u8; 4]),
+    Counter32(u32),
+    Unsigned32(u32),
+    Timeticks(u32),
+    Opaque(Vec<u8>),
+    Counter64(u64),
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+}
+
+impl Value {
+    fn encode(&self) -> Result<Vec<u8>> {
+        Ok(match self {
+            Value::Integer(i) => ber::encode_integer(*i),
+            Value::OctetString(s) => ber::encode_octet_string(s),
+            Value::Null => ber::encode_null(),
+            Value::ObjectIdentifier(oid) => ber::encode_oid(oid)?,
+            Value::IpAddress(ip) => ber::encode_tlv(ber::TYPE_IPADDRESS, ip),
+            Value::Counter32(n) => ber::encode_unsigned(ber::TYPE_COUNTER32, *n as u64),
+            Value::Unsigned32(n) => ber::encode_unsigned(ber::TYPE_UNSIGNED32, *n as u64),
+            Value::Timeticks(n) => ber::encode_unsigned(ber::TYPE_TIMETICKS, *n as u64),
+            Value::Opaque(bytes) => ber::encode_tlv(ber::TYPE_OPAQUE, bytes),
+            Value::Counter64(n) => ber::encode_unsigned(ber::TYPE_COUNTER64, *n),
+            Value::NoSuchObject => vec![ber::TYPE_NOSUCHOBJECT, 0],
+            Value::NoSuchInstance => vec![ber::TYPE_NOSUCHINSTANCE, 0],
+            Value::EndOfMibView => vec![ber::TYPE_ENDOFMIBVIEW, 0],
+        })
+    }
+
+    /// OctetString 값을 문자열로 변환합니다. (끝의 NUL 문자는 제거)
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            Value::OctetString(bytes) => {
+                Some(String::from_utf8_lossy(bytes).trim_end_matches('\0').to_string())
+            }
+            _ => None,
+        }
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self> {
+        let tlv = reader.read_tlv()?;
+        let as_u32 = |bytes: &[u8]| -> Result<u32> {
+            let n = ber::decode_unsigned(bytes)?;
+            u32::try_from(n).context("32-bit value out of range")
+        };
+        Ok(match tlv.tag {
+            ber::TYPE_INTEGER => Value::Integer(ber::decode_integer(tlv.value)?),
+            ber::TYPE_OCTETSTRING => Value::OctetString(tlv.value.to_vec()),
+            ber::TYPE_NULL => Value::Null,
+            ber::TYPE_OBJECTIDENTIFIER => Value::ObjectIdentifier(ber::decode_oid(tlv.value)?),
+            ber::TYPE_IPADDRESS => {
+                let ip: [u8; 4] = tlv.value.try_into().context("Invalid IpAddress length")?;
+                Value::IpAddress(ip)
+            }
+            ber::TYPE_COUNTER32 => Value::Counter32(as_u32(tlv.value)?),
+            ber::TYPE_UNSIGNED32 => Value::Unsigned32(as_u32(tlv.value)?),
+            ber::TYPE_TIMETICKS => Value::Timeticks(as_u32(tlv.value)?),
+            ber::TYPE_OPAQUE => Value::Opaque(tlv.value.to_vec()),
+            ber::TYPE_COUNTER64 => Value::Counter64(ber::decode_unsigned(tlv.value)?),
+            ber::TYPE_NOSUCHOBJECT => Value::NoSuchObject,
+            ber::TYPE_NOSUCHINSTANCE => Value::NoSuchInstance,
+            ber::TYPE_ENDOFMIBVIEW => Value::EndOfMibView,
+            other => bail!("Unsupported SNMP value type: 0x{:02x}", other),
+        })
+    }
+}
+
+impl std::fmt::Display for Value {
+    /// 이벤트 표시용 값 문자열 (출력 가능한 OctetString은 문자열, 그 외는 16진수)
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::OctetString(bytes) | Value::Opaque(bytes) => {
+                let text = String::from_utf8_lossy(bytes);
+                if matches!(self, Value::OctetString(_))
+                    && text.trim_end_matches('\0').chars().all(|c| !c.is_control() || c == '\t')
+                {
+                    write!(f, "{}", text.trim_end_matches('\0'))
+                } else {
+                    let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                    write!(f, "0x{}", hex.join(""))
+                }
+            }
+            Value::Null => write!(f, "null"),
+            Value::ObjectIdentifier(oid) => write!(f, "{}", ber::format_oid(oid)),
+            Value::IpAddress(ip) => write!(f, "{}.{}.{}.{}", ip[0], ip[1], ip[2], ip[3]),
+            Value::Counter32(n) | Value::Unsigned32(n) => write!(f, "{}", n),
+            Value::Timeticks(n) => write!(f, "{}", n),
+            Value::Counter64(n) => write!(f, "{}", n),
+            Value::NoSuchObject => write!(f, "noSuchObject"),
+            Value::NoSuchInstance => write!(f, "noSuchInstance"),
+            Value::EndOfMibView => write!(f, "endOfMibView"),
+        }
+    }
+}
+
+/// SNMP PDU (GETBULK의 경우 error_status/error_index 자리에 non-repeaters/max-repetitions가 들어감)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pdu {
+    pub pdu_type: u8,
+    pub request_id: i32,
+    pub error_status: u32,
+    pub error_index: u32,
+    pub varbinds: Vec<(Vec<u32>, Value)>,
+}
+
+impl Pdu {
+    /// 값이 Null인 varbind들로 요청 PDU를 만듭니다.
+    pub fn request(pdu_type: u8, request_id: i32, oids: &[Vec<u32>]) -> Self {
+        Self {
+            pdu_type,
+            request_id,
+            error_status: 0,
+            error_index: 0,
+            varbinds: oids.iter().map(|oid| (oid.clone(), Value::Null)).collect(),
+        }
+    }
+
+    /// GETBULK 요청 PDU를 만듭니다.
+    pub fn bulk_request(request_id: i32, oids: &[Vec<u32>], non_repeaters: u32, max_repetitions: u32) -> Self {
+        Self {
+            error_status: non_repeaters,
+            error_index: max_repetitions,
+            ..Self::request(MSG_GET_BULK, request_id, oids)
+        }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut varbinds = Vec::with_capacity(self.varbinds.len());
+        for (oid, value) in &self.varbinds {
+            varbinds.push(ber::encode_constructed(
+                ber::TYPE_SEQUENCE,
+                &[ber::encode_oid(oid)?, value.encode()?],
+            ));
+        }
+        Ok(ber::encode_constructed(
+            self.pdu_type,
+            &[
+                ber::encode_integer(self.request_id as i64),
+                ber::encode_integer(self.error_status as i64),
+                ber::encode_integer(self.error_index as i64),
+                ber::encode_constructed(ber::TYPE_SEQUENCE, &varbinds),
+            ],
+        ))
+    }
+
+    pub fn decode(reader: &mut Reader) -> Result<Self> {
+        let pdu_type = reader.peek_tag().context("Missing PDU")?;
+        let mut pdu = reader.read_constructed(pdu_type)?;
+        let request_id = pdu.read_integer()? as i32;
+        let error_status = pdu.read_integer()? as u32;
+        let error_index = pdu.read_integer()? as u32;
+        let mut list = pdu.read_constructed(ber::TYPE_SEQUENCE)?;
+        let mut varbinds = Vec::new();
+        while !list.is_empty() {
+            let mut varbind = list.read_constructed(ber::TYPE_SEQUENCE)?;
+            let oid = varbind.read_oid()?;
+            let value = Value::decode(&mut varbind)?;
+            varbinds.push((oid, value));
+        }
+        Ok(Self { pdu_type, request_id, error_status, error_index, varbinds })
+    }
+}
+
+/// SNMPv1 Trap-PDU (RFC 1157)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrapV1Pdu {
+    pub enterprise: Vec<u32>,
+    pub agent_addr: [u8; 4],
+    pub generic_trap: i64,
+    pub specific_trap: i64,
+    pub timestamp: u32,
+    pub varbinds: Vec<(Vec<u32>, Value)>,
+}
+
+impl TrapV1Pdu {
+    #[cfg(test)]
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut varbinds = Vec::with_capacity(self.varbinds.len());
+        for (oid, value) in &self.varbinds {
+            varbinds.push(ber::encode_constructed(
+                ber::TYPE_SEQUENCE,
+                &[ber::encode_oid(oid)?, value.encode()?],
+            ));
+        }
+        Ok(ber::encode_constructed(
+            MSG_TRAP_V1,
+            &[
+                ber::encode_oid(&self.enterprise)?,
+                ber::encode_tlv(ber::TYPE_IPADDRESS, &self.agent_addr),
+                ber::encode_integer(self.generic_trap),
+                ber::encode_integer(self.specific_trap),
+                ber::encode_unsigned(ber::TYPE_TIMETICKS, self.timestamp as u64),
+                ber::encode_constructed(ber::TYPE_SEQUENCE, &varbinds),
+            ],
+        ))
+    }
+
+    pub fn decode(reader: &mut Reader) -> Result<Self> {
+        let mut pdu = reader.read_constructed(MSG_TRAP_V1)?;
+        let enterprise = pdu.read_oid()?;
+        let agent_addr = match Value::decode(&mut pdu)? {
+            Value::IpAddress(ip) => ip,
+            other => bail!("Invalid v1 trap agent-addr: {:?}", other),
+        };
+        let generic_trap = pdu.read_integer()?;
+        let specific_trap = pdu.read_integer()?;
+        let timestamp = match Value::decode(&mut pdu)? {
+            Value::Timeticks(ticks) => ticks,
+            other => bail!("Invalid v1 trap time-stamp: {:?}", other),
+        };
+        let mut list = pdu.read_constructed(ber::TYPE_SEQUENCE)?;
+        let mut varbinds = Vec::new();
+        while !list.is_empty() {
+            let mut varbind = list.read_constructed(ber::TYPE_SEQUENCE)?;
+            let oid = varbind.read_oid()?;
+            let value = Value::decode(&mut varbind)?;
+            varbinds.push((oid, value));
+        }
+        Ok(Self { enterprise, agent_addr, generic_trap, specific_trap, timestamp, varbinds })
+    }
+}
+
+/// 커뮤니티 기반(v1/v2c) 메시지의 PDU
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommunityPdu {
+    V1Trap(TrapV1Pdu),
+    Standard(Pdu),
+}
+
+/// 메시지의 버전 필드만 읽습니다.
+pub fn peek_version(data: &[u8]) -> Result<i64> {
+    Reader::new(data).read_constructed(ber::TYPE_SEQUENCE)?.read_integer()
+}
+
+/// v2c 커뮤니티 메시지를 인코딩합니다.
+pub fn encode_community_message(community: &[u8], pdu: &Pdu) -> Result<Vec<u8>> {
+    Ok(ber::encode_constructed(
+        ber::TYPE_SEQUENCE,
+        &[
+            ber::encode_integer(VERSION_2C),
+            ber::encode_octet_string(community),
+            pdu.encode()?,
+        ],
+    ))
+}
+
+/// v1 트랩을 포함한 커뮤니티 메시지를 인코딩합니다. (테스트에서 트랩 송신용)
+#[cfg(test)]
+pub fn encode_community_message_any(version: i64, community: &[u8], pdu: &CommunityPdu) -> Result<Vec<u8>> {
+    let encoded = match pdu {
+        CommunityPdu::V1Trap(trap) => trap.encode()?,
+        CommunityPdu::Standard(pdu) => pdu.encode()?,
+    };
+    Ok(ber::encode_constructed(
+        ber::TYPE_SEQUENCE,
+        &[ber::encode_integer(version), ber::encode_octet_string(community), encoded],
+    ))
+}
+
+/// v1 Trap-PDU까지 포함해 커뮤니티 메시지를 디코딩합니다. (version, community, pdu)
+pub fn decode_community_message_any(data: &[u8]) -> Result<(i64, Vec<u8>, CommunityPdu)> {
+    let mut msg = Reader::new(data).read_constructed(ber::TYPE_SEQUENCE)?;
+    let version = msg.read_integer()?;
+    let community = msg.read_octet_string()?.to_vec();
+    let pdu = if msg.peek_tag() == Some(MSG_TRAP_V1) {
+        CommunityPdu::V1Trap(TrapV1Pdu::decode(&mut msg)?)
+    } else {
+        CommunityPdu::Standard(Pdu::decode(&mut msg)?)
+    };
+    Ok((version, community, pdu))
+}
+
+/// v1/v2c 커뮤니티 메시지를 디코딩합니다. (version, community, pdu)
+pub fn decode_community_message(data: &[u8]) -> Result<(i64, Vec<u8>, Pdu)> {
+    let mut msg = Reader::new(data).read_constructed(ber::TYPE_SEQUENCE)?;
+    let version = msg.read_integer()?;
+    let community = msg.read_octet_string()?.to_vec();
+    let pdu = Pdu::decode(&mut msg)?;
+    Ok((version, community, pdu))
+}
+
+/// USM 보안 파라미터 (RFC 3414)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsmSecurityParameters {
+    pub engine_id: Vec<u8>,
+    pub engine_boots: u32,
+    pub engine_time: u32,
+    pub user_name: Vec<u8>,
+    pub auth_params: Vec<u8>,
+    pub priv_params: Vec<u8>,
+}
+
+impl UsmSecurityParameters {
+    fn encode(&self) -> Vec<u8> {
+        ber::encode_constructed(
+            ber::TYPE_SEQUENCE,
+            &[
+                ber::encode_octet_string(&self.engine_id),
+                ber::encode_integer(self.engine_boots as i64),
+                ber::encode_integer(self.engine_time as i64),
+                ber::encode_octet_string(&self.user_name),
+                ber::encode_octet_string(&self.auth_params),
+                ber::encode_octet_string(&self.priv_params),
+            ],
+        )
+    }
+}
+
+/// scopedPDU (contextEngineID, contextName, PDU)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopedPdu {
+    pub context_engine_id: Vec<u8>,
+    pub context_name: Vec<u8>,
+    pub pdu: Pdu,
+}
+
+impl ScopedPdu {
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        Ok(ber::encode_constructed(
+            ber::TYPE_SEQUENCE,
+            &[
+                ber::encode_octet_string(&self.context_engine_id),
+                ber::encode_octet_string(&self.context_name),
+                self.pdu.encode()?,
+            ],
+        ))
+    }
+
+    /// 복호화 패딩이 뒤에 붙어 있을 수 있으므로 첫 TLV만 읽습니다.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut scoped = Reader::new(data).read_constructed(ber::TYPE_SEQUENCE)?;
+        let context_engine_id = scoped.read_octet_string()?.to_vec();
+        let context_name = scoped.read_octet_string()?.to_vec();
+        let pdu = Pdu::decode(&mut scoped)?;
+        Ok(Self { context_engine_id, context_name, pdu })
+    }
+}
+
+/// 암호화 여부에 따른 msgData
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScopedPduData {
+    Plaintext(ScopedPdu),
+    Encrypted(Vec<u8>),
+}
+
+/// SNMPv3 메시지
+#[derive(Debug, Clone, PartialEq)]
+pub struct V3Message {
+    pub msg_id: i32,
+    pub max_size: i64,
+    pub flags: u8,
+    pub security: UsmSecurityParameters,
+    pub data: ScopedPduData,
+}
+
+impl V3Message {
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let data = match &self.data {
+            ScopedPduData::Plaintext(scoped) => scoped.encode()?,
+            ScopedPduData::Encrypted(bytes) => ber::encode_octet_string(bytes),
+        };
+        Ok(ber::encode_constructed(
+            ber::TYPE_SEQUENCE,
+            &[
+                ber::encode_integer(VERSION_3),
+                ber::encode_constructed(
+                    ber::TYPE_SEQUENCE,
+                    &[
+                        ber::encode_integer(self.msg_id as i64),
+                        ber::encode_integer(self.max_size),
+                        ber::encode_octet_string(&[self.flags]),
+                        ber::encode_integer(SECURITY_MODEL_USM),
+                    ],
+                ),
+                ber::encode_octet_string(&self.security.encode()),
+                data,
+            ],
+        ))
+    }
+
+    /// 메시지를 디코딩하고, 인증 파라미터의 (메시지 내 위치, 길이)를 함께 반환합니다.
+    pub fn decode(data: &[u8]) -> Result<(Self, usize, usize)> {
+        let mut msg = Reader::new(data).read_constructed(ber::TYPE_SEQUENCE)?;
+        let version = msg.read_integer()?;
+        if version != VERSION_3 {
+            bail!("Not an SNMPv3 message (version {})", version);
+        }
+
+        let mut global = msg.read_constructed(ber::TYPE_SEQUENCE)?;
+        let msg_id = global.read_integer()? as i32;
+        let max_size = global.read_integer()?;
+        let flags = *global.read_octet_string()?.first().context("Empty msgFlags")?;
+        let security_model = global.read_integer()?;
+        if security_model != SECURITY_MODEL_USM {
+            bail!("Unsupported security model: {}", security_model);
+        }
+
+        // msgSecurityParameters는 OCTET STRING 안에 인코딩된 SEQUENCE
+        let mut params = msg.read_constructed(ber::TYPE_OCTETSTRING)?;
+        let mut usm = params.read_constructed(ber::TYPE_SEQUENCE)?;
+        let engine_id = usm.read_octet_string()?.to_vec();
+        let engine_boots = usm.read_integer()? as u32;
+        let engine_time = usm.read_integer()? as u32;
+        let user_name = usm.read_octet_string()?.to_vec();
+        let auth = usm.read_expected(ber::TYPE_OCTETSTRING)?;
+        let priv_params = usm.read_octet_string()?.to_vec();
+
+        let data = if flags & FLAG_PRIV != 0 {
+            ScopedPduData::Encrypted(msg.read_octet_string()?.to_vec())
+        } else {
+            ScopedPduData::Plaintext(ScopedPdu::decode(msg.read_expected(ber::TYPE_SEQUENCE)?.raw)?)
+        };
+
+        let message = Self {
+            msg_id,
+            max_size,
+            flags,
+            security: UsmSecurityParameters {
+                engine_id,
+                engine_boots,
+                engine_time,
+                user_name,
+                auth_params: auth.value.to_vec(),
+                priv_params,
+            },
+            data,
+        };
+        Ok((message, auth.offset, auth.value.len()))
+    }
+}
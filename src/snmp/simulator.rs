@@ -0,0 +1,396 @@
+//! 로컬 SNMP 에이전트 시뮬레이터 (v2c/v3, UDP)
+//!
+//! 픽스처 파일(JSON)에 정의된 OID 값으로 GET/GETNEXT/GETBULK에 응답합니다.
+//! `v3_users`를 지정하면 해당 USM 사용자의 v3 요청에도 응답합니다. (형식은 SNMPv3 설정과 동일)
+//! 실제 MWG 장비 없이 수집기를 테스트하거나 시연할 때 사용합니다.
+//!
+//! ```json
+//! {
+//!   "community": "public",
+//!   "delay_ms": 0,
+//!   "objects": {
+//!     "UCD-SNMP-MIB::ssCpuIdle.0": { "type": "integer", "value": 42 },
+//!     "IF-MIB::ifName.2": { "type": "string", "value": "eth0" },
+//!     "IF-MIB::ifHCInOctets.2": { "type": "counter64", "values": [0, 125000000, 250000000] },
+//!     "1.3.6.1.4.1.9999.1.0": { "error": "genErr" }
+//!   }
+//! }
+//! ```
+//!
+//! `values`는 GET할 때마다 다음 값으로 넘어가고 마지막 값에서 멈춥니다.
+//! `step`을 지정하면 (마지막) 값에 GET마다 step을 더합니다. 카운터는 타입의 폭에서 순환합니다.
+//! `error`가 있는 OID를 GET하면 해당 error-status(tooBig, noSuchName, badValue, readOnly, genErr)로 응답합니다.
+
+use super::mib;
+use super::pdu::{
+    self, Pdu, ScopedPdu, ScopedPduData, Value, FLAG_AUTH, FLAG_PRIV, MSG_GET, MSG_GET_BULK, MSG_GET_NEXT, MSG_REPORT,
+    MSG_RESPONSE,
+};
+use super::usm::{self, UsmReport, UsmUser};
+use super::SnmpV3Config;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+
+/// 시뮬레이터의 v3 엔진 ID
+pub const ENGINE_ID: &[u8] = b"\x80\x00\x1f\x88\x80mwg-simulator";
+/// 시뮬레이터의 engineBoots (재시작 횟수를 저장하지 않으므로 고정)
+const ENGINE_BOOTS: u32 = 1;
+/// RFC 3414 시간 창 (초)
+const TIME_WINDOW_SECS: u32 = 150;
+
+/// 픽스처 파일 구조
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimulatorFixture {
+    #[serde(default = "default_community")]
+    pub community: String,
+    /// 모든 응답을 지연시킬 시간 (타임아웃 테스트용)
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// true이면 요청에 응답하지 않음
+    #[serde(default)]
+    pub silent: bool,
+    /// v3 요청을 받을 USM 사용자
+    #[serde(default)]
+    pub v3_users: Vec<SnmpV3Config>,
+    /// OID (숫자 또는 MIB 이름) -> 값 정의
+    #[serde(default)]
+    pub objects: BTreeMap<String, FixtureObject>,
+}
+
+fn default_community() -> String {
+    "public".to_string()
+}
+
+/// 픽스처의 OID 하나
+#[derive(Debug, Clone, Deserialize)]
+pub struct FixtureObject {
+    /// integer, string, counter32, counter64, gauge32, timeticks
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
+    /// GET마다 순서대로 반환할 값
+    #[serde(default)]
+    pub values: Vec<serde_json::Value>,
+    /// GET마다 더할 증가량 (카운터/timeticks 시연용)
+    #[serde(default)]
+    pub step: Option<u64>,
+    /// 값 대신 반환할 error-status 이름
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl SimulatorFixture {
+    /// 픽스처 파일을 읽습니다.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("픽스처 파일을 읽을 수 없습니다: {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("픽스처 형식 오류: {}", path.display()))
+    }
+}
+
+/// 시뮬레이터가 응답할 OID 상태
+enum SimObject {
+    Values { values: Vec<Value>, next: usize, step: Option<u64> },
+    Error(u32),
+}
+
+impl SimObject {
+    fn parse(name: &str, object: &FixtureObject) -> Result<Self> {
+        if let Some(error) = &object.error {
+            let status = match error.as_str() {
+                "tooBig" => 1,
+                "noSuchName" => 2,
+                "badValue" => 3,
+                "readOnly" => 4,
+                "genErr" => 5,
+                other => bail!("{}: 알 수 없는 error-status: {}", name, other),
+            };
+            return Ok(SimObject::Error(status));
+        }
+
+        let kind = object.kind.as_deref().unwrap_or("integer");
+        let raw: Vec<&serde_json::Value> = match &object.value {
+            Some(value) => vec![value],
+            None => object.values.iter().collect(),
+        };
+        if raw.is_empty() {
+            bail!("{}: value 또는 values가 필요합니다", name);
+        }
+        let values = raw
+            .into_iter()
+            .map(|value| Self::convert(kind, value).with_context(|| format!("{}: 잘못된 {} 값 {}", name, kind, value)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(SimObject::Values { values, next: 0, step: object.step })
+    }
+
+    fn convert(kind: &str, value: &serde_json::Value) -> Result<Value> {
+        let unsigned32 = || -> Result<u32> {
+            value.as_u64().and_then(|v| u32::try_from(v).ok()).context("0 ~ 4294967295 범위의 정수가 아닙니다")
+        };
+        Ok(match kind {
+            "integer" => Value::Integer(value.as_i64().context("정수가 아닙니다")?),
+            "string" => Value::OctetString(value.as_str().context("문자열이 아닙니다")?.as_bytes().to_vec()),
+            "counter32" => Value::Counter32(unsigned32()?),
+            "gauge32" => Value::Unsigned32(unsigned32()?),
+            "timeticks" => Value::Timeticks(unsigned32()?),
+            "counter64" => Value::Counter64(value.as_u64().context("0 이상의 정수가 아닙니다")?),
+            other => bail!("지원하지 않는 타입: {}", other),
+        })
+    }
+
+    /// 현재 값 (walk에서는 시퀀스를 진행하지 않음)
+    fn peek(&self) -> Option<Value> {
+        match self {
+            SimObject::Values { values, next, .. } => values.get(*next).cloned(),
+            SimObject::Error(_) => None,
+        }
+    }
+
+    /// GET 응답 값 - 시퀀스를 한 칸 진행 (마지막 값에서 멈추고, step이 있으면 증가)
+    fn take(&mut self) -> Option<Value> {
+        let value = self.peek();
+        if let SimObject::Values { values, next, step } = self {
+            if *next + 1 < values.len() {
+                *next += 1;
+            } else if let Some(step) = *step {
+                let last = &mut values[*next];
+                *last = match &*last {
+                    Value::Counter32(c) => Value::Counter32(c.wrapping_add(step as u32)),
+                    Value::Counter64(c) => Value::Counter64(c.wrapping_add(step)),
+                    Value::Timeticks(t) => Value::Timeticks(t.wrapping_add(step as u32)),
+                    Value::Unsigned32(g) => Value::Unsigned32(g.saturating_add(step as u32)),
+                    Value::Integer(i) => Value::Integer(i.saturating_add(step as i64)),
+                    other => other.clone(),
+                };
+            }
+        }
+        value
+    }
+}
+
+/// 픽스처 값으로 응답하는 에이전트 (소켓 없이 요청 메시지 하나씩 처리)
+pub struct SimulatedAgent {
+    community: Vec<u8>,
+    users: Vec<SnmpV3Config>,
+    started: Instant,
+    objects: Mutex<BTreeMap<Vec<u32>, SimObject>>,
+}
+
+impl SimulatedAgent {
+    /// 픽스처를 검증하고 에이전트를 만듭니다.
+    pub fn new(fixture: &SimulatorFixture) -> Result<Self> {
+        let mut objects = BTreeMap::new();
+        for (name, object) in &fixture.objects {
+            let oid = mib::resolve(name).with_context(|| format!("픽스처 OID 오류: {}", name))?;
+            objects.insert(oid, SimObject::parse(name, object)?);
+        }
+        for user in &fixture.v3_users {
+            user.validate().with_context(|| format!("픽스처 v3 사용자 오류: {}", user.username))?;
+        }
+        Ok(Self {
+            community: fixture.community.as_bytes().to_vec(),
+            users: fixture.v3_users.clone(),
+            started: Instant::now(),
+            objects: Mutex::new(objects),
+        })
+    }
+
+    /// 요청 하나를 처리해 응답 메시지를 반환합니다.
+    /// 커뮤니티가 다르거나 해석할 수 없으면 응답하지 않고, v3 인증 오류는 USM Report로 알립니다.
+    pub fn handle(&self, data: &[u8]) -> Option<Vec<u8>> {
+        if pdu::peek_version(data).ok()? == pdu::VERSION_3 {
+            return self.handle_v3(data);
+        }
+        let (version, community, request) = pdu::decode_community_message(data).ok()?;
+        if version != pdu::VERSION_2C || community != self.community {
+            return None;
+        }
+        let response = self.respond(&request)?;
+        pdu::encode_community_message(&community, &response).ok()
+    }
+
+    fn handle_v3(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let (message, _, _) = pdu::V3Message::decode(data).ok()?;
+        let time = self.started.elapsed().as_secs() as u32;
+        let anonymous = UsmUser { name: message.security.user_name.clone(), auth: None, privacy: None };
+        let report = |user: &UsmUser, kind: UsmReport, request_id: i32| {
+            let scoped = ScopedPdu {
+                context_engine_id: ENGINE_ID.to_vec(),
+                context_name: Vec::new(),
+                pdu: Pdu {
+                    pdu_type: MSG_REPORT,
+                    request_id,
+                    error_status: 0,
+                    error_index: 0,
+                    varbinds: vec![(kind.oid(), Value::Counter32(1))],
+                },
+            };
+            usm::seal(user, ENGINE_ID, ENGINE_BOOTS, time, message.msg_id, 0, &scoped).ok()
+        };
+        let plaintext_request_id = match &message.data {
+            ScopedPduData::Plaintext(scoped) => scoped.pdu.request_id,
+            ScopedPduData::Encrypted(_) => 0,
+        };
+
+        // discovery 요청
+        if message.security.engine_id.is_empty() {
+            return report(&anonymous, UsmReport::UnknownEngineId, plaintext_request_id);
+        }
+
+        let config = self.users.iter().find(|user| user.username.as_bytes() == message.security.user_name.as_slice());
+        let Some(config) = config else {
+            return report(&anonymous, UsmReport::UnknownUserName, plaintext_request_id);
+        };
+        let user = UsmUser::localize(config, ENGINE_ID).ok()?;
+        if message.flags & (FLAG_AUTH | FLAG_PRIV) != user.security_flags() {
+            return report(&anonymous, UsmReport::UnsupportedSecurityLevel, plaintext_request_id);
+        }
+        let scoped = match usm::unseal(&user, data) {
+            Ok((_, scoped)) => scoped,
+            Err(e) => {
+                let kind = if e.to_string().contains("authentication") {
+                    UsmReport::WrongDigest
+                } else {
+                    UsmReport::DecryptionError
+                };
+                return report(&anonymous, kind, plaintext_request_id);
+            }
+        };
+        if user.auth.is_some()
+            && (message.security.engine_boots != ENGINE_BOOTS
+                || message.security.engine_time.abs_diff(time) > TIME_WINDOW_SECS)
+        {
+            // notInTimeWindow Report는 인증만 하고 암호화하지 않음 (RFC 3414 3.2 7a)
+            let auth_only = UsmUser { privacy: None, ..user };
+            return report(&auth_only, UsmReport::NotInTimeWindow, scoped.pdu.request_id);
+        }
+
+        let response = ScopedPdu { pdu: self.respond(&scoped.pdu)?, ..scoped };
+        usm::seal(&user, ENGINE_ID, ENGINE_BOOTS, time, message.msg_id, 0, &response).ok()
+    }
+
+    fn respond(&self, request: &Pdu) -> Option<Pdu> {
+        let mut objects = self.objects.lock().unwrap();
+        let mut response = Pdu {
+            pdu_type: MSG_RESPONSE,
+            request_id: request.request_id,
+            error_status: 0,
+            error_index: 0,
+            varbinds: Vec::new(),
+        };
+        match request.pdu_type {
+            MSG_GET => {
+                // error-status가 지정된 OID가 있으면 첫 번째 것을 error-index로 보고
+                if let Some((index, status)) = request.varbinds.iter().enumerate().find_map(|(i, (oid, _))| {
+                    match objects.get(oid) {
+                        Some(SimObject::Error(status)) => Some((i, *status)),
+                        _ => None,
+                    }
+                }) {
+                    response.error_status = status;
+                    response.error_index = index as u32 + 1;
+                    response.varbinds = request.varbinds.clone();
+                    return Some(response);
+                }
+                response.varbinds = request
+                    .varbinds
+                    .iter()
+                    .map(|(oid, _)| {
+                        let value = objects.get_mut(oid).and_then(SimObject::take).unwrap_or(Value::NoSuchObject);
+                        (oid.clone(), value)
+                    })
+                    .collect();
+            }
+            MSG_GET_NEXT => {
+                response.varbinds = request.varbinds.iter().map(|(oid, _)| Self::next_after(&objects, oid)).collect();
+            }
+            MSG_GET_BULK => {
+                // non-repeaters는 error_status, max-repetitions는 error_index 위치
+                let non_repeaters = (request.error_status as usize).min(request.varbinds.len());
+                for (oid, _) in &request.varbinds[..non_repeaters] {
+                    response.varbinds.push(Self::next_after(&objects, oid));
+                }
+                for (oid, _) in &request.varbinds[non_repeaters..] {
+                    let mut current = oid.clone();
+                    for _ in 0..request.error_index.max(1) {
+                        let (next, value) = Self::next_after(&objects, &current);
+                        let end = value == Value::EndOfMibView;
+                        response.varbinds.push((next.clone(), value));
+                        if end {
+                            break;
+                        }
+                        current = next;
+                    }
+                }
+            }
+            _ => return None,
+        }
+        Some(response)
+    }
+
+    /// 주어진 OID 다음의 (OID, 값) - 오류로 정의된 OID는 건너뜀
+    fn next_after(objects: &BTreeMap<Vec<u32>, SimObject>, oid: &[u32]) -> (Vec<u32>, Value) {
+        objects
+            .range::<[u32], _>((std::ops::Bound::Excluded(oid), std::ops::Bound::Unbounded))
+            .find_map(|(oid, object)| Some((oid.clone(), object.peek()?)))
+            .unwrap_or((oid.to_vec(), Value::EndOfMibView))
+    }
+}
+
+/// 로컬 SNMP 에이전트 시뮬레이터
+pub struct SnmpSimulator {
+    socket: Arc<UdpSocket>,
+    delay: Duration,
+    silent: bool,
+    agent: SimulatedAgent,
+}
+
+impl SnmpSimulator {
+    /// 픽스처를 검증하고 `addr`(예: "127.0.0.1:0")에 바인드합니다.
+    pub async fn bind(addr: &str, fixture: &SimulatorFixture) -> Result<Self> {
+        let agent = SimulatedAgent::new(fixture)?;
+        let socket = UdpSocket::bind(addr).await.with_context(|| format!("시뮬레이터 바인드 실패: {}", addr))?;
+        Ok(Self {
+            socket: Arc::new(socket),
+            delay: Duration::from_millis(fixture.delay_ms),
+            silent: fixture.silent,
+            agent,
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// 요청을 계속 처리합니다. (소켓 오류가 나면 종료)
+    pub async fn run(self) -> Result<()> {
+        let mut buf = vec![0u8; 65535];
+        loop {
+            let (len, peer) = self.socket.recv_from(&mut buf).await?;
+            if self.silent {
+                continue;
+            }
+            let Some(reply) = self.agent.handle(&buf[..len]) else {
+                continue;
+            };
+            if self.delay.is_zero() {
+                let _ = self.socket.send_to(&reply, peer).await;
+            } else {
+                let socket = self.socket.clone();
+                let delay = self.delay;
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    let _ = socket.send_to(&reply, peer).await;
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,734 @@
+use super::pdu::{self, Pdu, ScopedPdu, Value, MSG_REPORT, MSG_RESPONSE};
+use super::simulator::{SimulatedAgent, ENGINE_ID};
+use super::usm::{self, UsmReport, UsmUser};
+use super::*;
+use std::net::UdpSocket;
+use std::thread;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const CPU_OID: &str = "1.3.6.1.4.1.2021.11.11.0";
+const SEQUENCE_OID: &str = "1.3.6.1.4.1.9999.2";
+
+fn oid(s: &str) -> Vec<u32> {
+    s.split('.').map(|n| n.parse().unwrap()).collect()
+}
+
+/// 테스트용 로컬 SNMP 에이전트 (시뮬레이터 에이전트에 패킷 유실/위조 응답을 더함)
+struct TestAgent {
+    fixture: SimulatorFixture,
+    /// 응답하지 않고 버릴 요청 수 (재전송 테스트용)
+    drop_first: usize,
+    /// 응답을 인증/암호화 없이 다시 만들어 보냄 (위조 응답 테스트용)
+    downgrade_responses: bool,
+}
+
+impl TestAgent {
+    fn new(users: Vec<SnmpV3Config>) -> Self {
+        let mut objects = serde_json::Map::new();
+        objects.insert(CPU_OID.to_string(), serde_json::json!({ "value": 42 }));
+        // IF-MIB ifDescr / ifName (ifIndex 1, 2, 3)
+        for (if_index, name) in [(1, "lo"), (2, "eth0"), (3, "bond0")] {
+            for column in ["1.3.6.1.2.1.2.2.1.2", "1.3.6.1.2.1.31.1.1.1.1"] {
+                objects
+                    .insert(format!("{}.{}", column, if_index), serde_json::json!({ "type": "string", "value": name }));
+            }
+        }
+        // 동시 요청 매칭 확인용: 1.3.6.1.4.1.9999.2.N = N
+        for n in 1..=64 {
+            objects.insert(format!("{}.{}", SEQUENCE_OID, n), serde_json::json!({ "value": n }));
+        }
+        let fixture = serde_json::from_value(serde_json::json!({ "objects": objects, "v3_users": users })).unwrap();
+        Self { fixture, drop_first: 0, downgrade_responses: false }
+    }
+
+    /// 백그라운드 스레드에서 에이전트를 띄우고 "127.0.0.1:port" 주소를 반환합니다.
+    fn spawn(self) -> String {
+        self.spawn_on("127.0.0.1:0")
+    }
+
+    /// `bind` 주소에 에이전트를 띄우고 실제 주소를 반환합니다. (IPv6는 "[::1]:port")
+    fn spawn_on(mut self, bind: &str) -> String {
+        let agent = SimulatedAgent::new(&self.fixture).unwrap();
+        let socket = UdpSocket::bind(bind).unwrap();
+        let addr = socket.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            let mut buf = vec![0u8; 65535];
+            while let Ok((len, peer)) = socket.recv_from(&mut buf) {
+                if self.drop_first > 0 {
+                    self.drop_first -= 1;
+                    continue;
+                }
+                let reply = agent.handle(&buf[..len]).map(|reply| match self.downgrade_responses {
+                    true => self.downgrade(&reply),
+                    false => reply,
+                });
+                if let Some(reply) = reply {
+                    let _ = socket.send_to(&reply, peer);
+                }
+            }
+        });
+        addr
+    }
+
+    /// 사용자 키로 봉인된 v3 응답을 같은 내용의 noAuthNoPriv 메시지로 다시 만듭니다.
+    /// (discovery Report처럼 원래 인증이 없는 응답은 그대로 둠)
+    fn downgrade(&self, reply: &[u8]) -> Vec<u8> {
+        let resealed = || {
+            let (message, _, _) = pdu::V3Message::decode(reply).ok()?;
+            let security = &message.security;
+            let config = self.fixture.v3_users.iter().find(|u| u.username.as_bytes() == security.user_name)?;
+            let user = UsmUser::localize(config, &security.engine_id).ok()?;
+            let (_, scoped) = usm::unseal(&user, reply).ok()?;
+            let anonymous = UsmUser { auth: None, privacy: None, ..user };
+            usm::seal(
+                &anonymous,
+                &security.engine_id,
+                security.engine_boots,
+                security.engine_time,
+                message.msg_id,
+                0,
+                &scoped,
+            )
+            .ok()
+        };
+        resealed().unwrap_or_else(|| reply.to_vec())
+    }
+}
+
+fn v3_user(
+    username: &str,
+    auth: Option<(AuthProtocol, &str)>,
+    privacy: Option<(PrivProtocol, &str)>,
+) -> SnmpV3Config {
+    SnmpV3Config {
+        username: username.to_string(),
+        auth_protocol: auth.map(|(p, _)| p),
+        auth_password: auth.map(|(_, pw)| pw.to_string()),
+        priv_protocol: privacy.map(|(p, _)| p),
+        priv_password: privacy.map(|(_, pw)| pw.to_string()),
+        context_name: None,
+    }
+}
+
+/// 테스트마다 별도 트랜스포트 사용 (공유 소켓은 테스트 런타임이 끝나면 쓸 수 없음)
+async fn transport() -> Arc<SnmpTransport> {
+    Arc::new(SnmpTransport::bind().await.unwrap())
+}
+
+async fn v2c_client() -> SnmpClient {
+    SnmpClient::new("public".to_string())
+        .with_timeout(Duration::from_secs(2))
+        .with_transport(transport().await)
+}
+
+async fn client(config: SnmpV3Config) -> SnmpClient {
+    SnmpClient::with_credentials(SnmpCredentials::V3(config))
+        .with_timeout(Duration::from_secs(2))
+        .with_transport(transport().await)
+}
+
+#[test]
+fn ber_roundtrip() {
+    let values = [
+        Value::Integer(-129),
+        Value::Integer(i64::from(i32::MAX)),
+        Value::Counter32(u32::MAX),
+        Value::Counter64(u64::MAX),
+        Value::OctetString(b"eth0".to_vec()),
+        Value::ObjectIdentifier(oid("1.3.6.1.2.1.2.2.1.10.300")),
+        Value::NoSuchInstance,
+    ];
+    let pdu = Pdu {
+        pdu_type: MSG_RESPONSE,
+        request_id: 1234,
+        error_status: 0,
+        error_index: 0,
+        varbinds: values.iter().map(|v| (oid(CPU_OID), v.clone())).collect(),
+    };
+    let encoded = pdu::encode_community_message(b"public", &pdu).unwrap();
+    let (version, community, decoded) = pdu::decode_community_message(&encoded).unwrap();
+    assert_eq!(version, pdu::VERSION_2C);
+    assert_eq!(community, b"public");
+    assert_eq!(decoded, pdu);
+}
+
+#[test]
+fn password_to_key_matches_rfc3414_vectors() {
+    // RFC 3414 A.3.1 / A.3.2
+    let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+    let md5 = usm::localize_key(AuthProtocol::Md5, &usm::password_to_key(AuthProtocol::Md5, "maplesyrup"), &engine_id);
+    assert_eq!(
+        md5,
+        [0x52, 0x6f, 0x5e, 0xed, 0x9f, 0xcc, 0xe2, 0x6f, 0x89, 0x64, 0xc2, 0x93, 0x07, 0x87, 0xd8, 0x2b]
+    );
+    let sha = usm::localize_key(AuthProtocol::Sha, &usm::password_to_key(AuthProtocol::Sha, "maplesyrup"), &engine_id);
+    assert_eq!(
+        sha,
+        [
+            0x66, 0x95, 0xfe, 0xbc, 0x92, 0x88, 0xe3, 0x62, 0x82, 0x23, 0x5f, 0xc7, 0x15, 0x1f, 0x12, 0x84, 0x97,
+            0xb3, 0x8f, 0x3f
+        ]
+    );
+}
+
+#[tokio::test]
+async fn v2c_get() {
+    let addr = TestAgent::new(Vec::new()).spawn();
+    let value = v2c_client().await.get(&addr, CPU_OID).await.unwrap();
+    assert_eq!(value, 42.0);
+}
+
+#[tokio::test]
+async fn v3_handshake_all_security_levels() {
+    let users = vec![
+        v3_user("noauth", None, None),
+        v3_user("md5user", Some((AuthProtocol::Md5, "authpass123")), None),
+        v3_user("shades", Some((AuthProtocol::Sha, "authpass123")), Some((PrivProtocol::Des, "privpass123"))),
+        v3_user("md5aes", Some((AuthProtocol::Md5, "authpass123")), Some((PrivProtocol::Aes, "privpass123"))),
+        v3_user("shaaes", Some((AuthProtocol::Sha, "authpass123")), Some((PrivProtocol::Aes, "privpass123"))),
+    ];
+    let addr = TestAgent::new(users.clone()).spawn();
+    for user in users {
+        let name = user.username.clone();
+        let value = client(user)
+            .await
+            .get(&addr, CPU_OID)
+            .await
+            .unwrap_or_else(|e| panic!("{}: {:#}", name, e));
+        assert_eq!(value, 42.0, "user {}", name);
+    }
+    // discovery 결과는 에이전트 주소별로 캐시됨
+    assert_eq!(usm::cached_engine(&addr).unwrap().engine_id, ENGINE_ID);
+}
+
+#[tokio::test]
+async fn v3_wrong_auth_password_is_reported() {
+    let addr = TestAgent::new(vec![v3_user("md5user", Some((AuthProtocol::Md5, "authpass123")), None)]).spawn();
+    let err = client(v3_user("md5user", Some((AuthProtocol::Md5, "wrongpass123")), None))
+        .await
+        .get(&addr, CPU_OID)
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("wrong digest"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn v3_wrong_priv_password_is_reported() {
+    let addr = TestAgent::new(vec![v3_user(
+        "shaaes",
+        Some((AuthProtocol::Sha, "authpass123")),
+        Some((PrivProtocol::Aes, "privpass123")),
+    )])
+    .spawn();
+    let err = client(v3_user(
+        "shaaes",
+        Some((AuthProtocol::Sha, "authpass123")),
+        Some((PrivProtocol::Aes, "otherpriv123")),
+    ))
+    .await
+    .get(&addr, CPU_OID)
+    .await
+    .unwrap_err();
+    assert!(format!("{:#}", err).contains("decryption error"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn v3_unknown_user_is_reported() {
+    let addr = TestAgent::new(vec![v3_user("noauth", None, None)]).spawn();
+    let err = client(v3_user("nobody", None, None)).await.get(&addr, CPU_OID).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown user name"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn v3_security_level_mismatch_is_reported() {
+    let addr = TestAgent::new(vec![v3_user("md5user", Some((AuthProtocol::Md5, "authpass123")), None)]).spawn();
+    let err = client(v3_user("md5user", None, None)).await.get(&addr, CPU_OID).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("unsupported security level"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn v3_unauthenticated_response_is_rejected() {
+    let user = v3_user("shaaes", Some((AuthProtocol::Sha, "authpass123")), Some((PrivProtocol::Aes, "privpass123")));
+    let mut agent = TestAgent::new(vec![user.clone()]);
+    agent.downgrade_responses = true;
+    let addr = agent.spawn();
+    let err = client(user).await.get(&addr, CPU_OID).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("unauthenticated response for authPriv"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn v3_stale_engine_time_is_resynced() {
+    let user = v3_user("md5user", Some((AuthProtocol::Md5, "authpass123")), None);
+    let addr = TestAgent::new(vec![user.clone()]).spawn();
+    // 캐시에 오래된 engineTime을 넣어 notInTimeWindow를 유도
+    usm::cache_engine(&addr, usm::EngineState::new(ENGINE_ID.to_vec(), 1, 100_000));
+    assert_eq!(client(user).await.get(&addr, CPU_OID).await.unwrap(), 42.0);
+}
+
+#[tokio::test]
+async fn v3_timeout_when_agent_is_silent() {
+    let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = silent.local_addr().unwrap().to_string();
+    let err = client(v3_user("noauth", None, None))
+        .await
+        .with_timeout(Duration::from_millis(300))
+        .get(&addr, CPU_OID)
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("timed out"), "{:#}", err);
+}
+
+#[test]
+fn v3_config_validation() {
+    assert!(v3_user("", None, None).validate().is_err());
+    assert!(v3_user("u", None, Some((PrivProtocol::Aes, "privpass123"))).validate().is_err());
+    assert!(v3_user("u", Some((AuthProtocol::Sha, "short")), None).validate().is_err());
+    assert!(v3_user("u", Some((AuthProtocol::Sha, "authpass123")), Some((PrivProtocol::Des, "privpass123")))
+        .validate()
+        .is_ok());
+
+    let parsed: SnmpV3Config = serde_json::from_str(
+        r#"{"username": "monitor", "auth_protocol": "SHA", "auth_password": "authpass123",
+            "priv_protocol": "AES", "priv_password": "privpass123"}"#,
+    )
+    .unwrap();
+    assert_eq!(parsed.security_level(), "authPriv");
+    assert_eq!(parsed.priv_protocol, Some(PrivProtocol::Aes));
+}
+
+#[test]
+fn mib_names_resolve_to_numeric_oids() {
+    assert_eq!(mib::normalize("UCD-SNMP-MIB::ssCpuIdle.0").unwrap(), CPU_OID);
+    assert_eq!(mib::normalize("ssCpuIdle.0").unwrap(), CPU_OID);
+    assert_eq!(mib::normalize(" IF-MIB::ifHCInOctets.3 ").unwrap(), "1.3.6.1.2.1.31.1.1.1.6.3");
+    assert_eq!(mib::normalize("HOST-RESOURCES-MIB::hrProcessorLoad.196608").unwrap(), "1.3.6.1.2.1.25.3.3.1.2.196608");
+    assert_eq!(mib::normalize(".1.3.6.1.2.1.1.3.0").unwrap(), "1.3.6.1.2.1.1.3.0");
+
+    for invalid in [
+        "UCD-SNMP-MIB::ssCpuIdel.0", // 오타
+        "IF-MIB::ssCpuIdle.0",       // 다른 모듈의 객체
+        "FOO-MIB::ifName.1",
+        "1.3.6.1.4.1.2021.11.x.0",
+        "1.3..6.1",
+        "1.3.6.1.",
+        "ifName.1a",
+        "ifName.",
+        "1",
+        "5.1.2",
+        "",
+    ] {
+        assert!(mib::resolve(invalid).is_err(), "{} should be rejected", invalid);
+    }
+}
+
+#[tokio::test]
+async fn walk_and_bulk_walk_return_subtree() {
+    let addr = TestAgent::new(Vec::new()).spawn();
+    let client = v2c_client().await;
+    let expected: Vec<(Vec<u32>, Value)> = [(1, "lo"), (2, "eth0"), (3, "bond0")]
+        .iter()
+        .map(|(i, name)| (oid(&format!("1.3.6.1.2.1.31.1.1.1.1.{}", i)), Value::OctetString(name.as_bytes().to_vec())))
+        .collect();
+
+    assert_eq!(client.walk(&addr, "1.3.6.1.2.1.31.1.1.1.1").await.unwrap(), expected);
+    // 한 번에 2개씩 가져와도 하위 트리를 벗어나기 전에 멈춰야 함
+    assert_eq!(client.bulk_walk(&addr, "1.3.6.1.2.1.31.1.1.1.1", 2).await.unwrap(), expected);
+    // 하위 트리 밖으로 나가면 빈 결과
+    let tail = client.bulk_walk(&addr, CPU_OID, 10).await.unwrap();
+    assert!(tail.is_empty());
+}
+
+#[tokio::test]
+async fn v3_bulk_walk() {
+    let user = v3_user("shaaes", Some((AuthProtocol::Sha, "authpass123")), Some((PrivProtocol::Aes, "privpass123")));
+    let addr = TestAgent::new(vec![user.clone()]).spawn();
+    let names: Vec<String> = client(user)
+        .await
+        .bulk_walk(&addr, "1.3.6.1.2.1.2.2.1.2", 25)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|(_, value)| value.as_text())
+        .collect();
+    assert_eq!(names, ["lo", "eth0", "bond0"]);
+}
+
+#[tokio::test]
+async fn get_many_maps_varbind_errors_to_their_oid() {
+    let addr = TestAgent::new(Vec::new()).spawn();
+    let oids = [CPU_OID, "1.3.6.1.4.1.9999.1.0", "1.3.6.1.2.1.31.1.1.1.1.2", "not-an-oid", CPU_OID]
+        .map(String::from);
+    let results = v2c_client().await.get_many(&addr, &oids).await.unwrap();
+
+    assert_eq!(results.len(), oids.len());
+    assert_eq!(*results[0].as_ref().unwrap(), 42.0);
+    assert!(results[1].as_ref().unwrap_err().to_string().contains("noSuchObject"));
+    assert!(results[2].as_ref().unwrap_err().to_string().contains("OctetString"));
+    assert!(results[3].is_err());
+    assert_eq!(*results[4].as_ref().unwrap(), 42.0);
+}
+
+#[tokio::test]
+async fn lost_requests_are_retransmitted_with_backoff() {
+    let mut agent = TestAgent::new(Vec::new());
+    agent.drop_first = 2;
+    let addr = agent.spawn();
+    let options = SnmpOptions { timeout: Duration::from_millis(100), retries: 2, backoff: 2.0 };
+    assert_eq!(options.max_duration(), Duration::from_millis(700));
+
+    let client = v2c_client().await.with_options(options.clone());
+    assert_eq!(client.get(&addr, CPU_OID).await.unwrap(), 42.0);
+
+    // 재시도 횟수보다 많이 유실되면 타임아웃
+    let mut agent = TestAgent::new(Vec::new());
+    agent.drop_first = 3;
+    let addr = agent.spawn();
+    let started = Instant::now();
+    let err = client.get(&addr, CPU_OID).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("3 attempt(s)"), "{:#}", err);
+    assert!(started.elapsed() >= Duration::from_millis(700));
+}
+
+#[tokio::test]
+async fn concurrent_requests_share_one_socket() {
+    let addr = TestAgent::new(Vec::new()).spawn();
+    let shared = transport().await;
+    let requests = (1..=64).map(|n| {
+        let client = SnmpClient::new("public".to_string())
+            .with_timeout(Duration::from_secs(2))
+            .with_transport(shared.clone());
+        let addr = addr.clone();
+        async move { (n, client.get(&addr, &format!("{}.{}", SEQUENCE_OID, n)).await.unwrap()) }
+    });
+    // 요청 ID로 응답을 매칭하므로 각 요청은 자기 OID의 값을 받아야 함
+    for (n, value) in futures::future::join_all(requests).await {
+        assert_eq!(value, n as f64);
+    }
+}
+
+#[tokio::test]
+async fn responses_from_other_hosts_are_ignored() {
+    let agent = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let spoofer = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let transport = transport().await;
+    let client_addr = format!("127.0.0.1:{}", transport.local_addr().unwrap().port());
+    let response = |value: i64| {
+        let pdu = Pdu {
+            pdu_type: MSG_RESPONSE,
+            request_id: 4242,
+            error_status: 0,
+            error_index: 0,
+            varbinds: vec![(oid(CPU_OID), Value::Integer(value))],
+        };
+        pdu::encode_community_message(b"public", &pdu).unwrap()
+    };
+
+    let request = pdu::encode_community_message(b"public", &Pdu::request(pdu::MSG_GET, 4242, &[oid(CPU_OID)])).unwrap();
+    let options = SnmpOptions { timeout: Duration::from_secs(2), retries: 0, backoff: 1.0 };
+    let target = agent.local_addr().unwrap();
+    let exchange = transport.exchange(target, 4242, &request, &options);
+    let agent_side = async {
+        let mut buf = vec![0u8; 65535];
+        agent.recv(&mut buf).await.unwrap();
+        // 요청 ID를 맞춘 다른 호스트의 응답이 먼저 도착해도 에이전트의 응답을 기다려야 함
+        spoofer.send_to(&response(666), &client_addr).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        agent.send_to(&response(42), &client_addr).await.unwrap();
+    };
+    let (reply, _) = tokio::join!(exchange, agent_side);
+    let (_, _, pdu) = pdu::decode_community_message(&reply.unwrap()).unwrap();
+    assert_eq!(pdu.varbinds[0].1, Value::Integer(42));
+}
+
+#[test]
+fn agent_addresses_accept_ipv6_literals() {
+    assert_eq!(SnmpClient::agent_addr("10.0.0.1"), "10.0.0.1:161");
+    assert_eq!(SnmpClient::agent_addr("10.0.0.1:1161"), "10.0.0.1:1161");
+    assert_eq!(SnmpClient::agent_addr("fd00::2"), "[fd00::2]:161");
+    assert_eq!(SnmpClient::agent_addr("[fd00::2]"), "[fd00::2]:161");
+    assert_eq!(SnmpClient::agent_addr("[fd00::2]:1161"), "[fd00::2]:1161");
+    assert_eq!(agent_address("proxy.example.com", 1161), "proxy.example.com:1161");
+    assert_eq!(agent_address("fe80::1%eth0", 161), "[fe80::1%eth0]:161");
+}
+
+#[tokio::test]
+async fn ipv6_agents_are_polled_over_an_ipv6_socket() {
+    if std::net::UdpSocket::bind("[::1]:0").is_err() {
+        return; // IPv6가 꺼진 호스트
+    }
+    let client = v2c_client().await;
+    let v6 = TestAgent::new(Vec::new()).spawn_on("[::1]:0");
+    assert!(v6.starts_with("[::1]:"), "{}", v6);
+    assert_eq!(client.get(&v6, CPU_OID).await.unwrap(), 42.0);
+    // 같은 트랜스포트로 IPv4 에이전트도 계속 조회
+    let v4 = TestAgent::new(Vec::new()).spawn();
+    assert_eq!(client.get(&v4, CPU_OID).await.unwrap(), 42.0);
+}
+
+const SYS_UPTIME: &str = "1.3.6.1.2.1.1.3.0";
+const SNMP_TRAP_OID: &str = "1.3.6.1.6.3.1.1.4.1.0";
+const LINK_DOWN: &str = "1.3.6.1.6.3.1.1.5.3";
+
+async fn trap_receiver(users: Vec<SnmpV3Config>) -> (TrapReceiver, std::net::SocketAddr) {
+    let config = TrapConfig {
+        enabled: true,
+        port: 0,
+        bind_address: "127.0.0.1".to_string(),
+        v3_users: users,
+        ..TrapConfig::default()
+    };
+    let receiver = TrapReceiver::bind(&config).await.unwrap();
+    let addr = receiver.local_addr().unwrap();
+    (receiver, addr)
+}
+
+fn notification(pdu_type: u8, request_id: i32, trap_oid: &str) -> Pdu {
+    Pdu {
+        pdu_type,
+        request_id,
+        error_status: 0,
+        error_index: 0,
+        varbinds: vec![
+            (oid(SYS_UPTIME), Value::Timeticks(12345)),
+            (oid(SNMP_TRAP_OID), Value::ObjectIdentifier(oid(trap_oid))),
+            (oid("1.3.6.1.2.1.2.2.1.1.2"), Value::Integer(2)),
+            (oid("1.3.6.1.2.1.2.2.1.2.2"), Value::OctetString(b"eth0".to_vec())),
+        ],
+    }
+}
+
+async fn sender() -> tokio::net::UdpSocket {
+    tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap()
+}
+
+async fn recv_reply(socket: &tokio::net::UdpSocket) -> Vec<u8> {
+    let mut buf = vec![0u8; 65535];
+    let len = tokio::time::timeout(Duration::from_secs(2), socket.recv(&mut buf))
+        .await
+        .expect("no reply from trap receiver")
+        .unwrap();
+    buf.truncate(len);
+    buf
+}
+
+#[tokio::test]
+async fn v2c_trap_and_inform_are_received() {
+    let (receiver, addr) = trap_receiver(Vec::new()).await;
+    let socket = sender().await;
+
+    let trap = pdu::encode_community_message(b"public", &notification(pdu::MSG_TRAP, 1, LINK_DOWN)).unwrap();
+    socket.send_to(&trap, addr).await.unwrap();
+    let event = receiver.recv().await.unwrap();
+    assert_eq!(event.version, "v2c");
+    assert_eq!(event.kind, trap::TrapKind::Trap);
+    assert_eq!(event.name(), "linkDown");
+    assert_eq!(event.severity(), TrapSeverity::Critical);
+    assert_eq!(event.uptime, Some(12345));
+    assert_eq!(event.origin(), socket.local_addr().unwrap().ip());
+    assert_eq!(event.summary(), "1.3.6.1.2.1.2.2.1.1.2=2, 1.3.6.1.2.1.2.2.1.2.2=eth0");
+
+    // 인폼은 같은 request-id의 Response를 받아야 함
+    let inform = notification(pdu::MSG_INFORM, 77, "1.3.6.1.4.1.1230.2.7.0.1");
+    socket.send_to(&pdu::encode_community_message(b"public", &inform).unwrap(), addr).await.unwrap();
+    let (event, reply) = tokio::join!(receiver.recv(), recv_reply(&socket));
+    let event = event.unwrap();
+    assert_eq!(event.kind, trap::TrapKind::Inform);
+    assert_eq!(event.name(), "1.3.6.1.4.1.1230.2.7.0.1");
+    assert_eq!(event.severity(), TrapSeverity::Warning);
+    let (_, _, response) = pdu::decode_community_message(&reply).unwrap();
+    assert_eq!(response.pdu_type, MSG_RESPONSE);
+    assert_eq!(response.request_id, 77);
+    assert_eq!(response.varbinds, inform.varbinds);
+}
+
+#[tokio::test]
+async fn trap_with_wrong_community_is_rejected() {
+    let (receiver, addr) = trap_receiver(Vec::new()).await;
+    let socket = sender().await;
+
+    let trap = pdu::encode_community_message(b"private", &notification(pdu::MSG_TRAP, 1, LINK_DOWN)).unwrap();
+    socket.send_to(&trap, addr).await.unwrap();
+    let err = receiver.recv().await.unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown community"), "{:#}", err);
+
+    // snmpTrapOID가 없는 알림도 거부
+    let mut pdu = notification(pdu::MSG_TRAP, 2, LINK_DOWN);
+    pdu.varbinds.remove(1);
+    socket.send_to(&pdu::encode_community_message(b"public", &pdu).unwrap(), addr).await.unwrap();
+    let err = receiver.recv().await.unwrap_err();
+    assert!(format!("{:#}", err).contains("snmpTrapOID"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn v1_trap_is_converted_to_v2_trap_oid() {
+    let (receiver, addr) = trap_receiver(Vec::new()).await;
+    let socket = sender().await;
+
+    let send = |generic_trap: i64, specific_trap: i64| {
+        let trap = pdu::TrapV1Pdu {
+            enterprise: oid("1.3.6.1.4.1.1230.2.7"),
+            agent_addr: [10, 0, 0, 5],
+            generic_trap,
+            specific_trap,
+            timestamp: 500,
+            varbinds: vec![(oid("1.3.6.1.2.1.2.2.1.1.3"), Value::Integer(3))],
+        };
+        pdu::encode_community_message_any(pdu::VERSION_1, b"public", &pdu::CommunityPdu::V1Trap(trap)).unwrap()
+    };
+
+    socket.send_to(&send(2, 0), addr).await.unwrap();
+    let event = receiver.recv().await.unwrap();
+    assert_eq!(event.version, "v1");
+    assert_eq!(event.trap_oid, oid(LINK_DOWN));
+    assert_eq!(event.uptime, Some(500));
+    // 트랩을 중계한 주소보다 agent-addr를 우선
+    assert_eq!(event.origin().to_string(), "10.0.0.5");
+
+    // enterpriseSpecific: enterprise.0.specific
+    socket.send_to(&send(6, 42), addr).await.unwrap();
+    let event = receiver.recv().await.unwrap();
+    assert_eq!(event.trap_oid, oid("1.3.6.1.4.1.1230.2.7.0.42"));
+}
+
+#[tokio::test]
+async fn v3_trap_is_authenticated_with_sender_engine() {
+    let config = v3_user("trapuser", Some((AuthProtocol::Sha, "authpass1")), Some((PrivProtocol::Aes, "privpass1")));
+    let (receiver, addr) = trap_receiver(vec![config.clone()]).await;
+    let socket = sender().await;
+
+    // 트랩은 송신 측이 authoritative 엔진
+    let scoped = ScopedPdu {
+        context_engine_id: ENGINE_ID.to_vec(),
+        context_name: Vec::new(),
+        pdu: notification(pdu::MSG_TRAP, 5, LINK_DOWN),
+    };
+    let user = UsmUser::localize(&config, ENGINE_ID).unwrap();
+    let trap = usm::seal(&user, ENGINE_ID, 3, 1000, 9, 0, &scoped).unwrap();
+    socket.send_to(&trap, addr).await.unwrap();
+    let event = receiver.recv().await.unwrap();
+    assert_eq!(event.version, "v3");
+    assert_eq!(event.security_name, "trapuser");
+    assert_eq!(event.name(), "linkDown");
+
+    // 비밀번호가 다르면 인증 실패
+    let wrong = v3_user("trapuser", Some((AuthProtocol::Sha, "wrongpass")), Some((PrivProtocol::Aes, "privpass1")));
+    let user = UsmUser::localize(&wrong, ENGINE_ID).unwrap();
+    socket.send_to(&usm::seal(&user, ENGINE_ID, 3, 1000, 10, 0, &scoped).unwrap(), addr).await.unwrap();
+    let err = receiver.recv().await.unwrap_err();
+    assert!(format!("{:#}", err).contains("authentication failed"), "{:#}", err);
+
+    // 설정되지 않은 사용자
+    let unknown = v3_user("nobody", Some((AuthProtocol::Sha, "authpass1")), None);
+    let user = UsmUser::localize(&unknown, ENGINE_ID).unwrap();
+    socket.send_to(&usm::seal(&user, ENGINE_ID, 3, 1000, 11, 0, &scoped).unwrap(), addr).await.unwrap();
+    let err = receiver.recv().await.unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown user name"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn v3_trap_outside_time_window_is_rejected() {
+    let config = v3_user("trapuser", Some((AuthProtocol::Md5, "authpass1")), None);
+    let (receiver, addr) = trap_receiver(vec![config.clone()]).await;
+    let socket = sender().await;
+    let user = UsmUser::localize(&config, ENGINE_ID).unwrap();
+    let trap = |boots: u32, time: u32, msg_id: i32| {
+        let scoped = ScopedPdu {
+            context_engine_id: ENGINE_ID.to_vec(),
+            context_name: Vec::new(),
+            pdu: notification(pdu::MSG_TRAP, msg_id, LINK_DOWN),
+        };
+        usm::seal(&user, ENGINE_ID, boots, time, msg_id, 0, &scoped).unwrap()
+    };
+
+    // 처음 받은 트랩의 engineBoots/engineTime이 기준
+    socket.send_to(&trap(3, 1000, 1), addr).await.unwrap();
+    assert!(receiver.recv().await.is_ok());
+    // 시간 창(150초) 안의 과거 메시지와 이후 메시지는 허용
+    socket.send_to(&trap(3, 900, 2), addr).await.unwrap();
+    assert!(receiver.recv().await.is_ok());
+    socket.send_to(&trap(3, 2000, 3), addr).await.unwrap();
+    assert!(receiver.recv().await.is_ok());
+
+    // 재부팅 전 메시지나 시간 창보다 오래된 메시지(재전송)는 거부
+    for (boots, time) in [(2, 5000), (3, 1000), (2_147_483_647, 0)] {
+        socket.send_to(&trap(boots, time, 4), addr).await.unwrap();
+        let err = receiver.recv().await.unwrap_err();
+        assert!(format!("{:#}", err).contains("not in time window"), "{:#}", err);
+    }
+    // 재부팅 후 메시지는 허용
+    socket.send_to(&trap(4, 10, 5), addr).await.unwrap();
+    assert!(receiver.recv().await.is_ok());
+}
+
+#[tokio::test]
+async fn v3_inform_discovers_receiver_engine() {
+    let config = v3_user("informer", Some((AuthProtocol::Md5, "authpass1")), Some((PrivProtocol::Des, "privpass1")));
+    let (receiver, addr) = trap_receiver(vec![config.clone()]).await;
+    let receiver = Arc::new(receiver);
+    let socket = sender().await;
+
+    // 수신기가 계속 메시지를 처리하도록 백그라운드에서 대기
+    let events = {
+        let receiver = receiver.clone();
+        tokio::spawn(async move { receiver.recv().await })
+    };
+
+    // 1) 엔진 ID discovery
+    let anonymous = UsmUser { name: Vec::new(), auth: None, privacy: None };
+    let probe = ScopedPdu {
+        context_engine_id: Vec::new(),
+        context_name: Vec::new(),
+        pdu: Pdu::request(pdu::MSG_INFORM, 1, &[]),
+    };
+    let discovery = usm::seal(&anonymous, &[], 0, 0, 100, pdu::FLAG_REPORTABLE, &probe).unwrap();
+    socket.send_to(&discovery, addr).await.unwrap();
+    let (report, scoped) = usm::unseal(&anonymous, &recv_reply(&socket).await).unwrap();
+    assert_eq!(scoped.pdu.pdu_type, MSG_REPORT);
+    assert_eq!(UsmReport::from_oid(&scoped.pdu.varbinds[0].0), Some(UsmReport::UnknownEngineId));
+    let engine_id = report.security.engine_id.clone();
+    assert!(!engine_id.is_empty());
+
+    // 2) 수신기 엔진 ID로 지역화한 키로 인폼 전송
+    let user = UsmUser::localize(&config, &engine_id).unwrap();
+    let inform = ScopedPdu {
+        context_engine_id: engine_id.clone(),
+        context_name: Vec::new(),
+        pdu: notification(pdu::MSG_INFORM, 2, "1.3.6.1.6.3.1.1.5.1"),
+    };
+    let message = usm::seal(
+        &user,
+        &engine_id,
+        report.security.engine_boots,
+        report.security.engine_time,
+        101,
+        pdu::FLAG_REPORTABLE,
+        &inform,
+    )
+    .unwrap();
+    socket.send_to(&message, addr).await.unwrap();
+    let (response, scoped) = usm::unseal(&user, &recv_reply(&socket).await).unwrap();
+    assert_eq!(response.msg_id, 101);
+    assert_eq!(scoped.pdu.pdu_type, MSG_RESPONSE);
+    assert_eq!(scoped.pdu.request_id, 2);
+
+    let event = events.await.unwrap().unwrap();
+    assert_eq!(event.kind, trap::TrapKind::Inform);
+    assert_eq!(event.name(), "coldStart");
+}
+
+#[test]
+fn trap_config_validation() {
+    assert!(TrapConfig::default().validate().is_ok());
+    let config = TrapConfig { engine_id: Some("80001f8804".to_string()), ..TrapConfig::default() };
+    assert!(config.validate().is_ok());
+    let config = TrapConfig { engine_id: Some("abc".to_string()), ..TrapConfig::default() };
+    assert!(config.validate().is_err());
+    let config = TrapConfig { engine_id: Some("0102".to_string()), ..TrapConfig::default() };
+    assert!(config.validate().is_err());
+    // 16진수가 아닌 문자(멀티바이트 포함)는 바이트 단위로 자르기 전에 거부
+    let config = TrapConfig { engine_id: Some("80001f88가나".to_string()), ..TrapConfig::default() };
+    assert!(config.validate().unwrap_err().to_string().contains("not hex"));
+    let config = TrapConfig { v3_users: vec![v3_user("u", Some((AuthProtocol::Md5, "short")), None)], ..TrapConfig::default() };
+    assert!(config.validate().is_err());
+}
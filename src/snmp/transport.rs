@@ -0,0 +1,157 @@
+use super::pdu;
+use super::SnmpOptions;
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, Weak};
+use tokio::net::UdpSocket;
+use tokio::sync::oneshot;
+
+/// 응답을 기다리는 요청들: 요청 ID(v2c request-id / v3 msgID) -> (요청을 보낸 에이전트 주소, 응답 전달 채널)
+type PendingMap = Mutex<HashMap<i32, (SocketAddr, oneshot::Sender<Vec<u8>>)>>;
+
+/// 모든 SNMP 요청이 공유하는 비동기 UDP 소켓 (주소 체계별로 하나씩)
+///
+/// 소켓마다 수신 태스크가 응답을 읽어 요청 ID로 대기 중인 요청에 전달합니다.
+/// 요청을 보낸 에이전트가 아닌 주소에서 온 응답은 버립니다.
+pub struct SnmpTransport {
+    v4: Arc<UdpSocket>,
+    /// IPv6를 쓸 수 없는 호스트에서는 없음 (IPv4 에이전트는 그대로 수집)
+    v6: Option<Arc<UdpSocket>>,
+    pending: Arc<PendingMap>,
+}
+
+impl SnmpTransport {
+    /// 새 소켓을 열고 수신 태스크를 시작합니다. (토키오 런타임 안에서 호출)
+    pub async fn bind() -> Result<Self> {
+        let v4 = Arc::new(
+            UdpSocket::bind("0.0.0.0:0")
+                .await
+                .context("Failed to bind SNMP UDP socket")?,
+        );
+        let v6 = UdpSocket::bind("[::]:0").await.ok().map(Arc::new);
+        let pending: Arc<PendingMap> = Arc::new(Mutex::new(HashMap::new()));
+        for socket in std::iter::once(&v4).chain(v6.as_ref()) {
+            spawn_receiver(socket.clone(), Arc::downgrade(&pending));
+        }
+        Ok(Self { v4, v6, pending })
+    }
+
+    /// IPv4 소켓의 로컬 주소
+    #[cfg(test)]
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.v4.local_addr()?)
+    }
+
+    /// 대상 주소 체계에 맞는 소켓
+    fn socket_for(&self, target: SocketAddr) -> Result<&UdpSocket> {
+        match target {
+            SocketAddr::V4(_) => Ok(&self.v4),
+            SocketAddr::V6(_) => self
+                .v6
+                .as_deref()
+                .with_context(|| format!("No IPv6 socket available for SNMP agent {}", target)),
+        }
+    }
+
+    /// 프로세스 전체에서 공유하는 트랜스포트
+    pub async fn shared() -> Result<Arc<Self>> {
+        static SHARED: tokio::sync::OnceCell<Arc<SnmpTransport>> = tokio::sync::OnceCell::const_new();
+        SHARED
+            .get_or_try_init(|| async { Ok(Arc::new(Self::bind().await?)) })
+            .await
+            .cloned()
+    }
+
+    /// 요청을 보내고 같은 ID의 응답을 기다립니다.
+    /// 응답이 없으면 `options`에 따라 같은 메시지를 재전송하며, 재시도마다 대기 시간을 늘립니다.
+    pub async fn exchange(
+        &self,
+        target: SocketAddr,
+        id: i32,
+        message: &[u8],
+        options: &SnmpOptions,
+    ) -> Result<Vec<u8>> {
+        let socket = self.socket_for(target)?;
+        let (sender, mut receiver) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, (target, sender));
+        // 타임아웃이나 취소 시에도 대기 목록에서 제거
+        let _guard = PendingGuard { pending: &self.pending, id };
+
+        let mut wait = options.timeout;
+        for _ in 0..=options.retries {
+            socket
+                .send_to(message, target)
+                .await
+                .with_context(|| format!("Failed to send SNMP request to {}", target))?;
+            match tokio::time::timeout(wait, &mut receiver).await {
+                Ok(Ok(response)) => return Ok(response),
+                Ok(Err(_)) => bail!("SNMP transport closed"),
+                Err(_) => wait = wait.mul_f64(options.backoff.max(1.0)),
+            }
+        }
+        bail!(
+            "SNMP request timed out after {} attempt(s) ({:?} total)",
+            options.retries + 1,
+            options.max_duration()
+        )
+    }
+}
+
+/// 소켓에서 응답을 읽어 대기 중인 요청에 전달하는 태스크 (트랜스포트가 해제되면 종료)
+fn spawn_receiver(socket: Arc<UdpSocket>, pending: Weak<PendingMap>) {
+    tokio::spawn(async move {
+        let mut buf = vec![0u8; pdu::MAX_MESSAGE_SIZE as usize];
+        loop {
+            let (len, source) = match socket.recv_from(&mut buf).await {
+                Ok(received) => received,
+                // ICMP port unreachable 등은 해당 요청의 타임아웃으로 처리
+                Err(_) => continue,
+            };
+            let Some(pending) = pending.upgrade() else {
+                break;
+            };
+            let Some(id) = response_id(&buf[..len]) else {
+                continue;
+            };
+            // 이미 응답을 받았거나 포기한 요청의 늦은 응답, 다른 호스트가 요청 ID를 맞춰 보낸 응답은 버림
+            let mut pending = pending.lock().unwrap();
+            if pending.get(&id).is_some_and(|(target, _)| *target == source) {
+                if let Some((_, sender)) = pending.remove(&id) {
+                    let _ = sender.send(buf[..len].to_vec());
+                }
+            }
+        }
+    });
+}
+
+struct PendingGuard<'a> {
+    pending: &'a PendingMap,
+    id: i32,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.pending.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// 응답 메시지에서 요청 매칭용 ID를 읽습니다. (v3는 암호화되어 있어도 msgID는 평문)
+fn response_id(data: &[u8]) -> Option<i32> {
+    if pdu::peek_version(data).ok()? == pdu::VERSION_3 {
+        let (message, _, _) = pdu::V3Message::decode(data).ok()?;
+        Some(message.msg_id)
+    } else {
+        let (_, _, pdu) = pdu::decode_community_message(data).ok()?;
+        Some(pdu.request_id)
+    }
+}
+
+/// "host:port"(IPv6는 "[addr]:port")를 소켓 주소로 변환합니다.
+pub async fn resolve(agent_addr: &str) -> Result<SocketAddr> {
+    tokio::net::lookup_host(agent_addr)
+        .await
+        .with_context(|| format!("Failed to resolve SNMP agent address: {}", agent_addr))?
+        .next()
+        .with_context(|| format!("No address found for SNMP agent: {}", agent_addr))
+}
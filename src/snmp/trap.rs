@@ -0,0 +1,447 @@
+use super::pdu::{
+    self, CommunityPdu, Pdu, ScopedPdu, ScopedPduData, TrapV1Pdu, V3Message, FLAG_AUTH, FLAG_PRIV,
+    FLAG_REPORTABLE, MSG_INFORM, MSG_REPORT, MSG_RESPONSE, MSG_TRAP, VERSION_1, VERSION_2C, VERSION_3,
+};
+use super::usm::{self, UsmReport, UsmUser};
+use super::{SnmpV3Config, Value};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Mutex;
+use std::time::Instant;
+use tokio::net::UdpSocket;
+
+/// sysUpTime.0
+const SYS_UPTIME_OID: [u32; 9] = [1, 3, 6, 1, 2, 1, 1, 3, 0];
+/// snmpTrapOID.0
+const SNMP_TRAP_OID: [u32; 11] = [1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+/// snmpTraps (coldStart.1 ~ egpNeighborLoss.6)
+const SNMP_TRAPS_PREFIX: [u32; 9] = [1, 3, 6, 1, 6, 3, 1, 1, 5];
+
+/// 인폼 수신 시 사용하는 로컬 엔진의 engineBoots (재시작 횟수를 저장하지 않으므로 고정)
+const LOCAL_ENGINE_BOOTS: u32 = 1;
+/// RFC 3414 시간 창 (초)
+const TIME_WINDOW_SECS: u32 = 150;
+/// engineBoots 최댓값 (이 값이면 재부팅 전까지 어떤 메시지도 시간 창 안에 있지 않음)
+const MAX_ENGINE_BOOTS: u32 = 2_147_483_647;
+/// engine_id 미설정 시 사용하는 로컬 엔진 ID (net-snmp enterprise, text 형식)
+const DEFAULT_ENGINE_ID: &[u8] = b"\x80\x00\x1f\x88\x04mwg-monitor";
+
+/// 트랩 수신기 설정 (resource_config.json의 `trap` 블록)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TrapConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub bind_address: String,
+    /// v1/v2c 트랩 커뮤니티 (빈 문자열이면 v1/v2c 트랩을 받지 않음)
+    pub community: String,
+    /// v3 트랩/인폼을 보낼 수 있는 USM 사용자
+    pub v3_users: Vec<SnmpV3Config>,
+    /// v3 인폼 수신용 로컬 엔진 ID (16진수 문자열)
+    pub engine_id: Option<String>,
+}
+
+impl Default for TrapConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 162,
+            bind_address: "0.0.0.0".to_string(),
+            community: "public".to_string(),
+            v3_users: Vec::new(),
+            engine_id: None,
+        }
+    }
+}
+
+impl TrapConfig {
+    pub fn validate(&self) -> Result<()> {
+        for user in &self.v3_users {
+            user.validate()?;
+        }
+        self.local_engine_id()?;
+        Ok(())
+    }
+
+    /// 로컬 엔진 ID (RFC 3411: 5~32바이트)
+    fn local_engine_id(&self) -> Result<Vec<u8>> {
+        let Some(hex) = &self.engine_id else {
+            return Ok(DEFAULT_ENGINE_ID.to_vec());
+        };
+        let hex = hex.trim_start_matches("0x");
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("Invalid trap engine_id (not hex): {}", hex);
+        }
+        if hex.len() % 2 != 0 {
+            bail!("Invalid trap engine_id (odd number of hex digits): {}", hex);
+        }
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .with_context(|| format!("Invalid trap engine_id (not hex): {}", hex))?;
+        if !(5..=32).contains(&bytes.len()) {
+            bail!("Invalid trap engine_id length: {} bytes (must be 5-32)", bytes.len());
+        }
+        Ok(bytes)
+    }
+}
+
+/// 트랩 심각도
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TrapSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl TrapSeverity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrapSeverity::Info => "정보",
+            TrapSeverity::Warning => "경고",
+            TrapSeverity::Critical => "심각",
+        }
+    }
+}
+
+/// 알림 종류 (인폼은 응답을 보냄)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrapKind {
+    Trap,
+    Inform,
+}
+
+impl TrapKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrapKind::Trap => "trap",
+            TrapKind::Inform => "inform",
+        }
+    }
+}
+
+/// 수신한 트랩/인폼 (v1 트랩은 RFC 3584에 따라 v2 형식의 snmpTrapOID로 변환)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrapEvent {
+    pub source: SocketAddr,
+    /// v1 트랩의 agent-addr (0.0.0.0이 아닐 때)
+    pub agent_addr: Option<Ipv4Addr>,
+    pub version: &'static str,
+    pub kind: TrapKind,
+    /// 커뮤니티 또는 v3 사용자 이름
+    pub security_name: String,
+    pub trap_oid: Vec<u32>,
+    pub uptime: Option<u32>,
+    pub varbinds: Vec<(Vec<u32>, Value)>,
+}
+
+impl TrapEvent {
+    /// 트랩을 보낸 장비의 주소 (v1 agent-addr 우선)
+    pub fn origin(&self) -> IpAddr {
+        match self.agent_addr {
+            Some(ip) => IpAddr::V4(ip),
+            None => self.source.ip().to_canonical(),
+        }
+    }
+
+    /// 표준 트랩은 이름, 그 외는 점 표기 OID
+    pub fn name(&self) -> String {
+        match self.standard_trap() {
+            Some(1) => "coldStart".to_string(),
+            Some(2) => "warmStart".to_string(),
+            Some(3) => "linkDown".to_string(),
+            Some(4) => "linkUp".to_string(),
+            Some(5) => "authenticationFailure".to_string(),
+            Some(6) => "egpNeighborLoss".to_string(),
+            _ => self.trap_oid_string(),
+        }
+    }
+
+    /// 링크 다운은 심각, 재시작/인증 실패와 벤더 트랩은 경고, 복구 알림은 정보
+    pub fn severity(&self) -> TrapSeverity {
+        match self.standard_trap() {
+            Some(3) => TrapSeverity::Critical,
+            Some(2) | Some(4) => TrapSeverity::Info,
+            _ => TrapSeverity::Warning,
+        }
+    }
+
+    pub fn trap_oid_string(&self) -> String {
+        super::ber::format_oid(&self.trap_oid)
+    }
+
+    /// sysUpTime/snmpTrapOID를 제외한 varbind 요약 ("oid=value, ...")
+    pub fn summary(&self) -> String {
+        self.varbinds
+            .iter()
+            .filter(|(oid, _)| oid[..] != SYS_UPTIME_OID && oid[..] != SNMP_TRAP_OID)
+            .map(|(oid, value)| format!("{}={}", super::ber::format_oid(oid), value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn standard_trap(&self) -> Option<u32> {
+        if self.trap_oid.len() == SNMP_TRAPS_PREFIX.len() + 1 && self.trap_oid.starts_with(&SNMP_TRAPS_PREFIX) {
+            self.trap_oid.last().copied()
+        } else {
+            None
+        }
+    }
+
+    /// SNMPv2-Trap / InformRequest PDU에서 이벤트를 만듭니다.
+    fn from_pdu(source: SocketAddr, version: &'static str, security_name: String, pdu: Pdu) -> Result<Self> {
+        let kind = match pdu.pdu_type {
+            MSG_TRAP => TrapKind::Trap,
+            MSG_INFORM => TrapKind::Inform,
+            other => bail!("Unexpected PDU type for a notification: 0x{:02x}", other),
+        };
+        let trap_oid = pdu
+            .varbinds
+            .iter()
+            .find_map(|(oid, value)| match value {
+                Value::ObjectIdentifier(trap_oid) if oid[..] == SNMP_TRAP_OID => Some(trap_oid.clone()),
+                _ => None,
+            })
+            .context("Notification without snmpTrapOID.0")?;
+        let uptime = pdu.varbinds.iter().find_map(|(oid, value)| match value {
+            Value::Timeticks(ticks) if oid[..] == SYS_UPTIME_OID => Some(*ticks),
+            _ => None,
+        });
+        Ok(Self {
+            source,
+            agent_addr: None,
+            version,
+            kind,
+            security_name,
+            trap_oid,
+            uptime,
+            varbinds: pdu.varbinds,
+        })
+    }
+
+    /// v1 Trap-PDU에서 이벤트를 만듭니다. (RFC 3584 3.1)
+    fn from_v1(source: SocketAddr, community: String, trap: TrapV1Pdu) -> Self {
+        let trap_oid = if (0..=5).contains(&trap.generic_trap) {
+            let mut oid = SNMP_TRAPS_PREFIX.to_vec();
+            oid.push(trap.generic_trap as u32 + 1);
+            oid
+        } else {
+            let mut oid = trap.enterprise.clone();
+            oid.extend_from_slice(&[0, trap.specific_trap as u32]);
+            oid
+        };
+        let agent_addr = Some(Ipv4Addr::from(trap.agent_addr)).filter(|ip| !ip.is_unspecified());
+        Self {
+            source,
+            agent_addr,
+            version: "v1",
+            kind: TrapKind::Trap,
+            security_name: community,
+            trap_oid,
+            uptime: Some(trap.timestamp),
+            varbinds: trap.varbinds,
+        }
+    }
+}
+
+/// SNMP 트랩/인폼 수신기
+///
+/// v1/v2c는 커뮤니티로, v3는 설정된 USM 사용자로 검증합니다.
+/// 인폼에는 Response를 돌려주며, v3 인폼 송신 측의 엔진 discovery에도 응답합니다.
+pub struct TrapReceiver {
+    socket: UdpSocket,
+    community: Vec<u8>,
+    users: Vec<SnmpV3Config>,
+    engine_id: Vec<u8>,
+    started: Instant,
+    /// 트랩 송신 엔진별로 마지막으로 받은 (engineBoots, engineTime, 받은 시각)
+    sender_times: Mutex<HashMap<Vec<u8>, (u32, u32, Instant)>>,
+}
+
+impl TrapReceiver {
+    pub async fn bind(config: &TrapConfig) -> Result<Self> {
+        config.validate()?;
+        let addr = format!("{}:{}", config.bind_address, config.port);
+        let socket = UdpSocket::bind(&addr)
+            .await
+            .with_context(|| format!("Failed to bind SNMP trap listener on {}", addr))?;
+        Ok(Self {
+            socket,
+            community: config.community.as_bytes().to_vec(),
+            users: config.v3_users.clone(),
+            engine_id: config.local_engine_id()?,
+            started: Instant::now(),
+            sender_times: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// 다음 트랩을 기다립니다.
+    /// 검증에 실패한 메시지는 에러로 반환하므로, 호출 측은 기록 후 계속 호출하면 됩니다.
+    pub async fn recv(&self) -> Result<TrapEvent> {
+        let mut buf = vec![0u8; pdu::MAX_MESSAGE_SIZE as usize];
+        loop {
+            let (len, source) = self
+                .socket
+                .recv_from(&mut buf)
+                .await
+                .context("Failed to receive SNMP trap")?;
+            let (event, reply) = self
+                .handle(&buf[..len], source)
+                .with_context(|| format!("Rejected SNMP message from {}", source))?;
+            if let Some(reply) = reply {
+                self.socket
+                    .send_to(&reply, source)
+                    .await
+                    .with_context(|| format!("Failed to send inform response to {}", source))?;
+            }
+            // discovery 요청 등 이벤트가 아닌 메시지는 응답만 하고 계속 대기
+            if let Some(event) = event {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// 메시지 하나를 처리해 (이벤트, 응답 메시지)를 반환합니다.
+    fn handle(&self, data: &[u8], source: SocketAddr) -> Result<(Option<TrapEvent>, Option<Vec<u8>>)> {
+        if pdu::peek_version(data)? == VERSION_3 {
+            return self.handle_v3(data, source);
+        }
+
+        let (version, community, message) = pdu::decode_community_message_any(data)?;
+        if self.community.is_empty() || community != self.community {
+            bail!("unknown community '{}'", String::from_utf8_lossy(&community));
+        }
+        let community = String::from_utf8_lossy(&community).to_string();
+        match (version, message) {
+            (VERSION_1, CommunityPdu::V1Trap(trap)) => Ok((Some(TrapEvent::from_v1(source, community, trap)), None)),
+            (VERSION_2C, CommunityPdu::Standard(message)) => {
+                let reply = if message.pdu_type == MSG_INFORM {
+                    Some(pdu::encode_community_message(self.community.as_slice(), &inform_response(&message))?)
+                } else {
+                    None
+                };
+                Ok((Some(TrapEvent::from_pdu(source, "v2c", community, message)?), reply))
+            }
+            (version, _) => bail!("Unsupported notification (version {})", version),
+        }
+    }
+
+    fn handle_v3(&self, data: &[u8], source: SocketAddr) -> Result<(Option<TrapEvent>, Option<Vec<u8>>)> {
+        let (message, _, _) = V3Message::decode(data)?;
+        let security = &message.security;
+
+        // 인폼 송신 측의 엔진 ID discovery: unknownEngineID Report로 우리 엔진 정보를 알려줌
+        if security.engine_id.is_empty() {
+            if message.flags & FLAG_REPORTABLE == 0 {
+                bail!("SNMPv3 message without engine ID");
+            }
+            let request_id = match &message.data {
+                ScopedPduData::Plaintext(scoped) => scoped.pdu.request_id,
+                ScopedPduData::Encrypted(_) => 0,
+            };
+            let anonymous = UsmUser { name: security.user_name.clone(), auth: None, privacy: None };
+            let report = self.report(&anonymous, message.msg_id, request_id, UsmReport::UnknownEngineId)?;
+            return Ok((None, Some(report)));
+        }
+
+        let name = String::from_utf8_lossy(&security.user_name).to_string();
+        let config = self
+            .users
+            .iter()
+            .find(|user| user.username == name)
+            .with_context(|| format!("SNMPv3 {}: {}", UsmReport::UnknownUserName.description(), name))?;
+        // 트랩은 송신 측, 인폼은 수신 측(우리) 엔진 ID로 키를 지역화
+        let user = UsmUser::localize(config, &security.engine_id)?;
+        if message.flags & (FLAG_AUTH | FLAG_PRIV) != user.security_flags() {
+            bail!("SNMPv3 {} (user: {})", UsmReport::UnsupportedSecurityLevel.description(), name);
+        }
+        let (message, scoped) = usm::unseal(&user, data)?;
+
+        if scoped.pdu.pdu_type != MSG_INFORM {
+            if message.flags & FLAG_AUTH != 0 {
+                self.check_sender_time_window(&message.security)?;
+            }
+            return Ok((Some(TrapEvent::from_pdu(source, "v3", name, scoped.pdu)?), None));
+        }
+
+        if message.security.engine_id != self.engine_id {
+            bail!("SNMPv3 inform for {}", UsmReport::UnknownEngineId.description());
+        }
+        if message.security.engine_boots != LOCAL_ENGINE_BOOTS
+            || message.security.engine_time.abs_diff(self.engine_time()) > TIME_WINDOW_SECS
+        {
+            // 송신 측이 Report의 시간으로 재동기화 후 재전송 (Report는 암호화하지 않음)
+            let auth_only = UsmUser { privacy: None, ..user };
+            let report = self.report(&auth_only, message.msg_id, scoped.pdu.request_id, UsmReport::NotInTimeWindow)?;
+            return Ok((None, Some(report)));
+        }
+
+        let response = ScopedPdu {
+            context_engine_id: scoped.context_engine_id.clone(),
+            context_name: scoped.context_name.clone(),
+            pdu: inform_response(&scoped.pdu),
+        };
+        let reply = usm::seal(&user, &self.engine_id, LOCAL_ENGINE_BOOTS, self.engine_time(), message.msg_id, 0, &response)?;
+        Ok((Some(TrapEvent::from_pdu(source, "v3", name, scoped.pdu)?), Some(reply)))
+    }
+
+    /// 인증된 트랩의 시간 창 검사 (RFC 3414 3.2 7b, 수신기가 non-authoritative인 경우)
+    ///
+    /// 송신 엔진에서 처음 받은 트랩의 engineBoots/engineTime을 기억해 두고, 그보다 오래된
+    /// 메시지(재부팅 전 또는 시간 창보다 과거)는 재전송 공격으로 보고 거부합니다.
+    fn check_sender_time_window(&self, security: &pdu::UsmSecurityParameters) -> Result<()> {
+        let (boots, time) = (security.engine_boots, security.engine_time);
+        let mut sender_times = self.sender_times.lock().unwrap();
+        let in_window = match sender_times.get(&security.engine_id) {
+            _ if boots == MAX_ENGINE_BOOTS => false,
+            None => true,
+            Some(&(latest_boots, latest_time, received)) => {
+                let estimated = latest_time.saturating_add(received.elapsed().as_secs() as u32);
+                boots > latest_boots || (boots == latest_boots && time.saturating_add(TIME_WINDOW_SECS) >= estimated)
+            }
+        };
+        if !in_window {
+            bail!("SNMPv3 trap {} (engineBoots {}, engineTime {})", UsmReport::NotInTimeWindow.description(), boots, time);
+        }
+        let newer = match sender_times.get(&security.engine_id) {
+            Some(&(latest_boots, latest_time, _)) => boots > latest_boots || (boots == latest_boots && time > latest_time),
+            None => true,
+        };
+        if newer {
+            sender_times.insert(security.engine_id.clone(), (boots, time, Instant::now()));
+        }
+        Ok(())
+    }
+
+    fn engine_time(&self) -> u32 {
+        self.started.elapsed().as_secs() as u32
+    }
+
+    /// 로컬 엔진 이름으로 USM Report를 만듭니다.
+    fn report(&self, user: &UsmUser, msg_id: i32, request_id: i32, kind: UsmReport) -> Result<Vec<u8>> {
+        let scoped = ScopedPdu {
+            context_engine_id: self.engine_id.clone(),
+            context_name: Vec::new(),
+            pdu: Pdu {
+                pdu_type: MSG_REPORT,
+                request_id,
+                error_status: 0,
+                error_index: 0,
+                varbinds: vec![(kind.oid(), Value::Counter32(1))],
+            },
+        };
+        usm::seal(user, &self.engine_id, LOCAL_ENGINE_BOOTS, self.engine_time(), msg_id, 0, &scoped)
+    }
+}
+
+/// 인폼에 대한 Response (같은 request-id와 varbind를 돌려줌)
+fn inform_response(inform: &Pdu) -> Pdu {
+    Pdu { pdu_type: MSG_RESPONSE, error_status: 0, error_index: 0, ..inform.clone() }
+}
@@ -0,0 +1,395 @@
+use super::pdu::{ScopedPdu, ScopedPduData, UsmSecurityParameters, V3Message, FLAG_AUTH, FLAG_PRIV};
+use super::{AuthProtocol, PrivProtocol, SnmpV3Config};
+use anyhow::{bail, Context, Result};
+use hmac::{Hmac, Mac};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// HMAC-96 인증 파라미터 길이
+const AUTH_PARAMS_LEN: usize = 12;
+
+/// usmStats 카운터 OID (Report PDU로 전달되는 USM 오류)
+const USM_STATS_PREFIX: [u32; 9] = [1, 3, 6, 1, 6, 3, 15, 1, 1];
+
+/// Report PDU로 전달된 USM 오류 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsmReport {
+    UnsupportedSecurityLevel,
+    NotInTimeWindow,
+    UnknownUserName,
+    UnknownEngineId,
+    WrongDigest,
+    DecryptionError,
+}
+
+impl UsmReport {
+    /// Report varbind의 OID로 오류 종류를 판별합니다.
+    pub fn from_oid(oid: &[u32]) -> Option<Self> {
+        if oid.len() < 10 || oid[..9] != USM_STATS_PREFIX {
+            return None;
+        }
+        match oid[9] {
+            1 => Some(UsmReport::UnsupportedSecurityLevel),
+            2 => Some(UsmReport::NotInTimeWindow),
+            3 => Some(UsmReport::UnknownUserName),
+            4 => Some(UsmReport::UnknownEngineId),
+            5 => Some(UsmReport::WrongDigest),
+            6 => Some(UsmReport::DecryptionError),
+            _ => None,
+        }
+    }
+
+    /// 에이전트가 Report에 넣을 usmStats OID
+    pub fn oid(&self) -> Vec<u32> {
+        let index = match self {
+            UsmReport::UnsupportedSecurityLevel => 1,
+            UsmReport::NotInTimeWindow => 2,
+            UsmReport::UnknownUserName => 3,
+            UsmReport::UnknownEngineId => 4,
+            UsmReport::WrongDigest => 5,
+            UsmReport::DecryptionError => 6,
+        };
+        let mut oid = USM_STATS_PREFIX.to_vec();
+        oid.extend_from_slice(&[index, 0]);
+        oid
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            UsmReport::UnsupportedSecurityLevel => "unsupported security level",
+            UsmReport::NotInTimeWindow => "not in time window",
+            UsmReport::UnknownUserName => "unknown user name",
+            UsmReport::UnknownEngineId => "unknown engine ID",
+            UsmReport::WrongDigest => "wrong digest (check auth protocol/password)",
+            UsmReport::DecryptionError => "decryption error (check privacy protocol/password)",
+        }
+    }
+}
+
+/// 에이전트(authoritative engine)의 엔진 정보
+#[derive(Debug, Clone)]
+pub struct EngineState {
+    pub engine_id: Vec<u8>,
+    pub boots: u32,
+    pub time: u32,
+    pub synced_at: Instant,
+}
+
+impl EngineState {
+    pub fn new(engine_id: Vec<u8>, boots: u32, time: u32) -> Self {
+        Self { engine_id, boots, time, synced_at: Instant::now() }
+    }
+
+    /// 마지막 동기화 이후 경과 시간을 반영한 현재 engineTime
+    pub fn current_time(&self) -> u32 {
+        self.time.saturating_add(self.synced_at.elapsed().as_secs() as u32)
+    }
+}
+
+/// 에이전트 주소별 엔진 정보 캐시 (discovery는 에이전트당 한 번만 수행)
+fn get_engine_cache() -> &'static Mutex<HashMap<String, EngineState>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, EngineState>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn cached_engine(agent_addr: &str) -> Option<EngineState> {
+    get_engine_cache().lock().unwrap().get(agent_addr).cloned()
+}
+
+pub fn cache_engine(agent_addr: &str, engine: EngineState) {
+    get_engine_cache().lock().unwrap().insert(agent_addr.to_string(), engine);
+}
+
+pub fn forget_engine(agent_addr: &str) {
+    get_engine_cache().lock().unwrap().remove(agent_addr);
+}
+
+/// 비밀번호 -> 키 변환 결과 캐시 (1MB 해시 연산이므로 재사용)
+type KeyCache = Mutex<HashMap<(AuthProtocol, String), Vec<u8>>>;
+
+fn get_key_cache() -> &'static KeyCache {
+    static CACHE: OnceLock<KeyCache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn digest(protocol: AuthProtocol, chunks: &[&[u8]]) -> Vec<u8> {
+    use md5::Digest;
+    match protocol {
+        AuthProtocol::Md5 => {
+            let mut hasher = md5::Md5::new();
+            for chunk in chunks {
+                hasher.update(chunk);
+            }
+            hasher.finalize().to_vec()
+        }
+        AuthProtocol::Sha => {
+            let mut hasher = sha1::Sha1::new();
+            for chunk in chunks {
+                hasher.update(chunk);
+            }
+            hasher.finalize().to_vec()
+        }
+    }
+}
+
+/// RFC 3414 A.2 password-to-key 알고리즘
+pub fn password_to_key(protocol: AuthProtocol, password: &str) -> Vec<u8> {
+    let cache_key = (protocol, password.to_string());
+    if let Some(key) = get_key_cache().lock().unwrap().get(&cache_key) {
+        return key.clone();
+    }
+
+    const EXPANDED_LEN: usize = 1024 * 1024;
+    let password = password.as_bytes();
+    let expanded: Vec<u8> = password.iter().copied().cycle().take(EXPANDED_LEN).collect();
+    let key = digest(protocol, &[&expanded]);
+
+    get_key_cache().lock().unwrap().insert(cache_key, key.clone());
+    key
+}
+
+/// 키를 에이전트 엔진 ID로 지역화합니다.
+pub fn localize_key(protocol: AuthProtocol, key: &[u8], engine_id: &[u8]) -> Vec<u8> {
+    digest(protocol, &[key, engine_id, key])
+}
+
+fn hmac_96(protocol: AuthProtocol, key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut full = match protocol {
+        AuthProtocol::Md5 => {
+            let mut mac = Hmac::<md5::Md5>::new_from_slice(key).expect("HMAC accepts any key length");
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }
+        AuthProtocol::Sha => {
+            let mut mac = Hmac::<sha1::Sha1>::new_from_slice(key).expect("HMAC accepts any key length");
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }
+    };
+    full.truncate(AUTH_PARAMS_LEN);
+    full
+}
+
+/// 엔진 ID로 지역화된 키를 가진 USM 사용자
+#[derive(Debug, Clone)]
+pub struct UsmUser {
+    pub name: Vec<u8>,
+    pub auth: Option<(AuthProtocol, Vec<u8>)>,
+    pub privacy: Option<(PrivProtocol, Vec<u8>)>,
+}
+
+impl UsmUser {
+    pub fn localize(config: &SnmpV3Config, engine_id: &[u8]) -> Result<Self> {
+        config.validate()?;
+        let auth = match (config.auth_protocol, &config.auth_password) {
+            (Some(protocol), Some(password)) => {
+                let key = localize_key(protocol, &password_to_key(protocol, password), engine_id);
+                Some((protocol, key))
+            }
+            _ => None,
+        };
+        let privacy = match (config.priv_protocol, &config.priv_password, &auth) {
+            (Some(protocol), Some(password), Some((auth_protocol, _))) => {
+                // 프라이버시 키도 인증 프로토콜의 해시로 지역화
+                let key = localize_key(*auth_protocol, &password_to_key(*auth_protocol, password), engine_id);
+                Some((protocol, key))
+            }
+            _ => None,
+        };
+        Ok(Self { name: config.username.as_bytes().to_vec(), auth, privacy })
+    }
+
+    /// 이 사용자의 보안 수준에 맞는 msgFlags (reportable 제외)
+    pub fn security_flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.auth.is_some() {
+            flags |= FLAG_AUTH;
+        }
+        if self.privacy.is_some() {
+            flags |= FLAG_PRIV;
+        }
+        flags
+    }
+}
+
+/// 프라이버시 salt 카운터 (재사용되지 않도록 시작값을 시간으로 초기화)
+fn next_salt() -> u64 {
+    static SALT: OnceLock<AtomicU64> = OnceLock::new();
+    SALT.get_or_init(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        AtomicU64::new(seed)
+    })
+    .fetch_add(1, Ordering::Relaxed)
+}
+
+fn encrypt(
+    protocol: PrivProtocol,
+    key: &[u8],
+    boots: u32,
+    time: u32,
+    plaintext: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    use aes::cipher::{AsyncStreamCipher, BlockEncryptMut, KeyIvInit};
+    let salt = next_salt();
+    match protocol {
+        PrivProtocol::Des => {
+            // RFC 3414 8.1.1.1: salt = engineBoots || 로컬 카운터, IV = pre-IV XOR salt
+            let salt_bytes = [boots.to_be_bytes(), (salt as u32).to_be_bytes()].concat();
+            let iv: Vec<u8> = key[8..16].iter().zip(&salt_bytes).map(|(a, b)| a ^ b).collect();
+            let mut buf = plaintext.to_vec();
+            buf.resize(plaintext.len().div_ceil(8) * 8, 0);
+            let len = buf.len();
+            cbc::Encryptor::<des::Des>::new_from_slices(&key[..8], &iv)
+                .map_err(|e| anyhow::anyhow!("DES init failed: {}", e))?
+                .encrypt_padded_mut::<aes::cipher::block_padding::NoPadding>(&mut buf, len)
+                .map_err(|e| anyhow::anyhow!("DES encryption failed: {}", e))?;
+            Ok((buf, salt_bytes))
+        }
+        PrivProtocol::Aes => {
+            // RFC 3826 3.1.2.1: IV = engineBoots || engineTime || 64비트 salt
+            let salt_bytes = salt.to_be_bytes().to_vec();
+            let iv = [&boots.to_be_bytes()[..], &time.to_be_bytes()[..], &salt_bytes[..]].concat();
+            let mut buf = plaintext.to_vec();
+            cfb_mode::Encryptor::<aes::Aes128>::new_from_slices(&key[..16], &iv)
+                .map_err(|e| anyhow::anyhow!("AES init failed: {}", e))?
+                .encrypt(&mut buf);
+            Ok((buf, salt_bytes))
+        }
+    }
+}
+
+fn decrypt(
+    protocol: PrivProtocol,
+    key: &[u8],
+    boots: u32,
+    time: u32,
+    priv_params: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>> {
+    use aes::cipher::{AsyncStreamCipher, BlockDecryptMut, KeyIvInit};
+    if priv_params.len() != 8 {
+        bail!("Invalid privacy parameters length: {}", priv_params.len());
+    }
+    match protocol {
+        PrivProtocol::Des => {
+            if !ciphertext.len().is_multiple_of(8) {
+                bail!("DES ciphertext length is not a multiple of 8");
+            }
+            let iv: Vec<u8> = key[8..16].iter().zip(priv_params).map(|(a, b)| a ^ b).collect();
+            let mut buf = ciphertext.to_vec();
+            cbc::Decryptor::<des::Des>::new_from_slices(&key[..8], &iv)
+                .map_err(|e| anyhow::anyhow!("DES init failed: {}", e))?
+                .decrypt_padded_mut::<aes::cipher::block_padding::NoPadding>(&mut buf)
+                .map_err(|e| anyhow::anyhow!("DES decryption failed: {}", e))?;
+            Ok(buf)
+        }
+        PrivProtocol::Aes => {
+            let iv = [&boots.to_be_bytes()[..], &time.to_be_bytes()[..], priv_params].concat();
+            let mut buf = ciphertext.to_vec();
+            cfb_mode::Decryptor::<aes::Aes128>::new_from_slices(&key[..16], &iv)
+                .map_err(|e| anyhow::anyhow!("AES init failed: {}", e))?
+                .decrypt(&mut buf);
+            Ok(buf)
+        }
+    }
+}
+
+/// scopedPDU를 사용자 보안 수준에 맞게 암호화/서명하여 메시지로 만듭니다.
+pub fn seal(
+    user: &UsmUser,
+    engine_id: &[u8],
+    boots: u32,
+    time: u32,
+    msg_id: i32,
+    extra_flags: u8,
+    scoped: &ScopedPdu,
+) -> Result<Vec<u8>> {
+    let mut security = UsmSecurityParameters {
+        engine_id: engine_id.to_vec(),
+        engine_boots: boots,
+        engine_time: time,
+        user_name: user.name.clone(),
+        auth_params: Vec::new(),
+        priv_params: Vec::new(),
+    };
+
+    let data = match &user.privacy {
+        Some((protocol, key)) => {
+            let (ciphertext, priv_params) = encrypt(*protocol, key, boots, time, &scoped.encode()?)?;
+            security.priv_params = priv_params;
+            ScopedPduData::Encrypted(ciphertext)
+        }
+        None => ScopedPduData::Plaintext(scoped.clone()),
+    };
+
+    if user.auth.is_some() {
+        security.auth_params = vec![0; AUTH_PARAMS_LEN];
+    }
+
+    let message = V3Message {
+        msg_id,
+        max_size: super::pdu::MAX_MESSAGE_SIZE,
+        flags: user.security_flags() | extra_flags,
+        security,
+        data,
+    };
+    let mut encoded = message.encode()?;
+
+    if let Some((protocol, key)) = &user.auth {
+        // 0으로 채운 자리를 기준으로 전체 메시지의 HMAC을 계산한 뒤 채워 넣기
+        let (_, offset, len) = V3Message::decode(&encoded)?;
+        let mac = hmac_96(*protocol, key, &encoded);
+        encoded[offset..offset + len].copy_from_slice(&mac);
+    }
+    Ok(encoded)
+}
+
+/// 수신한 메시지의 인증을 검증하고 scopedPDU를 복호화합니다.
+/// 메시지의 보안 수준(msgFlags)은 `user`의 보안 수준과 같아야 합니다.
+pub fn unseal(user: &UsmUser, raw: &[u8]) -> Result<(V3Message, ScopedPdu)> {
+    let (message, auth_offset, auth_len) = V3Message::decode(raw)?;
+    if message.flags & (FLAG_AUTH | FLAG_PRIV) != user.security_flags() {
+        bail!("SNMPv3 {}: message flags {:#04x}", UsmReport::UnsupportedSecurityLevel.description(), message.flags);
+    }
+
+    if message.flags & FLAG_AUTH != 0 {
+        let (protocol, key) = user
+            .auth
+            .as_ref()
+            .context("Authenticated message received for a user without auth key")?;
+        if auth_len != AUTH_PARAMS_LEN {
+            bail!("Invalid authentication parameters length: {}", auth_len);
+        }
+        let mut zeroed = raw.to_vec();
+        zeroed[auth_offset..auth_offset + auth_len].fill(0);
+        if hmac_96(*protocol, key, &zeroed) != message.security.auth_params {
+            bail!("SNMPv3 message authentication failed: {}", UsmReport::WrongDigest.description());
+        }
+    }
+
+    let scoped = match &message.data {
+        ScopedPduData::Plaintext(scoped) => scoped.clone(),
+        ScopedPduData::Encrypted(ciphertext) => {
+            let (protocol, key) = user
+                .privacy
+                .as_ref()
+                .context("Encrypted message received for a user without privacy key")?;
+            let plaintext = decrypt(
+                *protocol,
+                key,
+                message.security.engine_boots,
+                message.security.engine_time,
+                &message.security.priv_params,
+                ciphertext,
+            )?;
+            ScopedPdu::decode(&plaintext)
+                .with_context(|| format!("SNMPv3 {}", UsmReport::DecryptionError.description()))?
+        }
+    };
+    Ok((message, scoped))
+}
@@ -0,0 +1,68 @@
+//! SSH 단계별 오류
+
+use std::time::Duration;
+
+/// SSH 접속/명령 실행 오류 (anyhow::Error에서 downcast로 구분)
+#[derive(Debug)]
+pub enum SshError {
+    ConnectTimeout { addr: String, after: Duration },
+    Connect { addr: String, message: String },
+    HandshakeTimeout { after: Duration },
+    Handshake(String),
+    AuthTimeout { after: Duration },
+    Auth(String),
+    ExecTimeout { after: Duration },
+    Exec(String),
+    ReadTimeout { after: Duration },
+    Read(String),
+    /// 사용자가 취소
+    Cancelled,
+    /// 명령이 0이 아닌 상태로 끝나고 출력이 없음
+    CommandFailed { status: i32, stderr: String },
+    /// sudo/su 권한 상승 실패 (비밀번호 오류, sudoers 미등록 등)
+    Escalation(String),
+}
+
+impl SshError {
+    #[cfg(test)]
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self,
+            SshError::ConnectTimeout { .. }
+                | SshError::HandshakeTimeout { .. }
+                | SshError::AuthTimeout { .. }
+                | SshError::ExecTimeout { .. }
+                | SshError::ReadTimeout { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for SshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SshError::ConnectTimeout { addr, after } => write!(f, "SSH 연결 타임아웃 ({}, {}초)", addr, after.as_secs()),
+            SshError::Connect { addr, message } => write!(f, "SSH 연결 실패 ({}): {}", addr, message),
+            SshError::HandshakeTimeout { after } => write!(f, "SSH 핸드셰이크 타임아웃 ({}초)", after.as_secs()),
+            SshError::Handshake(message) => write!(f, "SSH 핸드셰이크 실패: {}", message),
+            SshError::AuthTimeout { after } => write!(f, "SSH 인증 타임아웃 ({}초)", after.as_secs()),
+            SshError::Auth(message) => write!(f, "SSH 인증 실패: {}", message),
+            SshError::ExecTimeout { after } => write!(f, "SSH 명령 시작 타임아웃 ({}초)", after.as_secs()),
+            SshError::Exec(message) => write!(f, "SSH 명령 실행 실패: {}", message),
+            SshError::ReadTimeout { after } => write!(f, "SSH 명령 응답 타임아웃 ({}초)", after.as_secs()),
+            SshError::Read(message) => write!(f, "SSH 출력 읽기 실패: {}", message),
+            SshError::Cancelled => write!(f, "SSH 명령이 취소되었습니다"),
+            SshError::CommandFailed { status, stderr } if stderr.trim().is_empty() => {
+                write!(f, "SSH 명령 실패: 종료 상태 {}", status)
+            }
+            SshError::CommandFailed { stderr, .. } => write!(f, "SSH 명령 실패: {}", stderr.trim()),
+            SshError::Escalation(message) => write!(f, "권한 상승 실패: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SshError {}
+
+/// libssh2의 블로킹 타임아웃(LIBSSH2_ERROR_TIMEOUT)인지
+pub(super) fn is_ssh2_timeout(err: &ssh2::Error) -> bool {
+    matches!(err.code(), ssh2::ErrorCode::Session(-9))
+}
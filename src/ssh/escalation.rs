@@ -0,0 +1,194 @@
+//! sudo / su 권한 상승
+
+use super::SshError;
+use serde::{Deserialize, Serialize};
+
+/// 권한 상승에 성공하면 명령보다 먼저 출력하는 표시 줄
+/// (이 줄이 나오기 전의 출력은 sudo/su의 프롬프트나 오류 메시지)
+const MARKER: &str = "__MMT_ESCALATED__";
+
+/// 명령을 실행할 때 사용할 권한 상승 방식
+///
+/// ```json
+/// "escalation": { "method": "sudo" }                          // NOPASSWD sudo
+/// "escalation": { "method": "sudo", "password": "secret" }    // sudo -S
+/// "escalation": { "method": "su", "password": "rootpw" }      // su (PTY 사용)
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+pub enum Escalation {
+    Sudo {
+        /// 없으면 비밀번호 없이 (`sudo -n`) 실행
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password: Option<String>,
+        /// 실행할 사용자 (기본 root)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        user: Option<String>,
+    },
+    Su {
+        /// 대상 사용자의 비밀번호
+        password: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        user: Option<String>,
+    },
+}
+
+impl Escalation {
+    fn label(&self) -> &'static str {
+        match self {
+            Escalation::Sudo { .. } => "sudo",
+            Escalation::Su { .. } => "su",
+        }
+    }
+
+    fn user(&self) -> &str {
+        match self {
+            Escalation::Sudo { user, .. } | Escalation::Su { user, .. } => user.as_deref().unwrap_or("root"),
+        }
+    }
+
+    /// 설정된 비밀번호 (`env:`/`cmd:` 참조일 수 있음)
+    pub fn password(&self) -> Option<&str> {
+        match self {
+            Escalation::Sudo { password, .. } => password.as_deref(),
+            Escalation::Su { password, .. } => Some(password),
+        }
+    }
+
+    /// 비밀번호 참조를 실제 값으로 바꾼 설정 (명령을 실행할 때마다 확인)
+    pub(super) fn resolved(&self) -> Result<Escalation, SshError> {
+        let resolve = |password: &str| {
+            crate::credential::resolve(password).map_err(|e| SshError::Escalation(format!("{:#}", e)))
+        };
+        Ok(match self {
+            Escalation::Sudo { password, user } => Escalation::Sudo {
+                password: password.as_deref().map(resolve).transpose()?,
+                user: user.clone(),
+            },
+            Escalation::Su { password, user } => Escalation::Su { password: resolve(password)?, user: user.clone() },
+        })
+    }
+
+    /// 명령을 권한 상승 명령으로 감쌉니다.
+    pub(super) fn wrap(&self, command: &str) -> String {
+        let script = shell_quote(&format!("echo {}; {}", MARKER, command));
+        let user = shell_quote(self.user());
+        match self {
+            // -k: 캐시된 인증을 쓰지 않아 비밀번호 줄이 항상 sudo에 소비되도록
+            Escalation::Sudo { password: Some(_), .. } => format!("sudo -k -S -p '' -u {} -- sh -c {}", user, script),
+            Escalation::Sudo { password: None, .. } => format!("sudo -n -u {} -- sh -c {}", user, script),
+            Escalation::Su { .. } => format!("su {} -c {}", user, script),
+        }
+    }
+
+    /// su는 터미널에서만 비밀번호를 읽으므로 PTY가 필요
+    pub(super) fn needs_pty(&self) -> bool {
+        matches!(self, Escalation::Su { .. })
+    }
+
+    /// 명령 시작 직후 stdin으로 보낼 비밀번호 (sudo -S)
+    pub(super) fn stdin_password(&self) -> Option<&str> {
+        match self {
+            Escalation::Sudo { password, .. } => password.as_deref(),
+            Escalation::Su { .. } => None,
+        }
+    }
+
+    /// 프롬프트가 나오면 보낼 비밀번호 (su)
+    fn prompt_password(&self) -> Option<&str> {
+        match self {
+            Escalation::Su { password, .. } => Some(password),
+            Escalation::Sudo { .. } => None,
+        }
+    }
+
+    /// 표시 줄이 나오지 않고 명령이 끝났을 때 원인을 설명하는 오류
+    pub(super) fn failure(&self, output: &str, stderr: &str, exit_status: i32) -> SshError {
+        let message: Vec<&str> = stderr
+            .lines()
+            .chain(output.lines())
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.to_lowercase().starts_with("password:"))
+            .collect();
+        let message = message.join(" / ");
+        let lower = message.to_lowercase();
+
+        let reason = if lower.contains("a password is required") {
+            "sudo 비밀번호가 필요합니다 (escalation.password 설정 필요)".to_string()
+        } else if lower.contains("incorrect password")
+            || lower.contains("sorry, try again")
+            || lower.contains("authentication failure")
+            || lower.contains("no password was provided")
+        {
+            format!("{} 비밀번호가 올바르지 않습니다", self.label())
+        } else if lower.contains("not in the sudoers") || lower.contains("is not allowed to") {
+            format!("{} 권한이 없습니다: {}", self.label(), message)
+        } else if message.is_empty() {
+            format!("{} 실패 (종료 코드 {})", self.label(), exit_status)
+        } else {
+            format!("{} 실패 (종료 코드 {}): {}", self.label(), exit_status, message)
+        };
+        SshError::Escalation(reason)
+    }
+}
+
+/// sh에서 한 인자로 쓰이도록 작은따옴표로 감쌈
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// 표시 줄이 나올 때까지 출력을 모아 두고, 그 뒤의 출력만 넘겨줍니다.
+pub(super) struct MarkerGate<'a> {
+    escalation: &'a Escalation,
+    pending: Vec<u8>,
+    passed: bool,
+    password_sent: bool,
+}
+
+impl<'a> MarkerGate<'a> {
+    pub(super) fn new(escalation: &'a Escalation) -> Self {
+        Self { escalation, pending: Vec::new(), passed: false, password_sent: false }
+    }
+
+    /// 받은 데이터를 넣고, 명령 출력으로 넘길 부분을 반환합니다. (표시 줄 전이면 빈 벡터)
+    pub(super) fn push(&mut self, data: &[u8]) -> Vec<u8> {
+        if self.passed {
+            return data.to_vec();
+        }
+        self.pending.extend_from_slice(data);
+        let mut start = 0;
+        while let Some(pos) = self.pending[start..].iter().position(|b| *b == b'\n') {
+            let line = &self.pending[start..start + pos];
+            start += pos + 1;
+            if line.strip_suffix(b"\r").unwrap_or(line) == MARKER.as_bytes() {
+                self.passed = true;
+                let rest = self.pending.split_off(start);
+                self.pending.clear();
+                return rest;
+            }
+        }
+        Vec::new()
+    }
+
+    /// 프롬프트가 나왔고 아직 비밀번호를 보내지 않았으면 보낼 비밀번호를 반환합니다.
+    pub(super) fn take_prompt_password(&mut self) -> Option<&'a str> {
+        if self.passed || self.password_sent {
+            return None;
+        }
+        let password = self.escalation.prompt_password()?;
+        if !String::from_utf8_lossy(&self.pending).to_lowercase().contains("password") {
+            return None;
+        }
+        self.password_sent = true;
+        Some(password)
+    }
+
+    pub(super) fn passed(&self) -> bool {
+        self.passed
+    }
+
+    /// 표시 줄 전까지 받은 출력 (프롬프트, 오류 메시지)
+    pub(super) fn pending_text(&self) -> String {
+        String::from_utf8_lossy(&self.pending).into_owned()
+    }
+}
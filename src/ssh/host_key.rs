@@ -0,0 +1,181 @@
+//! SSH 호스트 키 확인 (known_hosts, 처음 접속 시 사용자 확인)
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use ssh2::{CheckResult, HashType, KnownHostFileKind, KnownHostKeyFormat, Session};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// 처음 접속하는 서버의 호스트 키 (사용자 확인 대기)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostKeyPrompt {
+    pub host: String,
+    pub port: u16,
+    pub key_type: String,
+    pub fingerprint: String, // SHA256:base64 (ssh-keygen -l 형식)
+    entry: String,           // 수락하면 known_hosts에 추가할 줄
+}
+
+/// 호스트 키 검증 실패
+#[derive(Debug)]
+pub enum HostKeyError {
+    /// known_hosts에 없음 - TUI에서 수락하면 다음 접속부터 허용
+    Unknown(HostKeyPrompt),
+    /// 사용자가 거부한 키
+    Rejected { host: String, port: u16, fingerprint: String },
+    /// known_hosts의 키와 다름 (중간자 공격 가능성)
+    Changed { host: String, port: u16, fingerprint: String, known_hosts: PathBuf },
+}
+
+impl std::fmt::Display for HostKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HostKeyError::Unknown(prompt) => write!(
+                f,
+                "{}:{} 호스트 키를 확인해야 합니다 ({} {}) - 확인 창에서 수락 후 다시 시도하세요",
+                prompt.host, prompt.port, prompt.key_type, prompt.fingerprint
+            ),
+            HostKeyError::Rejected { host, port, fingerprint } => {
+                write!(f, "{}:{} 호스트 키를 거부했습니다 ({})", host, port, fingerprint)
+            }
+            HostKeyError::Changed { host, port, fingerprint, known_hosts } => write!(
+                f,
+                "{}:{} 호스트 키가 변경되었습니다! 접속을 거부합니다 (받은 키 {}). 서버 키 교체가 확실하면 {}에서 해당 항목을 삭제하세요",
+                host,
+                port,
+                fingerprint,
+                known_hosts.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HostKeyError {}
+
+static PENDING_HOST_KEYS: OnceLock<Mutex<Vec<HostKeyPrompt>>> = OnceLock::new();
+static REJECTED_HOST_KEYS: OnceLock<Mutex<HashSet<(String, u16, String)>>> = OnceLock::new();
+
+fn pending_host_keys() -> &'static Mutex<Vec<HostKeyPrompt>> {
+    PENDING_HOST_KEYS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn rejected_host_keys() -> &'static Mutex<HashSet<(String, u16, String)>> {
+    REJECTED_HOST_KEYS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// 확인을 기다리는 호스트 키를 하나 꺼냅니다.
+pub fn take_host_key_prompt() -> Option<HostKeyPrompt> {
+    let mut pending = pending_host_keys().lock().unwrap();
+    (!pending.is_empty()).then(|| pending.remove(0))
+}
+
+/// 호스트 키를 known_hosts에 추가합니다.
+pub fn accept_host_key(prompt: &HostKeyPrompt) -> Result<()> {
+    append_known_host(&known_hosts_path(), &prompt.entry)?;
+    Ok(())
+}
+
+/// known_hosts에 항목을 추가합니다. 같은 호스트와 키가 이미 있으면 (다른 창에서 먼저 수락한 경우 등) 추가하지 않고
+/// false를 반환합니다.
+pub(super) fn append_known_host(path: &Path, entry: &str) -> Result<bool> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("디렉터리를 만들 수 없습니다: {}", dir.display()))?;
+    }
+    let existing = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("known_hosts를 읽을 수 없습니다: {}", path.display())),
+    };
+    // 주석을 빼고 "호스트 키종류 키" 세 칸이 같으면 같은 항목
+    let same_key = |line: &str| line.split_whitespace().take(3).eq(entry.split_whitespace().take(3));
+    if existing.lines().any(same_key) {
+        return Ok(false);
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("known_hosts를 열 수 없습니다: {}", path.display()))?;
+    // 마지막 줄에 줄바꿈이 없으면 이어 붙지 않도록 먼저 줄을 바꿈
+    let separator = if existing.is_empty() || existing.ends_with('\n') { "" } else { "\n" };
+    writeln!(file, "{}{}", separator, entry.trim_end())
+        .with_context(|| format!("known_hosts 쓰기 실패: {}", path.display()))?;
+    Ok(true)
+}
+
+/// 호스트 키를 거부합니다. 프로그램을 다시 시작할 때까지 같은 키로는 묻지 않습니다.
+pub fn reject_host_key(prompt: &HostKeyPrompt) {
+    rejected_host_keys()
+        .lock()
+        .unwrap()
+        .insert((prompt.host.clone(), prompt.port, prompt.fingerprint.clone()));
+}
+
+fn known_hosts_path() -> PathBuf {
+    crate::app::config::get_config_path("known_hosts")
+}
+
+/// 서버 호스트 키를 known_hosts와 비교합니다.
+pub(super) fn verify_host_key(sess: &Session, host: &str, port: u16) -> Result<()> {
+    let (key, key_type) = sess.host_key().context("서버 호스트 키를 받을 수 없습니다")?;
+    let fingerprint = sess
+        .host_key_hash(HashType::Sha256)
+        .map(|hash| format!("SHA256:{}", base64::engine::general_purpose::STANDARD_NO_PAD.encode(hash)))
+        .context("호스트 키 지문을 계산할 수 없습니다")?;
+
+    let path = known_hosts_path();
+    let mut known_hosts = sess.known_hosts().context("known_hosts 초기화 실패")?;
+    if path.is_file() {
+        known_hosts
+            .read_file(&path, KnownHostFileKind::OpenSSH)
+            .with_context(|| format!("known_hosts를 읽을 수 없습니다: {}", path.display()))?;
+    }
+
+    match known_hosts.check_port(host, port, key) {
+        CheckResult::Match => Ok(()),
+        CheckResult::Mismatch => Err(HostKeyError::Changed {
+            host: host.to_string(),
+            port,
+            fingerprint,
+            known_hosts: path,
+        }
+        .into()),
+        CheckResult::NotFound => {
+            if rejected_host_keys().lock().unwrap().contains(&(host.to_string(), port, fingerprint.clone())) {
+                return Err(HostKeyError::Rejected { host: host.to_string(), port, fingerprint }.into());
+            }
+            // 수락 시 추가할 줄을 미리 만들어 둠 (기본 포트가 아니면 [host]:port 형식)
+            let name = if port == 22 { host.to_string() } else { format!("[{}]:{}", host, port) };
+            let mut entry = sess.known_hosts()?;
+            entry.add(&name, key, "", KnownHostKeyFormat::from(key_type))?;
+            let added = entry.iter()?.into_iter().next().context("known_hosts 항목 생성 실패")?;
+            let prompt = HostKeyPrompt {
+                host: host.to_string(),
+                port,
+                key_type: key_type_name(key_type).to_string(),
+                fingerprint,
+                entry: entry.write_string(&added, KnownHostFileKind::OpenSSH)?,
+            };
+            let mut pending = pending_host_keys().lock().unwrap();
+            if !pending.iter().any(|p| p.host == prompt.host && p.port == prompt.port) {
+                pending.push(prompt.clone());
+            }
+            Err(HostKeyError::Unknown(prompt).into())
+        }
+        CheckResult::Failure => anyhow::bail!("known_hosts 확인 실패: {}", path.display()),
+    }
+}
+
+fn key_type_name(key_type: ssh2::HostKeyType) -> &'static str {
+    match key_type {
+        ssh2::HostKeyType::Rsa => "ssh-rsa",
+        ssh2::HostKeyType::Dss => "ssh-dss",
+        ssh2::HostKeyType::Ecdsa256 => "ecdsa-sha2-nistp256",
+        ssh2::HostKeyType::Ecdsa384 => "ecdsa-sha2-nistp384",
+        ssh2::HostKeyType::Ecdsa521 => "ecdsa-sha2-nistp521",
+        ssh2::HostKeyType::Ed25519 => "ssh-ed25519",
+        ssh2::HostKeyType::Unknown => "unknown",
+    }
+}
@@ -0,0 +1,517 @@
+mod error;
+mod escalation;
+mod host_key;
+mod pool;
+mod sftp;
+mod stream;
+mod tunnel;
+
+pub use error::SshError;
+pub use escalation::Escalation;
+
+pub use host_key::{accept_host_key, reject_host_key, take_host_key_prompt, HostKeyPrompt};
+pub use pool::close_idle_sessions;
+pub use sftp::RemoteFile;
+pub use stream::OutputLines;
+pub(crate) use stream::line_channel;
+pub use tunnel::JumpHost;
+
+use crate::app::Proxy;
+use anyhow::{Context, Result};
+use host_key::verify_host_key;
+use error::is_ssh2_timeout;
+use escalation::MarkerGate;
+use pool::PoolKey;
+use ssh2::Session;
+use stream::LineSplitter;
+use std::io::prelude::*;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+/// SSH 인증 방식 (시도 순서대로)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum AuthMethod {
+    Agent,
+    KeyFile { path: PathBuf, passphrase: Option<String> },
+    Password(String),
+}
+
+impl AuthMethod {
+    fn label(&self) -> &'static str {
+        match self {
+            AuthMethod::Agent => "ssh-agent",
+            AuthMethod::KeyFile { .. } => "공개키",
+            AuthMethod::Password(_) => "비밀번호",
+        }
+    }
+}
+
+/// 시도할 인증 방식 - ssh-agent, 개인키, 비밀번호 순
+fn auth_methods(password: &str, key_file: Option<&(PathBuf, Option<String>)>, use_agent: bool) -> Vec<AuthMethod> {
+    let mut methods = Vec::new();
+    if use_agent {
+        methods.push(AuthMethod::Agent);
+    }
+    if let Some((path, passphrase)) = key_file {
+        methods.push(AuthMethod::KeyFile { path: path.clone(), passphrase: passphrase.clone() });
+    }
+    if !password.is_empty() {
+        methods.push(AuthMethod::Password(password.to_string()));
+    }
+    methods
+}
+
+/// SSH 클라이언트 (ssh2 사용)
+pub struct SshClient {
+    host: String,
+    port: u16,
+    username: String,
+    password: String,
+    key_file: Option<(PathBuf, Option<String>)>,
+    use_agent: bool,
+    jump_host: Option<JumpHost>,
+    escalation: Option<Escalation>,
+    connect_timeout: Duration,
+    timeout: Duration,
+    cancel: CancellationToken,
+}
+
+/// 명령 출력을 기다리는 동안 취소/타임아웃을 확인하는 주기
+const READ_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+impl SshClient {
+    pub fn new(host: String, port: u16, username: String, password: String) -> Self {
+        Self {
+            host,
+            port,
+            username,
+            password,
+            key_file: None,
+            use_agent: false,
+            jump_host: None,
+            escalation: None,
+            connect_timeout: Duration::from_secs(10),
+            timeout: Duration::from_secs(15),
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    /// 프록시 설정의 인증 정보(비밀번호, 개인키, ssh-agent)와 점프 호스트로 클라이언트를 만듭니다.
+    /// 프록시에 jump_host가 없으면 그룹의 jump_host를 사용합니다.
+    pub fn for_proxy(proxy: &Proxy, port: u16) -> Self {
+        let mut client = Self::new(proxy.host.clone(), port, proxy.username.clone(), proxy.password.clone())
+            .with_agent(proxy.ssh_agent)
+            .with_jump_host(proxy.jump_host.clone().or_else(|| proxy.group_jump_host.clone()));
+        if let Some(path) = &proxy.ssh_key_path {
+            client = client.with_key_file(expand_home(path), proxy.ssh_key_passphrase.clone());
+        }
+        client
+    }
+
+    /// 명령 실행 타임아웃 (명령 시작부터 출력을 모두 받을 때까지)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// TCP 연결, 핸드셰이크, 인증 각 단계의 타임아웃
+    #[cfg(test)]
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// 토큰이 취소되면 실행 중인 명령을 중단하고 `SshError::Cancelled`를 반환
+    pub fn with_cancel_token(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// 개인키 파일로 인증 (암호화된 키는 passphrase 필요)
+    pub fn with_key_file(mut self, path: impl Into<PathBuf>, passphrase: Option<String>) -> Self {
+        self.key_file = Some((path.into(), passphrase.filter(|p| !p.is_empty())));
+        self
+    }
+
+    /// ssh-agent(SSH_AUTH_SOCK)에 등록된 키로 인증
+    pub fn with_agent(mut self, use_agent: bool) -> Self {
+        self.use_agent = use_agent;
+        self
+    }
+
+    /// 점프 호스트를 거쳐 접속
+    pub fn with_jump_host(mut self, jump_host: Option<JumpHost>) -> Self {
+        self.jump_host = jump_host;
+        self
+    }
+
+    /// sudo/su로 권한을 올려 명령 실행
+    pub fn with_escalation(mut self, escalation: Option<Escalation>) -> Self {
+        self.escalation = escalation;
+        self
+    }
+
+    fn auth_methods(&self) -> Vec<AuthMethod> {
+        auth_methods(&self.password, self.key_file.as_ref(), self.use_agent)
+    }
+
+    fn pool_key(&self) -> PoolKey {
+        PoolKey {
+            host: self.host.clone(),
+            port: self.port,
+            username: self.username.clone(),
+            auth_methods: self.auth_methods(),
+            jump_host: self.jump_host.clone(),
+        }
+    }
+
+    fn timeouts(&self) -> Timeouts {
+        Timeouts { connect: self.connect_timeout, command: self.timeout }
+    }
+
+    /// 모든 libssh2 호출에 타임아웃이 걸려 있지만, 스레드가 돌아오지 않는 경우를 대비한 상한
+    /// (점프 호스트 접속 + 대상 접속의 연결/핸드셰이크/인증 + 명령 실행)
+    fn task_limit(&self) -> Duration {
+        self.connect_timeout * 6 + self.timeout + Duration::from_secs(5)
+    }
+
+    /// SSH를 통해 명령을 실행하고 결과를 반환합니다.
+    pub async fn execute(&self, command: &str) -> Result<String> {
+        let key = self.pool_key();
+        let command = command.to_string();
+        let timeouts = self.timeouts();
+        let escalation = self.escalation.clone();
+        let cancel = self.cancel.clone();
+
+        // 블로킹 작업을 스레드 풀에서 실행
+        let task_cancel = cancel.clone();
+        let handle = tokio::task::spawn_blocking(move || {
+            let mut output = Vec::new();
+            run_command(key, &command, escalation.as_ref(), timeouts, &task_cancel, &mut |data| {
+                output.extend_from_slice(data);
+                true
+            })?;
+            Ok(String::from_utf8_lossy(&output).into_owned())
+        });
+
+        let limit = self.task_limit();
+        match timeout(limit, handle).await {
+            Ok(result) => result.context("SSH task failed")?,
+            Err(_) => {
+                cancel.cancel();
+                Err(SshError::ReadTimeout { after: limit }.into())
+            }
+        }
+    }
+
+    /// 명령을 실행하고 출력을 받는 대로 한 줄씩 넘겨줍니다.
+    /// 출력이 큰 명령도 전체를 메모리에 모으지 않고 처리할 수 있습니다.
+    pub fn execute_lines(&self, command: &str) -> OutputLines {
+        let key = self.pool_key();
+        let command = command.to_string();
+        let timeouts = self.timeouts();
+        let escalation = self.escalation.clone();
+        let cancel = self.cancel.clone();
+        let (sender, receiver) = line_channel();
+
+        let task_cancel = cancel.clone();
+        let handle = tokio::task::spawn_blocking(move || {
+            let mut splitter = LineSplitter::default();
+            // 받는 쪽이 스트림을 버렸으면 false를 반환해 읽기를 중단
+            run_command(key, &command, escalation.as_ref(), timeouts, &task_cancel, &mut |data| {
+                splitter.push(data).into_iter().all(|line| sender.blocking_send(line).is_ok())
+            })?;
+            if let Some(line) = splitter.finish() {
+                let _ = sender.blocking_send(line);
+            }
+            Ok(())
+        });
+
+        OutputLines::new(receiver, handle, self.task_limit(), cancel)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Timeouts {
+    connect: Duration,
+    command: Duration,
+}
+
+fn timeout_ms(duration: Duration) -> u32 {
+    duration.as_millis().clamp(1, u32::MAX as u128) as u32
+}
+
+/// 풀의 세션(없으면 새 세션)으로 명령을 실행합니다. (블로킹)
+/// 받은 stdout은 도착하는 대로 `on_output`에 넘기며, `on_output`이 false를 반환하면 취소로 처리합니다.
+/// `escalation`이 있으면 sudo/su로 감싸 실행하고, 권한 상승이 확인된 뒤의 출력만 넘깁니다.
+fn run_command(
+    key: PoolKey,
+    command: &str,
+    escalation: Option<&Escalation>,
+    timeouts: Timeouts,
+    cancel: &CancellationToken,
+    on_output: &mut dyn FnMut(&[u8]) -> bool,
+) -> Result<()> {
+    if cancel.is_cancelled() {
+        return Err(SshError::Cancelled.into());
+    }
+    // 권한 상승 비밀번호 참조는 접속 전에 확인
+    let escalation = escalation.map(Escalation::resolved).transpose()?;
+    let escalation = escalation.as_ref();
+    let open_channel = |sess: &Session| {
+        sess.set_timeout(timeout_ms(timeouts.command));
+        sess.channel_session().map_err(|e| exec_error(&e, timeouts.command))
+    };
+
+    // 풀의 세션을 재사용하고, 채널을 열 수 없으면 (서버가 끊은 세션) 한 번 다시 연결
+    let (sess, mut channel) = match pool::checkout(&key) {
+        Some(sess) => match open_channel(&sess) {
+            Ok(channel) => (sess, channel),
+            Err(_) => {
+                let sess = connect(&key, timeouts)?;
+                let channel = open_channel(&sess)?;
+                (sess, channel)
+            }
+        },
+        None => {
+            let sess = connect(&key, timeouts)?;
+            let channel = open_channel(&sess)?;
+            (sess, channel)
+        }
+    };
+    if cancel.is_cancelled() {
+        return Err(SshError::Cancelled.into());
+    }
+
+    // 명령 실행 (권한 상승이면 sudo/su로 감싸고, sudo -S는 비밀번호를 stdin으로 전달)
+    match escalation {
+        Some(escalation) => {
+            if escalation.needs_pty() {
+                channel.request_pty("dumb", None, None).map_err(|e| exec_error(&e, timeouts.command))?;
+            }
+            channel.exec(&escalation.wrap(command)).map_err(|e| exec_error(&e, timeouts.command))?;
+            if let Some(password) = escalation.stdin_password() {
+                channel
+                    .write_all(format!("{}\n", password).as_bytes())
+                    .and_then(|_| channel.send_eof().map_err(std::io::Error::from))
+                    .map_err(|e| SshError::Exec(format!("sudo 비밀번호 전달 실패: {}", e)))?;
+            }
+        }
+        None => channel.exec(command).map_err(|e| exec_error(&e, timeouts.command))?,
+    }
+    let mut gate = escalation.map(MarkerGate::new);
+
+    // 출력 읽기 - 짧은 주기로 끊어 읽으며 취소와 전체 타임아웃 확인
+    let deadline = std::time::Instant::now() + timeouts.command;
+    sess.set_timeout(timeout_ms(READ_POLL_INTERVAL));
+    let mut received = 0usize;
+    let mut buf = [0u8; 16 * 1024];
+    loop {
+        match channel.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                let data = match gate.as_mut() {
+                    Some(gate) => {
+                        let data = gate.push(&buf[..n]);
+                        // su 비밀번호 프롬프트에 응답
+                        if let Some(password) = gate.take_prompt_password() {
+                            channel
+                                .write_all(format!("{}\n", password).as_bytes())
+                                .map_err(|e| SshError::Exec(format!("su 비밀번호 전달 실패: {}", e)))?;
+                        }
+                        std::borrow::Cow::Owned(data)
+                    }
+                    None => std::borrow::Cow::Borrowed(&buf[..n]),
+                };
+                received += data.len();
+                if !data.is_empty() && !on_output(&data) {
+                    return Err(SshError::Cancelled.into());
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(e) => return Err(SshError::Read(e.to_string()).into()),
+        }
+        if cancel.is_cancelled() {
+            return Err(SshError::Cancelled.into());
+        }
+        if std::time::Instant::now() >= deadline {
+            return Err(SshError::ReadTimeout { after: timeouts.command }.into());
+        }
+    }
+
+    // 채널 종료 대기
+    sess.set_timeout(timeout_ms(timeouts.connect));
+    channel.wait_close().map_err(|e| SshError::Read(format!("채널 종료 실패: {}", e)))?;
+    let exit_status = channel.exit_status().map_err(|e| SshError::Read(format!("종료 상태 확인 실패: {}", e)))?;
+
+    // stderr 읽기 (있는 경우)
+    let mut stderr = String::new();
+    channel.stderr().read_to_string(&mut stderr).ok();
+
+    // 채널까지 정상적으로 끝난 세션만 풀에 반납 (명령 자체의 실패는 세션 문제가 아님)
+    drop(channel);
+    pool::checkin(key, sess);
+
+    // 권한 상승 표시 줄이 나오지 않았으면 sudo/su 단계에서 실패
+    if let (Some(escalation), Some(gate)) = (escalation, &gate) {
+        if !gate.passed() {
+            return Err(escalation.failure(&gate.pending_text(), &stderr, exit_status).into());
+        }
+    }
+
+    // 종료 상태가 0이 아니고 stdout이 비어있으면 에러
+    if exit_status != 0 && received == 0 {
+        return Err(SshError::CommandFailed { status: exit_status, stderr }.into());
+    }
+
+    Ok(())
+}
+
+fn exec_error(err: &ssh2::Error, after: Duration) -> SshError {
+    if is_ssh2_timeout(err) {
+        SshError::ExecTimeout { after }
+    } else {
+        SshError::Exec(err.to_string())
+    }
+}
+
+/// 풀 키의 대상에 새 세션을 연결합니다. (블로킹)
+fn connect(key: &PoolKey, timeouts: Timeouts) -> Result<Session> {
+    // TCP 연결 (점프 호스트가 있으면 터널을 통해)
+    let tcp = match &key.jump_host {
+        Some(jump_host) => tunnel::open_tunnel(jump_host, &key.host, key.port, timeouts.connect)?,
+        None => connect_tcp(&key.host, key.port, timeouts.connect)?,
+    };
+    connect_session(tcp, &key.host, key.port, &key.username, &key.auth_methods, timeouts.connect)
+}
+
+/// 타임아웃을 걸고 TCP 연결 (호스트 이름이면 해석된 주소를 차례로 시도)
+fn connect_tcp(host: &str, port: u16, timeout: Duration) -> Result<TcpStream, SshError> {
+    let addr = format!("{}:{}", host, port);
+    let addrs = addr
+        .to_socket_addrs()
+        .map_err(|e| SshError::Connect { addr: addr.clone(), message: e.to_string() })?;
+    let mut last_error = None;
+    for socket_addr in addrs {
+        match TcpStream::connect_timeout(&socket_addr, timeout) {
+            Ok(tcp) => return Ok(tcp),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(match last_error {
+        Some(e) if e.kind() == std::io::ErrorKind::TimedOut => SshError::ConnectTimeout { addr, after: timeout },
+        Some(e) => SshError::Connect { addr, message: e.to_string() },
+        None => SshError::Connect { addr, message: "주소를 찾을 수 없습니다".to_string() },
+    })
+}
+
+/// 핸드셰이크, 호스트 키 확인, 인증까지 마친 세션을 반환합니다.
+/// 각 단계의 블로킹 호출은 `timeout`을 넘으면 해당 단계의 타임아웃 오류가 됩니다.
+fn connect_session(
+    tcp: TcpStream,
+    host: &str,
+    port: u16,
+    username: &str,
+    auth_methods: &[AuthMethod],
+    timeout: Duration,
+) -> Result<Session> {
+    // SSH 세션 생성
+    let mut sess = Session::new().map_err(|e| SshError::Handshake(e.to_string()))?;
+    sess.set_timeout(timeout_ms(timeout));
+
+    sess.set_tcp_stream(tcp);
+    sess.handshake().map_err(|e| {
+        if is_ssh2_timeout(&e) {
+            SshError::HandshakeTimeout { after: timeout }
+        } else {
+            SshError::Handshake(e.to_string())
+        }
+    })?;
+
+    // 호스트 키 확인 (인증 정보를 보내기 전에)
+    verify_host_key(&sess, host, port)?;
+
+    // 인증
+    authenticate(&sess, username, auth_methods, timeout)?;
+
+    // 풀에서 쉬는 동안 keepalive_send로 상태를 확인할 수 있도록 활성화
+    sess.set_keepalive(true, pool::HEALTH_CHECK_AFTER.as_secs() as u32);
+    Ok(sess)
+}
+
+/// 설정된 방식을 순서대로 시도하고, 모두 실패하면 방식별 실패 사유를 모아 반환합니다.
+fn authenticate(sess: &Session, username: &str, methods: &[AuthMethod], timeout: Duration) -> Result<(), SshError> {
+    if methods.is_empty() {
+        return Err(SshError::Auth("비밀번호, ssh_key_path, ssh_agent 중 하나가 필요합니다".to_string()));
+    }
+
+    let mut failures = Vec::new();
+    for method in methods {
+        let attempt = match method {
+            AuthMethod::Agent => authenticate_agent(sess, username),
+            // env:/cmd: 참조는 접속할 때마다 실제 값으로 바꿈
+            AuthMethod::KeyFile { path, passphrase } => passphrase
+                .as_deref()
+                .map(crate::credential::resolve)
+                .transpose()
+                .and_then(|passphrase| authenticate_key_file(sess, username, path, passphrase.as_deref())),
+            AuthMethod::Password(password) => crate::credential::resolve(password)
+                .and_then(|password| sess.userauth_password(username, &password).map_err(anyhow::Error::from)),
+        };
+        match attempt {
+            Ok(()) if sess.authenticated() => return Ok(()),
+            Ok(()) => failures.push(format!("{}: 인증되지 않음", method.label())),
+            Err(e) if e.chain().any(|c| c.downcast_ref::<ssh2::Error>().is_some_and(is_ssh2_timeout)) => {
+                return Err(SshError::AuthTimeout { after: timeout });
+            }
+            Err(e) => failures.push(format!("{}: {:#}", method.label(), e)),
+        }
+    }
+    Err(SshError::Auth(failures.join(", ")))
+}
+
+fn authenticate_agent(sess: &Session, username: &str) -> Result<()> {
+    if std::env::var_os("SSH_AUTH_SOCK").is_none() {
+        anyhow::bail!("SSH_AUTH_SOCK이 설정되지 않았습니다");
+    }
+    let mut agent = sess.agent().context("ssh-agent 초기화 실패")?;
+    agent.connect().context("ssh-agent 연결 실패")?;
+    agent.list_identities().context("ssh-agent 키 목록 조회 실패")?;
+    let identities = agent.identities()?;
+    if identities.is_empty() {
+        anyhow::bail!("ssh-agent에 등록된 키가 없습니다");
+    }
+    let result = identities
+        .iter()
+        .any(|identity| agent.userauth(username, identity).is_ok());
+    let _ = agent.disconnect();
+    if !result {
+        anyhow::bail!("등록된 키 {}개 모두 거부됨", identities.len());
+    }
+    Ok(())
+}
+
+fn authenticate_key_file(sess: &Session, username: &str, path: &Path, passphrase: Option<&str>) -> Result<()> {
+    if !path.is_file() {
+        anyhow::bail!("개인키 파일을 찾을 수 없습니다: {}", path.display());
+    }
+    // 같은 이름의 .pub 파일이 있으면 함께 전달 (없으면 libssh2가 개인키에서 추출)
+    let public_key = PathBuf::from(format!("{}.pub", path.display()));
+    let public_key = public_key.is_file().then_some(public_key);
+    sess.userauth_pubkey_file(username, public_key.as_deref(), path, passphrase)
+        .with_context(|| format!("{} (암호화된 키라면 ssh_key_passphrase 확인)", path.display()))
+}
+
+/// `~/`로 시작하는 경로를 홈 디렉터리 기준으로 바꿉니다.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests;
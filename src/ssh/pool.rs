@@ -0,0 +1,114 @@
+//! 인증을 마친 SSH 세션 풀
+//!
+//! 같은 접속 정보(호스트, 포트, 사용자, 인증 방식, 점프 호스트)로는 세션을 재사용하고
+//! 명령마다 새 채널만 엽니다. 자원 수집, 세션 조회, 트래픽 로그 조회가 모두 같은 풀을 사용합니다.
+
+use super::{AuthMethod, JumpHost};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// 이 시간 이상 쓰지 않은 세션은 닫음
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+/// 이 시간 이상 쉬었던 세션은 꺼낼 때 keepalive로 상태 확인
+pub const HEALTH_CHECK_AFTER: Duration = Duration::from_secs(30);
+/// 접속 정보별로 보관할 최대 유휴 세션 수 (동시 조회 시 추가로 연결된 세션)
+const MAX_IDLE_PER_KEY: usize = 2;
+
+/// 세션을 구분하는 접속 정보
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct PoolKey {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub auth_methods: Vec<AuthMethod>,
+    pub jump_host: Option<JumpHost>,
+}
+
+/// 유휴 세션 보관소 (세션 타입과 분리해 테스트할 수 있도록 제네릭)
+pub(super) struct SessionPool<T> {
+    idle: HashMap<PoolKey, Vec<(T, Instant)>>,
+}
+
+impl<T> SessionPool<T> {
+    pub fn new() -> Self {
+        Self { idle: HashMap::new() }
+    }
+
+    /// 가장 최근에 반납된 세션을 꺼냅니다.
+    /// 만료된 세션은 버리고, 오래 쉬었던 세션은 `healthy`를 통과해야 돌려줍니다.
+    pub fn take(&mut self, key: &PoolKey, now: Instant, healthy: impl Fn(&T) -> bool) -> Option<T> {
+        let sessions = self.idle.get_mut(key)?;
+        let mut found = None;
+        while let Some((session, idle_since)) = sessions.pop() {
+            let idle = now.saturating_duration_since(idle_since);
+            if idle >= IDLE_TIMEOUT {
+                continue;
+            }
+            if idle >= HEALTH_CHECK_AFTER && !healthy(&session) {
+                continue;
+            }
+            found = Some(session);
+            break;
+        }
+        if sessions.is_empty() {
+            self.idle.remove(key);
+        }
+        found
+    }
+
+    /// 사용이 끝난 세션을 반납합니다. 보관 한도를 넘으면 가장 오래된 세션을 돌려줍니다. (호출자가 닫음)
+    pub fn put(&mut self, key: PoolKey, session: T, now: Instant) -> Option<T> {
+        let sessions = self.idle.entry(key).or_default();
+        sessions.push((session, now));
+        (sessions.len() > MAX_IDLE_PER_KEY).then(|| sessions.remove(0).0)
+    }
+
+    /// 만료된 유휴 세션을 모두 꺼냅니다.
+    pub fn expire(&mut self, now: Instant) -> Vec<T> {
+        let mut expired = Vec::new();
+        for sessions in self.idle.values_mut() {
+            let (old, keep): (Vec<_>, Vec<_>) = std::mem::take(sessions)
+                .into_iter()
+                .partition(|(_, idle_since)| now.saturating_duration_since(*idle_since) >= IDLE_TIMEOUT);
+            *sessions = keep;
+            expired.extend(old.into_iter().map(|(session, _)| session));
+        }
+        self.idle.retain(|_, sessions| !sessions.is_empty());
+        expired
+    }
+}
+
+static SSH_POOL: OnceLock<Mutex<SessionPool<ssh2::Session>>> = OnceLock::new();
+
+fn ssh_pool() -> &'static Mutex<SessionPool<ssh2::Session>> {
+    SSH_POOL.get_or_init(|| Mutex::new(SessionPool::new()))
+}
+
+/// 재사용할 세션을 꺼냅니다. (없으면 None - 호출자가 새로 연결)
+pub(super) fn checkout(key: &PoolKey) -> Option<ssh2::Session> {
+    ssh_pool()
+        .lock()
+        .unwrap()
+        .take(key, Instant::now(), |session| session.keepalive_send().is_ok())
+}
+
+/// 정상적으로 사용한 세션을 풀에 반납합니다.
+pub(super) fn checkin(key: PoolKey, session: ssh2::Session) {
+    let evicted = ssh_pool().lock().unwrap().put(key, session, Instant::now());
+    if let Some(session) = evicted {
+        close(session);
+    }
+}
+
+/// 오래 쓰지 않은 세션을 닫습니다. (주기적으로 호출)
+pub fn close_idle_sessions() {
+    let expired = ssh_pool().lock().unwrap().expire(Instant::now());
+    for session in expired {
+        close(session);
+    }
+}
+
+fn close(session: ssh2::Session) {
+    let _ = session.disconnect(None, "idle session closed", None);
+}
@@ -0,0 +1,166 @@
+//! SFTP 파일 목록 조회와 다운로드
+
+use super::pool::{self, PoolKey};
+use super::{connect, timeout_ms, SshClient, SshError, Timeouts};
+use anyhow::{Context, Result};
+use ssh2::Session;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// 원격 디렉터리의 파일
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteFile {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub modified: Option<u64>, // 수정 시각 (유닉스 초)
+}
+
+/// 한 번에 읽는 크기
+const CHUNK_SIZE: usize = 32 * 1024;
+
+impl SshClient {
+    /// 디렉터리의 일반 파일 목록을 조회합니다.
+    pub async fn list_dir(&self, dir: &str) -> Result<Vec<RemoteFile>> {
+        let key = self.pool_key();
+        let dir = dir.to_string();
+        let timeouts = self.timeouts();
+        let cancel = self.cancel.clone();
+        tokio::task::spawn_blocking(move || {
+            with_sftp(key, timeouts, &cancel, |sftp| {
+                let entries = sftp
+                    .readdir(Path::new(&dir))
+                    .map_err(|e| SshError::Read(format!("디렉터리 조회 실패 ({}): {}", dir, e)))?;
+                Ok(entries
+                    .into_iter()
+                    .filter(|(_, stat)| stat.is_file())
+                    .filter_map(|(path, stat)| {
+                        Some(RemoteFile {
+                            name: path.file_name()?.to_string_lossy().into_owned(),
+                            path: path.to_string_lossy().into_owned(),
+                            size: stat.size.unwrap_or(0),
+                            modified: stat.mtime,
+                        })
+                    })
+                    .collect())
+            })
+        })
+        .await
+        .context("SSH task failed")?
+    }
+
+    /// 원격 파일의 `offset`부터 `end`까지를 로컬 파일 끝에 이어 씁니다.
+    /// 받은 바이트 수는 `progress`에 더하며, 취소되면 그때까지 받은 내용은 남겨 둡니다. (다음에 이어받기)
+    pub async fn download(
+        &self,
+        remote: &str,
+        local: &Path,
+        offset: u64,
+        end: u64,
+        progress: Arc<AtomicU64>,
+    ) -> Result<()> {
+        let key = self.pool_key();
+        let remote = remote.to_string();
+        let local = local.to_path_buf();
+        let timeouts = self.timeouts();
+        let cancel = self.cancel.clone();
+        tokio::task::spawn_blocking(move || {
+            with_sftp(key, timeouts, &cancel, |sftp| {
+                copy_range(sftp, &remote, &local, (offset, end), &progress, &cancel, timeouts.command)
+            })
+        })
+        .await
+        .context("SSH task failed")?
+    }
+}
+
+/// 풀의 세션(없으면 새 세션)으로 SFTP 채널을 열어 `f`를 실행합니다. (블로킹)
+fn with_sftp<T>(
+    key: PoolKey,
+    timeouts: Timeouts,
+    cancel: &CancellationToken,
+    f: impl FnOnce(&ssh2::Sftp) -> Result<T>,
+) -> Result<T> {
+    if cancel.is_cancelled() {
+        return Err(SshError::Cancelled.into());
+    }
+    let open_sftp = |sess: &Session| {
+        sess.set_timeout(timeout_ms(timeouts.command));
+        sess.sftp().map_err(|e| SshError::Exec(format!("SFTP 시작 실패: {}", e)))
+    };
+
+    // 풀의 세션을 재사용하고, SFTP를 열 수 없으면 한 번 다시 연결
+    let (sess, sftp) = match pool::checkout(&key).map(|sess| open_sftp(&sess).map(|sftp| (sess, sftp))) {
+        Some(Ok(opened)) => opened,
+        _ => {
+            let sess = connect(&key, timeouts)?;
+            let sftp = open_sftp(&sess)?;
+            (sess, sftp)
+        }
+    };
+    let result = f(&sftp);
+    drop(sftp);
+    // 취소나 파일 오류는 세션 문제가 아니지만, 읽기 타임아웃 뒤의 세션은 상태를 알 수 없어 버림
+    let broken = matches!(
+        result.as_ref().err().and_then(|e| e.downcast_ref::<SshError>()),
+        Some(SshError::ReadTimeout { .. })
+    );
+    if !broken {
+        sess.set_timeout(timeout_ms(timeouts.command));
+        pool::checkin(key, sess);
+    }
+    result
+}
+
+/// `range` (시작, 끝) 구간을 복사 (블로킹)
+fn copy_range(
+    sftp: &ssh2::Sftp,
+    remote: &str,
+    local: &Path,
+    (offset, end): (u64, u64),
+    progress: &AtomicU64,
+    cancel: &CancellationToken,
+    read_timeout: Duration,
+) -> Result<()> {
+    let mut source = sftp
+        .open(Path::new(remote))
+        .map_err(|e| SshError::Read(format!("파일 열기 실패 ({}): {}", remote, e)))?;
+    source
+        .seek(SeekFrom::Start(offset))
+        .map_err(|e| SshError::Read(format!("파일 위치 이동 실패 ({}): {}", remote, e)))?;
+    let mut target = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(local)
+        .with_context(|| format!("로컬 파일을 열 수 없습니다: {}", local.display()))?;
+
+    let mut remaining = end.saturating_sub(offset);
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    while remaining > 0 {
+        if cancel.is_cancelled() {
+            target.flush().ok();
+            return Err(SshError::Cancelled.into());
+        }
+        let want = buf.len().min(remaining as usize);
+        let n = match source.read(&mut buf[..want]) {
+            Ok(0) => anyhow::bail!("파일이 예상보다 짧습니다 ({}바이트 남음): {}", remaining, remote),
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                target.flush().ok();
+                return Err(SshError::ReadTimeout { after: read_timeout }.into());
+            }
+            Err(e) => return Err(SshError::Read(e.to_string()).into()),
+        };
+        target
+            .write_all(&buf[..n])
+            .with_context(|| format!("로컬 파일 쓰기 실패: {}", local.display()))?;
+        remaining -= n as u64;
+        progress.fetch_add(n as u64, Ordering::Relaxed);
+    }
+    target.flush().with_context(|| format!("로컬 파일 쓰기 실패: {}", local.display()))?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! 명령 출력을 줄 단위로 받는 스트림
+
+use super::SshError;
+use anyhow::{Context, Result};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// 스트림 채널에 쌓아 둘 최대 줄 수 (받는 쪽이 느리면 SSH 읽기를 잠시 멈춤)
+pub(super) const LINE_BUFFER: usize = 1024;
+
+/// `OutputLines`에 줄을 보낼 채널
+pub(crate) fn line_channel() -> (mpsc::Sender<String>, mpsc::Receiver<String>) {
+    mpsc::channel(LINE_BUFFER)
+}
+
+/// `CommandRunner::execute_lines`가 반환하는 출력 스트림
+///
+/// 줄을 모두 받은 뒤 `finish`로 명령의 성공 여부를 확인합니다.
+pub struct OutputLines {
+    lines: mpsc::Receiver<String>,
+    task: JoinHandle<Result<()>>,
+    limit: Duration,
+    deadline: Instant,
+    timed_out: bool,
+    cancel: CancellationToken,
+}
+
+impl OutputLines {
+    pub(crate) fn new(
+        lines: mpsc::Receiver<String>,
+        task: JoinHandle<Result<()>>,
+        limit: Duration,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self { lines, task, limit, deadline: Instant::now() + limit, timed_out: false, cancel }
+    }
+
+    /// 다음 줄 (출력이 끝났거나 실패하면 None - 원인은 `finish`에서 확인)
+    pub async fn next_line(&mut self) -> Option<String> {
+        match tokio::time::timeout_at(self.deadline, self.lines.recv()).await {
+            Ok(line) => line,
+            Err(_) => {
+                self.cancel.cancel();
+                self.timed_out = true;
+                None
+            }
+        }
+    }
+
+    /// 명령 종료를 기다리고 결과를 반환합니다.
+    pub async fn finish(mut self) -> Result<()> {
+        // 남은 줄을 버려야 읽기 스레드가 채널에서 막히지 않음
+        self.lines.close();
+        match tokio::time::timeout_at(self.deadline, self.task).await {
+            Ok(_) if self.timed_out => Err(SshError::ReadTimeout { after: self.limit }.into()),
+            Ok(result) => result.context("SSH task failed")?,
+            Err(_) => {
+                self.cancel.cancel();
+                Err(SshError::ReadTimeout { after: self.limit }.into())
+            }
+        }
+    }
+}
+
+/// 받은 바이트를 줄 단위로 자릅니다. (줄 끝의 `\r`은 제거)
+#[derive(Debug, Default)]
+pub(super) struct LineSplitter {
+    pending: Vec<u8>,
+}
+
+impl LineSplitter {
+    /// 데이터를 추가하고 완성된 줄을 반환합니다.
+    pub(super) fn push(&mut self, data: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(data);
+        let mut lines = Vec::new();
+        let mut start = 0;
+        while let Some(pos) = self.pending[start..].iter().position(|b| *b == b'\n') {
+            lines.push(Self::decode(&self.pending[start..start + pos]));
+            start += pos + 1;
+        }
+        self.pending.drain(..start);
+        lines
+    }
+
+    /// 줄바꿈 없이 끝난 마지막 줄
+    pub(super) fn finish(self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| Self::decode(&self.pending))
+    }
+
+    fn decode(line: &[u8]) -> String {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        String::from_utf8_lossy(line).into_owned()
+    }
+}
@@ -0,0 +1,293 @@
+use super::pool::{SessionPool, HEALTH_CHECK_AFTER, IDLE_TIMEOUT};
+use super::*;
+use std::time::Instant;
+
+fn proxy(json: &str) -> Proxy {
+    let base = r#"{"id": 1, "host": "10.0.0.1", "port": 22, "username": "admin", "group": "g""#;
+    serde_json::from_str(&format!("{}{}}}", base, json)).unwrap()
+}
+
+#[test]
+fn auth_methods_follow_proxy_settings() {
+    // 비밀번호 없이 개인키만 설정할 수 있음
+    let client = SshClient::for_proxy(&proxy(r#", "ssh_key_path": "/keys/mwg", "ssh_key_passphrase": """#), 22);
+    assert_eq!(
+        client.auth_methods(),
+        vec![AuthMethod::KeyFile { path: PathBuf::from("/keys/mwg"), passphrase: None }]
+    );
+
+    let client = SshClient::for_proxy(
+        &proxy(r#", "password": "pw", "ssh_agent": true, "ssh_key_path": "/keys/mwg", "ssh_key_passphrase": "secret""#),
+        2222,
+    );
+    assert_eq!(client.port, 2222);
+    assert_eq!(
+        client.auth_methods(),
+        vec![
+            AuthMethod::Agent,
+            AuthMethod::KeyFile { path: PathBuf::from("/keys/mwg"), passphrase: Some("secret".to_string()) },
+            AuthMethod::Password("pw".to_string()),
+        ]
+    );
+
+    assert!(SshClient::for_proxy(&proxy(""), 22).auth_methods().is_empty());
+}
+
+#[test]
+fn jump_hosts_come_from_proxy_or_group() {
+    let config: crate::app::ProxyConfig = serde_json::from_str(
+        r#"{
+            "proxies": [
+                {"id": 1, "host": "10.0.0.1", "port": 22, "username": "admin", "group": "prod"},
+                {"id": 2, "host": "10.0.0.2", "port": 22, "username": "admin", "group": "prod",
+                 "jump_host": {"host": "bastion-b", "username": "ops", "port": 2222}},
+                {"id": 3, "host": "10.0.0.3", "port": 22, "username": "admin", "group": "dev"}
+            ],
+            "groups": {"prod": {"jump_host": {"host": "bastion-a", "username": "jump", "ssh_agent": true}}}
+        }"#,
+    )
+    .unwrap();
+    let proxies = config.into_proxies();
+    let jump_of = |i: usize| SshClient::for_proxy(&proxies[i], 22).jump_host.map(|j| (j.host, j.port));
+
+    assert_eq!(jump_of(0), Some(("bastion-a".to_string(), 22)));
+    // 프록시 설정이 그룹 설정보다 우선
+    assert_eq!(jump_of(1), Some(("bastion-b".to_string(), 2222)));
+    assert_eq!(jump_of(2), None);
+
+    let bastion = proxies[0].group_jump_host.as_ref().unwrap();
+    assert_eq!(bastion.auth_methods(), vec![AuthMethod::Agent]);
+    // 그룹에서 가져온 값은 저장하지 않음
+    assert!(!serde_json::to_string(&proxies[0]).unwrap().contains("bastion"));
+}
+
+#[test]
+fn key_paths_expand_home() {
+    if let Some(home) = std::env::var_os("HOME") {
+        assert_eq!(expand_home("~/.ssh/id_ed25519"), PathBuf::from(home).join(".ssh/id_ed25519"));
+    }
+    assert_eq!(expand_home("/etc/mwg/key"), PathBuf::from("/etc/mwg/key"));
+}
+
+fn pool_key(host: &str) -> PoolKey {
+    PoolKey {
+        host: host.to_string(),
+        port: 22,
+        username: "admin".to_string(),
+        auth_methods: vec![AuthMethod::Password("pw".to_string())],
+        jump_host: None,
+    }
+}
+
+#[test]
+fn session_pool_reuses_checks_and_expires_sessions() {
+    let start = Instant::now();
+    let mut pool = SessionPool::new();
+    let healthy = |_: &&str| true;
+
+    // 접속 정보가 같아야 재사용 (인증 방식이 바뀌면 새 세션)
+    assert_eq!(pool.put(pool_key("a"), "a1", start), None);
+    let mut other_auth = pool_key("a");
+    other_auth.auth_methods = vec![AuthMethod::Agent];
+    assert_eq!(pool.take(&other_auth, start, healthy), None);
+    assert_eq!(pool.take(&pool_key("a"), start, healthy), Some("a1"));
+    assert_eq!(pool.take(&pool_key("a"), start, healthy), None);
+
+    // 보관 한도를 넘으면 가장 오래된 세션을 돌려받아 닫음
+    assert_eq!(pool.put(pool_key("a"), "a1", start), None);
+    assert_eq!(pool.put(pool_key("a"), "a2", start), None);
+    assert_eq!(pool.put(pool_key("a"), "a3", start), Some("a1"));
+
+    // 오래 쉬었던 세션은 상태 확인을 통과해야 재사용
+    let later = start + HEALTH_CHECK_AFTER;
+    assert_eq!(pool.take(&pool_key("a"), later, |s: &&str| *s != "a3"), Some("a2"));
+
+    // 유휴 시간이 지나면 정리
+    pool.put(pool_key("b"), "b1", start);
+    pool.put(pool_key("c"), "c1", start + IDLE_TIMEOUT);
+    assert_eq!(pool.expire(start + IDLE_TIMEOUT), vec!["b1"]);
+    assert_eq!(pool.take(&pool_key("b"), start, healthy), None);
+    assert_eq!(pool.take(&pool_key("c"), start + IDLE_TIMEOUT * 2, healthy), None);
+}
+
+/// 실행 결과의 SshError 변형이 조건을 만족하는지 확인
+fn assert_ssh_error(result: Result<String>, check: impl Fn(&SshError) -> bool) {
+    let err = result.unwrap_err();
+    let ssh = err.downcast_ref::<SshError>().unwrap_or_else(|| panic!("SshError가 아님: {:#}", err));
+    assert!(check(ssh), "예상과 다른 오류: {:?}", ssh);
+}
+
+#[tokio::test]
+async fn connect_phases_report_distinct_errors() {
+    let client = |port: u16| {
+        SshClient::new("127.0.0.1".to_string(), port, "admin".to_string(), "pw".to_string())
+            .with_connect_timeout(Duration::from_millis(300))
+            .with_timeout(Duration::from_millis(500))
+    };
+
+    // 아무도 듣지 않는 포트: 연결 거부
+    let closed_port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    assert_ssh_error(client(closed_port).execute("true").await, |e| matches!(e, SshError::Connect { .. }));
+
+    // 접속은 받지만 SSH 배너를 보내지 않는 서버: 핸드셰이크 타임아웃
+    let silent = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = silent.local_addr().unwrap().port();
+    let started = Instant::now();
+    assert_ssh_error(client(port).execute("true").await, |e| {
+        matches!(e, SshError::HandshakeTimeout { .. }) && e.is_timeout()
+    });
+    assert!(started.elapsed() < Duration::from_secs(5), "{:?}", started.elapsed());
+
+    // 취소된 토큰이면 접속하지 않음
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    assert_ssh_error(client(port).with_cancel_token(cancel).execute("true").await, |e| {
+        matches!(e, SshError::Cancelled)
+    });
+    drop(silent);
+}
+
+#[test]
+fn line_splitter_joins_chunks_into_lines() {
+    let mut splitter = stream::LineSplitter::default();
+    assert!(splitter.push(b"There are cur").is_empty());
+    assert_eq!(splitter.push(b"rently 2\r\nA | 1\nB | "), vec!["There are currently 2", "A | 1"]);
+    assert_eq!(splitter.push(b"2\n\nC"), vec!["B | 2", ""]);
+    assert_eq!(splitter.finish(), Some("C".to_string()));
+
+    // 줄바꿈으로 끝나면 남은 줄 없음, 잘못된 UTF-8은 대체 문자로
+    let mut splitter = stream::LineSplitter::default();
+    assert_eq!(splitter.push(b"\xffok\n"), vec!["\u{fffd}ok"]);
+    assert_eq!(splitter.finish(), None);
+}
+
+#[tokio::test]
+async fn output_lines_yield_before_command_finishes() {
+    let (sender, receiver) = tokio::sync::mpsc::channel(stream::LINE_BUFFER);
+    let (release, wait) = std::sync::mpsc::channel::<()>();
+    let task = tokio::task::spawn_blocking(move || {
+        sender.blocking_send("first".to_string()).unwrap();
+        // 명령이 아직 끝나지 않은 상태에서도 첫 줄을 받을 수 있어야 함
+        wait.recv().unwrap();
+        sender.blocking_send("second".to_string()).unwrap();
+        Err(SshError::CommandFailed { status: 1, stderr: "boom".to_string() }.into())
+    });
+    let mut output = stream::OutputLines::new(receiver, task, Duration::from_secs(5), CancellationToken::new());
+
+    assert_eq!(output.next_line().await.as_deref(), Some("first"));
+    release.send(()).unwrap();
+    assert_eq!(output.next_line().await.as_deref(), Some("second"));
+    assert_eq!(output.next_line().await, None);
+    let err = output.finish().await.unwrap_err();
+    assert!(matches!(err.downcast_ref::<SshError>(), Some(SshError::CommandFailed { status: 1, .. })));
+
+    // 상한 시간이 지나면 토큰을 취소하고 ReadTimeout
+    let (_sender, receiver) = tokio::sync::mpsc::channel::<String>(1);
+    let cancel = CancellationToken::new();
+    let task_cancel = cancel.clone();
+    let task = tokio::task::spawn_blocking(move || {
+        while !task_cancel.is_cancelled() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        Err(SshError::Cancelled.into())
+    });
+    let mut output = stream::OutputLines::new(receiver, task, Duration::from_millis(200), cancel.clone());
+    assert_eq!(output.next_line().await, None);
+    assert!(cancel.is_cancelled());
+    let err = output.finish().await.unwrap_err();
+    assert!(matches!(err.downcast_ref::<SshError>(), Some(SshError::ReadTimeout { .. })));
+}
+
+#[test]
+fn escalation_wraps_commands_and_reports_failures() {
+    let proxy = proxy(r#", "password": "pw", "escalation": {"method": "sudo", "password": "s3cret"}"#);
+    let sudo = proxy.escalation.clone().unwrap();
+    assert_eq!(sudo, Escalation::Sudo { password: Some("s3cret".to_string()), user: None });
+    let nopasswd: Escalation = serde_json::from_str(r#"{"method": "sudo", "user": "mwg"}"#).unwrap();
+    let su: Escalation = serde_json::from_str(r#"{"method": "su", "password": "rootpw"}"#).unwrap();
+    assert!(su.needs_pty() && !sudo.needs_pty());
+    assert_eq!(sudo.stdin_password(), Some("s3cret"));
+    assert_eq!(su.stdin_password(), None);
+
+    // 작은따옴표가 들어간 명령도 그대로 실행되고, 표시 줄이 먼저 나옴
+    let command = "printf '%s\\n' \"it's ok\"";
+    let wrapped = nopasswd.wrap(command);
+    let script = wrapped.strip_prefix("sudo -n -u 'mwg' -- ").expect(&wrapped);
+    assert!(sudo.wrap(command).starts_with("sudo -k -S -p '' -u 'root' -- sh -c "));
+    assert!(su.wrap(command).starts_with("su 'root' -c "));
+    let output = std::process::Command::new("sh").arg("-c").arg(script).output().unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    let mut gate = escalation::MarkerGate::new(&nopasswd);
+    assert_eq!(gate.push(output.as_bytes()), b"it's ok\n");
+    assert!(gate.passed());
+
+    // su: 프롬프트에 한 번만 비밀번호를 보내고, PTY의 \r\n과 나뉘어 온 표시 줄을 처리
+    let mut gate = escalation::MarkerGate::new(&su);
+    assert!(gate.push(b"Pass").is_empty());
+    assert_eq!(gate.take_prompt_password(), None);
+    assert!(gate.push(b"word: ").is_empty());
+    assert_eq!(gate.take_prompt_password(), Some("rootpw"));
+    assert_eq!(gate.take_prompt_password(), None);
+    assert!(gate.push(b"\r\n__MMT_ESC").is_empty());
+    assert_eq!(gate.push(b"ALATED__\r\nline 1\r\n"), b"line 1\r\n");
+    assert_eq!(gate.push(b"line 2"), b"line 2");
+
+    // 표시 줄 없이 끝나면 원인별 메시지
+    let reason = |escalation: &Escalation, output: &str, stderr: &str| match escalation.failure(output, stderr, 1) {
+        SshError::Escalation(reason) => reason,
+        other => panic!("{:?}", other),
+    };
+    assert!(reason(&nopasswd, "", "sudo: a password is required\n").contains("비밀번호가 필요"));
+    assert!(reason(&sudo, "", "Sorry, try again.\nsudo: 1 incorrect password attempt\n").contains("sudo 비밀번호가 올바르지"));
+    assert!(reason(&su, "Password: \r\nsu: Authentication failure\r\n", "").contains("su 비밀번호가 올바르지"));
+    assert!(reason(&sudo, "", "monitor is not in the sudoers file.\n").contains("권한이 없습니다"));
+    assert_eq!(reason(&sudo, "", "sudo: command not found\n"), "sudo 실패 (종료 코드 1): sudo: command not found");
+}
+
+#[test]
+fn credential_references_stay_unresolved_until_use() {
+    let proxy = proxy(
+        r#", "password": "env:MMT_TEST_SSH_PASS", "ssh_key_path": "/keys/mwg", "ssh_key_passphrase": "plain",
+           "escalation": {"method": "su", "password": "cmd:echo rootpw"}"#,
+    );
+    // 참조는 풀 키에 그대로 남고 (비밀번호가 바뀌어도 같은 세션), 접속할 때 실제 값으로 바뀜
+    let client = SshClient::for_proxy(&proxy, 22);
+    assert!(client.auth_methods().contains(&AuthMethod::Password("env:MMT_TEST_SSH_PASS".to_string())));
+    assert_eq!(proxy.credential_references(), vec!["env:MMT_TEST_SSH_PASS", "cmd:echo rootpw"]);
+
+    let su = proxy.escalation.clone().unwrap();
+    assert_eq!(su.resolved().unwrap(), Escalation::Su { password: "rootpw".to_string(), user: None });
+
+    // 실패한 참조는 오류로 알리고, 프록시별로 조회할 수 있음
+    assert_eq!(proxy.credential_error(), None);
+    let failing: Escalation = serde_json::from_str(r#"{"method": "sudo", "password": "env:MMT_TEST_SSH_UNSET"}"#).unwrap();
+    let err = failing.resolved().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "권한 상승 실패: 자격 증명 참조 실패 (env:MMT_TEST_SSH_UNSET): 환경 변수가 설정되지 않았습니다"
+    );
+    let mut other = proxy.clone();
+    other.escalation = Some(failing);
+    assert!(other.credential_error().unwrap().contains("env:MMT_TEST_SSH_UNSET"));
+}
+
+#[test]
+fn accepted_host_keys_are_not_appended_twice() {
+    use super::host_key::append_known_host;
+    let path = std::env::temp_dir().join(format!("mmt-known-hosts-{}", std::process::id()));
+    let ed25519 = "[10.0.0.1]:2222 ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIKey";
+    let rsa = "[10.0.0.1]:2222 ssh-rsa AAAAB3Nza";
+    // 줄바꿈 없이 끝난 기존 파일에도 새 줄로 추가
+    std::fs::write(&path, "bastion ssh-ed25519 AAAAOther").unwrap();
+
+    assert!(append_known_host(&path, &format!("{}\n", ed25519)).unwrap());
+    assert!(!append_known_host(&path, ed25519).unwrap());
+    // 주석만 다른 같은 키도 중복
+    assert!(!append_known_host(&path, &format!("{} mmt", ed25519)).unwrap());
+    // 같은 호스트의 다른 키 종류는 추가
+    assert!(append_known_host(&path, rsa).unwrap());
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content, format!("bastion ssh-ed25519 AAAAOther\n{}\n{}\n", ed25519, rsa));
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,146 @@
+//! 점프 호스트(배스천)를 통한 접속
+
+use super::{auth_methods, connect_session, connect_tcp, expand_home, is_ssh2_timeout, AuthMethod, SshError};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use ssh2::{BlockDirections, Channel, Session};
+use std::io::prelude::*;
+use std::net::{TcpListener, TcpStream};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::time::Duration;
+
+/// 점프 호스트(배스천) 설정
+/// OpenSSH의 ProxyJump처럼 배스천에 먼저 접속한 뒤 direct-tcpip 채널로 대상 서버에 접속합니다.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JumpHost {
+    pub host: String,
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    pub username: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub password: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key_passphrase: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ssh_agent: bool,
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+impl JumpHost {
+    pub(super) fn auth_methods(&self) -> Vec<AuthMethod> {
+        let key_file = self
+            .ssh_key_path
+            .as_ref()
+            .map(|path| (expand_home(path), self.ssh_key_passphrase.clone().filter(|p| !p.is_empty())));
+        auth_methods(&self.password, key_file.as_ref(), self.ssh_agent)
+    }
+}
+
+/// 점프 호스트에 접속해 대상 서버로 가는 direct-tcpip 채널을 열고,
+/// 채널을 로컬 루프백 소켓으로 중계합니다. (ssh2 세션은 실제 소켓이 필요하므로)
+pub(super) fn open_tunnel(jump_host: &JumpHost, host: &str, port: u16, timeout: Duration) -> Result<TcpStream> {
+    let bastion_addr = format!("{}:{}", jump_host.host, jump_host.port);
+    let tcp = connect_tcp(&jump_host.host, jump_host.port, timeout).context("점프 호스트 연결 실패")?;
+    let bastion = connect_session(tcp, &jump_host.host, jump_host.port, &jump_host.username, &jump_host.auth_methods(), timeout)
+        .with_context(|| format!("점프 호스트 {} 접속 실패", bastion_addr))?;
+    let target = format!("{}:{}", host, port);
+    let channel = bastion.channel_direct_tcpip(host, port, None).map_err(|e| {
+        let error = if is_ssh2_timeout(&e) {
+            SshError::ConnectTimeout { addr: target.clone(), after: timeout }
+        } else {
+            SshError::Connect { addr: target.clone(), message: format!("점프 호스트 {}에서 터널을 열 수 없습니다: {}", bastion_addr, e) }
+        };
+        anyhow::Error::from(error)
+    })?;
+
+    let listener = TcpListener::bind(("127.0.0.1", 0)).context("터널용 로컬 소켓을 열 수 없습니다")?;
+    let local = TcpStream::connect(listener.local_addr()?)?;
+    let (forwarded, peer) = listener.accept()?;
+    // 다른 로컬 프로세스가 먼저 접속한 경우가 아닌지 확인
+    if peer != local.local_addr()? {
+        anyhow::bail!("터널 로컬 소켓에 예상하지 못한 접속: {}", peer);
+    }
+    std::thread::spawn(move || forward_channel(bastion, channel, forwarded));
+    Ok(local)
+}
+
+/// 로컬 소켓과 배스천 채널 사이에서 데이터를 중계합니다. 어느 한쪽이 닫히면 종료합니다.
+/// 두 소켓을 poll로 기다리므로 쉬는 동안에는 깨어나지 않고, 풀이 쉬는 세션을 닫아
+/// 로컬 소켓이 닫히면 바로 종료합니다.
+fn forward_channel(bastion: Session, mut channel: Channel, mut local: TcpStream) {
+    bastion.set_blocking(false);
+    let fds = [local.as_raw_fd(), bastion.as_raw_fd()];
+    let mut buf = vec![0u8; 32 * 1024];
+    // libssh2가 소켓에서 미리 읽어 둔 데이터는 poll에 보이지 않으므로 기다리기 전에 채널을 비움
+    while let Ok(true) = drain_channel(&mut channel, &mut local, &mut buf) {
+        let Ok(ready) = poll(&[(fds[0], libc::POLLIN), (fds[1], libc::POLLIN)]) else {
+            break;
+        };
+        if ready[0] {
+            match local.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    if write_channel(&bastion, &mut channel, &buf[..n]).is_err() {
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(_) => break,
+            }
+        }
+    }
+    let _ = channel.close();
+}
+
+/// 채널에서 지금 읽을 수 있는 데이터를 모두 로컬 소켓으로 넘깁니다. 채널이 닫혔으면 false를 반환합니다.
+fn drain_channel(channel: &mut Channel, local: &mut TcpStream, buf: &mut [u8]) -> std::io::Result<bool> {
+    loop {
+        match channel.read(buf) {
+            Ok(0) => return Ok(false),
+            Ok(n) => local.write_all(&buf[..n])?,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => return Ok(true),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// 논블로킹 채널에 데이터를 모두 씁니다. 막히면 libssh2가 기다리는 방향으로 배스천 소켓을 기다립니다.
+fn write_channel(bastion: &Session, channel: &mut Channel, mut data: &[u8]) -> std::io::Result<()> {
+    while !data.is_empty() {
+        match channel.write(data) {
+            Ok(n) => data = &data[n..],
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                let events = match bastion.block_directions() {
+                    BlockDirections::Inbound => libc::POLLIN,
+                    BlockDirections::Outbound => libc::POLLOUT,
+                    BlockDirections::Both | BlockDirections::None => libc::POLLIN | libc::POLLOUT,
+                };
+                poll(&[(bastion.as_raw_fd(), events)])?;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// 소켓 중 하나가 준비될 때까지 기다리고, 소켓별로 준비됐는지 (닫힘/오류 포함) 반환합니다.
+fn poll(fds: &[(RawFd, libc::c_short)]) -> std::io::Result<Vec<bool>> {
+    let mut pollfds: Vec<libc::pollfd> =
+        fds.iter().map(|&(fd, events)| libc::pollfd { fd, events, revents: 0 }).collect();
+    loop {
+        // SAFETY: pollfds는 호출하는 동안 살아 있는 배열이고 길이를 함께 넘김
+        let result = unsafe { libc::poll(pollfds.as_mut_ptr(), pollfds.len() as libc::nfds_t, -1) };
+        if result >= 0 {
+            return Ok(pollfds.iter().map(|p| p.revents != 0).collect());
+        }
+        let error = std::io::Error::last_os_error();
+        if error.kind() != std::io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
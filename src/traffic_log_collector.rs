@@ -1,7 +1,10 @@
 use crate::app::Proxy;
-use crate::ssh::SshClient;
+use crate::command_runner::{self, CommandRunner};
 use anyhow::{Context, Result};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 /// 트래픽 로그 수집기 설정
 #[derive(Debug, Clone)]
@@ -14,7 +17,6 @@ pub struct TrafficLogCollectorConfig {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogDirection {
-    Head, // 처음부터
     Tail, // 끝부터
 }
 
@@ -32,22 +34,51 @@ impl Default for TrafficLogCollectorConfig {
 /// 트래픽 로그 수집기
 pub struct TrafficLogCollector {
     config: TrafficLogCollectorConfig,
+    cancel: CancellationToken,
+    lines_received: Arc<AtomicUsize>, // 지금까지 받은 로그 라인 수
+    runner: Option<Arc<dyn CommandRunner>>, // 설정하면 프록시 설정 대신 이 실행기 사용
 }
 
 impl TrafficLogCollector {
     pub fn new(config: TrafficLogCollectorConfig) -> Self {
-        Self { config }
+        Self { config, cancel: CancellationToken::new(), lines_received: Arc::default(), runner: None }
+    }
+
+    /// 토큰이 취소되면 진행 중인 SSH 명령을 중단
+    pub fn with_cancel_token(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// 조회 중 받은 라인 수를 이 카운터에 더함 (조회가 끝나기 전에 진행 상황 표시용)
+    pub fn with_line_counter(mut self, lines_received: Arc<AtomicUsize>) -> Self {
+        self.lines_received = lines_received;
+        self
+    }
+
+    /// 프록시 설정(SSH/로컬) 대신 주어진 실행기로 명령 실행
+    #[cfg(test)]
+    pub fn with_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = Some(runner);
+        self
+    }
+
+    fn runner_for(&self, proxy: &Proxy) -> Arc<dyn CommandRunner> {
+        match &self.runner {
+            Some(runner) => runner.clone(),
+            None => command_runner::runner_for(
+                proxy,
+                self.config.ssh_port,
+                Duration::from_secs(self.config.timeout_sec),
+                self.cancel.clone(),
+                proxy.escalation.clone(),
+            ),
+        }
     }
 
     /// 프록시에서 트래픽 로그를 조회합니다.
     pub async fn fetch_logs(&self, proxy: &Proxy, log_path: &str) -> Result<Vec<String>> {
-        let ssh_client = SshClient::new(
-            proxy.host.clone(),
-            self.config.ssh_port,
-            proxy.username.clone(),
-            proxy.password.clone(),
-        )
-        .with_timeout(Duration::from_secs(self.config.timeout_sec));
+        let runner = self.runner_for(proxy);
 
         // 로그 조회 명령어 생성
         let limit_str = self.config.limit.to_string();
@@ -55,26 +86,67 @@ impl TrafficLogCollector {
         let safe_path = format!("'{}'", log_path.replace('\'', r"'\''"));
         
         let command = match self.config.direction {
-            LogDirection::Head => {
-                format!("timeout 5s nice -n 10 ionice -c2 -n7 head -n {} {} | sed -e 's/[^[:print:]\\t]//g' | head -c 1048576 | cat", 
-                    limit_str, safe_path)
-            }
             LogDirection::Tail => {
                 format!("timeout 5s nice -n 10 ionice -c2 -n7 tail -n {} {} | sed -e 's/[^[:print:]\\t]//g' | head -c 1048576 | cat", 
                     limit_str, safe_path)
             }
         };
 
-        let output = ssh_client.execute(&command).await
+        // 받는 대로 빈 라인을 제거하며 모음
+        let mut output = runner.execute_lines(&command);
+        let mut lines = Vec::new();
+        while let Some(line) = output.next_line().await {
+            let line = line.trim();
+            if !line.is_empty() {
+                lines.push(line.to_string());
+                self.lines_received.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        output.finish().await
             .context(format!("SSH 명령어 실행 실패: {}", command))?;
 
-        // 라인으로 분리하고 빈 라인 제거
-        let lines: Vec<String> = output
-            .lines()
-            .map(|l| l.trim().to_string())
-            .filter(|l| !l.is_empty())
-            .collect();
-
         Ok(lines)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_runner::ScriptedRunner;
+    use crate::traffic_log_parser::TrafficLogRecord;
+
+    const RECORDED: &str = include_str!("../fixtures/mwg_access.log");
+
+    #[tokio::test]
+    async fn fetch_logs_tails_quoted_path_and_parses_recorded_log() {
+        let proxy: Proxy = serde_json::from_str(
+            r#"{"id": 1, "host": "10.0.0.1", "port": 22, "username": "u", "password": "p", "group": "g"}"#,
+        )
+        .unwrap();
+        let runner = Arc::new(ScriptedRunner::new().respond("tail -n 200 ", RECORDED));
+        let lines_received = Arc::new(AtomicUsize::new(0));
+        let config = TrafficLogCollectorConfig { limit: 200, ..TrafficLogCollectorConfig::default() };
+        let collector = TrafficLogCollector::new(config)
+            .with_runner(runner.clone())
+            .with_line_counter(lines_received.clone());
+
+        let lines = collector.fetch_logs(&proxy, "/var/log/mwg/it's access.log").await.unwrap();
+        assert_eq!(lines.len(), 3); // 마지막 빈 줄 제거
+        assert_eq!(lines_received.load(Ordering::Relaxed), 3);
+        let executed = runner.executed();
+        assert!(executed[0].contains(r"'/var/log/mwg/it'\''s access.log'"), "{}", executed[0]);
+
+        let records: Vec<TrafficLogRecord> = lines.iter().map(|line| TrafficLogRecord::parse(line).unwrap()).collect();
+        assert_eq!(records[1].username.as_deref(), Some("bob"));
+        assert_eq!(records[1].response_statuscode, Some(403));
+        assert_eq!(records[1].url_host.as_deref(), Some("blocked.example.net"));
+        assert_eq!(records[2].url_parametersstring.as_deref(), Some("q=rust"));
+        assert_eq!(records[0].content_lenght, Some(5120));
+
+        // 실행 실패는 명령과 함께 보고
+        let collector = TrafficLogCollector::new(TrafficLogCollectorConfig::default())
+            .with_runner(Arc::new(ScriptedRunner::new().fail("tail -n 500", "No such file or directory")));
+        let err = collector.fetch_logs(&proxy, "/missing.log").await.unwrap_err();
+        assert!(format!("{:#}", err).contains("No such file or directory"));
+    }
+}
@@ -253,101 +253,135 @@ impl TrafficLogAnalyzer {
 
     /// 로그 라인들을 분석하여 TOP N 결과 반환
     pub fn analyze(&self, lines: &[String]) -> TopNAnalysis {
-        let mut client_counter: HashMap<String, (usize, i64, i64)> = HashMap::new();
-        let mut host_counter: HashMap<String, (usize, i64, i64)> = HashMap::new();
-        let mut url_counter: HashMap<String, usize> = HashMap::new();
-        let mut unique_clients = std::collections::HashSet::new();
-        let mut unique_hosts = std::collections::HashSet::new();
-        
-        let mut total_recv_bytes = 0i64;
-        let mut total_sent_bytes = 0i64;
-        let mut blocked_count = 0usize;
-        let mut parsed_records = 0usize;
-        let mut unparsed_records = 0usize;
-
+        let mut tally = Tally::default();
         for line in lines {
-            if line.trim().is_empty() {
-                continue;
+            tally.add(line);
+        }
+        tally.finish(self.top_n)
+    }
+
+    /// 로그 파일들을 순서대로 읽으며 분석 (`.gz`는 압축을 풀며 읽음)
+    /// 파일 전체를 메모리에 올리지 않으므로 하루치 로그도 분석할 수 있습니다.
+    pub fn analyze_files(&self, paths: &[std::path::PathBuf]) -> Result<TopNAnalysis> {
+        use std::io::BufRead;
+
+        let mut tally = Tally::default();
+        let mut buf = Vec::new();
+        for path in paths {
+            let mut reader = crate::log_cache::open_log(path)?;
+            loop {
+                buf.clear();
+                let n = reader
+                    .read_until(b'\n', &mut buf)
+                    .map_err(|e| anyhow::anyhow!("로그 파일 읽기 실패 ({}): {}", path.display(), e))?;
+                if n == 0 {
+                    break;
+                }
+                // 제어 문자가 섞인 줄도 분석 (잘못된 UTF-8은 대체 문자로)
+                let line = String::from_utf8_lossy(&buf);
+                tally.add(line.trim_end_matches(['\r', '\n']));
             }
+        }
+        Ok(tally.finish(self.top_n))
+    }
+}
 
-            match TrafficLogRecord::parse(line) {
-                Ok(record) => {
-                    parsed_records += 1;
+/// 분석 중 누적 통계
+#[derive(Default)]
+struct Tally {
+    client_counter: HashMap<String, (usize, i64, i64)>,
+    host_counter: HashMap<String, (usize, i64, i64)>,
+    url_counter: HashMap<String, usize>,
+    total_records: usize,
+    total_recv_bytes: i64,
+    total_sent_bytes: i64,
+    blocked_count: usize,
+    parsed_records: usize,
+    unparsed_records: usize,
+}
 
-                    let client_ip = record.client_ip.as_ref()
-                        .map(|s| s.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    let url_host = record.url_host.as_ref()
-                        .map(|s| s.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    let url_path = record.url_path.as_ref()
-                        .map(|s| s.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    
-                    let recv_bytes = record.recv_byte.unwrap_or(0);
-                    let sent_bytes = record.sent_byte.unwrap_or(0);
-                    let action_names = record.action_names.as_ref()
-                        .map(|s| s.as_str())
-                        .unwrap_or("");
-                    let block_id = record.block_id.as_ref()
-                        .map(|s| s.as_str())
-                        .unwrap_or("");
+impl Tally {
+    fn add(&mut self, line: &str) {
+        self.total_records += 1;
+        if line.trim().is_empty() {
+            return;
+        }
 
-                    // 클라이언트 통계
-                    if !client_ip.is_empty() {
-                        unique_clients.insert(client_ip.clone());
-                        let entry = client_counter.entry(client_ip.clone()).or_insert((0, 0, 0));
-                        entry.0 += 1;
-                        entry.1 += recv_bytes;
-                        entry.2 += sent_bytes;
-                    }
+        match TrafficLogRecord::parse(line) {
+            Ok(record) => {
+                self.parsed_records += 1;
 
-                    // 호스트 통계
-                    if !url_host.is_empty() {
-                        unique_hosts.insert(url_host.clone());
-                        let entry = host_counter.entry(url_host.clone()).or_insert((0, 0, 0));
-                        entry.0 += 1;
-                        entry.1 += recv_bytes;
-                        entry.2 += sent_bytes;
-                    }
+                let client_ip = record.client_ip.as_deref()
+                    .unwrap_or("")
+                    .to_string();
+                let url_host = record.url_host.as_deref()
+                    .unwrap_or("")
+                    .to_string();
+                let url_path = record.url_path.as_deref()
+                    .unwrap_or("")
+                    .to_string();
+                
+                let recv_bytes = record.recv_byte.unwrap_or(0);
+                let sent_bytes = record.sent_byte.unwrap_or(0);
+                let action_names = record.action_names.as_deref()
+                    .unwrap_or("");
+                let block_id = record.block_id.as_deref()
+                    .unwrap_or("");
 
-                    // URL 통계
-                    if !url_host.is_empty() {
-                        let url = if !url_path.is_empty() {
-                            format!("{}://{}{}", 
-                                record.url_protocol.as_ref().unwrap_or(&"http".to_string()),
-                                url_host,
-                                url_path
-                            )
-                        } else {
-                            format!("{}://{}", 
-                                record.url_protocol.as_ref().unwrap_or(&"http".to_string()),
-                                url_host
-                            )
-                        };
-                        *url_counter.entry(url).or_insert(0) += 1;
-                    }
+                // 클라이언트 통계
+                if !client_ip.is_empty() {
+                    let entry = self.client_counter.entry(client_ip).or_insert((0, 0, 0));
+                    entry.0 += 1;
+                    entry.1 += recv_bytes;
+                    entry.2 += sent_bytes;
+                }
 
-                    // 전체 통계
-                    total_recv_bytes += recv_bytes;
-                    total_sent_bytes += sent_bytes;
+                // 호스트 통계
+                if !url_host.is_empty() {
+                    let entry = self.host_counter.entry(url_host.clone()).or_insert((0, 0, 0));
+                    entry.0 += 1;
+                    entry.1 += recv_bytes;
+                    entry.2 += sent_bytes;
+                }
 
-                    // 차단 카운트
-                    if !block_id.is_empty() || action_names.contains("block") || action_names.contains("Block") {
-                        blocked_count += 1;
-                    }
+                // URL 통계
+                if !url_host.is_empty() {
+                    let url = if !url_path.is_empty() {
+                        format!("{}://{}{}", 
+                            record.url_protocol.as_ref().unwrap_or(&"http".to_string()),
+                            url_host,
+                            url_path
+                        )
+                    } else {
+                        format!("{}://{}", 
+                            record.url_protocol.as_ref().unwrap_or(&"http".to_string()),
+                            url_host
+                        )
+                    };
+                    *self.url_counter.entry(url).or_insert(0) += 1;
                 }
-                Err(_) => {
-                    unparsed_records += 1;
+
+                // 전체 통계
+                self.total_recv_bytes += recv_bytes;
+                self.total_sent_bytes += sent_bytes;
+
+                // 차단 카운트
+                if !block_id.is_empty() || action_names.contains("block") || action_names.contains("Block") {
+                    self.blocked_count += 1;
                 }
             }
+            Err(_) => {
+                self.unparsed_records += 1;
+            }
         }
+    }
+
+    fn finish(self, top_n: usize) -> TopNAnalysis {
+        let unique_clients = self.client_counter.len();
+        let unique_hosts = self.host_counter.len();
 
         // TOP N 정렬
-        let mut top_clients: Vec<TopClient> = client_counter
+        let mut top_clients: Vec<TopClient> = self.client_counter
             .into_iter()
             .map(|(ip, (count, recv, sent))| TopClient {
                 client_ip: ip,
@@ -356,10 +390,10 @@ impl TrafficLogAnalyzer {
                 sent_bytes: sent,
             })
             .collect();
-        top_clients.sort_by(|a, b| b.request_count.cmp(&a.request_count));
-        top_clients.truncate(self.top_n);
+        top_clients.sort_by_key(|item| std::cmp::Reverse(item.request_count));
+        top_clients.truncate(top_n);
 
-        let mut top_hosts: Vec<TopHost> = host_counter
+        let mut top_hosts: Vec<TopHost> = self.host_counter
             .into_iter()
             .map(|(host, (count, recv, sent))| TopHost {
                 host,
@@ -368,31 +402,31 @@ impl TrafficLogAnalyzer {
                 sent_bytes: sent,
             })
             .collect();
-        top_hosts.sort_by(|a, b| b.request_count.cmp(&a.request_count));
-        top_hosts.truncate(self.top_n);
+        top_hosts.sort_by_key(|item| std::cmp::Reverse(item.request_count));
+        top_hosts.truncate(top_n);
 
-        let mut top_urls: Vec<TopUrl> = url_counter
+        let mut top_urls: Vec<TopUrl> = self.url_counter
             .into_iter()
             .map(|(url, count)| TopUrl {
                 url,
                 request_count: count,
             })
             .collect();
-        top_urls.sort_by(|a, b| b.request_count.cmp(&a.request_count));
-        top_urls.truncate(self.top_n);
+        top_urls.sort_by_key(|item| std::cmp::Reverse(item.request_count));
+        top_urls.truncate(top_n);
 
         TopNAnalysis {
             top_clients,
             top_hosts,
             top_urls,
-            total_records: lines.len(),
-            parsed_records,
-            unparsed_records,
-            total_recv_bytes,
-            total_sent_bytes,
-            blocked_count,
-            unique_clients: unique_clients.len(),
-            unique_hosts: unique_hosts.len(),
+            total_records: self.total_records,
+            parsed_records: self.parsed_records,
+            unparsed_records: self.unparsed_records,
+            total_recv_bytes: self.total_recv_bytes,
+            total_sent_bytes: self.total_sent_bytes,
+            blocked_count: self.blocked_count,
+            unique_clients,
+            unique_hosts,
         }
     }
 }
@@ -97,6 +97,28 @@ pub fn load_thresholds() -> HashMap<String, ThresholdConfig> {
     if !thresholds.contains_key("interface_traffic") {
         thresholds.insert("interface_traffic".to_string(), ThresholdConfig { warning: 1000000000.0, critical: 5000000000.0 });
     }
+    if !thresholds.contains_key("interface_utilization") {
+        thresholds.insert("interface_utilization".to_string(), ThresholdConfig { warning: 70.0, critical: 90.0 });
+    }
+    if !thresholds.contains_key("interface_errors") {
+        thresholds.insert("interface_errors".to_string(), ThresholdConfig { warning: 1.0, critical: 10.0 });
+    }
+    if !thresholds.contains_key("interface_discards") {
+        thresholds.insert("interface_discards".to_string(), ThresholdConfig { warning: 10.0, critical: 100.0 });
+    }
+    // SSH 호스트 지표 (load는 코어당 1분 평균 부하, 나머지는 %)
+    if !thresholds.contains_key("load") {
+        thresholds.insert("load".to_string(), ThresholdConfig { warning: 1.0, critical: 2.0 });
+    }
+    if !thresholds.contains_key("swap") {
+        thresholds.insert("swap".to_string(), ThresholdConfig { warning: 30.0, critical: 70.0 });
+    }
+    if !thresholds.contains_key("disk") {
+        thresholds.insert("disk".to_string(), ThresholdConfig { warning: 80.0, critical: 90.0 });
+    }
+    if !thresholds.contains_key("open_files") {
+        thresholds.insert("open_files".to_string(), ThresholdConfig { warning: 70.0, critical: 90.0 });
+    }
     
     thresholds
 }
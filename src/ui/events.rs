@@ -0,0 +1,181 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::{App, TrapListenerStatus};
+use crate::snmp::TrapSeverity;
+
+pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // 수신 상태/통계 영역
+            Constraint::Min(3),     // 이벤트 테이블
+            Constraint::Length(if app.events.search_mode { 6 } else { 3 }),  // 단축키 도움말 + 검색 UI
+        ])
+        .split(area);
+
+    // 수신 상태/통계 영역
+    let status_chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Horizontal)
+        .constraints([
+            Constraint::Length(30), // 수신 상태
+            Constraint::Length(34), // 심각도별 건수
+            Constraint::Length(18), // 심각도 필터
+            Constraint::Min(0),     // 마지막 메시지
+        ])
+        .split(chunks[0]);
+
+    let (status_text, status_color) = match &app.events.listener_status {
+        TrapListenerStatus::Disabled => ("비활성화 (trap.enabled)".to_string(), Color::Gray),
+        TrapListenerStatus::Listening(addr) => (format!("✓ 수신중 {}", addr), Color::Green),
+        TrapListenerStatus::Failed(_) => ("✗ 시작 실패".to_string(), Color::Red),
+    };
+    frame.render_widget(
+        Paragraph::new(status_text)
+            .block(Block::default().borders(Borders::ALL).title("트랩 수신"))
+            .style(Style::default().fg(status_color)),
+        status_chunks[0],
+    );
+
+    let count = |severity: TrapSeverity| app.events.events.iter().filter(|e| e.severity == severity).count();
+    frame.render_widget(
+        Paragraph::new(format!(
+            "심각 {} | 경고 {} | 정보 {}",
+            count(TrapSeverity::Critical),
+            count(TrapSeverity::Warning),
+            count(TrapSeverity::Info)
+        ))
+        .block(Block::default().borders(Borders::ALL).title(format!("이벤트 (총 {}건)", app.events.events.len())))
+        .style(Style::default().fg(Color::Cyan)),
+        status_chunks[1],
+    );
+
+    frame.render_widget(
+        Paragraph::new(app.events.get_severity_filter_display())
+            .block(Block::default().borders(Borders::ALL).title("필터(F)"))
+            .style(Style::default().fg(Color::Yellow)),
+        status_chunks[2],
+    );
+
+    // 시작 실패/거부된 메시지가 있으면 에러, 없으면 마지막 CSV 저장 경로
+    let (message, message_color) = if let TrapListenerStatus::Failed(e) = &app.events.listener_status {
+        (e.clone(), Color::Red)
+    } else if let Some(e) = &app.events.last_error {
+        (format!("거부 {}건 | {}", app.events.rejected_count, e), Color::Red)
+    } else if let Some(path) = &app.events.last_export {
+        (format!("저장됨: {}", path), Color::Green)
+    } else {
+        ("-".to_string(), Color::Gray)
+    };
+    frame.render_widget(
+        Paragraph::new(message)
+            .block(Block::default().borders(Borders::ALL).title("메시지"))
+            .style(Style::default().fg(message_color)),
+        status_chunks[3],
+    );
+
+    // 이벤트 테이블
+    let filtered = app.events.filtered_events();
+    let filtered_count = filtered.len();
+    let table = if filtered.is_empty() {
+        let empty_message = if app.events.events.is_empty() {
+            "수신된 트랩이 없습니다."
+        } else {
+            "필터 조건에 맞는 이벤트가 없습니다."
+        };
+        Table::new(
+            vec![Row::new(vec![Cell::from(empty_message)])],
+            [Constraint::Percentage(100)],
+        )
+        .block(Block::default().borders(Borders::ALL).title("이벤트 목록"))
+    } else {
+        let rows: Vec<Row> = filtered
+            .iter()
+            .map(|event| {
+                let severity_color = match event.severity {
+                    TrapSeverity::Critical => Color::Red,
+                    TrapSeverity::Warning => Color::Yellow,
+                    TrapSeverity::Info => Color::Green,
+                };
+                let proxy_display = match (&event.proxy_name, &event.group) {
+                    (Some(name), Some(group)) => format!("{} ({})", name, group),
+                    (Some(name), None) => name.clone(),
+                    _ => "미등록".to_string(),
+                };
+                Row::new(vec![
+                    Cell::from(event.received_at.format("%m-%d %H:%M:%S").to_string()),
+                    Cell::from(event.severity.label())
+                        .style(Style::default().fg(severity_color).add_modifier(Modifier::BOLD)),
+                    Cell::from(proxy_display)
+                        .style(Style::default().fg(if event.proxy_id.is_some() { Color::White } else { Color::DarkGray })),
+                    Cell::from(event.source.clone()).style(Style::default().fg(Color::Cyan)),
+                    Cell::from(format!("{}/{}", event.version, event.kind)),
+                    Cell::from(event.name.clone()).style(Style::default().fg(severity_color)),
+                    Cell::from(event.details.clone()).style(Style::default().fg(Color::Gray)),
+                ])
+            })
+            .collect();
+
+        Table::new(rows, [
+            Constraint::Length(16), // 수신 시각
+            Constraint::Length(6),  // 심각도
+            Constraint::Length(24), // 프록시
+            Constraint::Length(16), // 송신 주소
+            Constraint::Length(11), // 버전/종류
+            Constraint::Length(28), // 트랩 이름
+            Constraint::Min(0),     // 상세 (나머지 공간)
+        ])
+        .header(Row::new(vec![
+            Cell::from("수신시각").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("심각도").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("프록시").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("송신주소").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("버전").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("트랩").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("상세").style(Style::default().add_modifier(Modifier::BOLD)),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title(format!("이벤트 목록 ({}건)", filtered_count)))
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+    };
+    frame.render_stateful_widget(table, chunks[1], &mut app.events.table_state);
+
+    // 키보드 단축키 도움말
+    let help_text = "Tab: 탭전환 | ↑↓: 행이동 | F: 심각도필터 | /: 검색 | E: CSV저장 | X: 목록비우기";
+    if app.events.search_mode {
+        let search_chunks = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // 도움말
+                Constraint::Length(3),  // 검색 입력
+            ])
+            .split(chunks[2]);
+
+        frame.render_widget(
+            Paragraph::new(help_text)
+                .block(Block::default().borders(Borders::ALL).title("단축키"))
+                .style(Style::default().fg(Color::Gray)),
+            search_chunks[0],
+        );
+
+        // 검색 입력 UI
+        let cursor = "█";
+        frame.render_widget(
+            Paragraph::new(format!("검색어: {}{} | 결과 {}건 | Enter: 완료(유지) | Esc: 취소(초기화)",
+                app.events.search_query, cursor, filtered_count))
+                .block(Block::default().borders(Borders::ALL).title("검색 모드"))
+                .style(Style::default().fg(Color::Yellow)),
+            search_chunks[1],
+        );
+    } else {
+        frame.render_widget(
+            Paragraph::new(help_text)
+                .block(Block::default().borders(Borders::ALL).title("단축키"))
+                .style(Style::default().fg(Color::Gray)),
+            chunks[2],
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::ssh::HostKeyPrompt;
+
+/// 처음 접속하는 SSH 서버의 호스트 키 확인 창
+pub fn draw(frame: &mut Frame, prompt: &HostKeyPrompt, error: Option<&str>, area: Rect) {
+    let popup_area = centered_rect(70, 11, area);
+
+    let mut lines = vec![
+        Line::from(format!("{}:{} 서버는 known_hosts에 등록되어 있지 않습니다.", prompt.host, prompt.port)),
+        Line::from(""),
+        Line::from(vec![Span::raw("키 종류: "), Span::styled(prompt.key_type.clone(), Style::default().fg(Color::Cyan))]),
+        Line::from(vec![
+            Span::raw("지문:    "),
+            Span::styled(prompt.fingerprint.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+        Line::from("서버 관리자에게 받은 지문과 같을 때만 수락하세요. 수락 후 조회를 다시 실행하세요."),
+    ];
+    if let Some(error) = error {
+        lines.push(Line::from(Span::styled(format!("저장 실패: {}", error), Style::default().fg(Color::Red))));
+    }
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title("SSH 호스트 키 확인 [y: 수락 / n, Esc: 거부]"),
+        ),
+        popup_area,
+    );
+}
+
+/// 가로는 비율, 세로는 줄 수로 중앙에 위치한 사각형 계산
+fn centered_rect(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(height), Constraint::Min(0)])
+        .split(r);
+
+    Layout::default()
+        .direction(ratatui::layout::Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -1,4 +1,6 @@
 mod config;
+mod events;
+mod host_key;
 mod proxy_management;
 mod resource_usage;
 mod session_browser;
@@ -19,13 +21,14 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         .split(frame.size());
 
     // 탭 헤더
-    let tabs = Tabs::new(vec!["프록시관리", "자원사용률", "세션브라우저", "트래픽로그"])
+    let tabs = Tabs::new(vec!["프록시관리", "자원사용률", "세션브라우저", "트래픽로그", "이벤트"])
         .block(Block::default().borders(Borders::ALL).title(app.title.clone()))
         .select(match app.current_tab {
             TabIndex::ProxyManagement => 0,
             TabIndex::ResourceUsage => 1,
             TabIndex::SessionBrowser => 2,
             TabIndex::TrafficLogs => 3,
+            TabIndex::Events => 4,
         })
         .style(Style::default().fg(Color::White))
         .highlight_style(
@@ -42,5 +45,11 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         TabIndex::ResourceUsage => resource_usage::draw(frame, app, chunks[1]),
         TabIndex::SessionBrowser => session_browser::draw(frame, app, chunks[1]),
         TabIndex::TrafficLogs => traffic_logs::draw(frame, app, chunks[1]),
+        TabIndex::Events => events::draw(frame, app, chunks[1]),
+    }
+
+    // SSH 호스트 키 확인 창 (모든 탭 위에 표시)
+    if let Some(prompt) = &app.host_key_prompt {
+        host_key::draw(frame, prompt, app.host_key_error.as_deref(), frame.size());
     }
 }
@@ -1,11 +1,13 @@
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
     Frame,
 };
 
-use crate::app::App;
+use crate::app::proxy_form::{FieldKind, ManagedItem, ProxyForm};
+use crate::app::{App, ProxyState};
 
 pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     // 통계 정보 영역, 테이블 영역, 단축키 영역으로 분할
@@ -24,7 +26,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         .constraints([
             Constraint::Length(20), // 전체 프록시 수
             Constraint::Length(20), // 그룹 수
-            Constraint::Min(0),     // 나머지
+            Constraint::Min(0),     // 저장 결과
         ])
         .split(chunks[0]);
     
@@ -37,86 +39,117 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         stats_chunks[0],
     );
 
-    // 그룹 수
-    use std::collections::HashSet;
-    let group_count: HashSet<String> = app.proxies.iter().map(|p| p.group.clone()).collect();
+    // 그룹 수 (프록시가 없는 그룹 포함)
+    let items = app.managed_items();
+    let group_count = items.iter().filter(|item| matches!(item, ManagedItem::Group(_))).count();
     frame.render_widget(
-        Paragraph::new(format!("그룹 수: {}개", group_count.len()))
+        Paragraph::new(format!("그룹 수: {}개", group_count))
             .block(Block::default().borders(Borders::ALL).title("그룹"))
             .style(Style::default().fg(Color::Green)),
         stats_chunks[1],
     );
 
-    // 프록시 목록 테이블
-    let proxy_table = if app.proxies.is_empty() {
+    // 마지막 저장 결과
+    let (save_text, save_color) = match (&app.proxy_management.last_error, &app.proxy_management.last_saved) {
+        (Some(error), _) => (format!("✗ {}", error), Color::Red),
+        (None, Some(saved)) => (saved.clone(), Color::Green),
+        (None, None) => ("A: 프록시 추가, G: 그룹 추가, E: 편집".to_string(), Color::Gray),
+    };
+    frame.render_widget(
+        Paragraph::new(save_text)
+            .block(Block::default().borders(Borders::ALL).title("저장"))
+            .style(Style::default().fg(save_color)),
+        stats_chunks[2],
+    );
+
+    // 프록시 목록 테이블 (그룹 헤더 행도 선택해 편집/삭제할 수 있음)
+    let proxy_table = if items.is_empty() {
         Table::new(
-            vec![Row::new(vec![Cell::from("프록시가 설정되지 않았습니다. config/proxies.json을 확인하세요.")])],
+            vec![Row::new(vec![Cell::from("프록시가 설정되지 않았습니다. A 키로 추가하거나 config/proxies.json을 확인하세요.")])],
             [Constraint::Percentage(100)],
         )
         .block(Block::default().borders(Borders::ALL).title("프록시 목록"))
     } else {
-        // 그룹별로 프록시 그룹화 및 정렬
-        use std::collections::HashMap;
-        let mut groups: HashMap<String, Vec<&crate::app::Proxy>> = HashMap::new();
-        for proxy in &app.proxies {
-            groups.entry(proxy.group.clone()).or_insert_with(Vec::new).push(proxy);
-        }
+        let mut rows = Vec::new();
+        for item in &items {
+            match item {
+                ManagedItem::Group(group) => {
+                    let member_count = app.proxies.iter().filter(|p| p.group == *group).count();
+                    // 그룹 공통 점프 호스트가 있으면 표시
+                    let jump_host = app.groups.get(group)
+                        .and_then(|g| g.jump_host.as_ref())
+                        .map(|j| format!("점프: {}@{}:{}", j.username, j.host, j.port))
+                        .unwrap_or_default();
+                    let header_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
 
-        // 그룹명으로 정렬
-        let mut sorted_groups: Vec<_> = groups.iter().collect();
-        sorted_groups.sort_by_key(|(group, _)| *group);
+                    // 그룹 헤더 행
+                    rows.push(Row::new(vec![
+                        Cell::from(format!("📁 {}", group)).style(header_style),
+                        Cell::from("").style(header_style),
+                        Cell::from(jump_host).style(header_style),
+                        Cell::from("").style(header_style),
+                        Cell::from("").style(header_style),
+                        Cell::from("").style(header_style),
+                        Cell::from(format!("({}개)", member_count)).style(header_style),
+                    ]));
+                }
+                ManagedItem::Proxy(id) => {
+                    let Some(proxy) = app.proxies.iter().find(|p| p.id == *id) else {
+                        continue;
+                    };
+                    let alias_display = proxy.alias.as_deref()
+                        .unwrap_or("-");
 
-        let mut rows = Vec::new();
-        for (group, proxies) in sorted_groups {
-            // 그룹별로 프록시 ID로 정렬
-            let mut sorted_proxies = proxies.clone();
-            sorted_proxies.sort_by_key(|p| p.id);
-
-            // 그룹 헤더 행
-            rows.push(Row::new(vec![
-                Cell::from(format!("📁 {}", group))
-                    .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-                Cell::from("")
-                    .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-                Cell::from("")
-                    .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-                Cell::from("")
-                    .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-                Cell::from(format!("({}개)", sorted_proxies.len()))
-                    .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            ]));
-
-            // 그룹 내 프록시들
-            for proxy in sorted_proxies {
-                let alias_display = proxy.alias.as_ref()
-                    .map(|a| a.as_str())
-                    .unwrap_or("-");
-                
-                let host_port = format!("{}:{}", proxy.host, proxy.port);
-                
-                let log_path_display = proxy.traffic_log_path.as_ref()
-                    .map(|p| {
-                        // 경로가 너무 길면 마지막 부분만 표시
-                        if p.len() > 30 {
-                            format!("...{}", &p[p.len().saturating_sub(27)..])
-                        } else {
-                            p.clone()
+                    let host_port = format!("{}:{}", proxy.host, proxy.port);
+
+                    let log_path_display = proxy.traffic_log_path.as_ref()
+                        .map(|p| {
+                            // 경로가 너무 길면 마지막 부분만 표시
+                            if p.len() > 30 {
+                                format!("...{}", &p[p.len().saturating_sub(27)..])
+                            } else {
+                                p.clone()
+                            }
+                        })
+                        .unwrap_or_else(|| "-".to_string());
+
+                    // env:/cmd:/vault: 참조는 마지막 접속에서 실패했으면 이유를 표시
+                    let credential_cell = match proxy.credential_error() {
+                        Some(error) => Cell::from(format!("✗ {}", error)).style(Style::default().fg(Color::Red)),
+                        None => {
+                            let references = proxy.credential_references();
+                            if references.is_empty() {
+                                Cell::from("-").style(Style::default().fg(Color::Gray))
+                            } else {
+                                Cell::from(references.join(", ")).style(Style::default().fg(Color::Green))
+                            }
                         }
-                    })
-                    .unwrap_or_else(|| "-".to_string());
-
-                rows.push(Row::new(vec![
-                    Cell::from(format!("  ├─ ID: {}", proxy.id))
-                        .style(Style::default().fg(Color::Gray)),
-                    Cell::from(alias_display)
-                        .style(Style::default().fg(Color::White)),
-                    Cell::from(host_port)
-                        .style(Style::default().fg(Color::Cyan)),
-                    Cell::from(proxy.username.clone())
-                        .style(Style::default().fg(Color::White)),
-                    Cell::from(log_path_display)
-                        .style(Style::default().fg(Color::Gray)),
-                ]));
+                    };
+
+                    // 비활성/점검 중이면 수집하지 않음
+                    let state = proxy.state();
+                    let state_color = match state {
+                        ProxyState::Active => Color::Green,
+                        ProxyState::Disabled => Color::DarkGray,
+                        ProxyState::Maintenance { .. } => Color::Yellow,
+                    };
+
+                    rows.push(Row::new(vec![
+                        Cell::from(format!("  ├─ ID: {}", proxy.id))
+                            .style(Style::default().fg(Color::Gray)),
+                        Cell::from(alias_display)
+                            .style(Style::default().fg(Color::White)),
+                        Cell::from(host_port)
+                            .style(Style::default().fg(Color::Cyan)),
+                        Cell::from(proxy.username.clone())
+                            .style(Style::default().fg(Color::White)),
+                        Cell::from(state.label())
+                            .style(Style::default().fg(state_color)),
+                        credential_cell,
+                        Cell::from(log_path_display)
+                            .style(Style::default().fg(Color::Gray)),
+                    ]));
+                }
             }
         }
 
@@ -125,6 +158,8 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             Constraint::Length(20),  // 별칭
             Constraint::Length(22),  // 호스트:포트
             Constraint::Length(15), // 사용자
+            Constraint::Length(24),  // 상태 (활성/비활성/점검 중)
+            Constraint::Length(30),  // 자격 증명 참조
             Constraint::Min(0),      // 로그 경로 (나머지 공간)
         ])
         .header(Row::new(vec![
@@ -132,18 +167,121 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             Cell::from("별칭").style(Style::default().add_modifier(Modifier::BOLD)),
             Cell::from("호스트:포트").style(Style::default().add_modifier(Modifier::BOLD)),
             Cell::from("사용자").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("상태").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("자격 증명").style(Style::default().add_modifier(Modifier::BOLD)),
             Cell::from("로그 경로").style(Style::default().add_modifier(Modifier::BOLD)),
         ]))
         .block(Block::default().borders(Borders::ALL).title(format!("프록시 목록 (총 {}개)", app.proxies.len())))
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
     };
-    frame.render_widget(proxy_table, chunks[1]);
+    frame.render_stateful_widget(proxy_table, chunks[1], &mut app.proxy_management.table_state);
 
     // 키보드 단축키 도움말
-    let help_text = "Tab: 탭전환 | 1~4: 탭선택";
+    let help_text = "↑↓: 선택 | A: 프록시 추가 | G: 그룹 추가 | E/Enter: 편집 | C: 복제 | D/Del: 삭제 | Tab: 탭전환 | 1~5: 탭선택";
     frame.render_widget(
         Paragraph::new(help_text)
             .block(Block::default().borders(Borders::ALL).title("단축키"))
             .style(Style::default().fg(Color::Gray)),
         chunks[2],
     );
+
+    // 추가/편집 창과 삭제 확인 창 (목록 위에 표시)
+    if let Some(form) = &app.proxy_management.form {
+        draw_form(frame, form, area);
+    } else if let Some(item) = &app.proxy_management.confirm_delete {
+        draw_confirm_delete(frame, item, area);
+    }
+}
+
+/// 프록시/그룹 입력 창
+fn draw_form(frame: &mut Frame, form: &ProxyForm, area: Rect) {
+    // 칸마다 한 줄 + 빈 줄 + 오류 + 도움말 + 테두리
+    let popup_area = centered_rect(70, form.fields.len() as u16 + 6, area);
+
+    let mut lines: Vec<Line> = form
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let focused = i == form.focused;
+            let value = match field.kind {
+                FieldKind::Choice(options) => options
+                    .iter()
+                    .map(|option| if *option == field.value { format!("[{}]", option) } else { option.to_string() })
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ if focused => format!("{}▏", field.display_value()),
+                _ => field.display_value(),
+            };
+            let label_style = if focused {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            Line::from(vec![
+                Span::styled(format!("{} {:<16}", if focused { "▶" } else { " " }, field.label), label_style),
+                Span::styled(value, Style::default().fg(if focused { Color::White } else { Color::Cyan })),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(match (&form.error, form.plaintext_warning()) {
+        (Some(error), _) => Line::from(Span::styled(format!("✗ {}", error), Style::default().fg(Color::Red))),
+        (None, Some(warning)) => Line::from(Span::styled(format!("⚠ {}", warning), Style::default().fg(Color::Yellow))),
+        (None, None) => Line::from(Span::styled(
+            "비밀번호 칸에는 env:, cmd:, vault: 참조를 쓸 수 있습니다. 권한 상승/SNMP/점검 시간 설정은 그대로 유지됩니다.",
+            Style::default().fg(Color::Gray),
+        )),
+    });
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(format!("{} [Tab/↑↓: 칸 이동 | Space/←→: 선택 | Enter: 저장 | Esc: 취소]", form.title())),
+        ),
+        popup_area,
+    );
+}
+
+/// 삭제 확인 창
+fn draw_confirm_delete(frame: &mut Frame, item: &ManagedItem, area: Rect) {
+    let popup_area = centered_rect(50, 5, area);
+    let target = match item {
+        ManagedItem::Group(name) => format!("그룹 '{}'", name),
+        ManagedItem::Proxy(id) => format!("프록시 ID {}", id),
+    };
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(format!("{}을(를) 삭제하고 proxies.json에 저장합니다.", target)),
+            Line::from(Span::styled("원본은 proxies.json.bak으로 백업됩니다.", Style::default().fg(Color::Gray))),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title("삭제 확인 [y: 삭제 / n, Esc: 취소]"),
+        ),
+        popup_area,
+    );
+}
+
+/// 가로는 비율, 세로는 줄 수로 중앙에 위치한 사각형 계산
+fn centered_rect(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(height), Constraint::Min(0)])
+        .split(r);
+
+    Layout::default()
+        .direction(ratatui::layout::Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
 }
@@ -1,11 +1,12 @@
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, InterfaceTraffic, LinkStatus, ProxyState, ResourceData};
 use crate::ui::config::{get_interface_names, load_thresholds, ThresholdConfig};
 use std::collections::HashMap;
 
@@ -15,6 +16,8 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         .constraints([
             Constraint::Length(3),  // 컨트롤 영역 (한 줄)
             Constraint::Min(3),     // 데이터 테이블
+            Constraint::Length(3),  // 선택한 프록시의 회선 상세
+            Constraint::Length(3),  // 선택한 프록시의 호스트 지표 (SSH)
             Constraint::Length(4),  // 키보드 단축키 도움말 (컴팩트)
         ])
         .split(area);
@@ -129,10 +132,27 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             .iter()
             .enumerate()
             .map(|(i, data)| {
+                // 비활성이거나 점검 중이라 수집하지 않은 경우 (상태는 아래 상세에 표시)
+                if let Some(state) = &data.skipped {
+                    let color = if matches!(state, ProxyState::Disabled) { Color::DarkGray } else { Color::Yellow };
+                    let style = if app.resource_usage.table_state.selected() == Some(i) {
+                        Style::default().bg(Color::Blue).fg(color)
+                    } else {
+                        Style::default().fg(color)
+                    };
+                    let proxy_display_name = data.proxy_name.as_ref().unwrap_or(&data.host);
+                    let mut cells = vec![Cell::from(proxy_display_name.clone()).style(style)];
+                    // 기본 지표 8개와 회선 컬럼
+                    for _ in 0..8 + interface_names.len() {
+                        cells.push(Cell::from("-").style(style));
+                    }
+                    cells.push(Cell::from(if matches!(state, ProxyState::Disabled) { "○" } else { "⏸" }).style(style));
+                    return Row::new(cells);
+                }
+
                 // 실패한 경우
                 if data.collection_failed {
-                    let _error_msg = data.error_message.as_ref()
-                        .map(|s| s.as_str())
+                    let _error_msg = data.error_message.as_deref()
                         .unwrap_or("실패");
                     
                     let style = if app.resource_usage.table_state.selected() == Some(i) {
@@ -152,6 +172,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                         Cell::from("-").style(style),
                         Cell::from("-").style(style),
                         Cell::from("-").style(style),
+                        Cell::from("-").style(style),
                     ];
                     
                     // 각 회선에 대해 빈 셀 추가
@@ -183,24 +204,11 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                         .unwrap_or_else(|| "-".to_string())
                     };
 
-                    // bps를 컴팩트한 형식으로 표시 (K/M/G 단위)
-                    let format_bps = |v: Option<f64>| -> String {
-                        v.map(|bps| {
-                            if bps >= 1_000_000_000.0 {
-                                format!("{:.1}G", bps / 1_000_000_000.0)
-                            } else if bps >= 1_000_000.0 {
-                                format!("{:.1}M", bps / 1_000_000.0)
-                            } else if bps >= 1_000.0 {
-                                format!("{:.1}K", bps / 1_000.0)
-                            } else {
-                                format!("{:.0}", bps)
-                            }
-                        })
-                        .unwrap_or_else(|| "-".to_string())
-                    };
-
                     let cpu_str = format_percent(data.cpu);
                     let mem_str = format_percent(data.mem);
+                    // 사용률이 가장 높은 파일시스템 (마운트별 값은 호스트 상세에 표시)
+                    let disk = data.fullest_disk().map(|d| d.used_percent);
+                    let disk_str = format_percent(disk);
                     let cc_str = format_count(data.cc);
                     let cs_str = format_count(data.cs);
                     let http_str = format_bps(data.http);
@@ -217,9 +225,9 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                     }
                     
                     // 회선 정보를 HashMap으로 변환 (빠른 조회를 위해)
-                    let interface_map: HashMap<String, (f64, f64)> = data.interfaces
+                    let interface_map: HashMap<&str, &InterfaceTraffic> = data.interfaces
                         .iter()
-                        .map(|iface| (iface.name.clone(), (iface.in_mbps, iface.out_mbps)))
+                        .map(|iface| (iface.name.as_str(), iface))
                         .collect();
 
                     let base_style = if app.resource_usage.table_state.selected() == Some(i) {
@@ -232,6 +240,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                     let proxy_display_name = data.proxy_name.as_ref().unwrap_or(&data.host);
                     let cpu_color = thresholds.get("cpu").map(|t| get_threshold_color(data.cpu, t)).unwrap_or(Color::White);
                     let mem_color = thresholds.get("mem").map(|t| get_threshold_color(data.mem, t)).unwrap_or(Color::White);
+                    let disk_color = thresholds.get("disk").map(|t| get_threshold_color(disk, t)).unwrap_or(Color::White);
                     let cc_color = thresholds.get("cc").map(|t| get_threshold_color(data.cc, t)).unwrap_or(Color::White);
                     let cs_color = thresholds.get("cs").map(|t| get_threshold_color(data.cs, t)).unwrap_or(Color::White);
                     let http_color = thresholds.get("http").map(|t| get_threshold_color(data.http, t)).unwrap_or(Color::White);
@@ -242,6 +251,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                         Cell::from(proxy_display_name.clone()).style(base_style),
                         Cell::from(cpu_str).style(base_style.fg(cpu_color)),
                         Cell::from(mem_str).style(base_style.fg(mem_color)),
+                        Cell::from(disk_str).style(base_style.fg(disk_color)),
                         Cell::from(cc_str).style(base_style.fg(cc_color)),
                         Cell::from(cs_str).style(base_style.fg(cs_color)),
                         Cell::from(http_str).style(base_style.fg(http_color)),
@@ -249,43 +259,29 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                         Cell::from(ftp_str).style(base_style.fg(ftp_color)),
                     ];
                     
-                    // 각 회선에 대해 별도 컬럼 추가 (bps를 컴팩트하게 표시)
-                    let interface_threshold = thresholds.get("interface_traffic");
+                    // 각 회선에 대해 별도 컬럼 추가 (bps를 컴팩트하게 표시, 속도를 알면 사용률도 표시)
                     for if_name in &interface_names {
-                        if let Some((in_bps, out_bps)) = interface_map.get(if_name) {
-                            let in_str = if *in_bps >= 1_000_000_000.0 {
-                                format!("{:.1}G", in_bps / 1_000_000_000.0)
-                            } else if *in_bps >= 1_000_000.0 {
-                                format!("{:.1}M", in_bps / 1_000_000.0)
-                            } else if *in_bps >= 1_000.0 {
-                                format!("{:.1}K", in_bps / 1_000.0)
+                        if let Some(iface) = interface_map.get(if_name.as_str()) {
+                            let (text, color) = interface_cell(iface, &thresholds);
+                            let style = if iface.is_down() {
+                                base_style.fg(color).add_modifier(Modifier::BOLD)
                             } else {
-                                format!("{:.0}", in_bps)
+                                base_style.fg(color)
                             };
-                            let out_str = if *out_bps >= 1_000_000_000.0 {
-                                format!("{:.1}G", out_bps / 1_000_000_000.0)
-                            } else if *out_bps >= 1_000_000.0 {
-                                format!("{:.1}M", out_bps / 1_000_000.0)
-                            } else if *out_bps >= 1_000.0 {
-                                format!("{:.1}K", out_bps / 1_000.0)
-                            } else {
-                                format!("{:.0}", out_bps)
-                            };
-                            
-                            // 인터페이스 트래픽 색상 결정 (in/out 중 더 높은 값 기준)
-                            let max_traffic = in_bps.max(*out_bps);
-                            let traffic_color = interface_threshold
-                                .map(|t| get_threshold_color(Some(max_traffic), t))
-                                .unwrap_or(Color::White);
-                            
-                            cells.push(Cell::from(format!("{}/{}", in_str, out_str)).style(base_style.fg(traffic_color)));
+                            cells.push(Cell::from(text).style(style));
                         } else {
                             cells.push(Cell::from("-").style(base_style));
                         }
                     }
                     
-                    // 상태 컬럼
-                    cells.push(Cell::from("✓").style(base_style));
+                    // 상태 컬럼 (DOWN 회선이 있으면 ▼, 멈춘 프로세스가 있으면 !)
+                    if data.has_down_interface() {
+                        cells.push(Cell::from("▼").style(base_style.fg(Color::Red).add_modifier(Modifier::BOLD)));
+                    } else if data.has_missing_process() {
+                        cells.push(Cell::from("!").style(base_style.fg(Color::Red).add_modifier(Modifier::BOLD)));
+                    } else {
+                        cells.push(Cell::from("✓").style(base_style));
+                    }
                     
                     Row::new(cells)
                 }
@@ -297,6 +293,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             Constraint::Length(12),  // 프록시
             Constraint::Length(5),   // CPU
             Constraint::Length(5),   // MEM
+            Constraint::Length(5),   // DISK (가장 높은 파일시스템)
             Constraint::Length(5),   // CC
             Constraint::Length(5),   // CS
             Constraint::Length(6),   // HTTP (bps)
@@ -306,7 +303,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         
         // 각 회선에 대해 컬럼 추가 (너비 증가로 잘림 방지)
         for _ in &interface_names {
-            constraints.push(Constraint::Length(12)); // 각 회선 컬럼 (in/out bps)
+            constraints.push(Constraint::Length(16)); // 각 회선 컬럼 (in/out bps + 사용률)
         }
         
         constraints.push(Constraint::Length(3)); // 상태 컬럼
@@ -316,6 +313,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             Cell::from("프록시").style(Style::default().add_modifier(Modifier::BOLD)),
             Cell::from("CPU%").style(Style::default().add_modifier(Modifier::BOLD)),
             Cell::from("MEM%").style(Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("DISK%").style(Style::default().add_modifier(Modifier::BOLD)),
             Cell::from("CC").style(Style::default().add_modifier(Modifier::BOLD)),
             Cell::from("CS").style(Style::default().add_modifier(Modifier::BOLD)),
             Cell::from("HTTP").style(Style::default().add_modifier(Modifier::BOLD)),
@@ -332,10 +330,10 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                         let prefix_len = (if_name.len() - 1).min(4);
                         format!("{}{}", &if_name[..prefix_len], last_char)
                     } else {
-                        format!("{}", &if_name[..5])
+                        if_name[..5].to_string()
                     }
                 } else {
-                    format!("{}", &if_name[..5])
+                    if_name[..5].to_string()
                 }
             } else {
                 if_name.clone()
@@ -354,14 +352,183 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
 
     frame.render_stateful_widget(table, chunks[1], &mut app.resource_usage.table_state);
 
+    // 선택한 프록시의 회선 상태/에러/폐기 (수집하지 않은 프록시는 그 상태)
+    let selected_data = app.resource_usage.table_state.selected().and_then(|i| app.resource_usage.data.get(i));
+    let detail = selected_data
+        .and_then(|data| data.skipped.as_ref())
+        .map(|state| Line::from(Span::styled(format!("수집 안 함: {}", state.label()), Style::default().fg(Color::Yellow))))
+        .or_else(|| {
+            selected_data
+                .filter(|data| !data.interfaces.is_empty())
+                .map(|data| Line::from(interface_details(&data.interfaces, &thresholds)))
+        })
+        .unwrap_or_else(|| Line::from(Span::styled("회선 정보 없음", Style::default().fg(Color::Gray))));
+    frame.render_widget(
+        Paragraph::new(detail).block(Block::default().borders(Borders::ALL).title("회선 상세 (상태 / 초당 에러 in/out / 초당 폐기 in/out)")),
+        chunks[2],
+    );
+
+    // 선택한 프록시의 부하/스왑/파일 디스크립터/디스크/프로세스
+    let host = app
+        .resource_usage
+        .table_state
+        .selected()
+        .and_then(|i| app.resource_usage.data.get(i))
+        .filter(|data| data.load_avg.is_some() || !data.disks.is_empty() || !data.processes.is_empty())
+        .map(|data| Line::from(host_details(data, &thresholds)))
+        .unwrap_or_else(|| Line::from(Span::styled("호스트 지표 없음 (host_metrics 설정 확인)", Style::default().fg(Color::Gray))));
+    frame.render_widget(
+        Paragraph::new(host).block(Block::default().borders(Borders::ALL).title("호스트 (코어당 부하 / 스왑 / FD / 가동 / 디스크 / 프로세스)")),
+        chunks[3],
+    );
+
     // 키보드 단축키 도움말 (컴팩트)
-    let help_text = vec![
-        "Tab: 탭전환 | ↑↓: 테이블이동 | Space: 자동수집토글 | +/-: 주기 | Shift+←→: 그룹",
-    ];
+    let help_text = ["Tab: 탭전환 | ↑↓: 테이블이동 | Space: 자동수집토글 | +/-: 주기 | Shift+←→: 그룹"];
     frame.render_widget(
         Paragraph::new(help_text.join("\n"))
             .block(Block::default().borders(Borders::ALL).title("단축키"))
             .style(Style::default().fg(Color::Gray)),
-        chunks[2],
+        chunks[4],
     );
 }
+
+/// bps를 컴팩트한 형식으로 표시 (K/M/G 단위)
+fn format_bps(v: Option<f64>) -> String {
+    v.map(|bps| {
+        if bps >= 1_000_000_000.0 {
+            format!("{:.1}G", bps / 1_000_000_000.0)
+        } else if bps >= 1_000_000.0 {
+            format!("{:.1}M", bps / 1_000_000.0)
+        } else if bps >= 1_000.0 {
+            format!("{:.1}K", bps / 1_000.0)
+        } else {
+            format!("{:.0}", bps)
+        }
+    })
+    .unwrap_or_else(|| "-".to_string())
+}
+
+/// 임계치 단계 (0: 정상, 1: 경고, 2: 위험)
+fn threshold_level(value: Option<f64>, threshold: Option<&ThresholdConfig>) -> u8 {
+    match (value, threshold) {
+        (Some(v), Some(t)) if v >= t.critical => 2,
+        (Some(v), Some(t)) if v >= t.warning => 1,
+        _ => 0,
+    }
+}
+
+fn level_color(level: u8) -> Color {
+    match level {
+        2 => Color::Red,
+        1 => Color::Yellow,
+        _ => Color::White,
+    }
+}
+
+/// 회선 셀 내용과 색상
+/// 트래픽은 속도를 알면 사용률(%), 모르면 bps 임계치로 판단하고, 에러/폐기 임계치를 넘으면 `E`를 붙입니다.
+fn interface_cell(iface: &InterfaceTraffic, thresholds: &HashMap<String, ThresholdConfig>) -> (String, Color) {
+    if iface.is_down() {
+        return ("▼DOWN".to_string(), Color::Red);
+    }
+
+    let utilization = iface.max_utilization();
+    let traffic_level = match utilization {
+        Some(u) => threshold_level(Some(u), thresholds.get("interface_utilization")),
+        None => threshold_level(iface.max_bps(), thresholds.get("interface_traffic")),
+    };
+    let error_level = threshold_level(iface.max_errors(), thresholds.get("interface_errors"))
+        .max(threshold_level(iface.max_discards(), thresholds.get("interface_discards")));
+
+    let mut text = format!("{}/{}", format_bps(iface.in_bps), format_bps(iface.out_bps));
+    if let Some(u) = utilization {
+        text.push_str(&format!(" {:.0}%", u));
+    }
+    if error_level > 0 {
+        text.push_str(" E");
+    }
+    (text, level_color(traffic_level.max(error_level)))
+}
+
+/// 선택한 프록시의 회선 상태/에러/폐기 요약
+fn interface_details<'a>(
+    interfaces: &[InterfaceTraffic],
+    thresholds: &HashMap<String, ThresholdConfig>,
+) -> Vec<Span<'a>> {
+    let format_rate = |v: Option<f64>| v.map(|v| format!("{:.1}", v)).unwrap_or_else(|| "-".to_string());
+    let mut spans = Vec::new();
+    for (i, iface) in interfaces.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" | "));
+        }
+        let status = iface.status.map(|s| s.label()).unwrap_or("-");
+        let status_color = match iface.status {
+            Some(LinkStatus::Up) => Color::Green,
+            Some(LinkStatus::Down) => Color::Red,
+            Some(_) => Color::Yellow,
+            None => Color::Gray,
+        };
+        spans.push(Span::styled(format!("{} {}", iface.name, status), Style::default().fg(status_color)));
+        let level = threshold_level(iface.max_errors(), thresholds.get("interface_errors"))
+            .max(threshold_level(iface.max_discards(), thresholds.get("interface_discards")));
+        spans.push(Span::styled(
+            format!(
+                " err {}/{} drop {}/{}",
+                format_rate(iface.in_errors),
+                format_rate(iface.out_errors),
+                format_rate(iface.in_discards),
+                format_rate(iface.out_discards)
+            ),
+            Style::default().fg(if level == 0 { Color::Gray } else { level_color(level) }),
+        ));
+    }
+    spans
+}
+
+/// 가동 시간을 "3일 4시간" / "5시간 12분" 형식으로 표시
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    if days > 0 {
+        format!("{}일 {}시간", days, hours)
+    } else {
+        format!("{}시간 {}분", hours, minutes)
+    }
+}
+
+/// 선택한 프록시의 호스트 지표 요약
+fn host_details<'a>(data: &ResourceData, thresholds: &HashMap<String, ThresholdConfig>) -> Vec<Span<'a>> {
+    let format_percent = |v: Option<f64>| v.map(|v| format!("{:.0}%", v)).unwrap_or_else(|| "-".to_string());
+    let styled = |text: String, level: u8| Span::styled(text, Style::default().fg(level_color(level)));
+
+    let load = data.load_per_core();
+    let mut spans = vec![
+        styled(
+            format!("부하 {}", load.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string())),
+            threshold_level(load, thresholds.get("load")),
+        ),
+        Span::raw(" | "),
+        styled(format!("스왑 {}", format_percent(data.swap)), threshold_level(data.swap, thresholds.get("swap"))),
+        Span::raw(" | "),
+        styled(
+            format!("FD {}", format_percent(data.open_files)),
+            threshold_level(data.open_files, thresholds.get("open_files")),
+        ),
+        Span::raw(" | "),
+        Span::raw(format!("가동 {}", data.uptime_secs.map(format_uptime).unwrap_or_else(|| "-".to_string()))),
+    ];
+    for disk in &data.disks {
+        spans.push(Span::raw(" | "));
+        let level = threshold_level(Some(disk.used_percent), thresholds.get("disk"));
+        spans.push(styled(format!("{} {:.0}%", disk.mount, disk.used_percent), level));
+    }
+    for process in &data.processes {
+        spans.push(Span::raw(" | "));
+        if process.count > 0 {
+            spans.push(Span::styled(format!("{} ✓", process.name), Style::default().fg(Color::Green)));
+        } else {
+            let style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+            spans.push(Span::styled(format!("{} ✗", process.name), style));
+        }
+    }
+    spans
+}
@@ -34,7 +34,7 @@ fn calculate_column_widths(
         "상태", "InUse", "URL",
     ];
 
-    let mut max_widths = vec![0; 19];
+    let mut max_widths = [0; 19];
 
     // 헤더 길이 고려
     for (i, header) in headers.iter().enumerate() {
@@ -138,13 +138,28 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         crate::app::CollectionStatus::Collecting => {
             let elapsed = app.session_browser.query_start_time
                 .map(|start| (chrono::Local::now() - start).num_seconds());
-            let progress_text = if let Some((completed, total)) = app.session_browser.query_progress {
+            // 세션을 받기 시작하면 (SSH 출력을 읽는 동안) 지금까지 받은 세션 수를 표시
+            let rows = app.session_browser.rows_received.load(std::sync::atomic::Ordering::Relaxed);
+            let progress_text = if rows > 0 {
+                format!("{} 조회중 {}건", spinner_char, rows)
+            } else if let Some((completed, total)) = app.session_browser.query_progress {
                 format!("{} 조회중 ({}/{})", spinner_char, completed, total)
             } else {
                 format!("{} 조회중", spinner_char)
             };
             (progress_text, Color::Yellow, elapsed)
         }
+        crate::app::CollectionStatus::Success
+            if matches!(app.session_browser.query_progress, Some((completed, total)) if completed < total) =>
+        {
+            // 일부 프록시 조회 실패 (오류 내용은 세션 목록 제목에 표시)
+            let (completed, total) = app.session_browser.query_progress.unwrap_or_default();
+            (format!("⚠ 완료\n실패 {}개", total - completed), Color::Yellow, None)
+        }
+        crate::app::CollectionStatus::Success if app.session_browser.skipped_proxies > 0 => {
+            // 비활성/점검 중이라 조회하지 않은 프록시 수
+            (format!("✓ 완료\n제외 {}개", app.session_browser.skipped_proxies), Color::Green, None)
+        }
         crate::app::CollectionStatus::Success => ("✓ 완료".to_string(), Color::Green, None),
         crate::app::CollectionStatus::Failed => ("✗ 실패".to_string(), Color::Red, None),
     };
@@ -287,6 +302,9 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             } else {
                 "조회 실패".to_string()
             }
+        } else if app.session_browser.query_status == crate::app::CollectionStatus::Idle
+            && app.session_browser.skipped_proxies > 0 {
+            format!("조회할 프록시가 없습니다. (비활성/점검 중 {}개)", app.session_browser.skipped_proxies)
         } else {
             "데이터가 없습니다. [R] 키를 눌러 조회하세요.".to_string()
         };
@@ -314,24 +332,24 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                 };
 
                 // 모든 필드 준비
-                let transaction = session.transaction.as_ref().map(|s| s.as_str()).unwrap_or("N/A");
+                let transaction = session.transaction.as_deref().unwrap_or("N/A");
                 let creation_time = session.creation_time
                     .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
                     .unwrap_or_else(|| "N/A".to_string());
-                let protocol = session.protocol.as_ref().map(|s| s.as_str()).unwrap_or("N/A");
-                let cust_id = session.cust_id.as_ref().map(|s| s.as_str()).unwrap_or("N/A");
-                let user_name = session.user_name.as_ref().map(|s| s.as_str()).unwrap_or("N/A");
+                let protocol = session.protocol.as_deref().unwrap_or("N/A");
+                let cust_id = session.cust_id.as_deref().unwrap_or("N/A");
+                let user_name = session.user_name.as_deref().unwrap_or("N/A");
                 let client_ip = session.client_ip.clone();
-                let client_side_mwg_ip = session.client_side_mwg_ip.as_ref().map(|s| s.as_str()).unwrap_or("N/A");
-                let server_side_mwg_ip = session.server_side_mwg_ip.as_ref().map(|s| s.as_str()).unwrap_or("N/A");
-                let server_ip = session.server_ip.as_ref().map(|s| s.as_str()).unwrap_or("N/A");
+                let client_side_mwg_ip = session.client_side_mwg_ip.as_deref().unwrap_or("N/A");
+                let server_side_mwg_ip = session.server_side_mwg_ip.as_deref().unwrap_or("N/A");
+                let server_ip = session.server_ip.as_deref().unwrap_or("N/A");
                 let cl_bytes_received = session.cl_bytes_received.map(|v| format!("{}", v)).unwrap_or_else(|| "N/A".to_string());
                 let cl_bytes_sent = session.cl_bytes_sent.map(|v| format!("{}", v)).unwrap_or_else(|| "N/A".to_string());
                 let srv_bytes_received = session.srv_bytes_received.map(|v| format!("{}", v)).unwrap_or_else(|| "N/A".to_string());
                 let srv_bytes_sent = session.srv_bytes_sent.map(|v| format!("{}", v)).unwrap_or_else(|| "N/A".to_string());
                 let trxn_index = session.trxn_index.map(|v| format!("{}", v)).unwrap_or_else(|| "N/A".to_string());
                 let age_seconds = session.age_seconds.map(|v| format!("{}", v)).unwrap_or_else(|| "N/A".to_string());
-                let status = session.status.as_ref().map(|s| s.as_str()).unwrap_or("N/A");
+                let status = session.status.as_deref().unwrap_or("N/A");
                 let in_use = session.in_use.map(|v| format!("{}", v)).unwrap_or_else(|| "N/A".to_string());
                 let url_display = session.url.as_ref().map(|s| {
                     if s.len() > 50 {
@@ -450,7 +468,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             Cell::from(header_text).style(style)
         }).collect();
 
-        let title = if app.session_browser.search_query.is_empty() {
+        let mut title = if app.session_browser.search_query.is_empty() {
             format!(
                 "세션 목록 (총 {}개, 페이지 {}/{})",
                 total_filtered,
@@ -466,6 +484,9 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                 app.session_browser.total_pages.max(1)
             )
         };
+        if let Some(ref error) = app.session_browser.last_error {
+            title.push_str(&format!(" ⚠ {}", error));
+        }
         
         Table::new(rows, visible_constraints)
         .header(Row::new(header_cells))
@@ -479,11 +500,9 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     // 키보드 단축키 도움말
     let total_columns = 19;
     let current_col = app.session_browser.column_offset + 1;
-    let help_text = vec![
-        format!("Tab: 탭전환 | ↑↓: 행이동 | ←→: 컬럼스크롤({}/{}) | Shift+←→: 그룹선택 | R: 세션조회 | Enter: 상세보기", 
+    let help_text = [format!("Tab: 탭전환 | ↑↓: 행이동 | ←→: 컬럼스크롤({}/{}) | Shift+←→: 그룹선택 | R: 세션조회 (Esc: 취소) | Enter: 상세보기", 
             current_col, total_columns),
-        format!("PageDown/Space: 다음페이지 | PageUp/b: 이전페이지 | Home/End: 첫/마지막페이지 | /: 검색 | S: 정렬(컬럼선택시)"),
-    ];
+        "PageDown/Space: 다음페이지 | PageUp/b: 이전페이지 | Home/End: 첫/마지막페이지 | /: 검색 | S: 정렬(컬럼선택시)".to_string()];
     
     if app.session_browser.search_mode {
         // 검색 모드일 때 검색 UI 표시
@@ -553,7 +572,7 @@ fn draw_detail_modal(frame: &mut Frame, app: &mut App, area: Rect) {
     let page_start = app.session_browser.current_page * app.session_browser.page_size;
     let page_end = (page_start + app.session_browser.page_size).min(filtered_sessions.len());
     let paginated_sessions: Vec<&crate::app::SessionData> = if page_start < filtered_sessions.len() {
-        filtered_sessions[page_start..page_end].iter().copied().collect()
+        filtered_sessions[page_start..page_end].to_vec()
     } else {
         Vec::new()
     };
@@ -60,11 +60,19 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     let spinner_chars = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
     let spinner_char = spinner_chars[app.traffic_logs.spinner_frame % spinner_chars.len()];
     
-    let (status_text, status_color, elapsed_sec) = if app.traffic_logs.query_status == crate::app::CollectionStatus::Collecting
+    let downloading = app.traffic_logs.download_status == crate::app::CollectionStatus::Collecting
+        || app.traffic_logs.download_status == crate::app::CollectionStatus::Starting;
+    let (status_text, status_color, elapsed_sec) = if downloading {
+        let elapsed = app.traffic_logs.download_start_time
+            .map(|start| (chrono::Local::now() - start).num_seconds());
+        (format!("{} {}", spinner_char, download_progress_text(&app.traffic_logs.download_progress)), Color::Yellow, elapsed)
+    } else if app.traffic_logs.query_status == crate::app::CollectionStatus::Collecting
         || app.traffic_logs.query_status == crate::app::CollectionStatus::Starting {
         let elapsed = app.traffic_logs.query_start_time
             .map(|start| (chrono::Local::now() - start).num_seconds());
-        (format!("{} 조회중", spinner_char), Color::Yellow, elapsed)
+        // 받은 라인 수는 SSH 출력을 읽는 동안 계속 늘어남
+        let lines = app.traffic_logs.lines_received.load(std::sync::atomic::Ordering::Relaxed);
+        (format!("{} 조회중 {}줄", spinner_char, lines), Color::Yellow, elapsed)
     } else {
         match app.traffic_logs.query_status {
             crate::app::CollectionStatus::Idle => ("대기중".to_string(), Color::Gray, None),
@@ -122,7 +130,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     let page_info = if filtered_count > 0 {
         format!("페이지 {}/{} ({}개)", 
             app.traffic_logs.current_page + 1,
-            ((filtered_count + app.traffic_logs.page_size - 1) / app.traffic_logs.page_size).max(1),
+            filtered_count.div_ceil(app.traffic_logs.page_size).max(1),
             filtered_count)
     } else {
         "페이지 0/0 (0개)".to_string()
@@ -163,10 +171,13 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             } else {
                 let spinner_char = spinner_chars[app.traffic_logs.spinner_frame % spinner_chars.len()];
                 
-                let empty_message = if app.traffic_logs.query_status == crate::app::CollectionStatus::Collecting
+                let empty_message = if downloading {
+                    format!("{} 로그 파일 다운로드 중... {}", spinner_char, download_progress_text(&app.traffic_logs.download_progress))
+                } else if app.traffic_logs.query_status == crate::app::CollectionStatus::Collecting
                     || app.traffic_logs.query_status == crate::app::CollectionStatus::Starting {
                     format!("{} 조회 중...", spinner_char)
-                } else if app.traffic_logs.query_status == crate::app::CollectionStatus::Failed {
+                } else if app.traffic_logs.query_status == crate::app::CollectionStatus::Failed
+                    || app.traffic_logs.download_status == crate::app::CollectionStatus::Failed {
                     if let Some(ref error) = app.traffic_logs.last_error {
                         format!("조회 실패: {}", error)
                     } else {
@@ -201,13 +212,13 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     let current_col = app.traffic_logs.column_offset + 1;
     let help_text = if app.traffic_logs.view_mode == crate::app::states::TrafficLogViewMode::LogList {
         vec![
-            format!("Tab: 탭전환 | ↑↓: 행이동 | ←→: 컬럼스크롤({}/{}) | Shift+↑↓: 프록시선택 | R: 조회 | Enter: 상세보기", 
+            format!("Tab: 탭전환 | ↑↓: 행이동 | ←→: 컬럼스크롤({}/{}) | Shift+↑↓: 프록시선택 | R: 조회 (Esc: 취소) | Enter: 상세보기", 
                 current_col, total_columns),
             format!("PageDown/Space: 다음페이지 | PageUp/b: 이전페이지 | /: 검색 | +/-: 조회라인수 ({})", app.traffic_logs.log_limit),
         ]
     } else {
         vec![
-            format!("Tab: 탭전환 | ←→: 뷰모드변경({}) | Shift+↑↓: 프록시선택 | R: 로그조회", view_mode_text),
+            format!("Tab: 탭전환 | ←→: 뷰모드변경({}) | Shift+↑↓: 프록시선택 | R: 로그조회 | D: 파일받아 전체분석 (Esc: 취소)", view_mode_text),
             format!("+/-: 조회라인수 조정 (현재: {})", app.traffic_logs.log_limit),
         ]
     };
@@ -357,10 +368,10 @@ fn draw_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
                     .map(|c| c.to_string())
                     .unwrap_or_else(|| "-".to_string()),
                 record.recv_byte
-                    .map(|b| format_bytes(b))
+                    .map(format_bytes)
                     .unwrap_or_else(|| "-".to_string()),
                 record.sent_byte
-                    .map(|b| format_bytes(b))
+                    .map(format_bytes)
                     .unwrap_or_else(|| "-".to_string()),
                 record.url_protocol.clone().unwrap_or_else(|| "-".to_string()),
                 record.url_categories.as_ref()
@@ -396,7 +407,7 @@ fn draw_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
             "로그 목록 (총 {}개, 페이지 {}/{})",
             total_filtered,
             app.traffic_logs.current_page + 1,
-            ((total_filtered + app.traffic_logs.page_size - 1) / app.traffic_logs.page_size).max(1)
+            total_filtered.div_ceil(app.traffic_logs.page_size).max(1)
         )
     } else {
         format!(
@@ -404,7 +415,7 @@ fn draw_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
             app.traffic_logs.search_query,
             total_filtered,
             app.traffic_logs.current_page + 1,
-            ((total_filtered + app.traffic_logs.page_size - 1) / app.traffic_logs.page_size).max(1)
+            total_filtered.div_ceil(app.traffic_logs.page_size).max(1)
         )
     };
 
@@ -478,8 +489,8 @@ fn draw_detail_modal(frame: &mut Frame, app: &mut App, area: Rect) {
                 ListItem::new(format!("카테고리: {}", record.url_categories.as_ref().unwrap_or(&"N/A".to_string()))),
                 ListItem::new(format!("평판: {}", record.url_reputationstring.as_ref().unwrap_or(&"N/A".to_string()))),
                 ListItem::new(format!("미디어타입: {}", record.mediatype_header.as_ref().unwrap_or(&"N/A".to_string()))),
-                ListItem::new(format!("수신바이트: {}", record.recv_byte.map(|b| format_bytes(b)).unwrap_or_else(|| "N/A".to_string()))),
-                ListItem::new(format!("송신바이트: {}", record.sent_byte.map(|b| format_bytes(b)).unwrap_or_else(|| "N/A".to_string()))),
+                ListItem::new(format!("수신바이트: {}", record.recv_byte.map(format_bytes).unwrap_or_else(|| "N/A".to_string()))),
+                ListItem::new(format!("송신바이트: {}", record.sent_byte.map(format_bytes).unwrap_or_else(|| "N/A".to_string()))),
                 ListItem::new(format!("UserAgent: {}", record.user_agent.as_ref().unwrap_or(&"N/A".to_string()))),
                 ListItem::new(format!("Referer: {}", record.referer.as_ref().unwrap_or(&"N/A".to_string()))),
                 ListItem::new(format!("지역: {}", record.url_geolocation.as_ref().unwrap_or(&"N/A".to_string()))),
@@ -522,8 +533,8 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-fn draw_summary_table(_frame: &mut Frame, _area: Rect, analysis: &crate::traffic_log_parser::TopNAnalysis, _app: &App) -> Table<'static> {
-    let rows = vec![
+fn draw_summary_table(_frame: &mut Frame, _area: Rect, analysis: &crate::traffic_log_parser::TopNAnalysis, app: &App) -> Table<'static> {
+    let mut rows = vec![
         Row::new(vec![
             Cell::from("총 레코드 수"),
             Cell::from(analysis.total_records.to_string()),
@@ -558,6 +569,21 @@ fn draw_summary_table(_frame: &mut Frame, _area: Rect, analysis: &crate::traffic
         ]),
     ];
 
+    // 다운로드한 로그 파일 (D 키로 분석한 경우)
+    for log in &app.traffic_logs.cached_logs {
+        let state = if log.downloaded == 0 {
+            "캐시".to_string()
+        } else if log.resumed {
+            format!("이어받음 {}", format_bytes(log.downloaded as i64))
+        } else {
+            format!("받음 {}", format_bytes(log.downloaded as i64))
+        };
+        rows.push(Row::new(vec![
+            Cell::from(format!("분석 파일 {}", log.name)),
+            Cell::from(format!("{} ({})", format_bytes(log.size as i64), state)),
+        ]));
+    }
+
     Table::new(rows, [Constraint::Min(20), Constraint::Min(15)])
         .header(Row::new(vec![
             Cell::from("항목").style(Style::default().add_modifier(Modifier::BOLD)),
@@ -649,6 +675,20 @@ fn draw_top_urls_table(_frame: &mut Frame, _area: Rect, analysis: &crate::traffi
     .block(Block::default().borders(Borders::ALL).title("TOP URL"))
 }
 
+/// 다운로드 진행률 ("45% 2/5")
+fn download_progress_text(progress: &crate::log_cache::DownloadProgress) -> String {
+    use std::sync::atomic::Ordering;
+    let total = progress.total.load(Ordering::Relaxed);
+    let downloaded = progress.downloaded.load(Ordering::Relaxed);
+    let percent = (downloaded.min(total) * 100).checked_div(total).unwrap_or(0);
+    format!(
+        "{}% {}/{}",
+        percent,
+        progress.files_done.load(Ordering::Relaxed),
+        progress.files_total.load(Ordering::Relaxed)
+    )
+}
+
 fn format_bytes(bytes: i64) -> String {
     if bytes < 1024 {
         format!("{} B", bytes)
@@ -0,0 +1,527 @@
+//! 암호화된 자격 증명 볼트 (config/vault.json)
+//!
+//! proxies.json의 비밀번호 칸에 평문 대신 `vault:<항목 이름>`을 쓰면 시작할 때 마스터 암호로
+//! 볼트를 열어 두고, 접속할 때 실제 값으로 바꿉니다. (`crate::credential`)
+//! 볼트 내용은 PBKDF2-SHA256으로 만든 키로 AES-256-GCM 암호화합니다.
+//! resource_config.json의 SNMP 커뮤니티와 v3 비밀번호도 같은 볼트를 참조할 수 있습니다.
+//! `rust-mmt vault migrate`는 proxies.json(또는 지정한 설정 파일)의 평문 비밀번호를 볼트로 옮기고 참조로 바꿉니다.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::app::config::write_atomic;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// 볼트 항목을 가리키는 값의 접두사
+pub const REF_PREFIX: &str = "vault:";
+
+/// 설정하면 마스터 암호를 묻지 않고 이 환경 변수 값을 사용
+pub const PASSPHRASE_ENV: &str = "MMT_VAULT_PASSPHRASE";
+
+const VERSION: u32 = 1;
+const KDF: &str = "pbkdf2-sha256";
+const DEFAULT_ITERATIONS: u32 = 600_000;
+
+/// 프록시 설정에서 비밀 값이 들어가는 칸 (프록시 기준 경로)
+const PROXY_SECRETS: [&[&str]; 8] = [
+    &["password"],
+    &["ssh_key_passphrase"],
+    &["escalation", "password"],
+    &["jump_host", "password"],
+    &["jump_host", "ssh_key_passphrase"],
+    &["snmp_community"],
+    &["snmp_v3", "auth_password"],
+    &["snmp_v3", "priv_password"],
+];
+
+/// 그룹 설정에서 비밀 값이 들어가는 칸 (그룹 기준 경로)
+const GROUP_SECRETS: [&[&str]; 2] = [&["jump_host", "password"], &["jump_host", "ssh_key_passphrase"]];
+
+/// resource_config.json의 전역 SNMP 설정에서 비밀 값이 들어가는 칸
+const SNMP_SECRETS: [&[&str]; 3] = [&["community"], &["v3", "auth_password"], &["v3", "priv_password"]];
+
+/// vault.json 파일 형식
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String, // 항목 이름 -> 비밀 값 JSON을 암호화한 값
+}
+
+/// 복호화한 볼트
+#[derive(Debug)]
+pub struct Vault {
+    entries: BTreeMap<String, String>,
+    iterations: u32,
+    storage: Option<Storage>, // 파일에서 열었으면 다시 저장할 위치와 마스터 암호
+}
+
+/// 연 볼트 파일과 마스터 암호 (입력 창에서 새 비밀 값을 저장할 때 사용)
+struct Storage {
+    path: PathBuf,
+    passphrase: String,
+}
+
+impl std::fmt::Debug for Storage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Storage").field("path", &self.path).finish_non_exhaustive()
+    }
+}
+
+impl Vault {
+    pub fn new() -> Self {
+        Self { entries: BTreeMap::new(), iterations: DEFAULT_ITERATIONS, storage: None }
+    }
+
+    /// 볼트 파일을 마스터 암호로 엽니다.
+    pub fn open(path: &Path, passphrase: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("볼트 파일을 읽을 수 없습니다: {}", path.display()))?;
+        let file: VaultFile = serde_json::from_str(&content)
+            .with_context(|| format!("볼트 파일 형식이 올바르지 않습니다: {}", path.display()))?;
+        if file.version != VERSION || file.kdf != KDF {
+            anyhow::bail!("지원하지 않는 볼트 형식입니다 (version {}, kdf {})", file.version, file.kdf);
+        }
+        let decode = |field: &str, value: &str| {
+            BASE64.decode(value).with_context(|| format!("볼트 파일의 {} 값이 올바르지 않습니다", field))
+        };
+        let salt = decode("salt", &file.salt)?;
+        let nonce = decode("nonce", &file.nonce)?;
+        let ciphertext = decode("ciphertext", &file.ciphertext)?;
+        if nonce.len() != 12 {
+            anyhow::bail!("볼트 파일의 nonce 길이가 올바르지 않습니다");
+        }
+
+        let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt, file.iterations).into());
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| anyhow::anyhow!("마스터 암호가 올바르지 않거나 볼트 파일이 손상되었습니다"))?;
+        let entries = serde_json::from_slice(&plaintext).context("볼트 내용을 해석할 수 없습니다")?;
+        let storage = Some(Storage { path: path.to_path_buf(), passphrase: passphrase.to_string() });
+        Ok(Self { entries, iterations: file.iterations, storage })
+    }
+
+    /// 새 salt/nonce로 암호화해 저장합니다. (임시 파일에 쓴 뒤 교체)
+    pub fn save(&self, path: &Path, passphrase: &str) -> Result<()> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        getrandom::getrandom(&mut salt).map_err(|e| anyhow::anyhow!("난수 생성 실패: {}", e))?;
+        getrandom::getrandom(&mut nonce).map_err(|e| anyhow::anyhow!("난수 생성 실패: {}", e))?;
+
+        let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt, self.iterations).into());
+        let plaintext = serde_json::to_vec(&self.entries)?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| anyhow::anyhow!("볼트 암호화 실패"))?;
+        let file = VaultFile {
+            version: VERSION,
+            kdf: KDF.to_string(),
+            iterations: self.iterations,
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        write_atomic(path, &serde_json::to_string_pretty(&file)?)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries.get(name).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, secret: impl Into<String>) {
+        self.entries.insert(name.into(), secret.into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+/// proxies.json과 같은 디렉터리의 vault.json
+pub fn vault_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name("vault.json")
+}
+
+/// 마스터 암호 (환경 변수가 없으면 터미널에서 입력)
+pub fn read_passphrase(prompt: &str) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    rpassword::prompt_password(prompt)
+        .with_context(|| format!("마스터 암호를 입력받을 수 없습니다 ({} 환경 변수로 지정 가능)", PASSPHRASE_ENV))
+}
+
+/// 설정의 비밀 값 칸마다 `f(볼트 항목 이름, 값)`을 호출합니다.
+/// 항목 이름은 `proxy-<id>.password`, `group-<이름>.jump_host.password`, `snmp.v3.auth_password` 형식입니다.
+/// (proxies.json과 resource_config.json 모두 이 함수로 찾음)
+fn for_each_secret(config: &mut Value, mut f: impl FnMut(&str, &mut String) -> Result<()>) -> Result<()> {
+    let mut visit = |owner: &str, node: &mut Value, paths: &[&[&str]]| -> Result<()> {
+        for path in paths {
+            let mut value = Some(&mut *node);
+            for key in path.iter() {
+                value = value.and_then(|v| v.get_mut(*key));
+            }
+            if let Some(Value::String(secret)) = value {
+                f(&format!("{}.{}", owner, path.join(".")), secret)?;
+            }
+        }
+        Ok(())
+    };
+    if let Some(proxies) = config.get_mut("proxies").and_then(Value::as_array_mut) {
+        for (index, proxy) in proxies.iter_mut().enumerate() {
+            let id = proxy.get("id").and_then(Value::as_u64).unwrap_or(index as u64);
+            visit(&format!("proxy-{}", id), proxy, &PROXY_SECRETS)?;
+        }
+    }
+    if let Some(groups) = config.get_mut("groups").and_then(Value::as_object_mut) {
+        for (name, group) in groups.iter_mut() {
+            visit(&format!("group-{}", name), group, &GROUP_SECRETS)?;
+        }
+    }
+    visit("snmp", config, &SNMP_SECRETS)?;
+    Ok(())
+}
+
+/// 설정에 `vault:` 참조가 있는지
+fn is_referenced(config: &mut Value) -> bool {
+    let mut found = false;
+    let _ = for_each_secret(config, |_, secret| {
+        found |= secret.starts_with(REF_PREFIX);
+        Ok(())
+    });
+    found
+}
+
+/// 시작할 때 연 볼트 (열지 못했으면 그 이유)
+fn unlocked() -> &'static Mutex<Option<Result<Vault, String>>> {
+    static UNLOCKED: OnceLock<Mutex<Option<Result<Vault, String>>>> = OnceLock::new();
+    UNLOCKED.get_or_init(|| Mutex::new(None))
+}
+
+/// 볼트를 연 결과를 보관합니다. 참조는 접속할 때 `lookup`으로 찾습니다.
+fn set_unlocked(result: Result<Vault>) {
+    *unlocked().lock().unwrap() = Some(result.map_err(|e| format!("{:#}", e)));
+}
+
+/// 열어 둔 볼트는 프로세스 전역이므로 볼트를 열거나 `vault:` 참조를 확인하는 테스트는 이 잠금을 잡고 실행합니다.
+/// 잠금을 잡으면 볼트는 닫힌 상태에서 시작합니다.
+#[cfg(test)]
+pub(crate) fn lock_for_test() -> std::sync::MutexGuard<'static, ()> {
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+    let guard = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    *unlocked().lock().unwrap() = None;
+    guard
+}
+
+/// 설정 중 하나에라도 `vault:` 참조가 있을 때만 `unlock`으로 볼트를 열어 둡니다. 열려고 했으면 true를 반환합니다.
+/// 설정 값은 바꾸지 않으므로 저장해도 평문 비밀번호가 남지 않습니다.
+pub fn unlock_if_referenced(configs: &mut [&mut Value], unlock: impl FnOnce() -> Result<Vault>) -> bool {
+    if !configs.iter_mut().any(|config| is_referenced(config)) {
+        return false;
+    }
+    set_unlocked(unlock());
+    true
+}
+
+/// 열어 둔 볼트에서 항목을 찾습니다.
+pub fn lookup(name: &str) -> Result<String> {
+    match unlocked().lock().unwrap().as_ref() {
+        Some(Ok(vault)) => vault
+            .get(name)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("볼트에 없는 항목입니다: {}", name)),
+        Some(Err(e)) => anyhow::bail!("볼트를 열지 못했습니다: {}", e),
+        None => anyhow::bail!("볼트가 열려 있지 않습니다 (config/vault.json)"),
+    }
+}
+
+/// 열어 둔 볼트에 비밀 값을 저장할 수 있는지 (아니면 입력 창의 비밀번호는 평문으로 저장됨)
+pub fn is_writable() -> bool {
+    matches!(unlocked().lock().unwrap().as_ref(), Some(Ok(vault)) if vault.storage.is_some())
+}
+
+/// 입력 창에서 받은 평문 비밀 값을 `(볼트 항목 이름, 값)`마다 열어 둔 볼트로 옮기고 값은 참조로 바꿉니다.
+/// 볼트 파일을 저장한 뒤에 값을 바꾸므로 실패하면 아무것도 바뀌지 않습니다. 옮긴 값의 수를 반환합니다.
+/// 볼트를 저장할 수 없으면 (`is_writable`이 false) 0을 반환하고 평문을 그대로 둡니다.
+pub fn store(secrets: Vec<(String, &mut String)>) -> Result<usize> {
+    let secrets: Vec<_> = secrets
+        .into_iter()
+        .filter(|(_, secret)| !secret.is_empty() && !crate::credential::is_reference(secret))
+        .collect();
+    if secrets.is_empty() {
+        return Ok(0);
+    }
+    let mut unlocked = unlocked().lock().unwrap();
+    let Some(Ok(vault)) = unlocked.as_mut() else {
+        return Ok(0);
+    };
+    let Some(storage) = vault.storage.take() else {
+        return Ok(0);
+    };
+    let previous = vault.entries.clone();
+    for (name, secret) in &secrets {
+        vault.insert(name.as_str(), secret.as_str());
+    }
+    let saved = vault.save(&storage.path, &storage.passphrase);
+    vault.storage = Some(storage);
+    if let Err(e) = saved {
+        // 저장하지 못한 항목은 메모리에서도 되돌림
+        vault.entries = previous;
+        return Err(e.context("볼트에 비밀번호를 저장하지 못했습니다"));
+    }
+    let moved = secrets.len();
+    for (name, secret) in secrets {
+        *secret = format!("{}{}", REF_PREFIX, name);
+    }
+    Ok(moved)
+}
+
+/// 평문 비밀 값을 `vault`로 옮기고 설정에는 참조를 남깁니다. 옮긴 값의 수를 반환합니다.
+pub fn migrate(config: &mut Value, vault: &mut Vault) -> Result<usize> {
+    let mut moved = 0;
+    for_each_secret(config, |name, secret| {
+        if !secret.is_empty() && !secret.starts_with(REF_PREFIX) {
+            vault.insert(name, std::mem::take(secret));
+            *secret = format!("{}{}", REF_PREFIX, name);
+            moved += 1;
+        }
+        Ok(())
+    })?;
+    Ok(moved)
+}
+
+/// `vault migrate [설정 파일 경로]` - 평문 비밀번호를 볼트로 옮깁니다. (기본 proxies.json, resource_config.json도 가능)
+pub fn run_migrate(args: &[String]) -> Result<()> {
+    let config_path = args
+        .first()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| crate::app::config::get_config_path("proxies.json"));
+    let vault_path = vault_path(&config_path);
+    let content = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("설정 파일을 읽을 수 없습니다: {}", config_path.display()))?;
+    let mut config: Value = serde_json::from_str(&content)?;
+
+    let (mut vault, passphrase) = if vault_path.exists() {
+        let passphrase = read_passphrase("볼트 마스터 암호: ")?;
+        (Vault::open(&vault_path, &passphrase)?, passphrase)
+    } else {
+        let passphrase = read_passphrase("새 볼트 마스터 암호: ")?;
+        if std::env::var(PASSPHRASE_ENV).is_err() && read_passphrase("마스터 암호 확인: ")? != passphrase {
+            anyhow::bail!("마스터 암호가 일치하지 않습니다");
+        }
+        if passphrase.is_empty() {
+            anyhow::bail!("마스터 암호가 비어 있습니다");
+        }
+        (Vault::new(), passphrase)
+    };
+
+    let moved = migrate(&mut config, &mut vault)?;
+    if moved == 0 {
+        println!("옮길 평문 비밀번호가 없습니다: {}", config_path.display());
+        return Ok(());
+    }
+    let removed_backup = save_migrated(&config_path, &config, &vault, &vault_path, &passphrase)?;
+
+    println!("비밀번호 {}개를 볼트로 옮겼습니다: {} (항목 {}개)", moved, vault_path.display(), vault.len());
+    if let Some(backup_path) = removed_backup {
+        println!("평문 비밀번호가 남아 있던 백업을 지웠습니다: {}", backup_path.display());
+    }
+    Ok(())
+}
+
+/// 볼트와 참조로 바꾼 설정을 저장합니다.
+/// 볼트를 먼저 저장해야 중간에 실패해도 비밀번호를 잃지 않습니다.
+/// 설정은 백업 없이 교체하고, 이전 저장에서 남은 `<이름>.json.bak`은 평문이 들어 있으므로 지웁니다. (지웠으면 그 경로)
+fn save_migrated(
+    config_path: &Path,
+    config: &Value,
+    vault: &Vault,
+    vault_path: &Path,
+    passphrase: &str,
+) -> Result<Option<PathBuf>> {
+    vault.save(vault_path, passphrase)?;
+    write_atomic(config_path, &serde_json::to_string_pretty(config)?)?;
+    let backup_path = config_path.with_extension("json.bak");
+    if !backup_path.exists() {
+        return Ok(None);
+    }
+    std::fs::remove_file(&backup_path)
+        .with_context(|| format!("평문이 남은 백업을 지울 수 없습니다: {}", backup_path.display()))?;
+    Ok(Some(backup_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"{
+      "proxies": [
+        { "id": 1, "host": "10.0.0.1", "port": 22, "username": "admin", "password": "pw1", "group": "A",
+          "escalation": { "method": "sudo", "password": "sudo-pw" } },
+        { "id": 2, "host": "10.0.0.2", "port": 22, "username": "admin", "password": "", "group": "DMZ",
+          "ssh_agent": true }
+      ],
+      "groups": { "DMZ": { "jump_host": { "host": "bastion", "username": "jump", "password": "jump-pw" } } }
+    }"#;
+
+    const RESOURCE_CONFIG: &str = r#"{
+      "snmp_version": "3",
+      "community": "public",
+      "v3": { "username": "monitor", "auth_protocol": "SHA", "auth_password": "authpass123" }
+    }"#;
+
+    fn fast_vault() -> Vault {
+        Vault { entries: BTreeMap::new(), iterations: 1_000, storage: None }
+    }
+
+    #[test]
+    fn vault_round_trips_and_rejects_wrong_passphrase() {
+        let path = std::env::temp_dir().join(format!("mmt-vault-{}.json", std::process::id()));
+        let mut vault = fast_vault();
+        vault.insert("proxy-1.password", "pw1");
+        vault.save(&path, "master").unwrap();
+
+        // 파일에는 평문이 남지 않음
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("pw1") && !content.contains("proxy-1"), "{}", content);
+
+        let opened = Vault::open(&path, "master").unwrap();
+        assert_eq!(opened.get("proxy-1.password"), Some("pw1"));
+        let err = Vault::open(&path, "wrong").unwrap_err();
+        assert!(err.to_string().contains("마스터 암호가 올바르지 않거나"), "{}", err);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn migrate_moves_plaintext_secrets_and_lookup_restores_them() {
+        let _vault = lock_for_test();
+        let mut config: Value = serde_json::from_str(CONFIG).unwrap();
+        assert!(!is_referenced(&mut config));
+        let mut vault = fast_vault();
+        assert_eq!(migrate(&mut config, &mut vault).unwrap(), 3);
+        assert!(is_referenced(&mut config));
+        assert_eq!(config["proxies"][0]["password"], "vault:proxy-1.password");
+        assert_eq!(config["proxies"][0]["escalation"]["password"], "vault:proxy-1.escalation.password");
+        assert_eq!(config["groups"]["DMZ"]["jump_host"]["password"], "vault:group-DMZ.jump_host.password");
+        // 빈 비밀번호는 그대로 두고, 다시 실행해도 옮길 것이 없음
+        assert_eq!(config["proxies"][1]["password"], "");
+        assert_eq!(migrate(&mut config, &mut vault).unwrap(), 0);
+        // 다른 필드 순서는 유지
+        let keys: Vec<&String> = config["proxies"][0].as_object().unwrap().keys().collect();
+        assert_eq!(keys[..3], ["id", "host", "port"]);
+
+        // 볼트를 열기 전에는 참조를 바꿀 수 없고, 연 뒤에는 접속할 때 실제 값으로 바뀜
+        assert!(crate::credential::resolve("vault:proxy-1.password").is_err());
+        set_unlocked(Ok(vault));
+        let proxies = serde_json::from_value::<crate::app::ProxyConfig>(config).unwrap().into_proxies();
+        assert_eq!(crate::credential::resolve(&proxies[0].password).unwrap(), "pw1");
+        let escalation = proxies[0].escalation.as_ref().and_then(|e| e.password()).unwrap();
+        assert_eq!(crate::credential::resolve(escalation).unwrap(), "sudo-pw");
+        let jump_host = proxies[1].group_jump_host.as_ref().unwrap();
+        assert_eq!(crate::credential::resolve(&jump_host.password).unwrap(), "jump-pw");
+
+        // 전역 SNMP 설정의 커뮤니티와 v3 비밀번호도 옮김
+        let mut resource_config: Value = serde_json::from_str(RESOURCE_CONFIG).unwrap();
+        let mut vault = fast_vault();
+        assert_eq!(migrate(&mut resource_config, &mut vault).unwrap(), 2);
+        assert_eq!(resource_config["community"], "vault:snmp.community");
+        assert_eq!(resource_config["v3"]["auth_password"], "vault:snmp.v3.auth_password");
+        assert_eq!(vault.get("snmp.v3.auth_password"), Some("authpass123"));
+
+        let err = crate::credential::resolve("vault:missing").unwrap_err();
+        assert_eq!(format!("{:#}", err), "자격 증명 참조 실패 (vault:missing): 볼트에 없는 항목입니다: missing");
+        set_unlocked(Err(anyhow::anyhow!("마스터 암호가 올바르지 않거나 볼트 파일이 손상되었습니다")));
+        assert!(lookup("proxy-1.password").unwrap_err().to_string().starts_with("볼트를 열지 못했습니다: 마스터 암호"));
+    }
+
+    #[test]
+    fn migrate_leaves_no_plaintext_secret_on_disk() {
+        let dir = std::env::temp_dir().join(format!("mmt-vault-migrate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("proxies.json");
+        let vault_path = vault_path(&config_path);
+        std::fs::write(&config_path, CONFIG).unwrap();
+        // 프록시 관리 탭에서 저장할 때 남은 평문 백업
+        std::fs::write(config_path.with_extension("json.bak"), CONFIG).unwrap();
+
+        let mut config: Value = serde_json::from_str(CONFIG).unwrap();
+        let mut vault = fast_vault();
+        assert_eq!(migrate(&mut config, &mut vault).unwrap(), 3);
+        let removed = save_migrated(&config_path, &config, &vault, &vault_path, "master").unwrap();
+        assert_eq!(removed, Some(config_path.with_extension("json.bak")));
+
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let content = std::fs::read_to_string(&path).unwrap();
+            for secret in ["pw1", "sudo-pw", "jump-pw"] {
+                assert!(!content.contains(secret), "{}에 {}가 남아 있음", path.display(), secret);
+            }
+        }
+        assert_eq!(Vault::open(&vault_path, "master").unwrap().get("proxy-1.password"), Some("pw1"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn store_moves_form_secrets_into_the_opened_vault() {
+        let _vault = lock_for_test();
+        let path = std::env::temp_dir().join(format!("mmt-vault-store-{}.json", std::process::id()));
+        fast_vault().save(&path, "master").unwrap();
+
+        // 열린 볼트가 없으면 평문 그대로
+        let mut password = "pw1".to_string();
+        assert!(!is_writable());
+        assert_eq!(store(vec![("proxy-1.password".to_string(), &mut password)]).unwrap(), 0);
+        assert_eq!(password, "pw1");
+
+        // 파일에서 연 볼트는 저장할 수 있고, 참조와 빈 값은 건드리지 않음
+        let mut config: Value = serde_json::from_str(r#"{"proxies": [{"password": "vault:x"}]}"#).unwrap();
+        assert!(unlock_if_referenced(&mut [&mut config], || Vault::open(&path, "master")));
+        assert!(is_writable());
+        let mut passphrase = "env:KEY_PASS".to_string();
+        let mut jump_password = String::new();
+        let secrets = vec![
+            ("proxy-1.password".to_string(), &mut password),
+            ("proxy-1.ssh_key_passphrase".to_string(), &mut passphrase),
+            ("proxy-1.jump_host.password".to_string(), &mut jump_password),
+        ];
+        assert_eq!(store(secrets).unwrap(), 1);
+        assert_eq!(password, "vault:proxy-1.password");
+        assert_eq!((passphrase.as_str(), jump_password.as_str()), ("env:KEY_PASS", ""));
+        assert_eq!(lookup("proxy-1.password").unwrap(), "pw1");
+        assert_eq!(Vault::open(&path, "master").unwrap().get("proxy-1.password"), Some("pw1"));
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("pw1"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn vault_is_not_opened_when_nothing_references_it() {
+        let _vault = lock_for_test();
+        let mut config: Value = serde_json::from_str(CONFIG).unwrap();
+        let mut resource_config: Value = serde_json::from_str(RESOURCE_CONFIG).unwrap();
+        let unlocked = unlock_if_referenced(&mut [&mut config, &mut resource_config], || {
+            panic!("참조가 없으면 볼트를 열면 안 됨")
+        });
+        assert!(!unlocked);
+
+        let mut config: Value =
+            serde_json::from_str(r#"{"groups": {"DMZ": {"jump_host": {"password": "vault:jump"}}}}"#).unwrap();
+        assert!(is_referenced(&mut config));
+        let mut config: Value =
+            serde_json::from_str(r#"{"proxies": [{"snmp_v3": {"priv_password": "vault:p"}}]}"#).unwrap();
+        assert!(is_referenced(&mut config));
+    }
+}